@@ -4,6 +4,7 @@ use rancor::{Source, Strategy};
 
 use crate::{
     api::{access_pos_unchecked, root_position},
+    traits::LayoutRaw,
     validation::{ArchiveContext, ArchiveContextExt},
     Portable,
 };
@@ -68,6 +69,29 @@ where
     })
 }
 
+/// Check a value located in a byte slice with a given context.
+///
+/// `value` must be located within `bytes`, and is checked as if it were the
+/// root of an archive ending at `value`. The rest of the archive is not
+/// checked. See [`check_subtree`](crate::api::high::check_subtree) for more
+/// details.
+pub fn check_subtree_with_context<T, C, E>(
+    bytes: &[u8],
+    value: &T,
+    context: &mut C,
+) -> Result<(), E>
+where
+    T: CheckBytes<Strategy<C, E>> + Pointee<Metadata = ()>,
+    C: ArchiveContext<E> + ?Sized,
+    E: Source,
+{
+    // Values outside of `bytes` wrap around to a position which fails the
+    // bounds check.
+    let pos =
+        (value as *const T as usize).wrapping_sub(bytes.as_ptr() as usize);
+    check_pos_with_context::<T, C, E>(bytes, pos, context)
+}
+
 /// Access a byte slice with a given root position and context.
 ///
 /// This is a safe alternative to [`access_pos_unchecked`].
@@ -128,6 +152,39 @@ where
     unsafe { Ok(access_pos_unchecked::<T>(bytes, pos)) }
 }
 
+/// Access a byte slice with a given root position, root metadata, and context.
+///
+/// This is a safe alternative to
+/// [`access_pos_unsized_unchecked`](crate::api::access_pos_unsized_unchecked)
+/// which supports unsized root types.
+///
+/// Most of the time, the context should be newly-created and not reused. Prefer
+/// `access_pos_unsized` whenever possible.
+pub fn access_pos_unsized_with_context<'a, T, C, E>(
+    bytes: &'a [u8],
+    pos: usize,
+    metadata: T::Metadata,
+    context: &mut C,
+) -> Result<&'a T, E>
+where
+    T: Portable + CheckBytes<Strategy<C, E>> + LayoutRaw + Pointee + ?Sized,
+    C: ArchiveContext<E> + ?Sized,
+    E: Source,
+{
+    let context = Strategy::<C, E>::wrap(context);
+    let ptr = ptr_meta::from_raw_parts::<T>(
+        bytes.as_ptr().wrapping_add(pos).cast(),
+        metadata,
+    );
+    context.in_subtree(ptr, |context| {
+        // SAFETY: `in_subtree` has guaranteed that `ptr` is properly aligned
+        // and points to enough bytes for a `T`.
+        unsafe { T::check_bytes(ptr, context) }
+    })?;
+    // SAFETY: We just checked that `ptr` points to a valid `T`.
+    unsafe { Ok(&*ptr) }
+}
+
 /// Access a byte slice with a given context.
 ///
 /// This is a safe alternative to [`access_unchecked`].
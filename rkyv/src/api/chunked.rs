@@ -0,0 +1,384 @@
+//! APIs for archives which are split into independently loadable chunks.
+//!
+//! A chunked archive stores a long sequence of values as a series of chunks.
+//! Each chunk is a complete archive of a `Vec` of some of the values, so it can
+//! be loaded and validated without reading any other part of the archive. The
+//! chunks are followed by an index and a trailer:
+//!
+//! - The index is an archived `Vec<(u64, u64, u64)>` which contains the offset
+//!   and length of each chunk in bytes, and the total number of values in the
+//!   archive up to and including that chunk.
+//! - The trailer is the 8-byte magic `rkyvchnk` followed by the length of the
+//!   index as a little-endian `u64`.
+//!
+//! All offsets are 64-bit, so an archive may be larger than the address space
+//! of the machine reading it. Readers only need to keep the index and the
+//! chunks which they are using in memory. Because each chunk is serialized
+//! separately, shared pointers are only deduplicated within a single chunk.
+
+use std::io::Write;
+
+use rancor::{ResultExt as _, Source};
+
+use crate::{
+    alloc::vec::Vec,
+    api::high::{to_bytes, HighSerializer},
+    ser::{allocator::ArenaHandle, Allocator, Writer},
+    util::AlignedVec,
+    vec::{ArchivedVec, VecResolver},
+    Archive, Place, Serialize,
+};
+
+const MAGIC: [u8; 8] = *b"rkyvchnk";
+const TRAILER_SIZE: usize = 16;
+const CHUNK_ALIGNMENT: usize = AlignedVec::<16>::ALIGNMENT;
+
+type Index = Vec<(u64, u64, u64)>;
+
+struct Chunk<'a, T>(&'a [T]);
+
+impl<T: Archive> Archive for Chunk<'_, T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_slice(self.0, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for Chunk<'_, T>
+where
+    T: Serialize<S>,
+    S: rancor::Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::serialize_from_slice(self.0, serializer)
+    }
+}
+
+/// A serializer which writes a sequence of values as a chunked archive.
+///
+/// Each call to [`serialize_chunk`](ChunkedSerializer::serialize_chunk) writes
+/// a new chunk. Call [`finish`](ChunkedSerializer::finish) after writing all
+/// of the chunks to write the index.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+///
+/// use rkyv::{
+///     api::chunked::{ChunkedReader, ChunkedSerializer},
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let mut chunks = ChunkedSerializer::new(Vec::new());
+/// for start in (0..1000u32).step_by(100) {
+///     let values = (start..start + 100).collect::<Vec<_>>();
+///     chunks.serialize_chunk::<_, Error>(&values).unwrap();
+/// }
+/// let bytes = chunks.finish::<Error>().unwrap();
+///
+/// let mut reader = ChunkedReader::open::<Error>(Cursor::new(bytes)).unwrap();
+/// assert_eq!(reader.len(), 1000);
+///
+/// let (chunk, offset) = reader.locate(641).unwrap();
+/// let values = reader.load_chunk::<Archived<u32>, Error>(chunk).unwrap();
+/// assert_eq!(values[offset], 641);
+/// ```
+pub struct ChunkedSerializer<W> {
+    writer: W,
+    pos: u64,
+    index: Index,
+}
+
+impl<W: Write> ChunkedSerializer<W> {
+    /// Creates a new chunked serializer which writes to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            pos: 0,
+            index: Vec::new(),
+        }
+    }
+
+    /// Returns the number of chunks which have been written so far.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns whether no chunks have been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Serializes the given values as a new chunk and returns its index.
+    pub fn serialize_chunk<T, E>(&mut self, values: &[T]) -> Result<usize, E>
+    where
+        T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+        E: Source,
+    {
+        let bytes = to_bytes::<E>(&Chunk(values))?;
+        let offset = self.write_aligned::<E>(&bytes)?;
+        let end = self.index.last().map_or(0, |entry| entry.2);
+        self.index.push((
+            offset,
+            bytes.len() as u64,
+            end + values.len() as u64,
+        ));
+        Ok(self.index.len() - 1)
+    }
+
+    fn write_aligned<E: Source>(&mut self, bytes: &[u8]) -> Result<u64, E> {
+        const ZEROS: [u8; CHUNK_ALIGNMENT] = [0; CHUNK_ALIGNMENT];
+
+        let mask = CHUNK_ALIGNMENT as u64 - 1;
+        let padding =
+            ((CHUNK_ALIGNMENT as u64 - (self.pos & mask)) & mask) as usize;
+        self.writer.write_all(&ZEROS[..padding]).into_error()?;
+        self.writer.write_all(bytes).into_error()?;
+        let offset = self.pos + padding as u64;
+        self.pos = offset + bytes.len() as u64;
+        Ok(offset)
+    }
+
+    /// Writes the index and trailer and returns the writer.
+    pub fn finish<E: Source>(mut self) -> Result<W, E> {
+        let index = to_bytes::<E>(&self.index)?;
+        self.write_aligned::<E>(&index)?;
+        let mut trailer = [0; TRAILER_SIZE];
+        trailer[..8].copy_from_slice(&MAGIC);
+        trailer[8..].copy_from_slice(&(index.len() as u64).to_le_bytes());
+        self.writer.write_all(&trailer).into_error()?;
+        self.writer.flush().into_error()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod reader {
+    use core::{error::Error, fmt};
+    use std::io::{Read, Seek, SeekFrom};
+
+    use bytecheck::CheckBytes;
+    use rancor::{fail, ResultExt as _, Source};
+
+    use super::{Index, MAGIC, TRAILER_SIZE};
+    use crate::{
+        api::high::{CheckedArchive, HighValidator},
+        util::AlignedVec,
+        vec::ArchivedVec,
+        Archived, Portable,
+    };
+
+    #[derive(Debug)]
+    struct InvalidTrailerError;
+
+    impl fmt::Display for InvalidTrailerError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "the trailer of the chunked archive is invalid")
+        }
+    }
+
+    impl Error for InvalidTrailerError {}
+
+    #[derive(Debug)]
+    struct ChunkIndexOutOfBounds {
+        index: usize,
+        len: usize,
+    }
+
+    impl fmt::Display for ChunkIndexOutOfBounds {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "chunk index {} out of bounds for archive with {} chunks",
+                self.index, self.len,
+            )
+        }
+    }
+
+    impl Error for ChunkIndexOutOfBounds {}
+
+    /// A reader for chunked archives which only loads the chunks it is asked
+    /// for.
+    ///
+    /// The index is read and validated when the archive is opened. Each chunk
+    /// is read and validated separately when it is loaded.
+    pub struct ChunkedReader<R> {
+        reader: R,
+        index: CheckedArchive<AlignedVec, Archived<Index>>,
+    }
+
+    impl<R: Read + Seek> ChunkedReader<R> {
+        /// Reads and validates the index of a chunked archive.
+        pub fn open<E: Source>(mut reader: R) -> Result<Self, E> {
+            let end = reader.seek(SeekFrom::End(0)).into_error()?;
+            if end < TRAILER_SIZE as u64 {
+                fail!(InvalidTrailerError);
+            }
+            reader
+                .seek(SeekFrom::Start(end - TRAILER_SIZE as u64))
+                .into_error()?;
+            let mut trailer = [0; TRAILER_SIZE];
+            reader.read_exact(&mut trailer).into_error()?;
+            if trailer[..8] != MAGIC {
+                fail!(InvalidTrailerError);
+            }
+            let len = u64::from_le_bytes(trailer[8..].try_into().unwrap());
+            let Some(start) = (end - TRAILER_SIZE as u64).checked_sub(len)
+            else {
+                fail!(InvalidTrailerError);
+            };
+
+            let bytes = read_range::<_, E>(&mut reader, start, len)?;
+            let index = CheckedArchive::new::<E>(bytes)?;
+            Ok(Self { reader, index })
+        }
+
+        /// Returns the number of chunks in the archive.
+        pub fn chunk_count(&self) -> usize {
+            self.index.len()
+        }
+
+        /// Returns the total number of values in the archive.
+        pub fn len(&self) -> u64 {
+            self.index.last().map_or(0, |entry| entry.2.to_native())
+        }
+
+        /// Returns whether the archive contains no values.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Returns the number of values in the given chunk, or `None` if the
+        /// chunk index is out of bounds.
+        pub fn chunk_len(&self, chunk: usize) -> Option<u64> {
+            let end = self.index.get(chunk)?.2.to_native();
+            let start = match chunk {
+                0 => 0,
+                _ => self.index[chunk - 1].2.to_native(),
+            };
+            Some(end.saturating_sub(start))
+        }
+
+        /// Returns the chunk containing the value with the given index and
+        /// the position of the value within that chunk.
+        ///
+        /// Returns `None` if the index is out of bounds.
+        pub fn locate(&self, index: u64) -> Option<(usize, usize)> {
+            let chunk = self
+                .index
+                .partition_point(|entry| entry.2.to_native() <= index);
+            let start = match chunk {
+                0 => 0,
+                _ => self.index.get(chunk - 1)?.2.to_native(),
+            };
+            self.index.get(chunk)?;
+            Some((chunk, (index - start) as usize))
+        }
+
+        /// Reads, validates, and returns the chunk at the given index.
+        ///
+        /// `T` is the archived type of the values in the chunk.
+        pub fn load_chunk<T, E>(
+            &mut self,
+            chunk: usize,
+        ) -> Result<CheckedArchive<AlignedVec, ArchivedVec<T>>, E>
+        where
+            T: Portable,
+            ArchivedVec<T>: for<'a> CheckBytes<HighValidator<'a, E>>,
+            E: Source,
+        {
+            let Some(entry) = self.index.get(chunk) else {
+                fail!(ChunkIndexOutOfBounds {
+                    index: chunk,
+                    len: self.index.len(),
+                });
+            };
+            let (offset, len) = (entry.0.to_native(), entry.1.to_native());
+            let bytes = read_range::<_, E>(&mut self.reader, offset, len)?;
+            CheckedArchive::new::<E>(bytes)
+        }
+
+        /// Returns the underlying reader.
+        pub fn into_inner(self) -> R {
+            self.reader
+        }
+    }
+
+    fn read_range<R: Read + Seek, E: Source>(
+        reader: &mut R,
+        offset: u64,
+        len: u64,
+    ) -> Result<AlignedVec, E> {
+        let len = usize::try_from(len).into_error()?;
+        let mut bytes = AlignedVec::with_capacity(len);
+        bytes.resize(len, 0);
+        reader.seek(SeekFrom::Start(offset)).into_error()?;
+        reader.read_exact(&mut bytes).into_error()?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+pub use self::reader::ChunkedReader;
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    use std::io::Cursor;
+
+    use rancor::{Failure, Panic};
+
+    use super::{ChunkedReader, ChunkedSerializer};
+    use crate::{
+        alloc::{
+            string::{String, ToString},
+            vec::Vec,
+        },
+        string::ArchivedString,
+    };
+
+    #[test]
+    fn chunked_roundtrip() {
+        let mut chunks = ChunkedSerializer::new(Vec::new());
+        let sizes = [3, 0, 5, 1];
+        let mut next = 0;
+        for size in sizes {
+            let values = (next..next + size)
+                .map(|i: u32| i.to_string())
+                .collect::<Vec<String>>();
+            chunks.serialize_chunk::<_, Panic>(&values).unwrap();
+            next += size;
+        }
+        assert_eq!(chunks.len(), 4);
+        let bytes = chunks.finish::<Panic>().unwrap();
+
+        let mut reader =
+            ChunkedReader::open::<Panic>(Cursor::new(bytes.clone())).unwrap();
+        assert_eq!(reader.chunk_count(), 4);
+        assert_eq!(reader.len(), 9);
+        assert_eq!(reader.chunk_len(1), Some(0));
+        assert_eq!(reader.chunk_len(4), None);
+        assert_eq!(reader.locate(3), Some((2, 0)));
+        assert_eq!(reader.locate(8), Some((3, 0)));
+        assert_eq!(reader.locate(9), None);
+
+        for i in 0..9u64 {
+            let (chunk, offset) = reader.locate(i).unwrap();
+            let values =
+                reader.load_chunk::<ArchivedString, Panic>(chunk).unwrap();
+            assert_eq!(values[offset], i.to_string());
+        }
+        assert!(reader.load_chunk::<ArchivedString, Failure>(4).is_err());
+
+        let mut truncated = bytes;
+        truncated.pop();
+        let truncated = Cursor::new(truncated);
+        assert!(ChunkedReader::open::<Failure>(truncated).is_err());
+    }
+}
@@ -0,0 +1,744 @@
+//! APIs for self-describing archives.
+//!
+//! The format of rkyv's archived data depends on the format control features
+//! which are enabled (`little_endian`, `big_endian`, `aligned`, `unaligned`,
+//! and the `pointer_width_*` features). Bytes produced with one combination of
+//! features are not valid under another, and accessing them without a check
+//! will silently misparse.
+//!
+//! A framed archive begins with a [`Header`] that records the format it was
+//! produced with and an optional type fingerprint. The framed accessors verify
+//! the header before accessing the archived data. The header is
+//! [`HEADER_SIZE`] bytes long, so the alignment of the archived data is
+//! preserved.
+//!
+//! The fingerprint of the archived root type can be computed from its
+//! [`schema`](crate::schema). Archives written with [`to_bytes_versioned`] and
+//! read with [`access_versioned`] are rejected with a mismatch error if the
+//! layout of the archived type has changed since they were written.
+//!
+//! Archives of [`ExplicitEndian`] types can be read from peers with either
+//! endianness by using [`access_explicit_endian`], which selects between a
+//! little-endian and big-endian archived type based on the header. Archived
+//! types produced by `#[derive(Archive)]` always use the endianness of the
+//! enabled format control features, so they don't implement `ExplicitEndian`
+//! and can only be read from peers with the same endianness.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{api::framed, rancor::Error, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! struct Example {
+//!     name: String,
+//!     value: i32,
+//! }
+//!
+//! let value = Example {
+//!     name: "pi".to_string(),
+//!     value: 31415926,
+//! };
+//!
+//! let bytes = framed::to_bytes::<Error>(&value).unwrap();
+//! let archived = framed::access::<ArchivedExample, Error>(&bytes).unwrap();
+//! assert_eq!(archived.name, "pi");
+//! assert_eq!(archived.value, 31415926);
+//! ```
+
+use core::{error::Error, fmt, mem::size_of};
+
+use rancor::{fail, Source};
+
+use crate::{
+    api::high::HighSerializer,
+    primitive::FixedUsize,
+    schema::{fingerprint, Reflect},
+    ser::{allocator::ArenaHandle, Writer},
+    util::AlignedVec,
+    Portable, Serialize,
+};
+
+/// The magic bytes at the start of every framed archive.
+pub const MAGIC: [u8; 4] = *b"rkyv";
+
+/// The version of the framed header format.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The size of a framed header in bytes.
+pub const HEADER_SIZE: usize = 16;
+
+const FLAG_BIG_ENDIAN: u8 = 1 << 0;
+const FLAG_UNALIGNED: u8 = 1 << 1;
+const FLAG_FINGERPRINT: u8 = 1 << 2;
+
+/// The header of a framed archive.
+///
+/// The header is laid out as:
+///
+/// - 4 bytes: [`MAGIC`]
+/// - 1 byte: the header format version
+/// - 1 byte: format flags (endianness, alignment, and whether a fingerprint is
+///   present)
+/// - 1 byte: the pointer width in bytes
+/// - 1 byte: reserved, always zero
+/// - 8 bytes: the type fingerprint as a little-endian `u64`, or zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The version of the header format.
+    pub version: u8,
+    /// Whether the archived data is big-endian.
+    pub big_endian: bool,
+    /// Whether the archived data is unaligned.
+    pub unaligned: bool,
+    /// The width of archived pointers and sizes in bytes.
+    pub pointer_width: u8,
+    /// An optional fingerprint of the archived type.
+    pub fingerprint: Option<u64>,
+}
+
+impl Header {
+    /// Returns the header for archives produced with the enabled format
+    /// control features.
+    pub const fn native(fingerprint: Option<u64>) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            big_endian: cfg!(feature = "big_endian"),
+            unaligned: cfg!(feature = "unaligned"),
+            pointer_width: size_of::<FixedUsize>() as u8,
+            fingerprint,
+        }
+    }
+
+    /// Returns whether archives with this header can be accessed with the
+    /// enabled format control features.
+    pub const fn is_native_format(&self) -> bool {
+        let native = Self::native(None);
+        self.version == native.version
+            && self.big_endian == native.big_endian
+            && self.unaligned == native.unaligned
+            && self.pointer_width == native.pointer_width
+    }
+
+    /// Encodes the header as bytes.
+    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
+        let mut flags = 0;
+        if self.big_endian {
+            flags |= FLAG_BIG_ENDIAN;
+        }
+        if self.unaligned {
+            flags |= FLAG_UNALIGNED;
+        }
+        if self.fingerprint.is_some() {
+            flags |= FLAG_FINGERPRINT;
+        }
+
+        let mut bytes = [0; HEADER_SIZE];
+        bytes[0..4].copy_from_slice(&MAGIC);
+        bytes[4] = self.version;
+        bytes[5] = flags;
+        bytes[6] = self.pointer_width;
+        bytes[8..16]
+            .copy_from_slice(&self.fingerprint.unwrap_or(0).to_le_bytes());
+        bytes
+    }
+
+    /// Decodes a header from the start of the given bytes.
+    pub fn from_bytes<E: Source>(bytes: &[u8]) -> Result<Self, E> {
+        #[derive(Debug)]
+        struct InvalidHeaderError;
+
+        impl fmt::Display for InvalidHeaderError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    concat!(
+                        "buffer does not begin with a framed archive header\n",
+                        "help: framed archives must be produced with \
+                         `api::framed::to_bytes`",
+                    ),
+                )
+            }
+        }
+
+        impl Error for InvalidHeaderError {}
+
+        if bytes.len() < HEADER_SIZE || bytes[0..4] != MAGIC {
+            fail!(InvalidHeaderError);
+        }
+
+        let flags = bytes[5];
+        let mut fingerprint = [0; 8];
+        fingerprint.copy_from_slice(&bytes[8..16]);
+
+        Ok(Self {
+            version: bytes[4],
+            big_endian: flags & FLAG_BIG_ENDIAN != 0,
+            unaligned: flags & FLAG_UNALIGNED != 0,
+            pointer_width: bytes[6],
+            fingerprint: (flags & FLAG_FINGERPRINT != 0)
+                .then(|| u64::from_le_bytes(fingerprint)),
+        })
+    }
+}
+
+impl fmt::Display for Header {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "version {}, {}, {}, {}-bit pointers",
+            self.version,
+            if self.big_endian {
+                "big-endian"
+            } else {
+                "little-endian"
+            },
+            if self.unaligned {
+                "unaligned"
+            } else {
+                "aligned"
+            },
+            self.pointer_width as u32 * 8,
+        )
+    }
+}
+
+/// Serialize a value to bytes with a framed header.
+///
+/// The header does not include a fingerprint. Use
+/// [`to_bytes_with_fingerprint`] to include one.
+pub fn to_bytes<E>(
+    #[rustfmt::skip] value: &impl for<'a> Serialize<
+        HighSerializer<AlignedVec, ArenaHandle<'a>, E>,
+    >,
+) -> Result<AlignedVec, E>
+where
+    E: Source,
+{
+    to_bytes_in(value, AlignedVec::new(), None)
+}
+
+/// Serialize a value to bytes with a framed header which includes the given
+/// type fingerprint.
+pub fn to_bytes_with_fingerprint<E>(
+    #[rustfmt::skip] value: &impl for<'a> Serialize<
+        HighSerializer<AlignedVec, ArenaHandle<'a>, E>,
+    >,
+    fingerprint: u64,
+) -> Result<AlignedVec, E>
+where
+    E: Source,
+{
+    to_bytes_in(value, AlignedVec::new(), Some(fingerprint))
+}
+
+/// Serialize a value to bytes with a framed header which includes the
+/// [`fingerprint`] of its archived type.
+///
+/// Use [`access_versioned`] to access the archive with a fingerprint check.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{api::framed, rancor::Error, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// #[rkyv(schema)]
+/// struct Config {
+///     retries: u32,
+/// }
+///
+/// let bytes =
+///     framed::to_bytes_versioned::<_, Error>(&Config { retries: 3 }).unwrap();
+/// let archived =
+///     framed::access_versioned::<ArchivedConfig, Error>(&bytes).unwrap();
+/// assert_eq!(archived.retries, 3);
+/// ```
+pub fn to_bytes_versioned<T, E>(value: &T) -> Result<AlignedVec, E>
+where
+    T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+    T::Archived: Reflect,
+    E: Source,
+{
+    let fingerprint = fingerprint::<T::Archived>();
+    to_bytes_in(value, AlignedVec::new(), Some(fingerprint))
+}
+
+/// Serialize a value with a framed header and write the bytes to the given
+/// writer.
+///
+/// The writer should be at position zero, or at a position which is a
+/// multiple of the largest alignment of the archived data.
+pub fn to_bytes_in<W, E>(
+    value: &impl for<'a> Serialize<HighSerializer<W, ArenaHandle<'a>, E>>,
+    mut writer: W,
+    fingerprint: Option<u64>,
+) -> Result<W, E>
+where
+    W: Writer<E>,
+    E: Source,
+{
+    writer.write(&Header::native(fingerprint).to_bytes())?;
+    crate::api::high::to_bytes_in(value, writer)
+}
+
+/// Verifies the framed header of the given bytes and returns the archived data
+/// which follows it.
+///
+/// If `fingerprint` is `Some`, the header must contain the same fingerprint.
+pub fn verify<E: Source>(
+    bytes: &[u8],
+    fingerprint: Option<u64>,
+) -> Result<&[u8], E> {
+    verify_header::<E>(bytes, fingerprint, true).map(|(_, body)| body)
+}
+
+fn verify_header<E: Source>(
+    bytes: &[u8],
+    fingerprint: Option<u64>,
+    check_endian: bool,
+) -> Result<(Header, &[u8]), E> {
+    #[derive(Debug)]
+    struct FormatMismatchError {
+        found: Header,
+    }
+
+    impl fmt::Display for FormatMismatchError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                concat!(
+                    "framed archive has format ({}) but expected format ({})\n",
+                    "help: enable the same format control features that were \
+                     used to produce the archive",
+                ),
+                self.found,
+                Header::native(None),
+            )
+        }
+    }
+
+    impl Error for FormatMismatchError {}
+
+    #[derive(Debug)]
+    struct FingerprintMismatchError {
+        expected: u64,
+        found: Option<u64>,
+    }
+
+    impl fmt::Display for FingerprintMismatchError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self.found {
+                Some(found) => write!(
+                    f,
+                    "framed archive has type fingerprint {:#018x} but \
+                     expected {:#018x}\nhelp: the archive was produced with a \
+                     different layout of the archived type",
+                    found, self.expected,
+                ),
+                None => write!(
+                    f,
+                    "framed archive has no type fingerprint but expected \
+                     {:#018x}",
+                    self.expected,
+                ),
+            }
+        }
+    }
+
+    impl Error for FingerprintMismatchError {}
+
+    let header = Header::from_bytes::<E>(bytes)?;
+    let endian_matches =
+        !check_endian || header.big_endian == cfg!(feature = "big_endian");
+    let mut found = header;
+    found.big_endian = cfg!(feature = "big_endian");
+    if !endian_matches || !found.is_native_format() {
+        fail!(FormatMismatchError { found: header });
+    }
+    if let Some(expected) = fingerprint {
+        if header.fingerprint != Some(expected) {
+            fail!(FingerprintMismatchError {
+                expected,
+                found: header.fingerprint,
+            });
+        }
+    }
+
+    Ok((header, &bytes[HEADER_SIZE..]))
+}
+
+/// Access a framed byte slice without verifying its header.
+///
+/// This function does not check that the header or bytes are valid. Use
+/// [`access`] to safely access the buffer using verification and validation.
+///
+/// # Safety
+///
+/// The byte slice must begin with a framed header followed by bytes which
+/// represent a valid archived type when accessed at the default root position.
+/// See the [module docs](crate::api) for more information.
+pub unsafe fn access_unchecked<T: Portable>(bytes: &[u8]) -> &T {
+    // SAFETY: The caller has guaranteed that the bytes after the header
+    // represent a valid `T`.
+    unsafe { crate::api::access_unchecked::<T>(&bytes[HEADER_SIZE..]) }
+}
+
+/// Access a framed byte slice.
+///
+/// The header is verified to match the enabled format control features before
+/// the archived data is validated and accessed.
+#[cfg(feature = "bytecheck")]
+pub fn access<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    E: Source,
+{
+    crate::api::high::access::<T, E>(verify::<E>(bytes, None)?)
+}
+
+/// Access a framed byte slice which must have the given type fingerprint.
+///
+/// The header is verified to match the enabled format control features and
+/// the given fingerprint before the archived data is validated and accessed.
+#[cfg(feature = "bytecheck")]
+pub fn access_with_fingerprint<T, E>(
+    bytes: &[u8],
+    fingerprint: u64,
+) -> Result<&T, E>
+where
+    T: Portable
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    E: Source,
+{
+    crate::api::high::access::<T, E>(verify::<E>(bytes, Some(fingerprint))?)
+}
+
+/// Access a framed byte slice whose fingerprint must match the layout of `T`.
+///
+/// The fingerprint in the header is compared against the [`fingerprint`] of
+/// `T` before the archived data is validated. Archives produced with
+/// [`to_bytes_versioned`] from a different version of the type fail with a
+/// fingerprint mismatch error instead of being misread or failing validation.
+#[cfg(feature = "bytecheck")]
+pub fn access_versioned<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: Portable
+        + Reflect
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    E: Source,
+{
+    access_with_fingerprint::<T, E>(bytes, fingerprint::<T>())
+}
+
+/// A [`Portable`] type whose layout doesn't depend on the endianness format
+/// control features.
+///
+/// This is implemented for the explicitly-endian primitives in [`rend`], for
+/// single-byte primitives, and for arrays of `ExplicitEndian` types.
+/// Hand-written portable types which only contain `ExplicitEndian` fields can
+/// implement it too. Types whose archived layout follows the enabled
+/// endianness, like [`Archived<u32>`](crate::Archived), must not implement it.
+///
+/// [`rend`]: crate::rend
+pub trait ExplicitEndian: Portable {}
+
+macro_rules! impl_explicit_endian {
+    ($($type:ty),* $(,)?) => {
+        $(impl ExplicitEndian for $type {})*
+    };
+}
+
+impl_explicit_endian!(
+    (),
+    bool,
+    i8,
+    u8,
+    core::num::NonZeroI8,
+    core::num::NonZeroU8,
+    crate::rend::i16_le,
+    crate::rend::i32_le,
+    crate::rend::i64_le,
+    crate::rend::i128_le,
+    crate::rend::u16_le,
+    crate::rend::u32_le,
+    crate::rend::u64_le,
+    crate::rend::u128_le,
+    crate::rend::f32_le,
+    crate::rend::f64_le,
+    crate::rend::char_le,
+    crate::rend::NonZeroI16_le,
+    crate::rend::NonZeroI32_le,
+    crate::rend::NonZeroI64_le,
+    crate::rend::NonZeroI128_le,
+    crate::rend::NonZeroU16_le,
+    crate::rend::NonZeroU32_le,
+    crate::rend::NonZeroU64_le,
+    crate::rend::NonZeroU128_le,
+    crate::rend::i16_be,
+    crate::rend::i32_be,
+    crate::rend::i64_be,
+    crate::rend::i128_be,
+    crate::rend::u16_be,
+    crate::rend::u32_be,
+    crate::rend::u64_be,
+    crate::rend::u128_be,
+    crate::rend::f32_be,
+    crate::rend::f64_be,
+    crate::rend::char_be,
+    crate::rend::NonZeroI16_be,
+    crate::rend::NonZeroI32_be,
+    crate::rend::NonZeroI64_be,
+    crate::rend::NonZeroI128_be,
+    crate::rend::NonZeroU16_be,
+    crate::rend::NonZeroU32_be,
+    crate::rend::NonZeroU64_be,
+    crate::rend::NonZeroU128_be,
+);
+
+impl<T: ExplicitEndian, const N: usize> ExplicitEndian for [T; N] {}
+
+/// An archived value which may have been produced with either endianness.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ByEndian<L, B> {
+    /// The archived value was produced by a little-endian peer.
+    Little(L),
+    /// The archived value was produced by a big-endian peer.
+    Big(B),
+}
+
+/// Access a framed byte slice of explicitly-endian types which may have been
+/// produced with either endianness, without validating the archived data.
+///
+/// The endianness recorded in the header selects whether the data is accessed
+/// as an `L` or a `B`. All other parts of the format must still match the
+/// enabled format control features.
+///
+/// # Safety
+///
+/// The archived data must represent a valid `L` if the header is little-endian
+/// and a valid `B` if the header is big-endian. See the
+/// [module docs](crate::api) for more information.
+pub unsafe fn access_explicit_endian_unchecked<L, B, E>(
+    bytes: &[u8],
+) -> Result<ByEndian<&L, &B>, E>
+where
+    L: ExplicitEndian,
+    B: ExplicitEndian,
+    E: Source,
+{
+    let (header, body) = verify_header::<E>(bytes, None, false)?;
+    // SAFETY: The caller has guaranteed that the archived data is a valid `L`
+    // or `B` depending on the endianness recorded in the header.
+    unsafe {
+        if header.big_endian {
+            Ok(ByEndian::Big(crate::api::access_unchecked::<B>(body)))
+        } else {
+            Ok(ByEndian::Little(crate::api::access_unchecked::<L>(body)))
+        }
+    }
+}
+
+/// Access a framed byte slice of explicitly-endian types which may have been
+/// produced with either endianness.
+///
+/// `L` and `B` must be [`ExplicitEndian`] types, like [`rend::u32_le`] and
+/// [`rend::u32_be`]. The endianness recorded in the header selects whether the
+/// data is validated and accessed as an `L` or a `B`. All other parts of the
+/// format must still match the enabled format control features.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::framed::{access_explicit_endian, ByEndian, Header},
+///     rancor::Error,
+///     rend::{u32_be, u32_le},
+///     util::AlignedVec,
+/// };
+///
+/// // An archive produced by a big-endian peer
+/// let mut header = Header::native(None);
+/// header.big_endian = true;
+/// let mut bytes = AlignedVec::<16>::new();
+/// bytes.extend_from_slice(&header.to_bytes());
+/// bytes.extend_from_slice(&42u32.to_be_bytes());
+///
+/// let value = match access_explicit_endian::<u32_le, u32_be, Error>(&bytes) {
+///     Ok(ByEndian::Little(value)) => value.to_native(),
+///     Ok(ByEndian::Big(value)) => value.to_native(),
+///     Err(e) => panic!("{e}"),
+/// };
+/// assert_eq!(value, 42);
+/// ```
+#[cfg(feature = "bytecheck")]
+pub fn access_explicit_endian<L, B, E>(
+    bytes: &[u8],
+) -> Result<ByEndian<&L, &B>, E>
+where
+    L: ExplicitEndian
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    B: ExplicitEndian
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    E: Source,
+{
+    let (header, body) = verify_header::<E>(bytes, None, false)?;
+    if header.big_endian {
+        crate::api::high::access::<B, E>(body).map(ByEndian::Big)
+    } else {
+        crate::api::high::access::<L, E>(body).map(ByEndian::Little)
+    }
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    use rancor::{Failure, Panic};
+
+    use super::{
+        access, access_explicit_endian, access_versioned,
+        access_with_fingerprint, to_bytes, to_bytes_versioned,
+        to_bytes_with_fingerprint, ByEndian, Header, HEADER_SIZE,
+    };
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        rend::{u16_be, u16_le, u32_be, u32_le},
+        util::AlignedVec,
+        Archive, Archived, Serialize,
+    };
+
+    #[test]
+    fn header_roundtrip() {
+        let header = Header::native(Some(0x1234_5678_9abc_def0));
+        let decoded = Header::from_bytes::<Panic>(&header.to_bytes()).unwrap();
+        assert_eq!(header, decoded);
+        assert!(decoded.is_native_format());
+    }
+
+    #[test]
+    fn framed_access() {
+        let value = vec![String::from("hello"), String::from("world")];
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+        let archived = access::<Archived<Vec<String>>, Panic>(&bytes).unwrap();
+        assert_eq!(archived[0], "hello");
+        assert_eq!(archived[1], "world");
+
+        assert!(access_with_fingerprint::<Archived<Vec<String>>, Failure>(
+            &bytes, 1
+        )
+        .is_err());
+        assert!(access::<Archived<Vec<String>>, Failure>(
+            &bytes[HEADER_SIZE..]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn framed_format_mismatch() {
+        let mut bytes = to_bytes_with_fingerprint::<Panic>(&42u32, 7).unwrap();
+        assert_eq!(
+            *access_with_fingerprint::<Archived<u32>, Panic>(&bytes, 7)
+                .unwrap(),
+            42
+        );
+
+        bytes[6] = 1;
+        assert!(access::<Archived<u32>, Failure>(&bytes).is_err());
+    }
+
+    #[test]
+    fn framed_explicit_endian() {
+        for big_endian in [false, true] {
+            let mut header = Header::native(None);
+            header.big_endian = big_endian;
+            let mut bytes = AlignedVec::<16>::new();
+            bytes.extend_from_slice(&header.to_bytes());
+            if big_endian {
+                bytes.extend_from_slice(&1234u32.to_be_bytes());
+            } else {
+                bytes.extend_from_slice(&1234u32.to_le_bytes());
+            }
+
+            let value =
+                match access_explicit_endian::<u32_le, u32_be, Panic>(&bytes)
+                    .unwrap()
+                {
+                    ByEndian::Little(value) => {
+                        assert!(!big_endian);
+                        value.to_native()
+                    }
+                    ByEndian::Big(value) => {
+                        assert!(big_endian);
+                        value.to_native()
+                    }
+                };
+            assert_eq!(value, 1234);
+
+            let native = cfg!(feature = "big_endian") == big_endian;
+            assert_eq!(
+                access::<Archived<u32>, Failure>(&bytes).is_ok(),
+                native
+            );
+        }
+    }
+
+    #[test]
+    fn framed_explicit_endian_array() {
+        let mut header = Header::native(None);
+        header.big_endian = true;
+        let mut bytes = AlignedVec::<16>::new();
+        bytes.extend_from_slice(&header.to_bytes());
+        bytes.extend_from_slice(&1u16.to_be_bytes());
+        bytes.extend_from_slice(&2u16.to_be_bytes());
+
+        type Pair<T> = [T; 2];
+        let archived =
+            access_explicit_endian::<Pair<u16_le>, Pair<u16_be>, Panic>(&bytes)
+                .unwrap();
+        let ByEndian::Big(values) = archived else {
+            panic!("expected a big-endian archive");
+        };
+        assert_eq!(values.map(|value| value.to_native()), [1, 2]);
+    }
+
+    mod v1 {
+        use crate::{alloc::string::String, Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, schema)]
+        pub struct Record {
+            pub id: u32,
+            pub name: String,
+        }
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, schema)]
+    struct Record {
+        id: u64,
+        name: String,
+    }
+
+    #[test]
+    fn framed_versioned() {
+        let value = v1::Record {
+            id: 7,
+            name: String::from("seven"),
+        };
+        let bytes = to_bytes_versioned::<_, Panic>(&value).unwrap();
+        let archived =
+            access_versioned::<v1::ArchivedRecord, Panic>(&bytes).unwrap();
+        assert_eq!(archived.id, 7);
+        assert_eq!(archived.name, "seven");
+
+        let error = access_versioned::<ArchivedRecord, rancor::Error>(&bytes)
+            .unwrap_err();
+        assert!(error.to_string().contains("type fingerprint"));
+
+        let unversioned = to_bytes::<Panic>(&value).unwrap();
+        assert!(
+            access_versioned::<v1::ArchivedRecord, Failure>(&unversioned)
+                .is_err()
+        );
+    }
+}
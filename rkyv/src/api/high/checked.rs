@@ -3,20 +3,32 @@
 //! These APIs have default writers, automatically manage allocators, and
 //! support shared pointers.
 
+use core::{fmt, marker::PhantomData, ops::Deref};
+
 use bytecheck::CheckBytes;
+use ptr_meta::Pointee;
 use rancor::{Source, Strategy};
 
 use crate::{
+    access_unchecked,
     api::{
-        access_pos_unchecked_mut, access_pos_with_context, access_with_context,
-        check_pos_with_context, deserialize_using, root_position,
+        access_pos_unchecked_mut, access_pos_unsized_with_context,
+        access_pos_with_context, access_with_context, check_pos_with_context,
+        check_subtree_with_context, deserialize_using,
+        high::{to_bytes, HighSerializer},
+        root_position,
     },
+    boxed::ArchivedBox,
     de::pooling::Pool,
     seal::Seal,
+    ser::allocator::ArenaHandle,
+    traits::{ArchivePointee, LayoutRaw},
+    util::AlignedVec,
     validation::{
         archive::ArchiveValidator, shared::SharedValidator, Validator,
+        ValidatorConfig,
     },
-    Archive, Deserialize, Portable,
+    Archive, Deserialize, Portable, Serialize,
 };
 
 /// A high-level validator.
@@ -115,6 +127,197 @@ where
     access_with_context::<_, _, E>(bytes, &mut validator(bytes))
 }
 
+/// Access a byte slice with a given root position and root metadata.
+///
+/// This is a safe alternative to
+/// [`access_pos_unsized_unchecked`](crate::api::access_pos_unsized_unchecked)
+/// and is part of the [high-level API](crate::api::high). See
+/// [`to_bytes_with_pos`](crate::api::high::to_bytes_with_pos) for an example.
+pub fn access_pos_unsized<T, E>(
+    bytes: &[u8],
+    pos: usize,
+    metadata: T::Metadata,
+) -> Result<&T, E>
+where
+    T: Portable
+        + for<'a> CheckBytes<HighValidator<'a, E>>
+        + LayoutRaw
+        + Pointee
+        + ?Sized,
+    E: Source,
+{
+    access_pos_unsized_with_context::<_, _, E>(
+        bytes,
+        pos,
+        metadata,
+        &mut validator(bytes),
+    )
+}
+
+/// Access an unsized root in a byte slice.
+///
+/// This is a safe alternative to
+/// [`access_unsized_unchecked`](crate::api::access_unsized_unchecked) and is
+/// part of the [high-level API](crate::api::high). See
+/// [`to_bytes_unsized`](crate::api::high::to_bytes_unsized) for an example.
+pub fn access_unsized<T, E>(bytes: &[u8]) -> Result<&T, E>
+where
+    T: ArchivePointee + Portable + ?Sized,
+    ArchivedBox<T>: for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    access::<ArchivedBox<T>, E>(bytes).map(ArchivedBox::get)
+}
+
+/// Access a byte slice which may not be aligned, copying it into `buffer` if
+/// necessary.
+///
+/// If `bytes` is aligned like an [`AlignedVec`], it is accessed in place.
+/// Otherwise, it is copied into `buffer` and accessed from there. With the
+/// `unaligned` feature, archives have no alignment requirements and `bytes` is
+/// always accessed in place. `buffer` can be reused between calls to avoid
+/// allocating each time a buffer needs to be copied.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::access_copy_if_unaligned, rancor::Error, to_bytes,
+///     util::AlignedVec, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// // Simulate a network buffer with an unaligned payload
+/// let bytes = to_bytes::<Error>(&value).unwrap();
+/// let mut packet = vec![0u8];
+/// packet.extend_from_slice(&bytes);
+///
+/// let mut buffer = AlignedVec::new();
+/// let archived = access_copy_if_unaligned::<ArchivedExample, Error>(
+///     &packet[1..],
+///     &mut buffer,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(archived.name, "pi");
+/// assert_eq!(archived.value, 31415926);
+/// ```
+pub fn access_copy_if_unaligned<'a, T, E>(
+    bytes: &'a [u8],
+    buffer: &'a mut AlignedVec,
+) -> Result<&'a T, E>
+where
+    T: Portable + for<'b> CheckBytes<HighValidator<'b, E>>,
+    E: Source,
+{
+    let aligned = bytes.as_ptr() as usize % AlignedVec::<16>::ALIGNMENT == 0;
+    if cfg!(feature = "unaligned") || aligned {
+        access::<T, E>(bytes)
+    } else {
+        buffer.clear();
+        buffer.extend_from_slice(bytes);
+        access::<T, E>(buffer)
+    }
+}
+
+/// Access a byte slice with a given root position, enforcing the limits of a
+/// [`ValidatorConfig`].
+///
+/// This is part of the [high-level API](crate::api::high).
+pub fn access_pos_with<'a, T, E>(
+    bytes: &'a [u8],
+    pos: usize,
+    config: &ValidatorConfig,
+) -> Result<&'a T, E>
+where
+    T: Portable + for<'b> CheckBytes<HighValidator<'b, E>>,
+    E: Source,
+{
+    access_pos_with_context::<_, _, E>(
+        bytes,
+        pos,
+        &mut Validator::with_config(bytes, config),
+    )
+}
+
+/// Access a byte slice, enforcing the limits of a [`ValidatorConfig`].
+///
+/// This is part of the [high-level API](crate::api::high). See
+/// [`ValidatorConfig`] for an example.
+pub fn access_with<'a, T, E>(
+    bytes: &'a [u8],
+    config: &ValidatorConfig,
+) -> Result<&'a T, E>
+where
+    T: Portable + for<'b> CheckBytes<HighValidator<'b, E>>,
+    E: Source,
+{
+    access_with_context::<_, _, E>(
+        bytes,
+        &mut Validator::with_config(bytes, config),
+    )
+}
+
+/// Check a single value located somewhere in a byte slice.
+///
+/// This validates `value` and everything reachable from it, without checking
+/// the rest of the archive. For large archives, this can be used to only check
+/// the parts which are actually used instead of the entire archive up front.
+///
+/// Archives are serialized depth-first, so `value` is checked as the root of
+/// an archive which ends at `value`. Because the rest of the archive is not
+/// checked, this does not verify that `value` doesn't overlap the data of other
+/// values. Returns an error if `value` is not located within `bytes`.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access_unchecked, api::high::check_subtree, rancor::Error, to_bytes,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: Box<str>,
+///     values: Vec<u32>,
+/// }
+///
+/// let value = Example {
+///     name: "pi".into(),
+///     values: (0..10_000).collect(),
+/// };
+///
+/// let bytes = to_bytes::<Error>(&value).unwrap();
+/// // SAFETY: The root of the archive was just serialized, so its own bytes are
+/// // valid. Only the values it points to are checked before they are used.
+/// let archived = unsafe { access_unchecked::<ArchivedExample>(&bytes) };
+///
+/// check_subtree::<_, Error>(&bytes, &archived.values).unwrap();
+/// assert_eq!(archived.values[1234], 1234);
+/// ```
+pub fn check_subtree<T, E>(bytes: &[u8], value: &T) -> Result<(), E>
+where
+    T: Portable + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    check_subtree_with_context::<_, _, E>(bytes, value, &mut validator(bytes))
+}
+
 /// Mutably access a byte slice with a given root position.
 ///
 /// This is a safe alternative to [`access_pos_unchecked_mut`] and is part of
@@ -256,3 +459,180 @@ where
     let mut deserializer = Pool::default();
     deserialize_using(access::<T::Archived, E>(bytes)?, &mut deserializer)
 }
+
+/// Compact an archive by re-serializing only the data reachable from its root.
+///
+/// Archives which are edited in place can accumulate bytes which are no longer
+/// reachable, like the elements past the end of a truncated vec or the values
+/// of tombstoned map entries. Compacting validates the archive, deserializes
+/// it, and serializes the result into a new buffer which contains only live
+/// data. Shared pointers which point to the same value in the original archive
+/// still point to the same value in the compacted archive.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::{compact, to_bytes_in},
+///     rancor::Error,
+///     util::AlignedVec,
+/// };
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+///
+/// // An archive with dead bytes before its data
+/// let mut writer = AlignedVec::<16>::new();
+/// writer.extend_from_slice(&[0; 64]);
+/// let bytes = to_bytes_in::<_, Error>(&value, writer).unwrap();
+///
+/// let compacted = compact::<Vec<String>, Error>(&bytes).unwrap();
+/// assert_eq!(compacted.len(), bytes.len() - 64);
+/// ```
+pub fn compact<T, E>(bytes: &[u8]) -> Result<AlignedVec, E>
+where
+    T: Archive
+        + for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+    T::Archived: for<'a> CheckBytes<HighValidator<'a, E>>
+        + Deserialize<T, Strategy<Pool, E>>,
+    E: Source,
+{
+    to_bytes(&from_bytes::<T, E>(bytes)?)
+}
+
+/// A byte buffer which has been validated as an archived `T`.
+///
+/// `CheckedArchive` validates its buffer once when it is created, and then
+/// accesses it without validation afterward. Because it owns its buffer, it
+/// can be stored and sent to other threads without needing to validate the
+/// buffer again or use `access_unchecked`. To share it between threads, wrap
+/// it in an `Arc`.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::CheckedArchive, rancor::Error, to_bytes, util::AlignedVec,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let bytes = to_bytes::<Error>(&value).unwrap();
+/// let archive =
+///     CheckedArchive::<AlignedVec, ArchivedExample>::new::<Error>(bytes)
+///         .unwrap();
+///
+/// std::thread::spawn(move || {
+///     assert_eq!(archive.name, "pi");
+///     assert_eq!(archive.value, 31415926);
+/// })
+/// .join()
+/// .unwrap();
+/// ```
+pub struct CheckedArchive<B, T> {
+    bytes: B,
+    address: usize,
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<B: Deref<Target = [u8]>, T: Portable> CheckedArchive<B, T> {
+    /// Validates the given buffer as an archived `T`.
+    pub fn new<E>(bytes: B) -> Result<Self, E>
+    where
+        T: for<'a> CheckBytes<HighValidator<'a, E>>,
+        E: Source,
+    {
+        access::<T, E>(&bytes)?;
+        // SAFETY: We just validated `bytes`.
+        unsafe { Ok(Self::new_unchecked(bytes)) }
+    }
+
+    /// Creates a `CheckedArchive` from the given buffer without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The bytes must pass validation when passed to [`access`].
+    pub unsafe fn new_unchecked(bytes: B) -> Self {
+        let address = bytes.as_ptr() as usize;
+        let len = bytes.len();
+        Self {
+            bytes,
+            address,
+            len,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the archived value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer now dereferences to different bytes than the ones
+    /// which were validated.
+    pub fn get(&self) -> &T {
+        let bytes = self.bytes();
+        // SAFETY: These exact bytes were validated when `self` was created,
+        // and they can't have been modified since because `self` owns them and
+        // only ever shares them immutably.
+        unsafe { access_unchecked::<T>(bytes) }
+    }
+
+    /// Returns the validated bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer now dereferences to different bytes than the ones
+    /// which were validated.
+    pub fn bytes(&self) -> &[u8] {
+        let bytes = &*self.bytes;
+        assert!(
+            bytes.as_ptr() as usize == self.address && bytes.len() == self.len,
+            "checked archive buffer dereferenced to different bytes than the \
+             ones which were validated",
+        );
+        bytes
+    }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.bytes
+    }
+}
+
+impl<B: Deref<Target = [u8]>, T: Portable> AsRef<T> for CheckedArchive<B, T> {
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<B: Deref<Target = [u8]>, T: Portable> Deref for CheckedArchive<B, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<B, T> fmt::Debug for CheckedArchive<B, T>
+where
+    B: Deref<Target = [u8]>,
+    T: Portable + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
@@ -10,21 +10,34 @@ use rancor::Strategy;
 
 #[cfg(feature = "bytecheck")]
 pub use self::checked::*;
+#[cfg(feature = "std")]
+use crate::ser::writer::{BufferedWriter, IoWriter};
 use crate::{
     access_unchecked,
     api::{deserialize_using, serialize_using},
-    de::Pool,
+    boxed::{ArchivedBox, BoxResolver},
+    de::{
+        Arena, ArenaDeserializer, DeserializeBorrowed, DeserializeInPlace, Pool,
+    },
     ser::{
-        allocator::ArenaHandle, sharing::Share, Allocator, Serializer, Writer,
+        allocator::ArenaHandle,
+        sharing::Share,
+        writer::{LimitedWriter, SizeWriter},
+        Allocator, Positional as _, Serializer, Writer,
     },
     util::{with_arena, AlignedVec},
-    Archive, Deserialize, Serialize,
+    Archive, ArchiveUnsized, Deserialize, Place, Serialize, SerializeUnsized,
 };
 
 /// A high-level serializer.
 ///
+/// The writer is wrapped in a [`LimitedWriter`], so serialization fails with an
+/// error instead of producing an archive which is too large for the enabled
+/// pointer width.
+///
 /// This is part of the [high-level API](crate::api::high).
-pub type HighSerializer<W, A, E> = Strategy<Serializer<W, A, Share>, E>;
+pub type HighSerializer<W, A, E> =
+    Strategy<Serializer<LimitedWriter<W>, A, Share>, E>;
 
 /// A high-level deserializer.
 ///
@@ -75,9 +88,14 @@ where
 
 /// Serialize a value and write the bytes to the given writer.
 ///
+/// The writer is returned after serializing, so buffers can be reused between
+/// calls to avoid allocating each time. Bytes are appended to the writer, so a
+/// reused [`AlignedVec`] should be cleared first. Scratch space is taken from
+/// a reused arena just like with [`to_bytes`].
+///
 /// This is part of the [high-level API](crate::api::high).
 ///
-/// # Example
+/// # Examples
 ///
 /// ```
 /// use rkyv::{
@@ -102,6 +120,19 @@ where
 ///
 /// assert_eq!(deserialized, value);
 /// ```
+///
+/// Reusing a buffer between calls:
+///
+/// ```
+/// use rkyv::{api::high::to_bytes_in, rancor::Error, util::AlignedVec};
+///
+/// let mut buffer = AlignedVec::<16>::new();
+/// for request in ["first", "second", "third"] {
+///     buffer.clear();
+///     buffer = to_bytes_in::<_, Error>(&request.to_string(), buffer).unwrap();
+///     // ... send `buffer` ...
+/// }
+/// ```
 pub fn to_bytes_in<W, E>(
     value: &impl for<'a> Serialize<HighSerializer<W, ArenaHandle<'a>, E>>,
     writer: W,
@@ -113,6 +144,202 @@ where
     with_arena(|arena| to_bytes_in_with_alloc(value, writer, arena.acquire()))
 }
 
+/// Serialize a value and stream the bytes to the given [`io::Write`] sink.
+///
+/// Small writes are coalesced in an internal buffer before they are passed to
+/// the sink, and the sink is flushed after serializing. Returns the position of
+/// the root, which is the default root position when the archive is read back
+/// on its own.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// [`io::Write`]: std::io::Write
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access, api::high::serialize_into, rancor::Error, util::AlignedVec,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     name: String,
+///     value: i32,
+/// }
+///
+/// let value = Example {
+///     name: "pi".to_string(),
+///     value: 31415926,
+/// };
+///
+/// let mut file = Vec::new();
+/// serialize_into::<_, Error>(&value, &mut file).unwrap();
+///
+/// let mut bytes = AlignedVec::<16>::new();
+/// bytes.extend_from_slice(&file);
+/// let archived = access::<ArchivedExample, Error>(&bytes).unwrap();
+/// assert_eq!(archived.name, "pi");
+/// ```
+#[cfg(feature = "std")]
+pub fn serialize_into<W, E>(
+    value: &impl for<'a> Serialize<
+        HighSerializer<BufferedWriter<IoWriter<W>, 8192>, ArenaHandle<'a>, E>,
+    >,
+    writer: W,
+) -> Result<usize, E>
+where
+    W: std::io::Write,
+    E: rancor::Source,
+{
+    use rancor::ResultExt as _;
+
+    with_arena(|arena| {
+        let writer = BufferedWriter::<_, 8192>::new(IoWriter::new(writer));
+        let mut serializer = Serializer::new(
+            LimitedWriter::new(writer),
+            arena.acquire(),
+            Share::new(),
+        );
+        let pos = serialize_using(value, &mut serializer)?;
+        let mut writer = serializer
+            .into_writer()
+            .into_inner()
+            .into_inner::<E>()?
+            .into_inner();
+        writer.flush().into_error()?;
+        Ok(pos)
+    })
+}
+
+/// Serialize a value to bytes and return the position of its root.
+///
+/// The value may be unsized, like `[T]` or `str`. The root position and the
+/// metadata of the value can be passed to
+/// [`access_pos_unsized`](crate::api::high::access_pos_unsized) to access it.
+/// Prefer [`to_bytes`] for sized values, which are placed at the default root
+/// position.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::{access_pos_unsized, to_bytes_with_pos},
+///     rancor::Error,
+/// };
+///
+/// let (bytes, pos) = to_bytes_with_pos::<Error>("hello world").unwrap();
+///
+/// let archived = access_pos_unsized::<str, Error>(&bytes, pos, 11).unwrap();
+/// assert_eq!(archived, "hello world");
+/// ```
+pub fn to_bytes_with_pos<E>(
+    value: &(impl for<'a> SerializeUnsized<
+        HighSerializer<AlignedVec, ArenaHandle<'a>, E>,
+    > + ?Sized),
+) -> Result<(AlignedVec, usize), E>
+where
+    E: rancor::Source,
+{
+    with_arena(|arena| {
+        let mut serializer = Serializer::new(
+            LimitedWriter::new(AlignedVec::new()),
+            arena.acquire(),
+            Share::new(),
+        );
+        let pos = value.serialize_unsized(Strategy::wrap(&mut serializer))?;
+        Ok((serializer.into_writer().into_inner(), pos))
+    })
+}
+
+struct UnsizedRoot<'v, T: ?Sized>(&'v T);
+
+impl<T: ArchiveUnsized + ?Sized> Archive for UnsizedRoot<'_, T> {
+    type Archived = ArchivedBox<T::Archived>;
+    type Resolver = BoxResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBox::resolve_from_ref(self.0, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for UnsizedRoot<'_, T>
+where
+    T: SerializeUnsized<S> + ?Sized,
+    S: rancor::Fallible + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBox::serialize_from_ref(self.0, serializer)
+    }
+}
+
+/// Serialize an unsized value to bytes.
+///
+/// The value may be unsized, like `[T]` or `str`. A pointer to the value is
+/// placed at the default root position, which stores both the position and
+/// the metadata of the value. The archived value can be accessed with
+/// [`access_unsized`](crate::api::high::access_unsized) without providing
+/// either of them.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::{access_unsized, to_bytes_unsized},
+///     rancor::Error,
+/// };
+///
+/// let bytes = to_bytes_unsized::<Error>("hello world").unwrap();
+///
+/// let archived = access_unsized::<str, Error>(&bytes).unwrap();
+/// assert_eq!(archived, "hello world");
+/// ```
+pub fn to_bytes_unsized<E>(
+    value: &(impl for<'a> SerializeUnsized<
+        HighSerializer<AlignedVec, ArenaHandle<'a>, E>,
+    > + ?Sized),
+) -> Result<AlignedVec, E>
+where
+    E: rancor::Source,
+{
+    to_bytes(&UnsizedRoot(value))
+}
+
+/// Returns the number of bytes that serializing a value would produce.
+///
+/// This runs the full serialization logic with a
+/// [`SizeWriter`], which only counts bytes instead of writing them.
+///
+/// This is part of the [high-level API](crate::api::high).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{api::high::serialized_size, rancor::Error, to_bytes};
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+/// let size = serialized_size::<Error>(&value).unwrap();
+/// assert_eq!(size, to_bytes::<Error>(&value).unwrap().len());
+/// ```
+pub fn serialized_size<E>(
+    #[rustfmt::skip] value: &impl for<'a> Serialize<
+        HighSerializer<SizeWriter, ArenaHandle<'a>, E>,
+    >,
+) -> Result<usize, E>
+where
+    E: rancor::Source,
+{
+    to_bytes_in(value, SizeWriter::new()).map(|writer| writer.pos())
+}
+
 /// Serialize a value using the given allocator.
 ///
 /// This is part of the [high-level API](crate::api::high).
@@ -206,9 +433,10 @@ where
     A: Allocator<E>,
     E: rancor::Source,
 {
-    let mut serializer = Serializer::new(writer, alloc, Share::new());
+    let mut serializer =
+        Serializer::new(LimitedWriter::new(writer), alloc, Share::new());
     serialize_using(value, &mut serializer)?;
-    Ok(serializer.into_writer())
+    Ok(serializer.into_writer().into_inner())
 }
 
 /// Deserialize a value from the given bytes.
@@ -295,6 +523,58 @@ pub fn deserialize<T, E>(
     deserialize_using(value, &mut Pool::new())
 }
 
+/// Deserialize a value which borrows from the given archived value.
+///
+/// This is part of the [high-level API](crate::api::high). See
+/// [`DeserializeBorrowed`] for more information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access, api::high::deserialize_borrowed, rancor::Error,
+///     string::ArchivedString, to_bytes,
+/// };
+///
+/// let bytes = to_bytes::<Error>(&"hello world".to_string()).unwrap();
+/// let archived = access::<ArchivedString, Error>(&bytes).unwrap();
+/// let borrowed = deserialize_borrowed::<&str, Error>(archived).unwrap();
+///
+/// assert_eq!(borrowed, "hello world");
+/// ```
+pub fn deserialize_borrowed<'a, T, E>(
+    value: &'a impl DeserializeBorrowed<'a, T, HighDeserializer<E>>,
+) -> Result<T, E> {
+    value.deserialize_borrowed(Strategy::wrap(&mut Pool::new()))
+}
+
+/// Deserialize the given archived value into an existing value, reusing its
+/// allocations.
+///
+/// This is part of the [high-level API](crate::api::high). See
+/// [`DeserializeInPlace`] for more information.
+pub fn deserialize_in_place<T, E>(
+    value: &impl DeserializeInPlace<T, HighDeserializer<E>>,
+    out: &mut T,
+) -> Result<(), E> {
+    value.deserialize_in_place(out, Strategy::wrap(&mut Pool::new()))
+}
+
+/// Deserialize a value, allocating fields which use
+/// [`InArena`](crate::with::InArena) from the given arena.
+///
+/// This is part of the [high-level API](crate::api::high). See
+/// [`RecyclingArena`](crate::de::RecyclingArena) for an example.
+pub fn deserialize_in_arena<'a, T, E>(
+    value: &impl Deserialize<
+        T,
+        Strategy<ArenaDeserializer<&'a mut (dyn Arena<E> + 'a)>, E>,
+    >,
+    arena: &'a mut (dyn Arena<E> + 'a),
+) -> Result<T, E> {
+    deserialize_using(value, &mut ArenaDeserializer::new(arena))
+}
+
 #[cfg(test)]
 mod tests {
     use rancor::Panic;
@@ -310,4 +590,199 @@ mod tests {
         let bytes = to_bytes_in::<_, Panic>(&value, Vec::new()).unwrap();
         assert!(!bytes.is_empty());
     }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn check_subtree_field() {
+        use rancor::Failure;
+
+        use crate::{
+            access_unchecked,
+            alloc::string::String,
+            api::high::{check_subtree, to_bytes},
+            Archive, Serialize,
+        };
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Example {
+            checked: String,
+            corrupted: String,
+        }
+
+        let value = Example {
+            checked: "a string long enough to be out of line".to_string(),
+            corrupted: "another string which is also out of line".to_string(),
+        };
+
+        let mut bytes = to_bytes::<Panic>(&value).unwrap();
+        let pos = bytes.windows(7).position(|w| w == b"another").unwrap();
+        bytes[pos] = 0xff;
+
+        let archived = unsafe { access_unchecked::<ArchivedExample>(&bytes) };
+        check_subtree::<_, Panic>(&bytes, &archived.checked).unwrap();
+        assert!(
+            check_subtree::<_, Failure>(&bytes, &archived.corrupted).is_err()
+        );
+
+        let other = to_bytes::<Panic>(&value).unwrap();
+        let other = unsafe { access_unchecked::<ArchivedExample>(&other) };
+        assert!(check_subtree::<_, Failure>(&bytes, &other.checked).is_err());
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn compact_shared() {
+        use crate::{
+            access,
+            alloc::{rc::Rc, string::String, vec},
+            api::high::compact,
+            util::AlignedVec,
+            Archived,
+        };
+
+        let shared = Rc::new(String::from("a string shared by both elements"));
+        let value = vec![shared.clone(), shared];
+
+        let mut writer = AlignedVec::<16>::new();
+        writer.extend_from_slice(&[0xaa; 256]);
+        let bytes = to_bytes_in::<_, Panic>(&value, writer).unwrap();
+
+        let compacted = compact::<Vec<Rc<String>>, Panic>(&bytes).unwrap();
+        assert_eq!(compacted.len(), bytes.len() - 256);
+
+        let archived =
+            access::<Archived<Vec<Rc<String>>>, Panic>(&compacted).unwrap();
+        assert_eq!(*archived[0], "a string shared by both elements");
+        assert!(core::ptr::eq(archived[0].get(), archived[1].get()));
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn checked_archive() {
+        use rancor::Failure;
+
+        use crate::{
+            api::high::{to_bytes, CheckedArchive},
+            string::ArchivedString,
+            util::AlignedVec,
+        };
+
+        let value = "a string long enough to be out of line".to_string();
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+        let archive =
+            CheckedArchive::<AlignedVec, ArchivedString>::new::<Panic>(bytes)
+                .unwrap();
+        assert_eq!(archive.get(), &value);
+        assert_eq!(archive.as_str(), value);
+
+        let mut bytes = archive.into_inner();
+        bytes[0] = 0xff;
+        assert!(
+            CheckedArchive::<AlignedVec, ArchivedString>::new::<Failure>(bytes)
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn unsized_root_pos() {
+        use rancor::Failure;
+
+        use crate::{
+            alloc::string::String,
+            api::high::{access_pos_unsized, to_bytes_with_pos},
+            string::ArchivedString,
+        };
+
+        let value = ["a".to_string(), "b".to_string(), "c".to_string()];
+        let (bytes, pos) = to_bytes_with_pos::<Panic>(&value[..]).unwrap();
+        let archived =
+            access_pos_unsized::<[ArchivedString], Panic>(&bytes, pos, 3)
+                .unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[2], "c");
+
+        let too_long =
+            access_pos_unsized::<[ArchivedString], Failure>(&bytes, pos, 1000);
+        assert!(too_long.is_err());
+
+        let (bytes, pos) = to_bytes_with_pos::<Panic>(&String::new()).unwrap();
+        let archived =
+            access_pos_unsized::<ArchivedString, Panic>(&bytes, pos, ())
+                .unwrap();
+        assert_eq!(archived, "");
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn unsized_root() {
+        use rancor::Failure;
+
+        use crate::{
+            alloc::string::String,
+            api::{
+                access_unsized_unchecked,
+                high::{access_unsized, to_bytes_unsized},
+            },
+            string::ArchivedString,
+            Archived,
+        };
+
+        let value = ["a".to_string(), "b".to_string(), "c".to_string()];
+        let bytes = to_bytes_unsized::<Panic>(&value[..]).unwrap();
+        let archived =
+            access_unsized::<[ArchivedString], Panic>(&bytes).unwrap();
+        assert_eq!(archived.len(), 3);
+        assert_eq!(archived[2], "c");
+
+        let bytes = to_bytes_unsized::<Panic>(&[1u32, 2, 3][..]).unwrap();
+        // SAFETY: `bytes` was just serialized from a `[u32]`.
+        let archived =
+            unsafe { access_unsized_unchecked::<[Archived<u32>]>(&bytes) };
+        assert_eq!(archived, [1, 2, 3]);
+
+        let mut bytes = to_bytes_unsized::<Panic>("hello").unwrap();
+        let len = bytes.len();
+        bytes[len - 1] = 0xff;
+        assert!(access_unsized::<str, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes_unsized::<Panic>(&String::new()).unwrap();
+        let archived = access_unsized::<ArchivedString, Panic>(&bytes).unwrap();
+        assert_eq!(archived, "");
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn access_unaligned_copy() {
+        use crate::{
+            api::high::{access_copy_if_unaligned, to_bytes},
+            string::ArchivedString,
+            util::AlignedVec,
+        };
+
+        let value = "a string long enough to be out of line".to_string();
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+        let mut buffer = AlignedVec::new();
+
+        let archived = access_copy_if_unaligned::<ArchivedString, Panic>(
+            &bytes,
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(archived, &value);
+        assert!(buffer.is_empty());
+
+        let mut shifted = AlignedVec::<16>::new();
+        shifted.push(0);
+        shifted.extend_from_slice(&bytes);
+        let archived = access_copy_if_unaligned::<ArchivedString, Panic>(
+            &shifted[1..],
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(archived, &value);
+        #[cfg(not(feature = "unaligned"))]
+        assert_eq!(buffer.as_slice(), bytes.as_slice());
+    }
 }
@@ -0,0 +1,215 @@
+//! APIs for accessing archives in memory-mapped files.
+//!
+//! Mapped archives own their mapping, so the archived value can be accessed
+//! for as long as the handle is alive. The mapping is checked for alignment and
+//! validated when the file is opened, and the archived value is accessed
+//! without copying the file into memory.
+
+use core::{error::Error, fmt, marker::PhantomData};
+use std::{fs::OpenOptions, path::Path};
+
+use bytecheck::CheckBytes;
+use memmap2_0_9::{Mmap, MmapMut};
+use rancor::{fail, ResultExt as _, Source};
+
+use crate::{
+    api::{
+        access_unchecked, access_unchecked_mut,
+        high::{access_mut, CheckedArchive, HighValidator},
+    },
+    seal::Seal,
+    util::AlignedVec,
+    Portable,
+};
+
+/// A validated archive in a read-only memory-mapped file.
+pub type MappedArchive<T> = CheckedArchive<Mmap, T>;
+
+#[derive(Debug)]
+struct UnalignedMappingError {
+    address: usize,
+}
+
+impl fmt::Display for UnalignedMappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "memory mapping at {:#x} is not aligned to {} bytes",
+            self.address,
+            AlignedVec::<16>::ALIGNMENT,
+        )
+    }
+}
+
+impl Error for UnalignedMappingError {}
+
+fn check_alignment<E: Source>(bytes: &[u8]) -> Result<(), E> {
+    let address = bytes.as_ptr() as usize;
+    if address % AlignedVec::<16>::ALIGNMENT != 0 {
+        fail!(UnalignedMappingError { address });
+    }
+    Ok(())
+}
+
+/// Maps the file at the given path and validates it as an archived `T`.
+///
+/// # Safety
+///
+/// The file must not be modified or truncated by any other code (including
+/// other processes) while the returned archive is alive.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{api::mmap::open, rancor::Error, to_bytes, Archive, Serialize};
+///
+/// #[derive(Archive, Serialize)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let path = std::env::temp_dir().join("rkyv_mmap_open_example");
+/// let config = Config {
+///     name: "edge".to_string(),
+///     retries: 3,
+/// };
+/// std::fs::write(&path, to_bytes::<Error>(&config).unwrap()).unwrap();
+///
+/// // SAFETY: Nothing else modifies the file while it is mapped.
+/// let archived = unsafe { open::<ArchivedConfig, Error>(&path) }.unwrap();
+/// assert_eq!(archived.name, "edge");
+/// assert_eq!(archived.retries, 3);
+/// # drop(archived);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub unsafe fn open<T, E>(path: impl AsRef<Path>) -> Result<MappedArchive<T>, E>
+where
+    T: Portable + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    let file = OpenOptions::new().read(true).open(path).into_error()?;
+    // SAFETY: The caller has guaranteed that the file is not modified while
+    // the mapping is alive.
+    let mmap = unsafe { Mmap::map(&file) }.into_error()?;
+    check_alignment::<E>(&mmap)?;
+    CheckedArchive::new::<E>(mmap)
+}
+
+/// A validated archive in a writable memory-mapped file.
+///
+/// Changes made through [`get_mut`](MappedArchiveMut::get_mut) are written
+/// back to the file when the mapping is [flushed](MappedArchiveMut::flush) or
+/// dropped.
+pub struct MappedArchiveMut<T> {
+    mmap: MmapMut,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Portable> MappedArchiveMut<T> {
+    /// Returns the archived value.
+    pub fn get(&self) -> &T {
+        // SAFETY: The mapping was validated when it was opened, and can only
+        // be modified through a `Seal` since then.
+        unsafe { access_unchecked::<T>(&self.mmap) }
+    }
+
+    /// Returns a sealed mutable reference to the archived value.
+    pub fn get_mut(&mut self) -> Seal<'_, T> {
+        // SAFETY: The mapping was validated when it was opened, and can only
+        // be modified through a `Seal` since then.
+        unsafe { access_unchecked_mut::<T>(&mut self.mmap) }
+    }
+
+    /// Flushes outstanding changes to the file.
+    pub fn flush<E: Source>(&self) -> Result<(), E> {
+        self.mmap.flush().into_error()
+    }
+
+    /// Returns the underlying mapping.
+    pub fn into_inner(self) -> MmapMut {
+        self.mmap
+    }
+}
+
+impl<T: Portable> core::ops::Deref for MappedArchiveMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+/// Maps the file at the given path for reading and writing, and validates it
+/// as an archived `T`.
+///
+/// # Safety
+///
+/// The file must not be modified, truncated, or mapped by any other code
+/// (including other processes) while the returned archive is alive.
+pub unsafe fn open_mut<T, E>(
+    path: impl AsRef<Path>,
+) -> Result<MappedArchiveMut<T>, E>
+where
+    T: Portable + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .into_error()?;
+    // SAFETY: The caller has guaranteed that the file is not modified or
+    // mapped by any other code while the mapping is alive.
+    let mut mmap = unsafe { MmapMut::map_mut(&file) }.into_error()?;
+    check_alignment::<E>(&mmap)?;
+    access_mut::<T, E>(&mut mmap)?;
+    Ok(MappedArchiveMut {
+        mmap,
+        _phantom: PhantomData,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use rancor::{Failure, Panic};
+
+    use super::{open, open_mut};
+    use crate::{
+        alloc::vec::Vec, api::high::to_bytes, vec::ArchivedVec, Archived,
+    };
+
+    #[test]
+    fn mmap_open() {
+        let path = std::env::temp_dir()
+            .join(format!("rkyv_mmap_open_{}", std::process::id()));
+        let value = (0..100u32).collect::<Vec<_>>();
+        fs::write(&path, to_bytes::<Panic>(&value).unwrap()).unwrap();
+
+        type Root = ArchivedVec<Archived<u32>>;
+
+        // SAFETY: The file is only used by this test.
+        let archived = unsafe { open::<Root, Panic>(&path) }.unwrap();
+        assert_eq!(archived.as_slice(), value.as_slice());
+        drop(archived);
+
+        // SAFETY: The file is only used by this test.
+        let mut archived = unsafe { open_mut::<Root, Panic>(&path) }.unwrap();
+        let slice = ArchivedVec::as_slice_seal(archived.get_mut());
+        *slice.index(7) = 700u32.into();
+        archived.flush::<Panic>().unwrap();
+        drop(archived);
+
+        // SAFETY: The file is only used by this test.
+        let archived = unsafe { open::<Root, Panic>(&path) }.unwrap();
+        assert_eq!(archived[7], 700);
+        drop(archived);
+
+        fs::write(&path, [0xff; 3]).unwrap();
+        // SAFETY: The file is only used by this test.
+        assert!(unsafe { open::<Root, Failure>(&path) }.is_err());
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -18,19 +18,31 @@
 
 #[cfg(feature = "bytecheck")]
 mod checked;
+#[cfg(feature = "std")]
+pub mod chunked;
+#[cfg(feature = "alloc")]
+pub mod framed;
 #[cfg(feature = "alloc")]
 pub mod high;
 pub mod low;
+#[cfg(all(feature = "memmap2-0_9", feature = "bytecheck"))]
+pub mod mmap;
+#[cfg(feature = "alloc")]
+pub mod multi;
 #[cfg(test)]
 pub mod test;
 
 use core::mem::size_of;
 
+use ptr_meta::Pointee;
 use rancor::Strategy;
 
 #[cfg(feature = "bytecheck")]
 pub use self::checked::*;
-use crate::{seal::Seal, ser::Writer, Deserialize, Portable, SerializeUnsized};
+use crate::{
+    boxed::ArchivedBox, seal::Seal, ser::Writer, traits::ArchivePointee,
+    Deserialize, Portable, SerializeUnsized,
+};
 
 #[cfg(debug_assertions)]
 fn sanity_check_buffer<T: Portable>(ptr: *const u8, pos: usize, size: usize) {
@@ -161,6 +173,102 @@ pub unsafe fn access_pos_unchecked<T: Portable>(
     unsafe { &*bytes.as_ptr().add(pos).cast() }
 }
 
+/// Access a byte slice with a given root position and root metadata.
+///
+/// This is like [`access_pos_unchecked`], but also supports unsized root types
+/// like `[T]` and `str`. The pointer metadata of an unsized root isn't stored
+/// in the archive, so it must be provided. For slices and strings, the
+/// metadata is the length. For sized types, the metadata is `()`.
+///
+/// The root position is returned by [`serialize_using`] and
+/// [`to_bytes_with_pos`](high::to_bytes_with_pos), which makes it possible to
+/// access archives which are embedded at arbitrary offsets in larger buffers.
+///
+/// This function does not check that the bytes are valid to access. Use
+/// [`access_pos_unsized`](high::access_pos_unsized) to safely access the
+/// buffer using validation.
+///
+/// # Safety
+///
+/// The byte slice must represent a valid archived type when accessed with the
+/// given root position and metadata. See the [module docs](crate::api) for
+/// more information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::{access_pos_unsized_unchecked, high::to_bytes_with_pos},
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let values = [1u32, 2, 3];
+/// let (bytes, pos) = to_bytes_with_pos::<Error>(&values[..]).unwrap();
+///
+/// let archived = unsafe {
+///     access_pos_unsized_unchecked::<[Archived<u32>]>(&bytes, pos, 3)
+/// };
+/// assert_eq!(archived.len(), 3);
+/// assert_eq!(archived[2], 3);
+/// ```
+pub unsafe fn access_pos_unsized_unchecked<T>(
+    bytes: &[u8],
+    pos: usize,
+    metadata: T::Metadata,
+) -> &T
+where
+    T: Portable + Pointee + ?Sized,
+{
+    let ptr = ptr_meta::from_raw_parts::<T>(
+        bytes.as_ptr().wrapping_add(pos).cast(),
+        metadata,
+    );
+    // SAFETY: The caller has guaranteed that a valid `T` with the given
+    // metadata is located at `pos` in the byte slice.
+    unsafe { &*ptr }
+}
+
+/// Access an unsized root in a byte slice.
+///
+/// The byte slice must have been serialized with
+/// [`to_bytes_unsized`](high::to_bytes_unsized), which stores the position and
+/// metadata of the unsized root in the archive.
+///
+/// This function does not check that the bytes are valid to access. Use
+/// [`access_unsized`](high::access_unsized) to safely access the buffer using
+/// validation.
+///
+/// # Safety
+///
+/// The byte slice must represent a valid archive with an unsized `T` at its
+/// root. See the [module docs](crate::api) for more information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::{access_unsized_unchecked, high::to_bytes_unsized},
+///     rancor::Error,
+///     Archived,
+/// };
+///
+/// let values = [1u32, 2, 3];
+/// let bytes = to_bytes_unsized::<Error>(&values[..]).unwrap();
+///
+/// let archived =
+///     unsafe { access_unsized_unchecked::<[Archived<u32>]>(&bytes) };
+/// assert_eq!(archived, [1, 2, 3]);
+/// ```
+pub unsafe fn access_unsized_unchecked<T>(bytes: &[u8]) -> &T
+where
+    T: ArchivePointee + Portable + ?Sized,
+{
+    // SAFETY: The caller has guaranteed that the byte slice has an unsized
+    // `T` at its root, which is stored behind an `ArchivedBox`.
+    unsafe { access_unchecked::<ArchivedBox<T>>(bytes) }.get()
+}
+
 /// Mutably access a byte slice with a given root position.
 ///
 /// Most of the time, the root position should be calculated using the root type
@@ -0,0 +1,516 @@
+//! APIs for archives which contain multiple independent roots.
+//!
+//! A multi-root archive is a sequence of serialized roots followed by a table
+//! of their positions. The table is itself an archived `Vec<usize>` and is the
+//! last object in the buffer, so it can be found at the default root position.
+//!
+//! All of the roots in an archive are serialized with the same serializer, so
+//! they share an arena and any shared pointers which are reachable from more
+//! than one root are only serialized once.
+//!
+//! Existing archives can also be appended as roots without deserializing them.
+//! Relative pointers are relative to their own position, so an archive which
+//! is copied to a suitably aligned position in another buffer stays valid and
+//! only the position of its root needs to be recorded.
+
+use rancor::Source;
+
+use crate::{
+    access_unchecked,
+    alloc::vec::Vec,
+    api::{
+        access_pos_unchecked, high::HighSerializer, root_position,
+        serialize_using,
+    },
+    ser::{
+        allocator::ArenaHandle, sharing::Share, writer::LimitedWriter,
+        Positional as _, Serializer, Writer, WriterExt as _,
+    },
+    util::{with_arena, AlignedVec},
+    Archived, Portable, Serialize,
+};
+
+/// The alignment that appended archives are written at.
+///
+/// Objects in an appended archive keep their positions relative to the start of
+/// the archive, so they stay aligned as long as none of them were aligned to
+/// more than this. This is the largest alignment used by
+/// [`AsBytes`](crate::with::AsBytes) and [`Align`](crate::with::Align) in
+/// practice, and covers the alignment of every primitive.
+pub const APPEND_ALIGNMENT: usize = 64;
+
+/// A serializer which writes multiple independent roots to a single archive.
+///
+/// Each call to [`serialize`](RootsSerializer::serialize) adds a new root and
+/// returns its index. Call [`finish`](RootsSerializer::finish) after
+/// serializing all of the roots to write the offset table.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::multi::{access_root, RootsSerializer},
+///     rancor::Error,
+///     string::ArchivedString,
+///     util::{with_arena, AlignedVec},
+///     Archived,
+/// };
+///
+/// let bytes = with_arena(|arena| {
+///     let mut roots =
+///         RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+///     roots.serialize::<_, Error>(&"hello".to_string()).unwrap();
+///     roots.serialize::<_, Error>(&42u32).unwrap();
+///     roots.finish::<Error>().unwrap()
+/// });
+///
+/// let first = access_root::<ArchivedString, Error>(&bytes, 0).unwrap();
+/// assert_eq!(first, "hello");
+/// let second = access_root::<Archived<u32>, Error>(&bytes, 1).unwrap();
+/// assert_eq!(*second, 42);
+/// ```
+pub struct RootsSerializer<'a, W> {
+    serializer: Serializer<LimitedWriter<W>, ArenaHandle<'a>, Share>,
+    positions: Vec<usize>,
+}
+
+impl<'a, W> RootsSerializer<'a, W> {
+    /// Creates a new roots serializer from a writer and arena handle.
+    pub fn new(writer: W, arena: ArenaHandle<'a>) -> Self {
+        Self {
+            serializer: Serializer::new(
+                LimitedWriter::new(writer),
+                arena,
+                Share::new(),
+            ),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Returns the number of roots which have been serialized so far.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns whether no roots have been serialized yet.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Serializes a new root and returns its index.
+    pub fn serialize<T, E>(&mut self, value: &T) -> Result<usize, E>
+    where
+        T: Serialize<HighSerializer<W, ArenaHandle<'a>, E>>,
+        W: Writer<E>,
+        E: Source,
+    {
+        let pos = serialize_using::<_, E>(value, &mut self.serializer)?;
+        self.positions.push(pos);
+        Ok(self.positions.len() - 1)
+    }
+
+    /// Appends an existing archive with a root of type `T` and returns the
+    /// index of its root.
+    ///
+    /// The archive is copied without being deserialized. Shared pointers in the
+    /// appended archive are not deduplicated with those in other roots. The
+    /// appended archive is not validated, but its root is validated when it is
+    /// accessed with [`access_root`].
+    ///
+    /// The archive is written at a position aligned to [`APPEND_ALIGNMENT`].
+    /// Returns an error if `T` requires a greater alignment. Archives which
+    /// contain other objects aligned to more than `APPEND_ALIGNMENT` can't be
+    /// appended.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rkyv::{
+    ///     api::multi::{access_root, RootsSerializer},
+    ///     rancor::Error,
+    ///     string::ArchivedString,
+    ///     to_bytes,
+    ///     util::{with_arena, AlignedVec},
+    ///     Archived,
+    /// };
+    ///
+    /// let snapshot = to_bytes::<Error>(&"yesterday".to_string()).unwrap();
+    ///
+    /// let bytes = with_arena(|arena| {
+    ///     let mut roots =
+    ///         RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+    ///     roots.serialize::<_, Error>(&7u32).unwrap();
+    ///     roots
+    ///         .append_archive::<ArchivedString, Error>(&snapshot)
+    ///         .unwrap();
+    ///     roots.finish::<Error>().unwrap()
+    /// });
+    ///
+    /// let first = access_root::<Archived<u32>, Error>(&bytes, 0).unwrap();
+    /// assert_eq!(*first, 7);
+    /// let second = access_root::<ArchivedString, Error>(&bytes, 1).unwrap();
+    /// assert_eq!(second, "yesterday");
+    /// ```
+    pub fn append_archive<T, E>(&mut self, bytes: &[u8]) -> Result<usize, E>
+    where
+        T: Portable,
+        W: Writer<E>,
+        E: Source,
+    {
+        use core::{error::Error, fmt, mem::align_of};
+
+        use rancor::fail;
+
+        #[derive(Debug)]
+        struct AppendAlignmentError {
+            align: usize,
+        }
+
+        impl fmt::Display for AppendAlignmentError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "archived root type requires {}-byte alignment, but \
+                     appended archives are only aligned to {} bytes",
+                    self.align, APPEND_ALIGNMENT,
+                )
+            }
+        }
+
+        impl Error for AppendAlignmentError {}
+
+        if align_of::<T>() > APPEND_ALIGNMENT {
+            fail!(AppendAlignmentError {
+                align: align_of::<T>(),
+            });
+        }
+
+        let base = self.append_bytes::<E>(bytes)?;
+        self.positions.push(base + root_position::<T>(bytes.len()));
+        Ok(self.positions.len() - 1)
+    }
+
+    /// Appends all of the roots of an existing multi-root archive and returns
+    /// the range of their new indices.
+    ///
+    /// The offset table of the appended archive is validated, but its roots
+    /// are not. They are validated when they are accessed with
+    /// [`access_root`]. Like [`append_archive`](Self::append_archive), the
+    /// archive is written at a position aligned to [`APPEND_ALIGNMENT`].
+    #[cfg(feature = "bytecheck")]
+    pub fn append_roots<E>(
+        &mut self,
+        bytes: &[u8],
+    ) -> Result<core::ops::Range<usize>, E>
+    where
+        W: Writer<E>,
+        E: Source,
+    {
+        let table = crate::api::high::access::<Archived<Vec<usize>>, E>(bytes)?;
+        let base = self.append_bytes::<E>(bytes)?;
+        let start = self.positions.len();
+        self.positions
+            .extend(table.iter().map(|pos| base + pos.to_native() as usize));
+        Ok(start..self.positions.len())
+    }
+
+    fn append_bytes<E>(&mut self, bytes: &[u8]) -> Result<usize, E>
+    where
+        W: Writer<E>,
+        E: Source,
+    {
+        self.serializer.align(APPEND_ALIGNMENT)?;
+        let base = self.serializer.pos();
+        self.serializer.write(bytes)?;
+        Ok(base)
+    }
+
+    /// Writes the offset table and returns the writer.
+    pub fn finish<E>(mut self) -> Result<W, E>
+    where
+        W: Writer<E>,
+        E: Source,
+    {
+        serialize_using::<_, E>(&self.positions, &mut self.serializer)?;
+        Ok(self.serializer.into_writer().into_inner())
+    }
+}
+
+/// Serializes a sequence of values as the roots of a single archive.
+///
+/// The root at index `i` can be accessed with [`access_root`] or
+/// [`access_root_unchecked`].
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::multi::{access_root, serialize_roots},
+///     rancor::Error,
+///     vec::ArchivedVec,
+///     Archived,
+/// };
+///
+/// let roots = [vec![1u32, 2], vec![3, 4, 5]];
+/// let bytes = serialize_roots::<_, Error>(&roots).unwrap();
+///
+/// let second =
+///     access_root::<ArchivedVec<Archived<u32>>, Error>(&bytes, 1).unwrap();
+/// assert_eq!(second.as_slice(), &[3, 4, 5]);
+/// ```
+pub fn serialize_roots<T, E>(roots: &[T]) -> Result<AlignedVec, E>
+where
+    T: for<'a> Serialize<HighSerializer<AlignedVec, ArenaHandle<'a>, E>>,
+    E: Source,
+{
+    with_arena(|arena| {
+        let mut serializer =
+            RootsSerializer::new(AlignedVec::new(), arena.acquire());
+        for root in roots {
+            serializer.serialize(root)?;
+        }
+        serializer.finish()
+    })
+}
+
+/// Returns the number of roots in a multi-root archive.
+///
+/// # Safety
+///
+/// The byte slice must represent a valid multi-root archive. See the
+/// [module docs](crate::api) for more information.
+pub unsafe fn root_count_unchecked(bytes: &[u8]) -> usize {
+    // SAFETY: The caller has guaranteed that the bytes are a valid multi-root
+    // archive, which ends with an archived offset table.
+    unsafe { access_unchecked::<Archived<Vec<usize>>>(bytes).len() }
+}
+
+/// Accesses the root at the given index of a multi-root archive.
+///
+/// This function does not check that the bytes are valid to access. Use
+/// [`access_root`] to safely access the root using validation.
+///
+/// # Safety
+///
+/// The byte slice must represent a valid multi-root archive, and the root at
+/// `index` must be a valid `T`. See the [module docs](crate::api) for more
+/// information.
+///
+/// # Panics
+///
+/// Panics if `index` is not less than the number of roots in the archive.
+pub unsafe fn access_root_unchecked<T: Portable>(
+    bytes: &[u8],
+    index: usize,
+) -> &T {
+    // SAFETY: The caller has guaranteed that the bytes are a valid multi-root
+    // archive, which ends with an archived offset table.
+    let table = unsafe { access_unchecked::<Archived<Vec<usize>>>(bytes) };
+    let pos = table[index].to_native() as usize;
+    // SAFETY: The caller has guaranteed that the root at `index` is a valid
+    // `T`.
+    unsafe { access_pos_unchecked::<T>(bytes, pos) }
+}
+
+/// Returns the number of roots in a multi-root archive.
+///
+/// This is a safe alternative to [`root_count_unchecked`] which validates the
+/// offset table.
+#[cfg(feature = "bytecheck")]
+pub fn root_count<E: Source>(bytes: &[u8]) -> Result<usize, E> {
+    crate::api::high::access::<Archived<Vec<usize>>, E>(bytes)
+        .map(|table| table.len())
+}
+
+/// Accesses the root at the given index of a multi-root archive.
+///
+/// This is a safe alternative to [`access_root_unchecked`]. The offset table
+/// and the requested root are validated, but other roots are not.
+#[cfg(feature = "bytecheck")]
+pub fn access_root<T, E>(bytes: &[u8], index: usize) -> Result<&T, E>
+where
+    T: Portable
+        + for<'a> bytecheck::CheckBytes<crate::api::high::HighValidator<'a, E>>,
+    E: Source,
+{
+    use core::{error::Error, fmt};
+
+    use rancor::fail;
+
+    let table = crate::api::high::access::<Archived<Vec<usize>>, E>(bytes)?;
+    let Some(pos) = table.get(index) else {
+        #[derive(Debug)]
+        struct RootIndexOutOfBounds {
+            index: usize,
+            len: usize,
+        }
+
+        impl fmt::Display for RootIndexOutOfBounds {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "root index {} out of bounds for archive with {} roots",
+                    self.index, self.len,
+                )
+            }
+        }
+
+        impl Error for RootIndexOutOfBounds {}
+
+        fail!(RootIndexOutOfBounds {
+            index,
+            len: table.len(),
+        });
+    };
+    crate::api::high::access_pos::<T, E>(bytes, pos.to_native() as usize)
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    use rancor::{Failure, Panic};
+
+    use super::{access_root, root_count, serialize_roots, RootsSerializer};
+    use crate::{
+        alloc::{
+            rc::Rc,
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        string::ArchivedString,
+        util::{with_arena, AlignedVec},
+        with::Align,
+        Archive, Archived, Portable, Serialize,
+    };
+
+    type Root = (u32, Rc<String>);
+
+    #[test]
+    fn multi_root_shares_pointers() {
+        let shared = Rc::new("shared".to_string());
+        let bytes = with_arena(|arena| {
+            let mut roots =
+                RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+            for i in 0..3u32 {
+                let index =
+                    roots.serialize::<_, Panic>(&(i, shared.clone())).unwrap();
+                assert_eq!(index, i as usize);
+            }
+            roots.finish::<Panic>().unwrap()
+        });
+
+        assert_eq!(root_count::<Panic>(&bytes).unwrap(), 3);
+
+        let mut addresses = Vec::new();
+        for i in 0..3 {
+            let root = access_root::<Archived<Root>, Panic>(&bytes, i).unwrap();
+            assert_eq!(root.0, i as u32);
+            assert_eq!(*root.1, "shared");
+            addresses.push(&*root.1 as *const ArchivedString);
+        }
+        assert!(addresses.iter().all(|a| *a == addresses[0]));
+
+        assert!(access_root::<Archived<Root>, Failure>(&bytes, 3).is_err());
+    }
+
+    #[test]
+    fn serialize_roots_slice() {
+        let roots = vec![vec![1u8], vec![2, 3], vec![]];
+        let bytes = serialize_roots::<_, Panic>(&roots).unwrap();
+        for (i, root) in roots.iter().enumerate() {
+            let archived =
+                access_root::<Archived<Vec<u8>>, Panic>(&bytes, i).unwrap();
+            assert_eq!(archived.as_slice(), root.as_slice());
+        }
+    }
+
+    #[test]
+    fn append_archives() {
+        let shared = Rc::new("shared".to_string());
+        let first = serialize_roots::<_, Panic>(&[
+            (0u32, shared.clone()),
+            (1u32, shared),
+        ])
+        .unwrap();
+        let second =
+            crate::to_bytes::<Panic>(&(2u32, Rc::new("single".to_string())))
+                .unwrap();
+
+        let bytes = with_arena(|arena| {
+            // Start at a misaligned position to check that appended archives
+            // are realigned.
+            let mut roots =
+                RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+            roots.serialize::<_, Panic>(&0xffu8).unwrap();
+            assert_eq!(roots.append_roots::<Panic>(&first).unwrap(), 1..3);
+            assert_eq!(
+                roots
+                    .append_archive::<Archived<Root>, Panic>(&second)
+                    .unwrap(),
+                3
+            );
+            roots.finish::<Panic>().unwrap()
+        });
+
+        assert_eq!(root_count::<Panic>(&bytes).unwrap(), 4);
+        assert_eq!(
+            *access_root::<Archived<u8>, Panic>(&bytes, 0).unwrap(),
+            0xff
+        );
+        let a = access_root::<Archived<Root>, Panic>(&bytes, 1).unwrap();
+        let b = access_root::<Archived<Root>, Panic>(&bytes, 2).unwrap();
+        let c = access_root::<Archived<Root>, Panic>(&bytes, 3).unwrap();
+        assert_eq!((a.0, b.0, c.0), (0, 1, 2));
+        assert!(core::ptr::eq(a.1.get(), b.1.get()));
+        assert_eq!(*c.1, "single");
+
+        with_arena(|arena| {
+            let mut roots =
+                RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+            assert!(roots.append_roots::<Failure>(&[0xff; 3]).is_err());
+        });
+    }
+
+    #[test]
+    fn append_over_aligned() {
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Samples {
+            #[rkyv(with = Align<64>)]
+            bytes: Vec<u8>,
+        }
+
+        let value = Samples {
+            bytes: vec![1, 2, 3],
+        };
+        let archive = crate::to_bytes::<Panic>(&value).unwrap();
+
+        let bytes = with_arena(|arena| {
+            let mut roots =
+                RootsSerializer::new(AlignedVec::<64>::new(), arena.acquire());
+            roots.serialize::<_, Panic>(&[0xffu8; 17]).unwrap();
+            roots
+                .append_archive::<ArchivedSamples, Panic>(&archive)
+                .unwrap();
+            roots.finish::<Panic>().unwrap()
+        });
+
+        let root = access_root::<ArchivedSamples, Panic>(&bytes, 1).unwrap();
+        assert_eq!(root.bytes.as_slice(), &[1, 2, 3]);
+        assert_eq!(root.bytes.as_ptr() as usize % 64, 0);
+
+        #[derive(Portable)]
+        #[rkyv(crate)]
+        #[repr(C, align(128))]
+        #[allow(dead_code)]
+        struct TooAligned(u8);
+
+        with_arena(|arena| {
+            let mut roots =
+                RootsSerializer::new(AlignedVec::<16>::new(), arena.acquire());
+            assert!(roots
+                .append_archive::<TooAligned, Failure>(&[0; 128])
+                .is_err());
+        });
+    }
+}
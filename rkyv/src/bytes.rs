@@ -0,0 +1,274 @@
+//! An archived byte buffer with an aligned payload.
+
+use core::{borrow::Borrow, fmt, hash, ops::Deref};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::{Writer, WriterExt as _},
+    Archive, Place, Portable, RelPtr,
+};
+
+/// An archived buffer of raw bytes.
+///
+/// The payload of an `ArchivedBytes` always starts on an `ALIGN`-byte boundary
+/// relative to the start of the archive. As long as the archive buffer is
+/// itself aligned to at least `ALIGN` bytes, the payload can be handed directly
+/// to code which requires aligned input, like SIMD routines.
+///
+/// Unlike [`ArchivedVec<u8>`](crate::vec::ArchivedVec), validating an
+/// `ArchivedBytes` only checks that the payload is in bounds and properly
+/// aligned. The bytes themselves are never inspected.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedBytes<const ALIGN: usize = 1> {
+    ptr: RelPtr<u8>,
+    len: ArchivedUsize,
+}
+
+impl<const ALIGN: usize> ArchivedBytes<ALIGN> {
+    /// The alignment of the payload in bytes.
+    pub const ALIGN: usize = ALIGN;
+
+    /// Returns a pointer to the first byte of the payload.
+    #[inline]
+    pub fn as_ptr(&self) -> *const u8 {
+        unsafe { self.ptr.as_ptr() }
+    }
+
+    /// Returns the length of the payload in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the payload is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the payload as a slice of bytes.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Returns the payload as a sealed mutable slice of bytes.
+    pub fn as_slice_seal(this: Seal<'_, Self>) -> Seal<'_, [u8]> {
+        let len = this.len();
+        munge!(let Self { ptr, .. } = this);
+        let slice = unsafe {
+            core::slice::from_raw_parts_mut(RelPtr::as_mut_ptr(ptr), len)
+        };
+        Seal::new(slice)
+    }
+
+    /// Resolves an archived byte buffer from the given bytes and parameters.
+    #[inline]
+    pub fn resolve_from_bytes(
+        bytes: &[u8],
+        resolver: BytesResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedBytes { ptr, len } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+        usize::resolve(&bytes.len(), (), len);
+    }
+
+    /// Serializes the payload of an archived byte buffer from the given bytes.
+    ///
+    /// The serializer is padded to an `ALIGN`-byte boundary before the bytes
+    /// are written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ALIGN` is not a power of two.
+    pub fn serialize_from_bytes<S: Fallible + Writer + ?Sized>(
+        bytes: &[u8],
+        serializer: &mut S,
+    ) -> Result<BytesResolver, S::Error> {
+        assert!(ALIGN.is_power_of_two(), "ALIGN must be a power of 2");
+
+        let pos = serializer.align_and_write(ALIGN, bytes)?;
+        Ok(BytesResolver {
+            pos: pos as FixedUsize,
+        })
+    }
+}
+
+impl<const ALIGN: usize> AsRef<[u8]> for ArchivedBytes<ALIGN> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> Borrow<[u8]> for ArchivedBytes<ALIGN> {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> fmt::Debug for ArchivedBytes<ALIGN> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<const ALIGN: usize> Deref for ArchivedBytes<ALIGN> {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> Eq for ArchivedBytes<ALIGN> {}
+
+impl<const ALIGN: usize> hash::Hash for ArchivedBytes<ALIGN> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<const ALIGN: usize> Ord for ArchivedBytes<ALIGN> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<const A: usize, const B: usize> PartialEq<ArchivedBytes<B>>
+    for ArchivedBytes<A>
+{
+    #[inline]
+    fn eq(&self, other: &ArchivedBytes<B>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> PartialEq<[u8]> for ArchivedBytes<ALIGN> {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<const ALIGN: usize> PartialEq<ArchivedBytes<ALIGN>> for [u8] {
+    #[inline]
+    fn eq(&self, other: &ArchivedBytes<ALIGN>) -> bool {
+        self == other.as_slice()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const ALIGN: usize> PartialEq<crate::alloc::vec::Vec<u8>>
+    for ArchivedBytes<ALIGN>
+{
+    #[inline]
+    fn eq(&self, other: &crate::alloc::vec::Vec<u8>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<const ALIGN: usize> PartialEq<ArchivedBytes<ALIGN>>
+    for crate::alloc::vec::Vec<u8>
+{
+    #[inline]
+    fn eq(&self, other: &ArchivedBytes<ALIGN>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<const ALIGN: usize> PartialOrd for ArchivedBytes<ALIGN> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The resolver for [`ArchivedBytes`].
+pub struct BytesResolver {
+    pos: FixedUsize,
+}
+
+impl BytesResolver {
+    /// Creates a new `BytesResolver` from a position in the output buffer where
+    /// the payload of the archived byte buffer is stored.
+    pub fn from_pos(pos: usize) -> Self {
+        Self {
+            pos: pos as FixedUsize,
+        }
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use rancor::fail;
+
+    use crate::{
+        bytes::ArchivedBytes,
+        validation::{ArchiveContext, ArchiveContextExt},
+    };
+
+    /// An error resulting from an archived byte buffer whose payload is not
+    /// properly aligned.
+    #[derive(Debug)]
+    pub struct MisalignedBytesError {
+        address: usize,
+        align: usize,
+    }
+
+    impl fmt::Display for MisalignedBytesError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived bytes at address {:#x} are not aligned to {} bytes",
+                self.address, self.align,
+            )
+        }
+    }
+
+    impl Error for MisalignedBytesError {}
+
+    unsafe impl<C, const ALIGN: usize> Verify<C> for ArchivedBytes<ALIGN>
+    where
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let ptr = self.ptr.as_ptr_wrapping();
+            if !ALIGN.is_power_of_two() || ptr as usize & (ALIGN - 1) != 0 {
+                fail!(MisalignedBytesError {
+                    address: ptr as usize,
+                    align: ALIGN,
+                });
+            }
+
+            let bytes = core::ptr::slice_from_raw_parts(
+                ptr,
+                self.len.to_native() as usize,
+            );
+            context.in_subtree(bytes, |_| Ok(()))
+        }
+    }
+}
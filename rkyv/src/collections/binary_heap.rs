@@ -0,0 +1,179 @@
+//! An archived version of `BinaryHeap`.
+
+use core::{fmt, slice};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable, Serialize,
+};
+
+/// An archived `BinaryHeap`.
+///
+/// The elements are stored in the same heap order as the original binary heap,
+/// so the greatest element is always first. Validation does not check that the
+/// archived elements are actually in heap order; an improperly-ordered heap
+/// will return elements from [`peek`](ArchivedBinaryHeap::peek) and
+/// [`iter_sorted`](ArchivedBinaryHeap::iter_sorted) in an unspecified order.
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedBinaryHeap<T> {
+    inner: ArchivedVec<T>,
+}
+
+impl<T> ArchivedBinaryHeap<T> {
+    /// Returns the greatest element in the archived binary heap, or `None` if
+    /// it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.inner.first()
+    }
+
+    /// Returns the number of elements in the archived binary heap.
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether the archived binary heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the elements of the archived binary heap as a slice in heap
+    /// order.
+    pub fn as_slice(&self) -> &[T] {
+        self.inner.as_slice()
+    }
+
+    /// Returns an iterator over the elements of the archived binary heap in
+    /// heap order.
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.inner.iter()
+    }
+
+    /// Resolves an archived binary heap from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: BinaryHeapResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedBinaryHeap { inner } = out);
+        ArchivedVec::resolve_from_len(len, resolver.0, inner);
+    }
+
+    /// Serializes an archived binary heap from a slice of elements in heap
+    /// order.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<BinaryHeapResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        ArchivedVec::serialize_from_slice(slice, serializer)
+            .map(BinaryHeapResolver)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> ArchivedBinaryHeap<T> {
+    /// Returns an iterator over the elements of the archived binary heap in
+    /// descending order.
+    ///
+    /// The heap is not modified. Instead, the iterator keeps a frontier of
+    /// candidate elements, so yielding `k` elements takes `O(k log k)` time.
+    pub fn iter_sorted(&self) -> IterSorted<'_, T> {
+        let mut frontier = crate::alloc::collections::BinaryHeap::new();
+        if !self.is_empty() {
+            frontier.push(HeapEntry {
+                index: 0,
+                value: &self.as_slice()[0],
+            });
+        }
+        IterSorted {
+            heap: self.as_slice(),
+            frontier,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedBinaryHeap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a ArchivedBinaryHeap<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// The resolver for [`ArchivedBinaryHeap`].
+pub struct BinaryHeapResolver(VecResolver);
+
+#[cfg(feature = "alloc")]
+struct HeapEntry<'a, T> {
+    index: usize,
+    value: &'a T,
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> PartialEq for HeapEntry<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value.eq(other.value)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Eq for HeapEntry<'_, T> {}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> PartialOrd for HeapEntry<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Ord> Ord for HeapEntry<'_, T> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value.cmp(other.value)
+    }
+}
+
+/// An iterator over the elements of an [`ArchivedBinaryHeap`] in descending
+/// order.
+///
+/// This struct is created by [`ArchivedBinaryHeap::iter_sorted`].
+#[cfg(feature = "alloc")]
+pub struct IterSorted<'a, T> {
+    heap: &'a [T],
+    frontier: crate::alloc::collections::BinaryHeap<HeapEntry<'a, T>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T: Ord> Iterator for IterSorted<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = self.frontier.pop()?;
+        for child in [2 * entry.index + 1, 2 * entry.index + 2] {
+            if let Some(value) = self.heap.get(child) {
+                self.frontier.push(HeapEntry {
+                    index: child,
+                    value,
+                });
+            }
+        }
+        Some(entry.value)
+    }
+}
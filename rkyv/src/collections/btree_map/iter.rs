@@ -1,4 +1,10 @@
-use core::{marker::PhantomData, ptr::addr_of_mut};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    marker::PhantomData,
+    ops::{Bound, RangeBounds},
+    ptr::addr_of_mut,
+};
 
 use crate::{
     alloc::vec::Vec,
@@ -55,6 +61,59 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
             _phantom: PhantomData,
         }
     }
+
+    /// Gets a cursor positioned at the first entry of the map.
+    pub fn cursor(&self) -> Cursor<'_, K, V, E> {
+        let this = (self as *const Self).cast_mut();
+        Cursor {
+            map: self,
+            inner: unsafe { RawIter::new(this) },
+        }
+    }
+
+    /// Gets a cursor positioned at the first entry of the map which is above
+    /// the given bound.
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let this = (self as *const Self).cast_mut();
+        Cursor {
+            map: self,
+            inner: unsafe { RawIter::seek(this, bound) },
+        }
+    }
+
+    /// Gets an iterator over a sub-range of the entries of the map, sorted by
+    /// key.
+    ///
+    /// Unlike `BTreeMap::range`, this returns an empty iterator instead of
+    /// panicking if the start of the range is greater than the end.
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V, E>
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+        R: RangeBounds<Q>,
+    {
+        let this = (self as *const Self).cast_mut();
+        let inner = unsafe { RawIter::seek(this, range.start_bound()) };
+        // The end of the range is the first key which is past the end bound.
+        let end = match range.end_bound() {
+            Bound::Included(key) => unsafe {
+                RawIter::seek(this, Bound::Excluded(key)).peek()
+            },
+            Bound::Excluded(key) => unsafe {
+                RawIter::seek(this, Bound::Included(key)).peek()
+            },
+            Bound::Unbounded => None,
+        };
+        Range {
+            inner,
+            end: end.map(|(k, _)| unsafe { &*k }),
+            _phantom: PhantomData,
+        }
+    }
 }
 
 /// An iterator over the entires of an `ArchivedBTreeMap`.
@@ -148,35 +207,211 @@ impl<'a, K, V, const E: usize> Iterator for ValuesSeal<'a, K, V, E> {
     }
 }
 
+/// A cursor over the entries of an `ArchivedBTreeMap`.
+///
+/// A cursor points to an entry of the map, or to the end of the map if there
+/// are no more entries. Iterating a cursor yields the entry it points to and
+/// then advances it to the next entry, so a cursor can be used to seek to a
+/// key and then scan forward from it.
+///
+/// This struct is created by the [`cursor`](ArchivedBTreeMap::cursor) and
+/// [`lower_bound`](ArchivedBTreeMap::lower_bound) methods on
+/// [`ArchivedBTreeMap`]. See their documentation for more.
+pub struct Cursor<'a, K, V, const E: usize> {
+    map: &'a ArchivedBTreeMap<K, V, E>,
+    inner: RawIter<K, V, E>,
+}
+
+impl<'a, K, V, const E: usize> Cursor<'a, K, V, E> {
+    /// Returns the entry the cursor points to, or `None` if the cursor is at
+    /// the end of the map.
+    pub fn peek(&self) -> Option<(&'a K, &'a V)> {
+        self.inner
+            .peek()
+            .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
+    }
+
+    /// Moves the cursor to the first entry of the map which is above the given
+    /// bound.
+    pub fn seek<Q>(&mut self, bound: Bound<&Q>)
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        *self = self.map.lower_bound(bound);
+    }
+}
+
+impl<'a, K, V, const E: usize> Iterator for Cursor<'a, K, V, E> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
+    }
+}
+
+/// An iterator over a sub-range of the entries of an `ArchivedBTreeMap`.
+///
+/// This struct is created by the [`range`](ArchivedBTreeMap::range) method on
+/// [`ArchivedBTreeMap`]. See its documentation for more.
+pub struct Range<'a, K, V, const E: usize> {
+    inner: RawIter<K, V, E>,
+    end: Option<&'a K>,
+    _phantom: PhantomData<&'a ArchivedBTreeMap<K, V, E>>,
+}
+
+impl<'a, K: Ord, V, const E: usize> Iterator for Range<'a, K, V, E> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (k, _) = self.inner.peek()?;
+        if let Some(end) = self.end {
+            if unsafe { &*k } >= end {
+                self.inner.stack.clear();
+                return None;
+            }
+        }
+        self.inner
+            .next()
+            .map(|(k, v)| (unsafe { &*k }, unsafe { &*v }))
+    }
+}
+
 struct RawIter<K, V, const E: usize> {
-    remaining: usize,
+    // Each entry on the stack is a node and the index of the next entry to
+    // yield from it. The top of the stack is always the next entry to yield.
     stack: Vec<(*mut Node<K, V, E>, usize)>,
 }
 
 impl<K, V, const E: usize> RawIter<K, V, E> {
     unsafe fn new(map: *mut ArchivedBTreeMap<K, V, E>) -> Self {
-        let remaining = unsafe { (*map).len.to_native() as usize };
-        let mut stack = Vec::new();
-        if remaining != 0 {
-            stack.reserve(entries_to_height::<E>(remaining) as usize);
-            let mut current =
-                unsafe { RelPtr::as_ptr_raw(addr_of_mut!((*map).root)) };
-            loop {
-                stack.push((current, 0));
-                let kind = unsafe { (*current).kind };
-                match kind {
-                    NodeKind::Inner => {
-                        let inner = current.cast::<InnerNode<K, V, E>>();
-                        let lesser =
-                            unsafe { addr_of_mut!((*inner).lesser_nodes[0]) };
-                        current = unsafe { RelPtr::as_ptr_raw(lesser) };
+        let len = unsafe { (*map).len.to_native() as usize };
+        let mut result = Self { stack: Vec::new() };
+        if len != 0 {
+            result.stack.reserve(entries_to_height::<E>(len) as usize);
+            let root = unsafe { RelPtr::as_ptr_raw(addr_of_mut!((*map).root)) };
+            unsafe {
+                result.push_leftmost(root);
+            }
+        }
+
+        result
+    }
+
+    /// Creates a raw iterator which starts at the first entry satisfying the
+    /// given lower bound.
+    unsafe fn seek<Q>(
+        map: *mut ArchivedBTreeMap<K, V, E>,
+        bound: Bound<&Q>,
+    ) -> Self
+    where
+        Q: Ord + ?Sized,
+        K: Borrow<Q> + Ord,
+    {
+        let key = match bound {
+            Bound::Unbounded => return unsafe { Self::new(map) },
+            Bound::Included(key) | Bound::Excluded(key) => key,
+        };
+        let inclusive = matches!(bound, Bound::Included(_));
+
+        let len = unsafe { (*map).len.to_native() as usize };
+        let mut result = Self { stack: Vec::new() };
+        if len == 0 {
+            return result;
+        }
+        result.stack.reserve(entries_to_height::<E>(len) as usize);
+
+        let mut current =
+            unsafe { RelPtr::as_ptr_raw(addr_of_mut!((*map).root)) };
+        loop {
+            let count = unsafe { node_len(current) };
+            let mut i = 0;
+            while i < count {
+                let k = unsafe { &*Self::key_ptr(current, i) };
+                match key.cmp(k.borrow()) {
+                    Ordering::Less => break,
+                    Ordering::Equal if inclusive => {
+                        result.stack.push((current, i));
+                        return result;
                     }
-                    NodeKind::Leaf => break,
+                    Ordering::Equal | Ordering::Greater => i += 1,
                 }
             }
+
+            if i < count {
+                result.stack.push((current, i));
+            }
+            match unsafe { child(current, i) } {
+                Some(next) => current = next,
+                None => break,
+            }
         }
 
-        Self { remaining, stack }
+        result
+    }
+
+    /// Pushes the path from the given node to its leftmost entry.
+    unsafe fn push_leftmost(&mut self, mut current: *mut Node<K, V, E>) {
+        loop {
+            self.stack.push((current, 0));
+            match unsafe { child(current, 0) } {
+                Some(next) => current = next,
+                None => break,
+            }
+        }
+    }
+
+    fn key_ptr(node: *mut Node<K, V, E>, i: usize) -> *mut K {
+        unsafe { addr_of_mut!((*node).keys[i]).cast::<K>() }
+    }
+
+    fn value_ptr(node: *mut Node<K, V, E>, i: usize) -> *mut V {
+        unsafe { addr_of_mut!((*node).values[i]).cast::<V>() }
+    }
+
+    fn peek(&self) -> Option<(*mut K, *mut V)> {
+        let &(current, i) = self.stack.last()?;
+        Some((Self::key_ptr(current, i), Self::value_ptr(current, i)))
+    }
+}
+
+/// Returns the number of entries in the given node.
+unsafe fn node_len<K, V, const E: usize>(node: *mut Node<K, V, E>) -> usize {
+    let kind = unsafe { (*node).kind };
+    match kind {
+        NodeKind::Inner => E,
+        NodeKind::Leaf => {
+            let leaf = node.cast::<LeafNode<K, V, E>>();
+            unsafe { (*leaf).len.to_native() as usize }
+        }
+    }
+}
+
+/// Returns the child node of the given node which contains the entries
+/// preceding entry `i`, or the greater node if `i` is `E`.
+unsafe fn child<K, V, const E: usize>(
+    node: *mut Node<K, V, E>,
+    i: usize,
+) -> Option<*mut Node<K, V, E>> {
+    let kind = unsafe { (*node).kind };
+    match kind {
+        NodeKind::Leaf => None,
+        NodeKind::Inner => {
+            let inner = node.cast::<InnerNode<K, V, E>>();
+            let ptr = if i < E {
+                unsafe { addr_of_mut!((*inner).lesser_nodes[i]) }
+            } else {
+                unsafe { addr_of_mut!((*inner).greater_node) }
+            };
+            let is_invalid = unsafe { RelPtr::is_invalid_raw(ptr) };
+            if is_invalid {
+                None
+            } else {
+                Some(unsafe { RelPtr::as_ptr_raw(ptr) })
+            }
+        }
     }
 }
 
@@ -185,55 +420,19 @@ impl<K, V, const E: usize> Iterator for RawIter<K, V, E> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (current, i) = self.stack.pop()?;
-        self.remaining -= 1;
-
-        let k = unsafe { addr_of_mut!((*current).keys[i]).cast::<K>() };
-        let v = unsafe { addr_of_mut!((*current).values[i]).cast::<V>() };
         let next_i = i + 1;
 
-        // Advance to the next item
-        let kind = unsafe { (*current).kind };
-        match kind {
-            NodeKind::Inner => {
-                let inner = current.cast::<InnerNode<K, V, E>>();
-                if next_i < E {
-                    // More values in the current node
-                    self.stack.push((current, next_i));
-
-                    // Recurse to a lesser if valid
-                    let next_lesser =
-                        unsafe { addr_of_mut!((*inner).lesser_nodes[next_i]) };
-                    let next_lesser_is_invalid =
-                        unsafe { RelPtr::is_invalid_raw(next_lesser) };
-                    if !next_lesser_is_invalid {
-                        self.stack.push((
-                            unsafe { RelPtr::as_ptr_raw(next_lesser).cast() },
-                            0,
-                        ));
-                    }
-                } else {
-                    // Recurse to a greater if valid
-                    let next_greater =
-                        unsafe { addr_of_mut!((*inner).greater_node) };
-                    let next_greater_is_invalid =
-                        unsafe { RelPtr::is_invalid_raw(next_greater) };
-                    if !next_greater_is_invalid {
-                        self.stack.push((
-                            unsafe { RelPtr::as_ptr_raw(next_greater).cast() },
-                            0,
-                        ));
-                    }
-                }
-            }
-            NodeKind::Leaf => {
-                let leaf = current.cast::<LeafNode<K, V, E>>();
-                let len = unsafe { (*leaf).len.to_native() as usize };
-                if next_i < len {
-                    self.stack.push((current, next_i));
-                }
+        // Advance to the next item. This is either the next entry in the
+        // current node or the leftmost entry of the subtree between them.
+        if next_i < unsafe { node_len(current) } {
+            self.stack.push((current, next_i));
+        }
+        if let Some(next) = unsafe { child(current, next_i) } {
+            unsafe {
+                self.push_leftmost(next);
             }
         }
 
-        Some((k, v))
+        Some((Self::key_ptr(current, i), Self::value_ptr(current, i)))
     }
 }
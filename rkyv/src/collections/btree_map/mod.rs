@@ -3,7 +3,7 @@
 use core::{
     borrow::Borrow,
     cmp::Ordering,
-    fmt,
+    fmt, hash,
     marker::PhantomData,
     mem::{size_of, MaybeUninit},
     ops::{ControlFlow, Index},
@@ -28,6 +28,9 @@ use crate::{
 #[cfg(feature = "alloc")]
 mod iter;
 
+#[cfg(feature = "alloc")]
+pub use self::iter::{Cursor, Iter, IterSeal, Keys, Range, Values, ValuesSeal};
+
 // B-trees are typically characterized as having a branching factor of B.
 // However, in this implementation our B-trees are characterized as having a
 // number of entries per node E where E = B - 1. This is done because it's
@@ -235,6 +238,58 @@ impl<K, V, const E: usize> ArchivedBTreeMap<K, V, E> {
             .map(|(k, v)| (unsafe { &*k }, Seal::new(unsafe { &mut *v })))
     }
 
+    /// Returns the first key-value pair in the B-tree map, or `None` if the
+    /// map is empty.
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.edge_key_value(false)
+    }
+
+    /// Returns the last key-value pair in the B-tree map, or `None` if the map
+    /// is empty.
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.edge_key_value(true)
+    }
+
+    /// Returns the first or last key-value pair in the B-tree map by following
+    /// the leftmost or rightmost branches from the root.
+    fn edge_key_value(&self, last: bool) -> Option<(&K, &V)> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let this = (self as *const Self).cast_mut();
+        let root_ptr = unsafe { addr_of_mut!((*this).root) };
+        let mut current = unsafe { RelPtr::as_ptr_raw(root_ptr) };
+        let len = loop {
+            let kind = unsafe { (*current).kind };
+            match kind {
+                NodeKind::Leaf => {
+                    let leaf = current.cast::<LeafNode<K, V, E>>();
+                    break unsafe { (*leaf).len.to_native() as usize };
+                }
+                NodeKind::Inner => {
+                    let inner = current.cast::<InnerNode<K, V, E>>();
+                    let next = if last {
+                        unsafe { addr_of_mut!((*inner).greater_node) }
+                    } else {
+                        unsafe { addr_of_mut!((*inner).lesser_nodes[0]) }
+                    };
+                    let next_is_invalid =
+                        unsafe { RelPtr::is_invalid_raw(next) };
+                    if next_is_invalid {
+                        break E;
+                    }
+                    current = unsafe { RelPtr::as_ptr_raw(next) };
+                }
+            }
+        };
+
+        let i = if last { len - 1 } else { 0 };
+        let k = unsafe { &*addr_of_mut!((*current).keys[i]).cast::<K>() };
+        let v = unsafe { &*addr_of_mut!((*current).values[i]).cast::<V>() };
+        Some((k, v))
+    }
+
     fn get_key_value_raw<Q>(
         this: *mut Self,
         key: &Q,
@@ -745,8 +800,8 @@ where
 #[cfg(feature = "alloc")]
 impl<K, V, const E: usize> Eq for ArchivedBTreeMap<K, V, E>
 where
-    K: PartialEq,
-    V: PartialEq,
+    K: Eq,
+    V: Eq,
 {
 }
 
@@ -787,6 +842,49 @@ where
     }
 }
 
+impl<K, V, const E: usize> hash::Hash for ArchivedBTreeMap<K, V, E>
+where
+    K: hash::Hash,
+    V: hash::Hash,
+{
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.visit(|k, v| {
+            k.hash(state);
+            v.hash(state);
+            ControlFlow::<()>::Continue(())
+        });
+    }
+}
+
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K, V, const E: usize> Ord for ArchivedBTreeMap<K, V, E>
+where
+    K: Ord,
+    V: Ord,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K, V, const E1: usize, const E2: usize>
+    PartialOrd<ArchivedBTreeMap<K, V, E2>> for ArchivedBTreeMap<K, V, E1>
+where
+    K: PartialOrd,
+    V: PartialOrd,
+{
+    fn partial_cmp(
+        &self,
+        other: &ArchivedBTreeMap<K, V, E2>,
+    ) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
 /// The resolver for [`ArchivedBTreeMap`].
 pub struct BTreeMapResolver {
     root_node_pos: FixedUsize,
@@ -1,12 +1,20 @@
 //! [`Archive`](crate::Archive) implementation for B-tree sets.
 
-use core::{borrow::Borrow, fmt, ops::ControlFlow};
+use core::{borrow::Borrow, fmt, hash, ops::ControlFlow};
 
 use munge::munge;
 use rancor::{Fallible, Source};
 
+#[cfg(feature = "alloc")]
+use crate::collections::{
+    btree_map::Keys,
+    set_ops::{Difference, Intersection, Union},
+};
 use crate::{
-    collections::btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+    collections::{
+        btree_map::{ArchivedBTreeMap, BTreeMapResolver},
+        set_ops::Contains,
+    },
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
 };
@@ -98,6 +106,104 @@ impl<K, const E: usize> ArchivedBTreeSet<K, E> {
     ) -> Option<T> {
         self.0.visit(|k, _| f(k))
     }
+
+    /// Returns whether every key of the set is also in `other`.
+    pub fn is_subset<Q, S>(&self, other: &S) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        self.visit(|k| {
+            if other.contains_value(k.borrow()) {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        })
+        .is_none()
+    }
+
+    /// Returns whether the set has no keys in common with `other`.
+    pub fn is_disjoint<Q, S>(&self, other: &S) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        self.visit(|k| {
+            if other.contains_value(k.borrow()) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })
+        .is_none()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, const E: usize> ArchivedBTreeSet<K, E> {
+    /// Gets an iterator over the keys of the set, in sorted order.
+    pub fn iter(&self) -> Keys<'_, K, (), E> {
+        self.0.keys()
+    }
+
+    /// Gets an iterator over the keys of the set which are also in `other`, in
+    /// sorted order.
+    pub fn intersection<'a, Q, S>(
+        &'a self,
+        other: &'a S,
+    ) -> Intersection<'a, Keys<'a, K, (), E>, S, Q>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        Intersection::new(self.iter(), other)
+    }
+
+    /// Gets an iterator over the keys of the set which are not in `other`, in
+    /// sorted order.
+    pub fn difference<'a, Q, S>(
+        &'a self,
+        other: &'a S,
+    ) -> Difference<'a, Keys<'a, K, (), E>, S, Q>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        Difference::new(self.iter(), other)
+    }
+
+    /// Gets an iterator over the keys which are in either the set or `other`,
+    /// without duplicates.
+    ///
+    /// `other` can iterate over the keys of another archived set or a live set.
+    /// The keys of this set are yielded first in sorted order, followed by the
+    /// keys of `other` which are not in this set. Keys are yielded as
+    /// references to `Q`, which both kinds of keys must borrow as.
+    pub fn union<'a, Q, I, L>(
+        &'a self,
+        other: I,
+    ) -> Union<'a, Keys<'a, K, (), E>, I::IntoIter, Self, Q>
+    where
+        K: Borrow<Q> + Ord,
+        Q: Ord + ?Sized,
+        I: IntoIterator<Item = &'a L>,
+        L: Borrow<Q> + 'a,
+    {
+        Union::new(self.iter(), other.into_iter(), self, |set: &Self, q| {
+            set.contains_key(q)
+        })
+    }
+}
+
+impl<K: Ord, const E: usize> Contains<K> for ArchivedBTreeSet<K, E> {
+    fn contains_value(&self, value: &K) -> bool {
+        self.contains_key(value)
+    }
 }
 
 impl<K, const E: usize> fmt::Debug for ArchivedBTreeSet<K, E>
@@ -114,5 +220,46 @@ where
     }
 }
 
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K: Eq, const E: usize> Eq for ArchivedBTreeSet<K, E> {}
+
+impl<K: hash::Hash, const E: usize> hash::Hash for ArchivedBTreeSet<K, E> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K: Ord, const E: usize> Ord for ArchivedBTreeSet<K, E> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.0.cmp(&other.0)
+    }
+}
+
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K: PartialEq, const E1: usize, const E2: usize>
+    PartialEq<ArchivedBTreeSet<K, E2>> for ArchivedBTreeSet<K, E1>
+{
+    fn eq(&self, other: &ArchivedBTreeSet<K, E2>) -> bool {
+        self.0 == other.0
+    }
+}
+
+// TODO(#515): ungate this impl
+#[cfg(feature = "alloc")]
+impl<K: PartialOrd, const E1: usize, const E2: usize>
+    PartialOrd<ArchivedBTreeSet<K, E2>> for ArchivedBTreeSet<K, E1>
+{
+    fn partial_cmp(
+        &self,
+        other: &ArchivedBTreeSet<K, E2>,
+    ) -> Option<core::cmp::Ordering> {
+        self.0.partial_cmp(&other.0)
+    }
+}
+
 /// The resolver for archived B-tree sets.
 pub struct BTreeSetResolver(BTreeMapResolver);
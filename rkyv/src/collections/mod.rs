@@ -1,6 +1,8 @@
 //! Archived versions of standard library containers.
 
+pub mod binary_heap;
 pub mod btree_map;
 pub mod btree_set;
+pub mod set_ops;
 pub mod swiss_table;
 pub mod util;
@@ -0,0 +1,188 @@
+//! Set algebra for archived sets.
+//!
+//! The set operations on [`ArchivedHashSet`] and [`ArchivedBTreeSet`] accept
+//! any set implementing [`Contains`], so they can be performed against another
+//! archived set or a live `HashSet` or `BTreeSet` without deserializing either
+//! side.
+//!
+//! The `union` methods instead accept an iterator over the keys of the other
+//! set, which can be the keys of another archived set or a live set. Because
+//! the union yields keys from both sets, it yields references to the type that
+//! both kinds of keys borrow as, which may need to be specified:
+//!
+//! ```
+//! use std::collections::BTreeSet;
+//!
+//! use rkyv::{rancor::Error, Archived};
+//!
+//! let archived_set = ["a", "b"].map(String::from);
+//! let archived_set = archived_set.into_iter().collect::<BTreeSet<_>>();
+//! let bytes = rkyv::to_bytes::<Error>(&archived_set).unwrap();
+//! let archived =
+//!     rkyv::access::<Archived<BTreeSet<String>>, Error>(&bytes).unwrap();
+//!
+//! let live = ["b", "c"].map(String::from);
+//! let live = live.into_iter().collect::<BTreeSet<_>>();
+//!
+//! let union = archived.union::<str, _, _>(&live).collect::<Vec<_>>();
+//! assert_eq!(union, ["a", "b", "c"]);
+//! ```
+//!
+//! When the other set is a live set, the type used to look up keys in it may
+//! need to be specified:
+//!
+//! ```
+//! use std::collections::HashSet;
+//!
+//! use rkyv::{rancor::Error, Archived};
+//!
+//! let archived_set = ["a", "b", "c"].map(String::from);
+//! let archived_set = archived_set.into_iter().collect::<HashSet<_>>();
+//! let bytes = rkyv::to_bytes::<Error>(&archived_set).unwrap();
+//! let archived =
+//!     rkyv::access::<Archived<HashSet<String>>, Error>(&bytes).unwrap();
+//!
+//! let live = ["b", "c", "d"].map(String::from);
+//! let live = live.into_iter().collect::<HashSet<_>>();
+//!
+//! let mut common = archived
+//!     .intersection::<str, _>(&live)
+//!     .map(|s| s.as_str())
+//!     .collect::<Vec<_>>();
+//! common.sort();
+//! assert_eq!(common, ["b", "c"]);
+//! assert!(!archived.is_subset::<str, _>(&live));
+//! ```
+//!
+//! [`ArchivedHashSet`]: crate::collections::swiss_table::ArchivedHashSet
+//! [`ArchivedBTreeSet`]: crate::collections::btree_set::ArchivedBTreeSet
+
+use core::{borrow::Borrow, marker::PhantomData};
+
+/// A set which can be queried for membership.
+pub trait Contains<Q: ?Sized> {
+    /// Returns whether the set contains the given value.
+    fn contains_value(&self, value: &Q) -> bool;
+}
+
+/// An iterator over the values of a set which are also in another set.
+///
+/// This struct is created by the `intersection` methods on archived sets. See
+/// their documentation for more.
+pub struct Intersection<'a, I, S: ?Sized, Q: ?Sized> {
+    iter: I,
+    other: &'a S,
+    _phantom: PhantomData<fn(&Q)>,
+}
+
+impl<'a, I, S: ?Sized, Q: ?Sized> Intersection<'a, I, S, Q> {
+    pub(crate) fn new(iter: I, other: &'a S) -> Self {
+        Self {
+            iter,
+            other,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I, K, S, Q> Iterator for Intersection<'a, I, S, Q>
+where
+    I: Iterator<Item = &'a K>,
+    K: Borrow<Q> + 'a,
+    S: Contains<Q> + ?Sized,
+    Q: ?Sized,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|k| other.contains_value((*k).borrow()))
+    }
+}
+
+/// An iterator over the values of a set which are not in another set.
+///
+/// This struct is created by the `difference` methods on archived sets. See
+/// their documentation for more.
+pub struct Difference<'a, I, S: ?Sized, Q: ?Sized> {
+    iter: I,
+    other: &'a S,
+    _phantom: PhantomData<fn(&Q)>,
+}
+
+impl<'a, I, S: ?Sized, Q: ?Sized> Difference<'a, I, S, Q> {
+    pub(crate) fn new(iter: I, other: &'a S) -> Self {
+        Self {
+            iter,
+            other,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, I, K, S, Q> Iterator for Difference<'a, I, S, Q>
+where
+    I: Iterator<Item = &'a K>,
+    K: Borrow<Q> + 'a,
+    S: Contains<Q> + ?Sized,
+    Q: ?Sized,
+{
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let other = self.other;
+        self.iter.find(|k| !other.contains_value((*k).borrow()))
+    }
+}
+
+/// An iterator over the values which are in either of two sets, without
+/// duplicates.
+///
+/// This struct is created by the `union` methods on archived sets. See their
+/// documentation for more.
+pub struct Union<'a, I, J, S: ?Sized, Q: ?Sized> {
+    first: I,
+    second: J,
+    first_set: &'a S,
+    contains: fn(&S, &Q) -> bool,
+}
+
+impl<'a, I, J, S: ?Sized, Q: ?Sized> Union<'a, I, J, S, Q> {
+    pub(crate) fn new(
+        first: I,
+        second: J,
+        first_set: &'a S,
+        contains: fn(&S, &Q) -> bool,
+    ) -> Self {
+        Self {
+            first,
+            second,
+            first_set,
+            contains,
+        }
+    }
+}
+
+impl<'a, I, J, K, L, S, Q> Iterator for Union<'a, I, J, S, Q>
+where
+    I: Iterator<Item = &'a K>,
+    J: Iterator<Item = &'a L>,
+    K: Borrow<Q> + 'a,
+    L: Borrow<Q> + 'a,
+    S: ?Sized,
+    Q: ?Sized + 'a,
+{
+    type Item = &'a Q;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(k) = self.first.next() {
+            return Some(k.borrow());
+        }
+
+        let (first_set, contains) = (self.first_set, self.contains);
+        self.second
+            .by_ref()
+            .map(|l| l.borrow())
+            .find(|q| !contains(first_set, q))
+    }
+}
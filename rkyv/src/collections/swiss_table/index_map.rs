@@ -87,6 +87,18 @@ impl<K, V, H> ArchivedIndexMap<K, V, H> {
             inner: unsafe { self.raw_iter() },
         }
     }
+
+    /// Returns the first key-value pair in insertion order, or `None` if the
+    /// map is empty.
+    pub fn first(&self) -> Option<(&K, &V)> {
+        self.entries().first().map(|e| (&e.key, &e.value))
+    }
+
+    /// Returns the last key-value pair in insertion order, or `None` if the
+    /// map is empty.
+    pub fn last(&self) -> Option<(&K, &V)> {
+        self.entries().last().map(|e| (&e.key, &e.value))
+    }
 }
 
 impl<K, V, H: Hasher + Default> ArchivedIndexMap<K, V, H> {
@@ -369,6 +381,15 @@ where
     }
 }
 
+impl<'a, K, V, H> IntoIterator for &'a ArchivedIndexMap<K, V, H> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<K, V, H> PartialEq for ArchivedIndexMap<K, V, H>
 where
     K: PartialEq,
@@ -43,6 +43,17 @@ impl<K, H> ArchivedIndexSet<K, H> {
     pub fn len(&self) -> usize {
         self.inner.len()
     }
+
+    /// Returns the first key in insertion order, or `None` if the set is
+    /// empty.
+    pub fn first(&self) -> Option<&K> {
+        self.inner.first().map(|(k, _)| k)
+    }
+
+    /// Returns the last key in insertion order, or `None` if the set is empty.
+    pub fn last(&self) -> Option<&K> {
+        self.inner.last().map(|(k, _)| k)
+    }
 }
 
 impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
@@ -124,6 +135,15 @@ impl<K, H: Default + Hasher> ArchivedIndexSet<K, H> {
     }
 }
 
+impl<'a, K, H> IntoIterator for &'a ArchivedIndexSet<K, H> {
+    type Item = &'a K;
+    type IntoIter = Keys<'a, K, ()>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 impl<K: fmt::Debug, H> fmt::Debug for ArchivedIndexSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
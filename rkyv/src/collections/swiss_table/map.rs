@@ -90,9 +90,66 @@ impl<K, V, H> ArchivedHashMap<K, V, H> {
             _phantom: PhantomData,
         }
     }
+
+    /// Returns the key-value pair whose key matches the given precomputed
+    /// hash and equality function.
+    ///
+    /// The hash must be computed the same way as the map's hasher would
+    /// compute it, for example with [`hash_key`](Self::hash_key).
+    pub fn get_key_value_by_hash<C>(&self, hash: u64, eq: C) -> Option<(&K, &V)>
+    where
+        C: Fn(&K) -> bool,
+    {
+        let entry = self.table.get_with(hash, |e| eq(&e.key))?;
+        Some((&entry.key, &entry.value))
+    }
+
+    /// Returns the index of the entry whose key matches the given precomputed
+    /// hash and equality function.
+    ///
+    /// Entry indices are stable for a given archive and can be passed to
+    /// [`get_index`](Self::get_index), but they are not in insertion order and
+    /// may be larger than the length of the map.
+    pub fn get_index_by_hash<C>(&self, hash: u64, eq: C) -> Option<usize>
+    where
+        C: Fn(&K) -> bool,
+    {
+        self.table.get_index_with(hash, |e| eq(&e.key))
+    }
+
+    /// Returns the key-value pair stored at the given entry index, or `None` if
+    /// there is no entry at that index.
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        let entry = self.table.get_index(index)?;
+        Some((&entry.key, &entry.value))
+    }
 }
 
 impl<K, V, H: Hasher + Default> ArchivedHashMap<K, V, H> {
+    /// Hashes the given key the same way that this hash map does.
+    pub fn hash_key<Q: Hash + ?Sized>(key: &Q) -> u64 {
+        hash_value::<Q, H>(key)
+    }
+
+    /// Returns the index of the entry corresponding to the supplied key using
+    /// the given comparison function.
+    pub fn get_index_of_with<Q, C>(&self, key: &Q, cmp: C) -> Option<usize>
+    where
+        Q: Hash + Eq + ?Sized,
+        C: Fn(&Q, &K) -> bool,
+    {
+        self.get_index_by_hash(Self::hash_key(key), |k| cmp(key, k))
+    }
+
+    /// Returns the index of the entry corresponding to the supplied key.
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get_index_of_with(key, |q, k| q == k.borrow())
+    }
+
     /// Returns the key-value pair corresponding to the supplied key using the
     /// given comparison function.
     pub fn get_key_value_with<Q, C>(&self, key: &Q, cmp: C) -> Option<(&K, &V)>
@@ -10,7 +10,10 @@ use munge::munge;
 use rancor::{Fallible, Source};
 
 use crate::{
-    collections::swiss_table::map::{ArchivedHashMap, HashMapResolver, Keys},
+    collections::{
+        set_ops::{Contains, Difference, Intersection, Union},
+        swiss_table::map::{ArchivedHashMap, HashMapResolver, Keys},
+    },
     hash::FxHasher64,
     ser::{Allocator, Writer},
     Place, Portable, Serialize,
@@ -41,6 +44,54 @@ impl<K, H> ArchivedHashSet<K, H> {
     pub fn iter(&self) -> Keys<K, (), H> {
         self.inner.keys()
     }
+
+    /// Gets an iterator over the keys of the hash set which are also in
+    /// `other`.
+    pub fn intersection<'a, Q, S>(
+        &'a self,
+        other: &'a S,
+    ) -> Intersection<'a, Keys<'a, K, (), H>, S, Q>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        Intersection::new(self.iter(), other)
+    }
+
+    /// Gets an iterator over the keys of the hash set which are not in
+    /// `other`.
+    pub fn difference<'a, Q, S>(
+        &'a self,
+        other: &'a S,
+    ) -> Difference<'a, Keys<'a, K, (), H>, S, Q>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        Difference::new(self.iter(), other)
+    }
+
+    /// Returns whether every key of the hash set is also in `other`.
+    pub fn is_subset<Q, S>(&self, other: &S) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        self.iter().all(|k| other.contains_value(k.borrow()))
+    }
+
+    /// Returns whether the hash set has no keys in common with `other`.
+    pub fn is_disjoint<Q, S>(&self, other: &S) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized,
+        S: Contains<Q> + ?Sized,
+    {
+        !self.iter().any(|k| other.contains_value(k.borrow()))
+    }
 }
 
 impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
@@ -62,6 +113,28 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
         self.inner.contains_key(k)
     }
 
+    /// Gets an iterator over the keys which are in either the hash set or
+    /// `other`, without duplicates.
+    ///
+    /// `other` can iterate over the keys of another archived set or a live set.
+    /// The keys of this set are yielded first, followed by the keys of `other`
+    /// which are not in this set. Keys are yielded as references to `Q`, which
+    /// both kinds of keys must borrow as.
+    pub fn union<'a, Q, I, L>(
+        &'a self,
+        other: I,
+    ) -> Union<'a, Keys<'a, K, (), H>, I::IntoIter, Self, Q>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+        I: IntoIterator<Item = &'a L>,
+        L: Borrow<Q> + 'a,
+    {
+        Union::new(self.iter(), other.into_iter(), self, |set: &Self, q| {
+            set.contains(q)
+        })
+    }
+
     /// Resolves an archived hash set from the given length and parameters.
     pub fn resolve_from_len(
         len: usize,
@@ -99,6 +172,16 @@ impl<K, H: Hasher + Default> ArchivedHashSet<K, H> {
     }
 }
 
+impl<K, H> Contains<K> for ArchivedHashSet<K, H>
+where
+    K: Hash + Eq,
+    H: Hasher + Default,
+{
+    fn contains_value(&self, value: &K) -> bool {
+        self.contains(value)
+    }
+}
+
 impl<K: fmt::Debug, H> fmt::Debug for ArchivedHashSet<K, H> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_set().entries(self.iter()).finish()
@@ -134,6 +134,21 @@ impl<T> ArchivedHashTable<T> {
         hash: u64,
         cmp: C,
     ) -> Option<NonNull<T>>
+    where
+        C: Fn(&T) -> bool,
+    {
+        let index = unsafe { Self::find_index_raw(this, hash, cmp)? };
+        Some(unsafe { Self::bucket_raw(this, index) })
+    }
+
+    /// # Safety
+    ///
+    /// `this` must point to a valid `ArchivedHashTable`
+    unsafe fn find_index_raw<C>(
+        this: *mut Self,
+        hash: u64,
+        cmp: C,
+    ) -> Option<usize>
     where
         C: Fn(&T) -> bool,
     {
@@ -167,7 +182,7 @@ impl<T> ArchivedHashTable<T> {
 
                     // Opt: These can be marked as likely true on nightly.
                     if cmp(bucket) {
-                        return Some(bucket_ptr);
+                        return Some(index);
                     }
                 }
 
@@ -198,6 +213,39 @@ impl<T> ArchivedHashTable<T> {
         Some(unsafe { ptr.as_ref() })
     }
 
+    /// Returns the bucket index of the entry matching the given hash and
+    /// comparison function.
+    ///
+    /// The returned index can be passed to [`get_index`](Self::get_index) to
+    /// retrieve the entry again without hashing or probing.
+    pub fn get_index_with<C>(&self, hash: u64, cmp: C) -> Option<usize>
+    where
+        C: Fn(&T) -> bool,
+    {
+        let this = (self as *const Self).cast_mut();
+        unsafe { Self::find_index_raw(this, hash, |e| cmp(e)) }
+    }
+
+    /// Returns the entry in the bucket with the given index, or `None` if the
+    /// index is out of bounds or the bucket is empty.
+    pub fn get_index(&self, index: usize) -> Option<&T> {
+        if self.is_empty() || index >= self.capacity() {
+            return None;
+        }
+
+        let this = (self as *const Self).cast_mut();
+        // SAFETY: The table is not empty, so its pointer is valid. `index` is
+        // less than the capacity of the table, so it is also less than the
+        // number of control bytes.
+        let control = unsafe { *Self::control_raw(this, index) };
+        // Full buckets have a control byte with the high bit cleared.
+        if control & 0x80 != 0 {
+            return None;
+        }
+
+        Some(unsafe { Self::bucket_raw(this, index).as_ref() })
+    }
+
     /// Returns the mutable key-value pair corresponding to the supplied key.
     pub fn get_seal_with<C>(
         this: Seal<'_, Self>,
@@ -658,6 +706,7 @@ mod verify {
             if len >= cap {
                 fail!(InvalidLength { len, cap });
             }
+            context.claim_elements(len)?;
 
             // Check memory allocation
             let probe_cap = Self::probe_cap(cap);
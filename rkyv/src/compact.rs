@@ -0,0 +1,486 @@
+//! Archived collections with compact length encodings.
+//!
+//! [`ArchivedVec`](crate::vec::ArchivedVec) stores its length inline as an
+//! [`ArchivedUsize`](crate::primitive::ArchivedUsize), so every archived vec
+//! is two words wide. The types in this module instead store their length as a
+//! [LEB128] varint at the start of their out-of-line data and only keep a
+//! relative pointer inline. For archives full of short strings and vecs, this
+//! roughly halves the space spent on lengths at the cost of decoding the
+//! varint on each access.
+//!
+//! Relative pointers remain fixed-width because every archived type must have
+//! a fixed size.
+//!
+//! These types are used with the [`Compact`](crate::with::Compact) wrapper.
+//!
+//! [LEB128]: https://en.wikipedia.org/wiki/LEB128
+
+use core::{borrow::Borrow, fmt, hash, mem::align_of, ops::Deref, str};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::FixedUsize,
+    ser::{Allocator, Writer, WriterExt as _},
+    Place, Portable, RelPtr, Serialize,
+};
+
+/// The maximum number of bytes in an encoded length.
+const MAX_VARINT_LEN: usize = (usize::BITS as usize + 6) / 7;
+
+/// Encodes a varint, returning the encoded bytes and the number of bytes used.
+fn encode_varint(mut value: usize) -> ([u8; MAX_VARINT_LEN], usize) {
+    let mut bytes = [0u8; MAX_VARINT_LEN];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+    (bytes, len)
+}
+
+#[cfg(all(test, feature = "alloc"))]
+fn write_varint<W: Writer<E> + ?Sized, E>(
+    value: usize,
+    writer: &mut W,
+) -> Result<(), E> {
+    let (bytes, len) = encode_varint(value);
+    writer.write(&bytes[..len])
+}
+
+/// Writes the varint length of a compact vec, padded so that the elements
+/// which follow it are aligned to `align`. Returns the position of the length.
+///
+/// The padding is written before the length so that readers can find the
+/// elements without knowing the position of the archived vec in the archive.
+fn write_header<W: Writer<E> + ?Sized, E>(
+    len: usize,
+    align: usize,
+    writer: &mut W,
+) -> Result<usize, E> {
+    let (bytes, varint_len) = encode_varint(len);
+    let end = writer.pos() + varint_len;
+    writer.pad(end.next_multiple_of(align) - end)?;
+    let pos = writer.pos();
+    writer.write(&bytes[..varint_len])?;
+    Ok(pos)
+}
+
+/// Decodes a varint from the given bytes, returning the decoded value and the
+/// number of bytes it occupied.
+///
+/// Returns `None` if the varint is not terminated within the maximum length or
+/// overflows a `usize`.
+fn read_varint(mut next: impl FnMut(usize) -> u8) -> Option<(usize, usize)> {
+    let mut value = 0usize;
+    for i in 0..MAX_VARINT_LEN {
+        let byte = next(i);
+        let bits = (byte & 0x7f) as usize;
+        let shift = 7 * i as u32;
+        if (bits << shift) >> shift != bits {
+            return None;
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Some((value, i + 1));
+        }
+    }
+    None
+}
+
+/// An archived [`Vec`] with a varint-encoded length.
+///
+/// This is the archived representation of a `Vec` with the
+/// [`Compact`](crate::with::Compact) wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedCompactVec<T> {
+    // Points to the varint length, which is immediately followed by the
+    // elements. Any padding needed to align the elements precedes the length.
+    ptr: RelPtr<T>,
+}
+
+impl<T> ArchivedCompactVec<T> {
+    /// Returns the length and the offset from the start of the out-of-line
+    /// data to the first element.
+    fn header(&self) -> (usize, usize) {
+        let start = unsafe { self.ptr.as_ptr() }.cast::<u8>();
+        // SAFETY: The out-of-line data always begins with a valid varint.
+        read_varint(|i| unsafe { *start.add(i) }).unwrap()
+    }
+
+    /// Returns a pointer to the first element of the archived vec.
+    pub fn as_ptr(&self) -> *const T {
+        let (_, offset) = self.header();
+        unsafe { self.ptr.as_ptr().cast::<u8>().add(offset).cast() }
+    }
+
+    /// Returns the number of elements in the archived vec.
+    pub fn len(&self) -> usize {
+        self.header().0
+    }
+
+    /// Returns whether the archived vec is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        let (len, offset) = self.header();
+        unsafe {
+            let ptr = self.ptr.as_ptr().cast::<u8>().add(offset).cast();
+            core::slice::from_raw_parts(ptr, len)
+        }
+    }
+
+    /// Resolves an archived compact vec from a resolver.
+    pub fn resolve_from_resolver(
+        resolver: CompactVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedCompactVec { ptr } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+    }
+
+    /// Serializes an archived compact vec from a given slice.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<CompactVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        use crate::util::SerVec;
+
+        if U::COPY_OPTIMIZATION.is_enabled() {
+            let pos = write_header(slice.len(), align_of::<T>(), serializer)?;
+            let as_bytes = unsafe {
+                core::slice::from_raw_parts(
+                    slice.as_ptr().cast::<u8>(),
+                    core::mem::size_of_val(slice),
+                )
+            };
+            serializer.write(as_bytes)?;
+
+            Ok(CompactVecResolver {
+                pos: pos as FixedUsize,
+            })
+        } else {
+            SerVec::with_capacity(
+                serializer,
+                slice.len(),
+                |resolvers, serializer| {
+                    for value in slice.iter() {
+                        resolvers.push(value.serialize(serializer)?);
+                    }
+
+                    let pos =
+                        write_header(slice.len(), align_of::<T>(), serializer)?;
+                    for (value, resolver) in slice.iter().zip(resolvers.drain())
+                    {
+                        unsafe {
+                            serializer.resolve_aligned(value, resolver)?;
+                        }
+                    }
+
+                    Ok(CompactVecResolver {
+                        pos: pos as FixedUsize,
+                    })
+                },
+            )?
+        }
+    }
+}
+
+impl<T> AsRef<[T]> for ArchivedCompactVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Borrow<[T]> for ArchivedCompactVec<T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedCompactVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T> Deref for ArchivedCompactVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for ArchivedCompactVec<T> {}
+
+impl<T: hash::Hash> hash::Hash for ArchivedCompactVec<T> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<ArchivedCompactVec<U>>
+    for ArchivedCompactVec<T>
+{
+    fn eq(&self, other: &ArchivedCompactVec<U>) -> bool {
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedCompactVec<T> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for [`ArchivedCompactVec`].
+pub struct CompactVecResolver {
+    pos: FixedUsize,
+}
+
+/// An archived [`String`] with a varint-encoded length.
+///
+/// This is the archived representation of a `String` with the
+/// [`Compact`](crate::with::Compact) wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedCompactString {
+    bytes: ArchivedCompactVec<u8>,
+}
+
+impl ArchivedCompactString {
+    /// Extracts a string slice containing the entire archived string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: Archived compact strings always contain valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    /// Resolves an archived compact string from a resolver.
+    pub fn resolve_from_resolver(
+        resolver: CompactVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedCompactString { bytes } = out);
+        ArchivedCompactVec::resolve_from_resolver(resolver, bytes);
+    }
+
+    /// Serializes an archived compact string from a given `str`.
+    pub fn serialize_from_str<S>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<CompactVecResolver, S::Error>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        ArchivedCompactVec::serialize_from_slice(value.as_bytes(), serializer)
+    }
+}
+
+impl AsRef<str> for ArchivedCompactString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ArchivedCompactString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArchivedCompactString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArchivedCompactString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Deref for ArchivedCompactString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl Eq for ArchivedCompactString {}
+
+impl hash::Hash for ArchivedCompactString {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl PartialEq for ArchivedCompactString {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl PartialEq<str> for ArchivedCompactString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ArchivedCompactString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{alloc::Layout, error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{fail, Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::ResultExt as _;
+
+    use super::{read_varint, MAX_VARINT_LEN};
+    use crate::{
+        compact::{ArchivedCompactString, ArchivedCompactVec},
+        validation::{check_slice, ArchiveContext, ArchiveContextExt},
+        Portable,
+    };
+
+    #[derive(Debug)]
+    struct InvalidVarintError;
+
+    impl fmt::Display for InvalidVarintError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "invalid varint length in archived compact vec")
+        }
+    }
+
+    impl Error for InvalidVarintError {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedCompactVec<T>
+    where
+        T: Portable + CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let start = self.ptr.as_ptr_wrapping().cast::<u8>();
+            let byte_layout = Layout::new::<u8>();
+
+            let mut varint_len = None;
+            for i in 0..MAX_VARINT_LEN {
+                let ptr = start.wrapping_add(i);
+                context.check_subtree_ptr(ptr, &byte_layout)?;
+                // SAFETY: `check_subtree_ptr` has guaranteed that `ptr` is
+                // located within the archive.
+                if unsafe { *ptr } & 0x80 == 0 {
+                    varint_len = Some(i + 1);
+                    break;
+                }
+            }
+            let Some(varint_len) = varint_len else {
+                fail!(InvalidVarintError);
+            };
+            // SAFETY: We checked that the first `varint_len` bytes starting at
+            // `start` are located within the archive.
+            let Some((len, _)) = read_varint(|i| {
+                if i < varint_len {
+                    unsafe { *start.add(i) }
+                } else {
+                    0
+                }
+            }) else {
+                fail!(InvalidVarintError);
+            };
+            context.claim_elements(len)?;
+
+            let data = start.wrapping_add(varint_len);
+            let array_layout = Layout::array::<T>(len).into_error()?;
+            context.check_subtree_ptr(data, &array_layout)?;
+
+            let total = data as usize - start as usize + array_layout.size();
+            let layout = Layout::from_size_align(total, 1).into_error()?;
+            context.in_subtree_raw(start, layout, |context| {
+                let ptr =
+                    core::ptr::slice_from_raw_parts(data.cast::<T>(), len);
+                // SAFETY: `check_subtree_ptr` has guaranteed that `ptr` is
+                // properly aligned and points to enough bytes for `len`
+                // elements.
+                unsafe { check_slice(ptr, context) }
+            })
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedCompactString
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let ptr = self.bytes.as_slice() as *const [u8] as *const str;
+            // SAFETY: `ptr` points to the bytes of the string, which have
+            // already been checked.
+            unsafe { str::check_bytes(ptr, context) }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::{read_varint, write_header, write_varint};
+    use crate::{alloc::vec::Vec, ser::Writer};
+
+    #[test]
+    fn varint_roundtrip() {
+        for value in [0, 1, 127, 128, 300, 16_383, 16_384, usize::MAX] {
+            let mut bytes = Vec::new();
+            write_varint::<_, rancor::Panic>(value, &mut bytes).unwrap();
+            assert_eq!(read_varint(|i| bytes[i]), Some((value, bytes.len())));
+        }
+        assert_eq!(read_varint(|_| 0xff), None);
+    }
+
+    #[test]
+    fn header_aligns_elements() {
+        let mut bytes = Vec::new();
+        Writer::<rancor::Panic>::write(&mut bytes, &[0xff]).unwrap();
+        let pos = write_header::<_, rancor::Panic>(300, 8, &mut bytes).unwrap();
+        assert_eq!(read_varint(|i| bytes[pos + i]), Some((300, 2)));
+        assert_eq!(bytes.len() % 8, 0);
+        assert_eq!(bytes.len(), pos + 2);
+    }
+}
@@ -0,0 +1,347 @@
+//! Deserialization with heap allocations supplied by the caller.
+
+use core::{
+    alloc::Layout,
+    fmt,
+    hash::BuildHasherDefault,
+    mem::ManuallyDrop,
+    ptr::{self, NonNull},
+};
+
+use hashbrown::HashMap;
+use rancor::{Fallible, Strategy};
+
+use crate::{
+    alloc::{
+        alloc::{alloc, dealloc, handle_alloc_error},
+        boxed::Box,
+        string::String,
+        vec::Vec,
+    },
+    de::{ErasedPtr, Pool, Pooling, PoolingState},
+    hash::FxHasher64,
+};
+
+/// A deserializer capability which supplies the memory for deserialized heap
+/// allocations.
+///
+/// Values deserialized with the [`InArena`](crate::with::InArena) wrapper
+/// request their memory from the arena instead of allocating it directly.
+/// Deserialized `Box`es, `Vec`s, and `String`s free their memory with the
+/// global allocator when they are dropped, so arenas must hand out memory which
+/// was allocated by the global allocator. [`RecyclingArena`] does this by
+/// reusing allocations which are returned to it.
+///
+/// # Safety
+///
+/// `allocate` must return memory which was allocated by the global allocator
+/// with exactly the given layout, and which is not in use by anything else.
+pub unsafe trait Arena<E = <Self as Fallible>::Error> {
+    /// Returns memory for an allocation with the given layout.
+    ///
+    /// `layout` always has a non-zero size.
+    fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, E>;
+}
+
+unsafe impl<T, E> Arena<E> for Strategy<T, E>
+where
+    T: Arena<E>,
+{
+    fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        T::allocate(self, layout)
+    }
+}
+
+unsafe impl<T, E> Arena<E> for &mut T
+where
+    T: Arena<E> + ?Sized,
+{
+    fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        T::allocate(self, layout)
+    }
+}
+
+/// An arena which reuses the allocations of values returned to it.
+///
+/// Values are returned to the arena with [`recycle_box`](Self::recycle_box),
+/// [`recycle_vec`](Self::recycle_vec) and
+/// [`recycle_string`](Self::recycle_string). Their contents are dropped and
+/// their allocations are kept to be handed out to later deserializations which
+/// need an allocation of the same layout. When no allocation of the right
+/// layout is available, a new one is made with the global allocator.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::deserialize_in_arena, de::RecyclingArena, rancor::Error,
+///     with::InArena, Archive, Deserialize, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize)]
+/// struct Message {
+///     #[rkyv(with = InArena)]
+///     body: String,
+/// }
+///
+/// let message = Message {
+///     body: "hello world".to_string(),
+/// };
+/// let bytes = rkyv::to_bytes::<Error>(&message).unwrap();
+/// let archived = rkyv::access::<ArchivedMessage, Error>(&bytes).unwrap();
+///
+/// let mut arena = RecyclingArena::new();
+/// let first: Message =
+///     deserialize_in_arena::<_, Error>(archived, &mut arena).unwrap();
+/// let address = first.body.as_ptr();
+/// arena.recycle_string(first.body);
+///
+/// let second: Message =
+///     deserialize_in_arena::<_, Error>(archived, &mut arena).unwrap();
+/// assert_eq!(second.body, "hello world");
+/// assert_eq!(second.body.as_ptr(), address);
+/// ```
+#[derive(Default)]
+pub struct RecyclingArena {
+    free: HashMap<Layout, Vec<NonNull<u8>>, BuildHasherDefault<FxHasher64>>,
+}
+
+impl RecyclingArena {
+    /// Creates a new empty arena.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of allocations which are available for reuse.
+    pub fn available(&self) -> usize {
+        self.free.values().map(Vec::len).sum()
+    }
+
+    /// Returns the allocation of a box to the arena.
+    ///
+    /// The contents of the box are dropped.
+    pub fn recycle_box<T: ?Sized>(&mut self, value: Box<T>) {
+        let layout = Layout::for_value::<T>(&value);
+        let ptr = Box::into_raw(value);
+        // SAFETY: `ptr` came from `Box::into_raw`, so it points to a valid `T`
+        // which is not used again.
+        unsafe {
+            ptr::drop_in_place(ptr);
+        }
+        // SAFETY: The box was allocated by the global allocator with `layout`
+        // unless `layout` has a zero size.
+        unsafe {
+            self.recycle(ptr.cast(), layout);
+        }
+    }
+
+    /// Returns the allocation of a vector to the arena.
+    ///
+    /// The elements of the vector are dropped. Only the capacity of the vector
+    /// is used to determine which allocations it can be reused for.
+    pub fn recycle_vec<T>(&mut self, mut value: Vec<T>) {
+        value.clear();
+        let value = ManuallyDrop::new(value);
+        let Ok(layout) = Layout::array::<T>(value.capacity()) else {
+            return;
+        };
+        // SAFETY: The vector was allocated by the global allocator with
+        // `layout` unless `layout` has a zero size.
+        unsafe {
+            self.recycle(value.as_ptr().cast_mut().cast(), layout);
+        }
+    }
+
+    /// Returns the allocation of a string to the arena.
+    pub fn recycle_string(&mut self, value: String) {
+        self.recycle_vec(value.into_bytes());
+    }
+
+    /// Frees all of the allocations held by the arena.
+    pub fn clear(&mut self) {
+        for (layout, ptrs) in self.free.drain() {
+            for ptr in ptrs {
+                // SAFETY: Every pointer in the arena was allocated by the
+                // global allocator with its layout.
+                unsafe {
+                    dealloc(ptr.as_ptr(), layout);
+                }
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// If `layout` has a non-zero size, then `ptr` must have been allocated by
+    /// the global allocator with `layout` and must not be used again.
+    unsafe fn recycle(&mut self, ptr: *mut u8, layout: Layout) {
+        if layout.size() > 0 {
+            // SAFETY: Memory allocated by the global allocator is never null.
+            let ptr = unsafe { NonNull::new_unchecked(ptr) };
+            self.free.entry(layout).or_default().push(ptr);
+        }
+    }
+}
+
+impl fmt::Debug for RecyclingArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecyclingArena")
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+// SAFETY: `RecyclingArena` owns all of the allocations it holds.
+unsafe impl Send for RecyclingArena {}
+
+// SAFETY: `RecyclingArena` has no interior mutability.
+unsafe impl Sync for RecyclingArena {}
+
+impl Drop for RecyclingArena {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+// SAFETY: `RecyclingArena` only returns allocations from the global allocator
+// with the requested layout, and removes them from the arena when it does.
+unsafe impl<E> Arena<E> for RecyclingArena {
+    fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        if let Some(ptr) = self.free.get_mut(&layout).and_then(Vec::pop) {
+            return Ok(ptr);
+        }
+
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { alloc(layout) };
+        match NonNull::new(ptr) {
+            Some(ptr) => Ok(ptr),
+            None => handle_alloc_error(layout),
+        }
+    }
+}
+
+/// A deserializer which pools shared pointers and allocates from an arena.
+///
+/// This is the deserializer used by
+/// [`deserialize_in_arena`](crate::api::high::deserialize_in_arena).
+#[derive(Debug, Default)]
+pub struct ArenaDeserializer<A, P = Pool> {
+    /// The arena of the deserializer.
+    pub arena: A,
+    /// The shared pointer pooling of the deserializer.
+    pub pool: P,
+}
+
+impl<A> ArenaDeserializer<A> {
+    /// Creates a new deserializer which allocates from the given arena.
+    pub fn new(arena: A) -> Self {
+        Self {
+            arena,
+            pool: Pool::new(),
+        }
+    }
+}
+
+unsafe impl<A: Arena<E>, P, E> Arena<E> for ArenaDeserializer<A, P> {
+    fn allocate(&mut self, layout: Layout) -> Result<NonNull<u8>, E> {
+        self.arena.allocate(layout)
+    }
+}
+
+impl<A, P: Pooling<E>, E> Pooling<E> for ArenaDeserializer<A, P> {
+    fn start_pooling(&mut self, address: usize) -> PoolingState {
+        self.pool.start_pooling(address)
+    }
+
+    unsafe fn finish_pooling(
+        &mut self,
+        address: usize,
+        ptr: ErasedPtr,
+        drop: unsafe fn(ErasedPtr),
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `finish_pooling` are the same as
+        // the requirements for calling this function.
+        unsafe { self.pool.finish_pooling(address, ptr, drop) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use super::RecyclingArena;
+    use crate::{
+        alloc::{boxed::Box, string::String, vec, vec::Vec},
+        api::{high::deserialize_in_arena, test::to_archived},
+        with::InArena,
+        Archive, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate)]
+    struct Record {
+        #[rkyv(with = InArena)]
+        name: String,
+        #[rkyv(with = InArena)]
+        values: Vec<u32>,
+        #[rkyv(with = InArena)]
+        id: Box<u64>,
+    }
+
+    #[test]
+    fn reuses_recycled_allocations() {
+        let value = Record {
+            name: String::from("a record with a name"),
+            values: vec![1, 2, 3, 4],
+            id: Box::new(42),
+        };
+
+        let mut arena = RecyclingArena::new();
+        to_archived(&value, |archived| {
+            let first: Record =
+                deserialize_in_arena::<_, Panic>(&*archived, &mut arena)
+                    .unwrap();
+            assert_eq!(first, value);
+            assert_eq!(arena.available(), 0);
+
+            let name = first.name.as_ptr();
+            let values = first.values.as_ptr();
+            let id = &*first.id as *const u64;
+            arena.recycle_string(first.name);
+            arena.recycle_vec(first.values);
+            arena.recycle_box(first.id);
+            assert_eq!(arena.available(), 3);
+
+            let second: Record =
+                deserialize_in_arena::<_, Panic>(&*archived, &mut arena)
+                    .unwrap();
+            assert_eq!(second, value);
+            assert_eq!(arena.available(), 0);
+            assert_eq!(second.name.as_ptr(), name);
+            assert_eq!(second.values.as_ptr(), values);
+            assert_eq!(&*second.id as *const u64, id);
+        });
+    }
+
+    #[test]
+    fn empty_values() {
+        let value = Record {
+            name: String::new(),
+            values: Vec::new(),
+            id: Box::new(0),
+        };
+
+        let mut arena = RecyclingArena::new();
+        to_archived(&value, |archived| {
+            let result: Record =
+                deserialize_in_arena::<_, Panic>(&*archived, &mut arena)
+                    .unwrap();
+            assert_eq!(result, value);
+
+            arena.recycle_string(result.name);
+            arena.recycle_vec(result.values);
+            assert_eq!(arena.available(), 0);
+        });
+    }
+}
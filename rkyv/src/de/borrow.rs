@@ -0,0 +1,74 @@
+//! Deserialization into types which borrow from the archive.
+
+use rancor::Fallible;
+pub use rkyv_derive::DeserializeBorrowed;
+
+/// Converts a type back from its archived form into a type which borrows from
+/// the archived value.
+///
+/// Unlike [`Deserialize`](crate::Deserialize), the deserialized value may hold
+/// references into the archive. This allows archived strings and byte slices
+/// to be deserialized into `&'a str`, `&'a [T]`, and `Cow<'a, _>` without
+/// copying them out of the buffer. Deserialized `Cow`s are always borrowed.
+///
+/// This can be derived with [`DeserializeBorrowed`](macro@DeserializeBorrowed)
+/// for types which borrow from another archived type:
+///
+/// ```
+/// use rkyv::{
+///     api::high::deserialize_borrowed, de::DeserializeBorrowed,
+///     rancor::Error, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Record {
+///     id: u32,
+///     name: String,
+///     notes: Vec<String>,
+/// }
+///
+/// #[derive(DeserializeBorrowed)]
+/// #[rkyv(borrow_from = ArchivedRecord)]
+/// struct RecordRef<'a> {
+///     id: u32,
+///     name: &'a str,
+/// }
+///
+/// let record = Record {
+///     id: 1,
+///     name: "first".to_string(),
+///     notes: Vec::new(),
+/// };
+/// let bytes = rkyv::to_bytes::<Error>(&record).unwrap();
+/// let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+///
+/// let borrowed: RecordRef<'_> =
+///     deserialize_borrowed::<_, Error>(archived).unwrap();
+/// assert_eq!(borrowed.id, 1);
+/// assert_eq!(borrowed.name, "first");
+/// ```
+///
+/// # Example
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// use rkyv::{api::high::deserialize_borrowed, rancor::Error, Archived};
+///
+/// let value = vec!["hello".to_string(), "world".to_string()];
+/// let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+/// let archived =
+///     rkyv::access::<Archived<Vec<String>>, Error>(&bytes).unwrap();
+///
+/// let borrowed: Vec<Cow<'_, str>> =
+///     deserialize_borrowed::<_, Error>(archived).unwrap();
+/// assert_eq!(borrowed, ["hello", "world"]);
+/// assert!(matches!(borrowed[0], Cow::Borrowed(_)));
+/// ```
+pub trait DeserializeBorrowed<'a, T, D: Fallible + ?Sized> {
+    /// Deserializes using the given deserializer, borrowing from `self`.
+    fn deserialize_borrowed(
+        &'a self,
+        deserializer: &mut D,
+    ) -> Result<T, D::Error>;
+}
@@ -0,0 +1,60 @@
+//! Deserialization into existing values.
+
+use rancor::Fallible;
+pub use rkyv_derive::DeserializeInPlace;
+
+/// Converts a type back from its archived form into an existing value.
+///
+/// Unlike [`Deserialize`](crate::Deserialize), which always creates a new
+/// value, `DeserializeInPlace` overwrites a value which already exists. Heap
+/// allocations owned by the existing value are reused where possible: strings
+/// and vectors keep their capacity, boxes keep their allocation, and hash maps
+/// keep their buckets. This makes it cheaper to repeatedly deserialize into the
+/// same scratch value.
+///
+/// This can be derived with [`DeserializeInPlace`](macro@DeserializeInPlace).
+/// Fields which are skipped with `#[rkyv(skip)]` are left unchanged, and fields
+/// which use `#[rkyv(with = ..)]` are deserialized into new values. Derived
+/// implementations for enums deserialize a new value with [`Deserialize`] when
+/// the variant changes, so enums must also derive `Deserialize`.
+///
+/// [`Deserialize`]: crate::Deserialize
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     api::high::deserialize_in_place, de::DeserializeInPlace, rancor::Error,
+///     Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, DeserializeInPlace, Default)]
+/// struct Example {
+///     name: String,
+///     values: Vec<u32>,
+/// }
+///
+/// let mut scratch = Example::default();
+/// for i in 0..4 {
+///     let value = Example {
+///         name: format!("example {i}"),
+///         values: (0..i).collect(),
+///     };
+///     let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+///     let archived = rkyv::access::<ArchivedExample, Error>(&bytes).unwrap();
+///
+///     deserialize_in_place::<_, Error>(archived, &mut scratch).unwrap();
+///     assert_eq!(scratch.name, value.name);
+///     assert_eq!(scratch.values, value.values);
+/// }
+/// ```
+pub trait DeserializeInPlace<T, D: Fallible + ?Sized> {
+    /// Deserializes into `out` using the given deserializer.
+    ///
+    /// If an error is returned, `out` is left in a valid but unspecified state.
+    fn deserialize_in_place(
+        &self,
+        out: &mut T,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error>;
+}
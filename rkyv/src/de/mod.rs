@@ -1,6 +1,15 @@
 //! Deserialization traits, deserializers, and adapters.
 
+#[cfg(feature = "alloc")]
+pub mod arena;
+pub mod borrow;
+pub mod in_place;
 pub mod pooling;
 
+#[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::pooling::*;
+pub use self::arena::{Arena, ArenaDeserializer, RecyclingArena};
+#[doc(inline)]
+pub use self::{
+    borrow::DeserializeBorrowed, in_place::DeserializeInPlace, pooling::*,
+};
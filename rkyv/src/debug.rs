@@ -0,0 +1,167 @@
+//! Rendering archives as JSON for inspection.
+//!
+//! These functions validate an archive and render its root as JSON through the
+//! [`serde`](crate::serde) impls of the archived types. Archived types
+//! generated by the derive macro must use `#[rkyv(serde)]` to be rendered.
+//! Enums are rendered with the names of their variants, and shared pointers are
+//! rendered as the values they point to.
+
+use core::{error::Error, fmt};
+
+use bytecheck::CheckBytes;
+use rancor::Source;
+use serde_json_1::Value;
+
+use crate::{
+    alloc::string::String,
+    api::high::{access, HighValidator},
+    serde::{AsSerde, SerializeArchived},
+    Portable,
+};
+
+#[derive(Debug)]
+struct JsonError(serde_json_1::Error);
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to render archive as JSON: {}", self.0)
+    }
+}
+
+impl Error for JsonError {}
+
+/// Validates a byte slice and renders its archived root as a JSON value.
+///
+/// `T` is the archived type at the root of the archive, the same as for
+/// [`access`].
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     debug::to_json_value, rancor::Error, to_bytes, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// #[rkyv(serde)]
+/// enum Event {
+///     Connected { id: u32 },
+///     Disconnected,
+/// }
+///
+/// let events = vec![Event::Connected { id: 7 }, Event::Disconnected];
+/// let bytes = to_bytes::<Error>(&events).unwrap();
+/// let value =
+///     to_json_value::<rkyv::Archived<Vec<Event>>, Error>(&bytes).unwrap();
+/// assert_eq!(
+///     value.to_string(),
+///     r#"[{"Connected":{"id":7}},"Disconnected"]"#,
+/// );
+/// ```
+pub fn to_json_value<T, E>(bytes: &[u8]) -> Result<Value, E>
+where
+    T: Portable + SerializeArchived + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    let archived = access::<T, E>(bytes)?;
+    serde_json_1::to_value(AsSerde(archived)).map_err(|e| E::new(JsonError(e)))
+}
+
+/// Validates a byte slice and renders its archived root as a pretty-printed
+/// JSON string.
+///
+/// `T` is the archived type at the root of the archive, the same as for
+/// [`access`].
+pub fn to_json_string<T, E>(bytes: &[u8]) -> Result<String, E>
+where
+    T: Portable + SerializeArchived + for<'a> CheckBytes<HighValidator<'a, E>>,
+    E: Source,
+{
+    let archived = access::<T, E>(bytes)?;
+    serde_json_1::to_string_pretty(&AsSerde(archived))
+        .map_err(|e| E::new(JsonError(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::{Failure, Panic};
+    use serde_json_1::json;
+
+    use super::{to_json_string, to_json_value};
+    use crate::{
+        alloc::{rc::Rc, string::String, vec, vec::Vec},
+        api::test::to_bytes,
+        Archive, Archived, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, serde)]
+    struct Node {
+        name: String,
+        shared: Rc<Leaf>,
+        other: Rc<Leaf>,
+        kind: Kind,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, serde)]
+    struct Leaf(u32);
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, serde)]
+    enum Kind {
+        Empty,
+        Pair(i8, i8),
+    }
+
+    #[test]
+    fn renders_shared_pointers_and_variants() {
+        let leaf = Rc::new(Leaf(42));
+        let nodes = vec![
+            Node {
+                name: "a".into(),
+                shared: leaf.clone(),
+                other: leaf,
+                kind: Kind::Empty,
+            },
+            Node {
+                name: "b".into(),
+                shared: Rc::new(Leaf(1)),
+                other: Rc::new(Leaf(2)),
+                kind: Kind::Pair(-1, 1),
+            },
+        ];
+
+        to_bytes(&nodes, |bytes| {
+            let value =
+                to_json_value::<Archived<Vec<Node>>, Panic>(bytes).unwrap();
+            assert_eq!(
+                value,
+                json!([
+                    {
+                        "name": "a",
+                        "shared": 42,
+                        "other": 42,
+                        "kind": "Empty"
+                    },
+                    {
+                        "name": "b",
+                        "shared": 1,
+                        "other": 2,
+                        "kind": { "Pair": [-1, 1] }
+                    }
+                ]),
+            );
+
+            let string =
+                to_json_string::<Archived<Vec<Node>>, Panic>(bytes).unwrap();
+            assert!(string.contains("\"name\": \"a\""));
+        });
+    }
+
+    #[test]
+    fn rejects_invalid_archives() {
+        let result = to_json_value::<Archived<u32>, Failure>(&[0, 1]);
+        assert!(result.is_err());
+    }
+}
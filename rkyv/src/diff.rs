@@ -0,0 +1,559 @@
+//! Structural diffs between archived values.
+//!
+//! Archived types which implement [`Diff`] can be compared part by part
+//! without deserializing them. [`diff`] walks two archived values of the same
+//! type and returns a [`Difference`] for every part that differs, along with
+//! the path to that part and references to both archived values.
+//!
+//! Structs are compared field by field, sequences element by element, and maps
+//! key by key. Enums with different variants and leaf values like integers and
+//! strings which are not equal are reported as a whole.
+//!
+//! Archived types generated by the derive macro implement `Diff` when
+//! `#[rkyv(diff)]` is added to the derive input. They must also implement
+//! `Debug`, which can be derived with `#[rkyv(derive(Debug))]`.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{diff::diff, rancor::Error, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[rkyv(diff, derive(Debug))]
+//! struct Player {
+//!     name: String,
+//!     hp: u32,
+//! }
+//!
+//! let before = vec![
+//!     Player {
+//!         name: "a".into(),
+//!         hp: 10,
+//!     },
+//!     Player {
+//!         name: "b".into(),
+//!         hp: 10,
+//!     },
+//! ];
+//! let after = vec![
+//!     Player {
+//!         name: "a".into(),
+//!         hp: 10,
+//!     },
+//!     Player {
+//!         name: "b".into(),
+//!         hp: 7,
+//!     },
+//! ];
+//!
+//! let before = rkyv::to_bytes::<Error>(&before).unwrap();
+//! let after = rkyv::to_bytes::<Error>(&after).unwrap();
+//! let before =
+//!     rkyv::access::<rkyv::Archived<Vec<Player>>, Error>(&before).unwrap();
+//! let after =
+//!     rkyv::access::<rkyv::Archived<Vec<Player>>, Error>(&after).unwrap();
+//!
+//! let differences = diff(before, after);
+//! assert_eq!(differences.len(), 1);
+//! assert_eq!(differences[0].path.to_string(), "[1].hp");
+//! assert_eq!(differences[0].to_string(), "[1].hp: 10 -> 7");
+//! ```
+
+use core::{
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::ControlFlow,
+};
+
+use crate::{
+    alloc::{format, string::String, vec::Vec},
+    boxed::ArchivedBox,
+    collections::swiss_table::ArchivedHashMap,
+    option::ArchivedOption,
+    primitive::{
+        ArchivedChar, ArchivedF32, ArchivedF64, ArchivedI128, ArchivedI16,
+        ArchivedI32, ArchivedI64, ArchivedNonZeroI128, ArchivedNonZeroI16,
+        ArchivedNonZeroI32, ArchivedNonZeroI64, ArchivedNonZeroU128,
+        ArchivedNonZeroU16, ArchivedNonZeroU32, ArchivedNonZeroU64,
+        ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64,
+    },
+    rc::ArchivedRc,
+    string::ArchivedString,
+    tuple::{
+        ArchivedTuple1, ArchivedTuple2, ArchivedTuple3, ArchivedTuple4,
+        ArchivedTuple5, ArchivedTuple6,
+    },
+    vec::ArchivedVec,
+    ArchivePointee,
+};
+
+/// A step in the path from the root of an archived value to one of its parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A field of a struct or enum variant. Unnamed fields are named by their
+    /// index.
+    Field(&'static str),
+    /// An element of a sequence.
+    Index(usize),
+    /// The value for a key of a map, formatted with `Debug`.
+    Key(String),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) => write!(f, ".{name}"),
+            Self::Index(index) => write!(f, "[{index}]"),
+            Self::Key(key) => write!(f, "[{key}]"),
+        }
+    }
+}
+
+/// The path from the root of an archived value to one of its parts.
+///
+/// Paths are displayed like Rust expressions, for example `.players[3].hp`.
+/// The path to the root is displayed as `.`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiffPath(pub Vec<PathSegment>);
+
+impl fmt::Display for DiffPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, ".");
+        }
+        for segment in self.0.iter() {
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A part of two archived values which differs between them.
+#[derive(Debug)]
+pub struct Difference<'a> {
+    /// The path to the part which differs.
+    pub path: DiffPath,
+    /// The part of the left value, or `None` if it only exists in the right
+    /// value.
+    pub left: Option<&'a dyn fmt::Debug>,
+    /// The part of the right value, or `None` if it only exists in the left
+    /// value.
+    pub right: Option<&'a dyn fmt::Debug>,
+}
+
+impl fmt::Display for Difference<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.path)?;
+        match self.left {
+            Some(left) => write!(f, "{left:?}")?,
+            None => write!(f, "(missing)")?,
+        }
+        write!(f, " -> ")?;
+        match self.right {
+            Some(right) => write!(f, "{right:?}"),
+            None => write!(f, "(missing)"),
+        }
+    }
+}
+
+/// Records the differences between two archived values as they are walked.
+#[derive(Debug, Default)]
+pub struct Differ<'a> {
+    path: Vec<PathSegment>,
+    differences: Vec<Difference<'a>>,
+}
+
+impl<'a> Differ<'a> {
+    /// Returns a new `Differ` with no differences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the differences which have been recorded.
+    pub fn differences(&self) -> &[Difference<'a>] {
+        &self.differences
+    }
+
+    /// Consumes the `Differ` and returns the differences which have been
+    /// recorded.
+    pub fn into_differences(self) -> Vec<Difference<'a>> {
+        self.differences
+    }
+
+    /// Records a difference at the current path.
+    pub fn report(
+        &mut self,
+        left: Option<&'a dyn fmt::Debug>,
+        right: Option<&'a dyn fmt::Debug>,
+    ) {
+        self.differences.push(Difference {
+            path: DiffPath(self.path.clone()),
+            left,
+            right,
+        });
+    }
+
+    fn in_segment(&mut self, segment: PathSegment, f: impl FnOnce(&mut Self)) {
+        self.path.push(segment);
+        f(self);
+        self.path.pop();
+    }
+
+    fn entry<T: Diff>(
+        &mut self,
+        segment: PathSegment,
+        left: Option<&'a T>,
+        right: Option<&'a T>,
+    ) {
+        self.in_segment(segment, |differ| match (left, right) {
+            (Some(left), Some(right)) => left.diff(right, differ),
+            (None, None) => (),
+            (left, right) => differ.report(
+                left.map(|l| l as &dyn fmt::Debug),
+                right.map(|r| r as &dyn fmt::Debug),
+            ),
+        });
+    }
+
+    /// Compares a field of two structs or enum variants.
+    pub fn field<T: Diff + ?Sized>(
+        &mut self,
+        name: &'static str,
+        left: &'a T,
+        right: &'a T,
+    ) {
+        self.in_segment(PathSegment::Field(name), |differ| {
+            left.diff(right, differ)
+        });
+    }
+
+    /// Compares an element of two sequences. An element which only exists in
+    /// one of the sequences is reported as missing from the other.
+    pub fn index<T: Diff>(
+        &mut self,
+        index: usize,
+        left: Option<&'a T>,
+        right: Option<&'a T>,
+    ) {
+        self.entry(PathSegment::Index(index), left, right);
+    }
+
+    /// Compares the values for a key of two maps. A value which only exists in
+    /// one of the maps is reported as missing from the other.
+    pub fn key<K: fmt::Debug + ?Sized, T: Diff>(
+        &mut self,
+        key: &K,
+        left: Option<&'a T>,
+        right: Option<&'a T>,
+    ) {
+        self.entry(PathSegment::Key(format!("{key:?}")), left, right);
+    }
+}
+
+/// An archived type which can be compared part by part with another value of
+/// the same type.
+pub trait Diff: fmt::Debug {
+    /// Records the differences between `self` and `other` in `differ`.
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>);
+}
+
+/// Returns the differences between two archived values.
+///
+/// An empty result means that the values are equal.
+pub fn diff<'a, T: Diff + ?Sized>(
+    left: &'a T,
+    right: &'a T,
+) -> Vec<Difference<'a>> {
+    let mut differ = Differ::new();
+    left.diff(right, &mut differ);
+    differ.into_differences()
+}
+
+/// Validates two byte slices and returns the differences between their
+/// archived roots.
+///
+/// `T` is the archived type at the root of both archives, the same as for
+/// [`access`](crate::api::high::access).
+#[cfg(feature = "bytecheck")]
+pub fn diff_bytes<'a, T, E>(
+    left: &'a [u8],
+    right: &'a [u8],
+) -> Result<Vec<Difference<'a>>, E>
+where
+    T: crate::Portable
+        + Diff
+        + for<'v> bytecheck::CheckBytes<crate::api::high::HighValidator<'v, E>>,
+    E: rancor::Source,
+{
+    let left = crate::api::high::access::<T, E>(left)?;
+    let right = crate::api::high::access::<T, E>(right)?;
+    Ok(diff(left, right))
+}
+
+macro_rules! impl_leaf {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Diff for $ty {
+                fn diff<'a>(
+                    &'a self,
+                    other: &'a Self,
+                    differ: &mut Differ<'a>,
+                ) {
+                    if self != other {
+                        differ.report(Some(self), Some(other));
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_leaf!(
+    (),
+    bool,
+    i8,
+    u8,
+    core::num::NonZeroI8,
+    core::num::NonZeroU8,
+    ArchivedI16,
+    ArchivedI32,
+    ArchivedI64,
+    ArchivedI128,
+    ArchivedU16,
+    ArchivedU32,
+    ArchivedU64,
+    ArchivedU128,
+    ArchivedF32,
+    ArchivedF64,
+    ArchivedChar,
+    ArchivedNonZeroI16,
+    ArchivedNonZeroI32,
+    ArchivedNonZeroI64,
+    ArchivedNonZeroI128,
+    ArchivedNonZeroU16,
+    ArchivedNonZeroU32,
+    ArchivedNonZeroU64,
+    ArchivedNonZeroU128,
+    ArchivedString,
+);
+
+impl<T: ?Sized> Diff for PhantomData<T> {
+    fn diff<'a>(&'a self, _: &'a Self, _: &mut Differ<'a>) {}
+}
+
+impl<T: Diff> Diff for [T] {
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        for i in 0..self.len().max(other.len()) {
+            differ.index(i, self.get(i), other.get(i));
+        }
+    }
+}
+
+impl<T: Diff, const N: usize> Diff for [T; N] {
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        self.as_slice().diff(other.as_slice(), differ);
+    }
+}
+
+impl<T: Diff> Diff for ArchivedVec<T> {
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        self.as_slice().diff(other.as_slice(), differ);
+    }
+}
+
+impl<T: Diff> Diff for ArchivedOption<T> {
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        match (self, other) {
+            (ArchivedOption::Some(left), ArchivedOption::Some(right)) => {
+                left.diff(right, differ)
+            }
+            (ArchivedOption::None, ArchivedOption::None) => (),
+            _ => differ.report(Some(self), Some(other)),
+        }
+    }
+}
+
+impl<T> Diff for ArchivedBox<T>
+where
+    T: ArchivePointee + Diff + ?Sized,
+    T::ArchivedMetadata: fmt::Debug,
+{
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        self.get().diff(other.get(), differ);
+    }
+}
+
+impl<T: ArchivePointee + Diff + ?Sized, F> Diff for ArchivedRc<T, F> {
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        self.get().diff(other.get(), differ);
+    }
+}
+
+macro_rules! impl_tuple {
+    ($name:ident, $($t:ident $index:tt),*) => {
+        impl<$($t: Diff),*> Diff for $name<$($t),*> {
+            fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+                $(
+                    differ.field(
+                        stringify!($index),
+                        &self.$index,
+                        &other.$index,
+                    );
+                )*
+            }
+        }
+    };
+}
+
+impl_tuple!(ArchivedTuple1, T0 0);
+impl_tuple!(ArchivedTuple2, T0 0, T1 1);
+impl_tuple!(ArchivedTuple3, T0 0, T1 1, T2 2);
+impl_tuple!(ArchivedTuple4, T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(ArchivedTuple5, T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(ArchivedTuple6, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+
+impl<K, V, H> Diff for ArchivedHashMap<K, V, H>
+where
+    K: Hash + Eq + fmt::Debug,
+    V: Diff,
+    H: Hasher + Default,
+{
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        for (key, value) in self.iter() {
+            differ.key(key, Some(value), other.get(key));
+        }
+        for (key, value) in other.iter() {
+            if !self.contains_key(key) {
+                differ.key(key, None, Some(value));
+            }
+        }
+    }
+}
+
+impl<K, V, const E: usize> Diff
+    for crate::collections::btree_map::ArchivedBTreeMap<K, V, E>
+where
+    K: Ord + fmt::Debug,
+    V: Diff,
+{
+    fn diff<'a>(&'a self, other: &'a Self, differ: &mut Differ<'a>) {
+        self.visit(|key, value| {
+            differ.key(key, Some(value), other.get(key));
+            ControlFlow::<()>::Continue(())
+        });
+        other.visit(|key, value| {
+            if !self.contains_key(key) {
+                differ.key(key, None, Some(value));
+            }
+            ControlFlow::<()>::Continue(())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, PathSegment};
+    use crate::{
+        alloc::{
+            boxed::Box, collections::BTreeMap, string::String, vec, vec::Vec,
+        },
+        api::test::to_archived,
+        Archive, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, diff, derive(Debug))]
+    struct State {
+        tick: u32,
+        players: Vec<Player>,
+        scores: BTreeMap<String, u32>,
+        #[rkyv(skip)]
+        _scratch: u64,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, diff, derive(Debug))]
+    struct Player {
+        name: String,
+        position: (Box<[f32; 2]>,),
+        status: Status,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, diff, derive(Debug))]
+    enum Status {
+        Alive(u8),
+        Dead,
+    }
+
+    fn player(name: &str, x: f32, status: Status) -> Player {
+        Player {
+            name: name.into(),
+            position: (Box::new([x, 0.0]),),
+            status,
+        }
+    }
+
+    #[test]
+    fn diff_game_state() {
+        let before = State {
+            tick: 1,
+            players: vec![
+                player("a", 0.0, Status::Alive(10)),
+                player("b", 0.0, Status::Alive(10)),
+            ],
+            scores: [("a".into(), 1), ("b".into(), 2)].into_iter().collect(),
+            _scratch: 0,
+        };
+        let after = State {
+            tick: 2,
+            players: vec![
+                player("a", 1.5, Status::Alive(9)),
+                player("b", 0.0, Status::Dead),
+                player("c", 0.0, Status::Alive(10)),
+            ],
+            scores: [("a".into(), 1), ("c".into(), 0)].into_iter().collect(),
+            _scratch: 1,
+        };
+
+        to_archived(&before, |before| {
+            to_archived(&after, |after| {
+                let differences = diff(&*before, &*after);
+                let paths = differences
+                    .iter()
+                    .map(|d| d.path.to_string())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    paths,
+                    [
+                        ".tick",
+                        ".players[0].position.0[0]",
+                        ".players[0].status.0",
+                        ".players[1].status",
+                        ".players[2]",
+                        ".scores[\"b\"]",
+                        ".scores[\"c\"]",
+                    ],
+                );
+                assert_eq!(differences[0].path.0, [PathSegment::Field("tick")]);
+                assert_eq!(
+                    differences[2].to_string(),
+                    ".players[0].status.0: 10 -> 9"
+                );
+                assert_eq!(
+                    differences[3].to_string(),
+                    ".players[1].status: Alive(10) -> Dead",
+                );
+                assert!(differences[4].left.is_none());
+                assert!(differences[4].right.is_some());
+                assert_eq!(
+                    differences[5].to_string(),
+                    ".scores[\"b\"]: 2 -> (missing)"
+                );
+
+                assert!(diff(&*before, &*before).is_empty());
+            });
+        });
+    }
+}
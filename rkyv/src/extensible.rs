@@ -0,0 +1,201 @@
+//! An archived value which records its serialized size.
+
+use core::{borrow::Borrow, fmt, mem::size_of, ops::Deref};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    Place, Portable, RelPtr, SerializeUnsized,
+};
+
+/// An archived value stored out-of-line along with its serialized size.
+///
+/// Normally, the layout of an archived struct is fixed by its definition, so
+/// adding a field to a struct makes its archives unreadable by code built
+/// against the old definition. An `ArchivedExtensible` instead points to the
+/// value and records how many bytes it was serialized with. Because archived
+/// structs are `repr(C)`, a struct with fields appended to the end starts with
+/// the same layout as the original struct. Readers with an older definition of
+/// `T` only read the prefix they know about, and validation only requires that
+/// the recorded size is at least as large as their `T`.
+///
+/// Fields may only be appended to the end of the struct, and existing fields
+/// may not be changed or removed.
+///
+/// This is the archived type of the [`Extensible`](crate::with::Extensible)
+/// wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedExtensible<T> {
+    ptr: RelPtr<T>,
+    size: ArchivedUsize,
+}
+
+impl<T> ArchivedExtensible<T> {
+    /// Returns a reference to the archived value.
+    pub fn get(&self) -> &T {
+        unsafe { &*self.ptr.as_ptr() }
+    }
+
+    /// Returns a sealed mutable reference to the archived value.
+    pub fn get_seal(this: Seal<'_, Self>) -> Seal<'_, T> {
+        munge!(let Self { ptr, .. } = this);
+        Seal::new(unsafe { &mut *RelPtr::as_mut_ptr(ptr) })
+    }
+
+    /// Returns the number of bytes the value was serialized with.
+    ///
+    /// This may be larger than `size_of::<T>()` if the value was serialized by
+    /// a newer version of `T` with additional fields.
+    pub fn size(&self) -> usize {
+        self.size.to_native() as usize
+    }
+
+    /// Resolves an archived extensible value from a resolver.
+    pub fn resolve_from_resolver(
+        resolver: ExtensibleResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedExtensible { ptr, size } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+        size.write(ArchivedUsize::from_native(size_of::<T>() as FixedUsize));
+    }
+
+    /// Serializes the value of an archived extensible value.
+    pub fn serialize_from_ref<U, S>(
+        value: &U,
+        serializer: &mut S,
+    ) -> Result<ExtensibleResolver, S::Error>
+    where
+        U: SerializeUnsized<S, Archived = T> + ?Sized,
+        S: Fallible + ?Sized,
+    {
+        Ok(ExtensibleResolver {
+            pos: value.serialize_unsized(serializer)? as FixedUsize,
+        })
+    }
+}
+
+impl<T> AsRef<T> for ArchivedExtensible<T> {
+    fn as_ref(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T> Borrow<T> for ArchivedExtensible<T> {
+    fn borrow(&self) -> &T {
+        self.get()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedExtensible<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl<T> Deref for ArchivedExtensible<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<ArchivedExtensible<U>>
+    for ArchivedExtensible<T>
+{
+    fn eq(&self, other: &ArchivedExtensible<U>) -> bool {
+        self.get() == other.get()
+    }
+}
+
+/// The resolver for [`ArchivedExtensible`].
+pub struct ExtensibleResolver {
+    pos: FixedUsize,
+}
+
+impl ExtensibleResolver {
+    /// Creates a new `ExtensibleResolver` from the position of a serialized
+    /// value.
+    pub fn from_pos(pos: usize) -> Self {
+        Self {
+            pos: pos as FixedUsize,
+        }
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{
+        alloc::Layout,
+        error::Error,
+        fmt,
+        mem::{align_of, size_of},
+    };
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::{fail, ResultExt as _};
+
+    use crate::{
+        extensible::ArchivedExtensible,
+        validation::{ArchiveContext, ArchiveContextExt},
+    };
+
+    /// An error resulting from an archived extensible value which is smaller
+    /// than the type it is accessed as.
+    #[derive(Debug)]
+    pub struct ExtensibleTooSmallError {
+        size: usize,
+        expected: usize,
+    }
+
+    impl fmt::Display for ExtensibleTooSmallError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived extensible value has a size of {} bytes, but the \
+                 accessed type requires at least {} bytes",
+                self.size, self.expected,
+            )
+        }
+    }
+
+    impl Error for ExtensibleTooSmallError {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedExtensible<T>
+    where
+        T: CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let size = self.size();
+            if size < size_of::<T>() {
+                fail!(ExtensibleTooSmallError {
+                    size,
+                    expected: size_of::<T>(),
+                });
+            }
+
+            let ptr = self.ptr.as_ptr_wrapping();
+            let layout =
+                Layout::from_size_align(size, align_of::<T>()).into_error()?;
+            context.in_subtree_raw(ptr.cast(), layout, |context| unsafe {
+                T::check_bytes(ptr, context)
+            })
+        }
+    }
+}
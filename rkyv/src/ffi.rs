@@ -12,8 +12,10 @@ use munge::munge;
 use rancor::Fallible;
 
 use crate::{
-    primitive::FixedUsize, ser::Writer, ArchiveUnsized, Place, Portable,
-    RelPtr, SerializeUnsized,
+    primitive::FixedUsize,
+    ser::Writer,
+    vec::{ArchivedVec, VecResolver},
+    ArchiveUnsized, Place, Portable, RelPtr, SerializeUnsized,
 };
 
 /// An archived [`CString`](crate::alloc::ffi::CString).
@@ -168,6 +170,341 @@ pub struct CStringResolver {
     pos: FixedUsize,
 }
 
+/// The encoding of the bytes in an [`ArchivedOsString`].
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[repr(u8)]
+#[rkyv(crate)]
+pub enum OsStrEncoding {
+    /// The bytes are valid UTF-8. These strings can be used on every platform.
+    Utf8,
+    /// The bytes are an arbitrary byte string from a Unix-like platform.
+    Unix,
+    /// The bytes are the [WTF-8](https://simonsapin.github.io/wtf-8/) encoding
+    /// of a potentially ill-formed UTF-16 string from Windows.
+    Wtf8,
+}
+
+/// An archived [`OsString`](std::ffi::OsString).
+///
+/// OS strings are stored as bytes tagged with the [encoding](OsStrEncoding)
+/// they were serialized with. Strings which are valid UTF-8 are always stored
+/// as UTF-8 so that they can be used on every platform. Other strings are
+/// stored in the native encoding of the serializing platform.
+///
+/// # Cross-platform deserialization
+///
+/// UTF-8 strings can be accessed and deserialized on every platform. Strings
+/// in a platform-specific encoding can only be converted back to an `OsStr` on
+/// a matching platform: `as_os_str` returns `None` and deserialization fails
+/// on other platforms. The raw bytes are always available through `as_bytes`,
+/// and `to_string_lossy` can be used to recover a readable string anywhere.
+///
+/// On Unix-like platforms, `as_os_str` borrows directly from the archive. On
+/// Windows, strings which are not valid UTF-8 must be decoded from WTF-8 and
+/// so can only be converted to an owned `OsString`.
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd, Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedOsString {
+    bytes: ArchivedVec<u8>,
+    encoding: OsStrEncoding,
+}
+
+impl ArchivedOsString {
+    /// Returns the encoding of the archived string.
+    #[inline]
+    pub fn encoding(&self) -> OsStrEncoding {
+        self.encoding
+    }
+
+    /// Returns the encoded bytes of the archived string.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Returns the archived string as a `str` if it is valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        match self.encoding {
+            // SAFETY: UTF-8 encoded strings are checked to be valid UTF-8
+            // during validation.
+            OsStrEncoding::Utf8 => {
+                Some(unsafe { core::str::from_utf8_unchecked(self.as_bytes()) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the archived string as an `OsStr` without copying, if its
+    /// encoding can be used on this platform.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn as_os_str(&self) -> Option<&std::ffi::OsStr> {
+        if let Some(s) = self.to_str() {
+            return Some(std::ffi::OsStr::new(s));
+        }
+
+        #[cfg(unix)]
+        if self.encoding == OsStrEncoding::Unix {
+            use std::os::unix::ffi::OsStrExt as _;
+
+            return Some(std::ffi::OsStr::from_bytes(self.as_bytes()));
+        }
+
+        None
+    }
+
+    /// Returns the archived string as an `OsString`, if its encoding can be
+    /// used on this platform.
+    #[cfg(feature = "std")]
+    pub fn to_os_string(&self) -> Option<std::ffi::OsString> {
+        if let Some(os_str) = self.as_os_str() {
+            return Some(os_str.to_os_string());
+        }
+
+        #[cfg(windows)]
+        if self.encoding == OsStrEncoding::Wtf8 {
+            use std::os::windows::ffi::OsStringExt as _;
+
+            let wide = wtf8::decode(self.as_bytes())?;
+            return Some(std::ffi::OsString::from_wide(&wide));
+        }
+
+        None
+    }
+
+    /// Converts the archived string to a `str`, replacing any invalid
+    /// sequences with the replacement character on every platform.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_lossy(&self) -> crate::alloc::borrow::Cow<'_, str> {
+        crate::alloc::string::String::from_utf8_lossy(self.as_bytes())
+    }
+
+    /// Resolves an archived OS string from the given OS string and parameters.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn resolve_from_os_str(
+        os_str: &std::ffi::OsStr,
+        resolver: OsStringResolver,
+        out: Place<Self>,
+    ) {
+        // We already checked that the string can be encoded during serialize
+        let (os_encoding, os_bytes) = encode_os_str(os_str).unwrap();
+
+        munge!(let ArchivedOsString { bytes, encoding } = out);
+        ArchivedVec::<u8>::resolve_from_len(
+            os_bytes.len(),
+            resolver.bytes,
+            bytes,
+        );
+        encoding.write(os_encoding);
+    }
+
+    /// Serializes an OS string.
+    #[cfg(feature = "std")]
+    pub fn serialize_from_os_str<S>(
+        os_str: &std::ffi::OsStr,
+        serializer: &mut S,
+    ) -> Result<OsStringResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        S::Error: rancor::Source,
+    {
+        #[derive(Debug)]
+        struct UnsupportedEncoding;
+
+        impl fmt::Display for UnsupportedEncoding {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "`OsStr` is not valid UTF-8 on an unsupported platform"
+                )
+            }
+        }
+
+        impl core::error::Error for UnsupportedEncoding {}
+
+        let Some((_, bytes)) = encode_os_str(os_str) else {
+            rancor::fail!(UnsupportedEncoding);
+        };
+        let pos = serializer.pos();
+        serializer.write(&bytes)?;
+        Ok(OsStringResolver {
+            bytes: VecResolver::from_pos(pos),
+        })
+    }
+}
+
+impl fmt::Debug for ArchivedOsString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_str() {
+            Some(s) => s.fmt(f),
+            None => f
+                .debug_struct("ArchivedOsString")
+                .field("encoding", &self.encoding)
+                .field("bytes", &self.as_bytes())
+                .finish(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<std::ffi::OsStr> for ArchivedOsString {
+    #[inline]
+    fn eq(&self, other: &std::ffi::OsStr) -> bool {
+        // Serialized strings are always encoded the same way on a given
+        // platform, so comparing encoded bytes is sufficient.
+        encode_os_str(other).is_some_and(|(encoding, bytes)| {
+            self.encoding == encoding && self.as_bytes() == &*bytes
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ArchivedOsString> for std::ffi::OsStr {
+    #[inline]
+    fn eq(&self, other: &ArchivedOsString) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<std::ffi::OsString> for ArchivedOsString {
+    #[inline]
+    fn eq(&self, other: &std::ffi::OsString) -> bool {
+        self.eq(other.as_os_str())
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ArchivedOsString> for std::ffi::OsString {
+    #[inline]
+    fn eq(&self, other: &ArchivedOsString) -> bool {
+        other.eq(self.as_os_str())
+    }
+}
+
+/// The resolver for `OsString`.
+pub struct OsStringResolver {
+    bytes: VecResolver,
+}
+
+/// Returns the encoding and encoded bytes of the given `OsStr`, or `None` if it
+/// is not valid UTF-8 and the current platform is not supported.
+#[cfg(feature = "std")]
+fn encode_os_str(
+    os_str: &std::ffi::OsStr,
+) -> Option<(OsStrEncoding, crate::alloc::borrow::Cow<'_, [u8]>)> {
+    use crate::alloc::borrow::Cow;
+
+    if let Some(s) = os_str.to_str() {
+        return Some((OsStrEncoding::Utf8, Cow::Borrowed(s.as_bytes())));
+    }
+
+    encode_native(os_str)
+}
+
+#[cfg(all(feature = "std", unix))]
+fn encode_native(
+    os_str: &std::ffi::OsStr,
+) -> Option<(OsStrEncoding, crate::alloc::borrow::Cow<'_, [u8]>)> {
+    use std::os::unix::ffi::OsStrExt as _;
+
+    let bytes = crate::alloc::borrow::Cow::Borrowed(os_str.as_bytes());
+    Some((OsStrEncoding::Unix, bytes))
+}
+
+#[cfg(all(feature = "std", windows))]
+fn encode_native(
+    os_str: &std::ffi::OsStr,
+) -> Option<(OsStrEncoding, crate::alloc::borrow::Cow<'_, [u8]>)> {
+    use std::os::windows::ffi::OsStrExt as _;
+
+    let bytes = wtf8::encode(os_str.encode_wide());
+    Some((OsStrEncoding::Wtf8, crate::alloc::borrow::Cow::Owned(bytes)))
+}
+
+#[cfg(all(feature = "std", not(any(unix, windows))))]
+fn encode_native(
+    _: &std::ffi::OsStr,
+) -> Option<(OsStrEncoding, crate::alloc::borrow::Cow<'_, [u8]>)> {
+    None
+}
+
+#[cfg(all(feature = "std", windows))]
+mod wtf8 {
+    use crate::alloc::vec::Vec;
+
+    /// Encodes potentially ill-formed UTF-16 as WTF-8.
+    pub fn encode(wide: impl Iterator<Item = u16>) -> Vec<u8> {
+        let mut result = Vec::new();
+        for c in char::decode_utf16(wide) {
+            match c {
+                Ok(c) => {
+                    let mut buf = [0; 4];
+                    result
+                        .extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+                Err(e) => {
+                    // Unpaired surrogates are encoded like three-byte UTF-8
+                    // sequences.
+                    let u = e.unpaired_surrogate();
+                    result.push(0xe0 | (u >> 12) as u8);
+                    result.push(0x80 | ((u >> 6) & 0x3f) as u8);
+                    result.push(0x80 | (u & 0x3f) as u8);
+                }
+            }
+        }
+        result
+    }
+
+    /// Decodes WTF-8 into potentially ill-formed UTF-16, or returns `None` if
+    /// the bytes are not well-formed.
+    pub fn decode(bytes: &[u8]) -> Option<Vec<u16>> {
+        let mut result = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let first = bytes[i];
+            let (len, min, mut code_point) = match first {
+                0x00..=0x7f => (1, 0, first as u32),
+                0xc2..=0xdf => (2, 0x80, (first & 0x1f) as u32),
+                0xe0..=0xef => (3, 0x800, (first & 0x0f) as u32),
+                0xf0..=0xf4 => (4, 0x10000, (first & 0x07) as u32),
+                _ => return None,
+            };
+            let rest = bytes.get(i + 1..i + len)?;
+            for &b in rest {
+                if b & 0xc0 != 0x80 {
+                    return None;
+                }
+                code_point = (code_point << 6) | (b & 0x3f) as u32;
+            }
+            if code_point < min || code_point > 0x10ffff {
+                return None;
+            }
+
+            if code_point >= 0x10000 {
+                let c = code_point - 0x10000;
+                result.push(0xd800 | (c >> 10) as u16);
+                result.push(0xdc00 | (c & 0x3ff) as u16);
+            } else {
+                result.push(code_point as u16);
+            }
+            i += len;
+        }
+        Some(result)
+    }
+}
+
 #[cfg(feature = "bytecheck")]
 mod verify {
     use core::ffi::CStr;
@@ -178,7 +515,7 @@ mod verify {
     };
 
     use crate::{
-        ffi::ArchivedCString,
+        ffi::{ArchivedCString, ArchivedOsString, OsStrEncoding},
         validation::{ArchiveContext, ArchiveContextExt},
     };
 
@@ -194,4 +531,20 @@ mod verify {
             })
         }
     }
+
+    unsafe impl<C> Verify<C> for ArchivedOsString
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            if self.encoding == OsStrEncoding::Utf8 {
+                let ptr = self.as_bytes() as *const [u8] as *const str;
+                // SAFETY: `ptr` points to the bytes of the string, which have
+                // already been checked.
+                unsafe { str::check_bytes(ptr, context)? };
+            }
+            Ok(())
+        }
+    }
 }
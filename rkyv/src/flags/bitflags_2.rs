@@ -0,0 +1,196 @@
+//! Archived versions of `bitflags` types.
+
+use core::{fmt, marker::PhantomData};
+
+use bitflags_2::{Bits, Flags};
+use munge::munge;
+
+use crate::{Archive, Archived, Place, Portable};
+
+/// An integer type which can be used as the bits of [`ArchivedFlags`].
+pub trait ArchiveBits: Bits + Archive {
+    /// Returns the native bits of an archived value.
+    fn from_archived(archived: &Self::Archived) -> Self;
+
+    /// Resolves the archived bits into a place.
+    fn resolve_bits(self, out: Place<Self::Archived>);
+}
+
+macro_rules! impl_archive_bits {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl ArchiveBits for $ty {
+                #[inline]
+                fn from_archived(archived: &Self::Archived) -> Self {
+                    <$ty>::from(*archived)
+                }
+
+                #[inline]
+                fn resolve_bits(self, out: Place<Self::Archived>) {
+                    self.resolve((), out);
+                }
+            }
+        )*
+    };
+}
+
+impl_archive_bits!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+/// An archived set of flags generated by the `bitflags!` macro.
+///
+/// The flags are stored as their raw bits. When validated, any bits which do
+/// not correspond to a known flag are rejected.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedFlags<F>
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+    bits: Archived<F::Bits>,
+    _phantom: PhantomData<F>,
+}
+
+impl<F> ArchivedFlags<F>
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+    /// Returns the raw bits of the flags.
+    #[inline]
+    pub fn bits(&self) -> F::Bits {
+        F::Bits::from_archived(&self.bits)
+    }
+
+    /// Returns the flags with the same bits.
+    #[inline]
+    pub fn to_flags(&self) -> F {
+        F::from_bits_retain(self.bits())
+    }
+
+    /// Returns whether no flags are set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits() == F::Bits::EMPTY
+    }
+
+    /// Returns whether all known flags are set.
+    #[inline]
+    pub fn is_all(&self) -> bool {
+        self.to_flags().is_all()
+    }
+
+    /// Returns whether all of the flags in `other` are set.
+    #[inline]
+    pub fn contains(&self, other: F) -> bool {
+        self.to_flags().contains(other)
+    }
+
+    /// Returns whether any of the flags in `other` are set.
+    #[inline]
+    pub fn intersects(&self, other: F) -> bool {
+        self.to_flags().intersects(other)
+    }
+
+    /// Emplaces an `ArchivedFlags` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &F, out: Place<Self>) {
+        munge!(let ArchivedFlags { bits, _phantom: _ } = out);
+        value.bits().resolve_bits(bits);
+    }
+}
+
+impl<F> fmt::Debug for ArchivedFlags<F>
+where
+    F: Flags + fmt::Debug,
+    F::Bits: ArchiveBits,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_flags(), f)
+    }
+}
+
+impl<F> Eq for ArchivedFlags<F>
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+}
+
+impl<F> PartialEq for ArchivedFlags<F>
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+impl<F> PartialEq<F> for ArchivedFlags<F>
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+    #[inline]
+    fn eq(&self, other: &F) -> bool {
+        self.bits() == other.bits()
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{any::type_name, error::Error, fmt};
+
+    use bitflags_2::Flags;
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use rancor::fail;
+
+    use super::{ArchiveBits, ArchivedFlags};
+
+    /// An error resulting from archived flags which contain unknown bits.
+    #[derive(Debug)]
+    pub struct UnknownBitsError {
+        type_name: &'static str,
+    }
+
+    impl fmt::Display for UnknownBitsError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived `{}` contains bits which do not correspond to any \
+                 flag",
+                self.type_name,
+            )
+        }
+    }
+
+    impl Error for UnknownBitsError {}
+
+    unsafe impl<C, F> Verify<C> for ArchivedFlags<F>
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+        F: Flags,
+        F::Bits: ArchiveBits,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if F::from_bits(self.bits()).is_none() {
+                fail!(UnknownBitsError {
+                    type_name: type_name::<F>(),
+                });
+            }
+            Ok(())
+        }
+    }
+}
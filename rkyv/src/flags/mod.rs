@@ -0,0 +1,4 @@
+//! Archived versions of bit flag types.
+
+#[cfg(feature = "bitflags-2")]
+pub mod bitflags_2;
@@ -8,7 +8,7 @@ use core::{
 use crate::primitive::{FixedIsize, FixedUsize};
 
 /// A cross-platform 64-bit implementation of fxhash.
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct FxHasher64 {
     hash: u64,
 }
@@ -0,0 +1,115 @@
+use core::ffi::CStr;
+
+use rancor::Fallible;
+
+use crate::{
+    alloc::{borrow::Cow, vec::Vec},
+    de::DeserializeBorrowed,
+    ffi::ArchivedCString,
+    string::ArchivedString,
+    vec::ArchivedVec,
+};
+
+impl<'a, D: Fallible + ?Sized> DeserializeBorrowed<'a, Cow<'a, str>, D>
+    for ArchivedString
+{
+    #[inline]
+    fn deserialize_borrowed(
+        &'a self,
+        _: &mut D,
+    ) -> Result<Cow<'a, str>, D::Error> {
+        Ok(Cow::Borrowed(self.as_str()))
+    }
+}
+
+impl<'a, T: Clone, D: Fallible + ?Sized>
+    DeserializeBorrowed<'a, Cow<'a, [T]>, D> for ArchivedVec<T>
+{
+    #[inline]
+    fn deserialize_borrowed(
+        &'a self,
+        _: &mut D,
+    ) -> Result<Cow<'a, [T]>, D::Error> {
+        Ok(Cow::Borrowed(self.as_slice()))
+    }
+}
+
+impl<'a, D: Fallible + ?Sized> DeserializeBorrowed<'a, Cow<'a, CStr>, D>
+    for ArchivedCString
+{
+    #[inline]
+    fn deserialize_borrowed(
+        &'a self,
+        _: &mut D,
+    ) -> Result<Cow<'a, CStr>, D::Error> {
+        Ok(Cow::Borrowed(self.as_c_str()))
+    }
+}
+
+impl<'a, T, U, D> DeserializeBorrowed<'a, Vec<T>, D> for ArchivedVec<U>
+where
+    U: DeserializeBorrowed<'a, T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_borrowed(
+        &'a self,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(self.len());
+        for item in self.iter() {
+            result.push(item.deserialize_borrowed(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::ffi::CStr;
+
+    use rancor::Failure;
+
+    use crate::{
+        alloc::{borrow::Cow, ffi::CString, string::String, vec, vec::Vec},
+        api::{high::deserialize_borrowed, test::to_archived},
+    };
+
+    #[test]
+    fn deserialize_borrowed_strings() {
+        let value = vec![Some(String::from("hello")), None];
+        to_archived(&value, |archived| {
+            let archived = &*archived;
+            let result: Vec<Option<&str>> =
+                deserialize_borrowed::<_, Failure>(archived).unwrap();
+            assert_eq!(result, [Some("hello"), None]);
+            let archived_str = archived[0].as_ref().unwrap().as_str();
+            assert_eq!(result[0].unwrap().as_ptr(), archived_str.as_ptr());
+
+            let result: Vec<Option<Cow<'_, str>>> =
+                deserialize_borrowed::<_, Failure>(archived).unwrap();
+            assert!(matches!(result[0], Some(Cow::Borrowed("hello"))));
+        });
+    }
+
+    #[test]
+    fn deserialize_borrowed_bytes() {
+        let value = vec![1u8, 2, 3, 4];
+        to_archived(&value, |archived| {
+            let archived = &*archived;
+            let result: Cow<'_, [u8]> =
+                deserialize_borrowed::<_, Failure>(archived).unwrap();
+            assert!(matches!(result, Cow::Borrowed([1, 2, 3, 4])));
+
+            let result: &[u8] =
+                deserialize_borrowed::<_, Failure>(archived).unwrap();
+            assert_eq!(result.as_ptr(), archived.as_ptr());
+        });
+
+        let value = CString::new("hello").unwrap();
+        to_archived(&value, |archived| {
+            let result: &CStr =
+                deserialize_borrowed::<_, Failure>(&*archived).unwrap();
+            assert_eq!(result, value.as_c_str());
+        });
+    }
+}
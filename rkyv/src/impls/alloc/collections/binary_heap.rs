@@ -0,0 +1,107 @@
+use core::cmp::Ordering;
+
+use rancor::Fallible;
+
+use crate::{
+    alloc::{collections::BinaryHeap, vec::Vec},
+    collections::binary_heap::{ArchivedBinaryHeap, BinaryHeapResolver},
+    ser::{Allocator, Writer},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl<T: Archive + Ord> Archive for BinaryHeap<T> {
+    type Archived = ArchivedBinaryHeap<T::Archived>;
+    type Resolver = BinaryHeapResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedBinaryHeap::resolve_from_len(self.len(), resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for BinaryHeap<T>
+where
+    T: Serialize<S> + Ord,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBinaryHeap::<T::Archived>::serialize_from_slice(
+            self.as_slice(),
+            serializer,
+        )
+    }
+}
+
+impl<T, D> Deserialize<BinaryHeap<T>, D> for ArchivedBinaryHeap<T::Archived>
+where
+    T: Archive + Ord,
+    T::Archived: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(
+        &self,
+        deserializer: &mut D,
+    ) -> Result<BinaryHeap<T>, D::Error> {
+        // The deserialized elements may not be ordered the same way as the
+        // archived elements, so the heap is rebuilt from scratch.
+        let mut result = Vec::with_capacity(self.len());
+        for value in self.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(BinaryHeap::from(result))
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<BinaryHeap<U>> for ArchivedBinaryHeap<T> {
+    fn eq(&self, other: &BinaryHeap<U>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialOrd<U>, U> PartialOrd<BinaryHeap<U>> for ArchivedBinaryHeap<T> {
+    fn partial_cmp(&self, other: &BinaryHeap<U>) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alloc::{collections::BinaryHeap, vec::Vec},
+        api::test::{deserialize, to_archived},
+    };
+
+    #[test]
+    fn binary_heap() {
+        let heap = [5, 1, 8, 3, 9, 2, 7, 4, 6, 0]
+            .into_iter()
+            .collect::<BinaryHeap<i32>>();
+
+        to_archived(&heap, |archived| {
+            assert_eq!(archived.len(), 10);
+            assert_eq!(archived.peek().unwrap(), &9);
+            assert!(archived.iter().eq(heap.iter()));
+            assert!(archived
+                .iter_sorted()
+                .map(|x| x.to_native())
+                .eq((0..10).rev()));
+
+            let deserialized = deserialize::<BinaryHeap<i32>>(&*archived);
+            assert_eq!(
+                deserialized.into_sorted_vec(),
+                (0..10).collect::<Vec<_>>()
+            );
+        });
+    }
+
+    #[test]
+    fn empty_binary_heap() {
+        to_archived(&BinaryHeap::<u8>::new(), |archived| {
+            assert!(archived.is_empty());
+            assert!(archived.peek().is_none());
+            assert_eq!(archived.iter_sorted().count(), 0);
+        });
+    }
+}
@@ -1,4 +1,4 @@
-use core::ops::ControlFlow;
+use core::{cmp::Ordering, ops::ControlFlow};
 
 use rancor::{Fallible, Source};
 
@@ -96,6 +96,34 @@ where
     }
 }
 
+impl<K, V, AK, AV> PartialOrd<BTreeMap<K, V>> for ArchivedBTreeMap<AK, AV>
+where
+    AK: PartialOrd<K>,
+    AV: PartialOrd<V>,
+{
+    fn partial_cmp(&self, other: &BTreeMap<K, V>) -> Option<Ordering> {
+        let mut iter = other.iter();
+        for (ak, av) in self.iter() {
+            let Some((k, v)) = iter.next() else {
+                return Some(Ordering::Greater);
+            };
+            match ak.partial_cmp(k) {
+                Some(Ordering::Equal) => (),
+                x => return x,
+            }
+            match av.partial_cmp(v) {
+                Some(Ordering::Equal) => (),
+                x => return x,
+            }
+        }
+        if iter.next().is_some() {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Equal)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::ops::ControlFlow;
@@ -272,4 +300,117 @@ mod tests {
             assert_eq!(i.next(), None);
         });
     }
+
+    #[test]
+    fn btree_map_iter_deep() {
+        // Trees with three or more levels have inner nodes whose lesser nodes
+        // are also inner nodes.
+        for size in [35, 36, 112, 215, 1000] {
+            let mut value = BTreeMap::new();
+            for i in 0..size {
+                value.insert(i, i);
+            }
+
+            to_archived(&value, |archived| {
+                assert!(archived.keys().map(|k| k.to_native()).eq(0..size));
+            });
+        }
+    }
+
+    #[test]
+    fn btree_map_range() {
+        use core::ops::Bound;
+
+        let mut value = BTreeMap::new();
+        for i in 0..215 {
+            value.insert(i * 2, i);
+        }
+
+        to_archived(&value, |archived| {
+            fn check(
+                value: &BTreeMap<i32, i32>,
+                archived: &ArchivedBTreeMap<ArchivedI32, ArchivedI32>,
+                range: (Bound<i32>, Bound<i32>),
+            ) {
+                let archived_range = (
+                    range.0.map(ArchivedI32::from_native),
+                    range.1.map(ArchivedI32::from_native),
+                );
+                assert!(archived
+                    .range(archived_range)
+                    .map(|(k, v)| (k.to_native(), v.to_native()))
+                    .eq(value.range(range).map(|(k, v)| (*k, *v))));
+            }
+
+            for start in [-1, 0, 1, 10, 213, 428, 429] {
+                for end in [start, start + 1, start + 37, 500] {
+                    check(
+                        &value,
+                        &archived,
+                        (Bound::Included(start), Bound::Excluded(end)),
+                    );
+                    check(
+                        &value,
+                        &archived,
+                        (Bound::Excluded(start), Bound::Included(end)),
+                    );
+                }
+                check(
+                    &value,
+                    &archived,
+                    (Bound::Included(start), Bound::Unbounded),
+                );
+                check(
+                    &value,
+                    &archived,
+                    (Bound::Unbounded, Bound::Included(start)),
+                );
+            }
+
+            let start = ArchivedI32::from_native(20);
+            let end = ArchivedI32::from_native(10);
+            assert_eq!(archived.range(start..end).count(), 0);
+        });
+    }
+
+    #[test]
+    fn btree_map_first_last_and_cursor() {
+        use core::ops::Bound;
+
+        to_archived(&BTreeMap::<i32, i32>::new(), |archived| {
+            assert!(archived.first_key_value().is_none());
+            assert!(archived.last_key_value().is_none());
+            assert!(archived.cursor().peek().is_none());
+        });
+
+        let mut value = BTreeMap::new();
+        for i in 0..112 {
+            value.insert(i.to_string(), i);
+        }
+
+        to_archived(&value, |archived| {
+            let (k, v) = archived.first_key_value().unwrap();
+            assert_eq!((k.as_str(), v.to_native()), ("0", 0));
+            let (k, v) = archived.last_key_value().unwrap();
+            assert_eq!((k.as_str(), v.to_native()), ("99", 99));
+
+            let mut cursor = archived.lower_bound(Bound::Included("5"));
+            assert_eq!(cursor.peek().unwrap().0, "5");
+            let scanned = cursor
+                .by_ref()
+                .take(3)
+                .map(|(k, _)| k.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(scanned, ["5", "50", "51"]);
+            assert_eq!(cursor.peek().unwrap().0, "52");
+
+            cursor.seek(Bound::Excluded("98"));
+            assert_eq!(cursor.next().unwrap().0, "99");
+            assert!(cursor.next().is_none());
+            assert!(cursor.peek().is_none());
+
+            cursor.seek::<str>(Bound::Unbounded);
+            assert_eq!(cursor.peek().unwrap().0, "0");
+        });
+    }
 }
@@ -1,10 +1,13 @@
-use core::ops::ControlFlow;
+use core::{borrow::Borrow, cmp::Ordering, ops::ControlFlow};
 
 use rancor::{Fallible, Source};
 
 use crate::{
     alloc::collections::BTreeSet,
-    collections::btree_set::{ArchivedBTreeSet, BTreeSetResolver},
+    collections::{
+        btree_set::{ArchivedBTreeSet, BTreeSetResolver},
+        set_ops::Contains,
+    },
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -88,11 +91,46 @@ impl<K, AK: PartialEq<K>> PartialEq<BTreeSet<K>> for ArchivedBTreeSet<AK> {
     }
 }
 
+impl<K, AK: PartialOrd<K>> PartialOrd<BTreeSet<K>> for ArchivedBTreeSet<AK> {
+    fn partial_cmp(&self, other: &BTreeSet<K>) -> Option<Ordering> {
+        let mut iter = other.iter();
+        for ak in self.iter() {
+            let Some(k) = iter.next() else {
+                return Some(Ordering::Greater);
+            };
+            match ak.partial_cmp(k) {
+                Some(Ordering::Equal) => (),
+                x => return x,
+            }
+        }
+        if iter.next().is_some() {
+            Some(Ordering::Less)
+        } else {
+            Some(Ordering::Equal)
+        }
+    }
+}
+
+impl<K, Q> Contains<Q> for BTreeSet<K>
+where
+    K: Borrow<Q> + Ord,
+    Q: Ord + ?Sized,
+{
+    fn contains_value(&self, value: &Q) -> bool {
+        self.contains(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{
-        alloc::{collections::BTreeSet, string::ToString},
-        api::test::roundtrip,
+        alloc::{
+            collections::BTreeSet,
+            string::{String, ToString},
+            vec::Vec,
+        },
+        api::test::{roundtrip, to_archived},
+        Archived,
     };
 
     #[test]
@@ -113,4 +151,44 @@ mod tests {
 
         roundtrip(&value);
     }
+
+    #[test]
+    fn btree_set_algebra() {
+        let a = (0..100).collect::<BTreeSet<u32>>();
+        let b = (50..150).step_by(2).collect::<BTreeSet<u32>>();
+
+        to_archived(&(a, b), |archived| {
+            let (a, archived_b) = (&archived.0, &archived.1);
+
+            let expected = (50..100).step_by(2).collect::<Vec<_>>();
+            let i = a.intersection(archived_b).map(|k| k.to_native());
+            assert!(i.eq(expected.iter().copied()));
+
+            let d = a.difference(archived_b).collect::<Vec<_>>();
+            assert_eq!(d.len(), 75);
+            let u = a
+                .union::<Archived<u32>, _, _>(archived_b.iter())
+                .collect::<Vec<_>>();
+            assert_eq!(u.len(), 150);
+
+            assert!(!a.is_subset(archived_b));
+            assert!(a.is_subset(a));
+            assert!(!a.is_disjoint(archived_b));
+        });
+
+        let words = ["b", "a"].map(String::from);
+        let words = words.into_iter().collect::<BTreeSet<_>>();
+        to_archived(&words, |archived| {
+            let live = ["a"].map(String::from).into_iter().collect();
+            let d = archived
+                .difference::<str, BTreeSet<String>>(&live)
+                .map(|k| k.as_str())
+                .collect::<Vec<_>>();
+            assert_eq!(d, ["b"]);
+
+            let live = ["c"].map(String::from);
+            let u = archived.union::<str, _, _>(&live).collect::<Vec<_>>();
+            assert_eq!(u, ["a", "b", "c"]);
+        });
+    }
 }
@@ -4,7 +4,7 @@ use rancor::{Fallible, ResultExt, Source};
 
 use crate::{
     alloc::{alloc::alloc, boxed::Box, collections::VecDeque, vec::Vec},
-    ser::{Allocator, Writer},
+    ser::{Allocator, Writer, WriterExt as _},
     traits::LayoutRaw,
     vec::{ArchivedVec, VecResolver},
     Archive, Deserialize, DeserializeUnsized, Place, Serialize,
@@ -33,6 +33,21 @@ where
             ArchivedVec::<T::Archived>::serialize_from_slice(a, serializer)
         } else if a.is_empty() {
             ArchivedVec::<T::Archived>::serialize_from_slice(b, serializer)
+        } else if T::COPY_OPTIMIZATION.is_enabled() {
+            // Both halves can be copied directly, so write them back-to-back
+            // instead of serializing the elements one at a time.
+            fn as_bytes<T>(slice: &[T]) -> &[u8] {
+                unsafe {
+                    core::slice::from_raw_parts(
+                        slice.as_ptr().cast::<u8>(),
+                        core::mem::size_of_val(slice),
+                    )
+                }
+            }
+
+            let pos = serializer.align_for::<T::Archived>()?;
+            serializer.write_vectored(&[as_bytes(a), as_bytes(b)])?;
+            Ok(VecResolver::from_pos(pos))
         } else {
             ArchivedVec::<T::Archived>::serialize_from_iter::<T, _, _>(
                 self.iter(),
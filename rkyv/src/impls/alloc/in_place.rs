@@ -0,0 +1,111 @@
+use rancor::Fallible;
+
+use crate::{
+    alloc::{boxed::Box, string::String, vec::Vec},
+    boxed::ArchivedBox,
+    de::DeserializeInPlace,
+    string::ArchivedString,
+    vec::ArchivedVec,
+    Deserialize,
+};
+
+impl<D: Fallible + ?Sized> DeserializeInPlace<String, D> for ArchivedString {
+    fn deserialize_in_place(
+        &self,
+        out: &mut String,
+        _: &mut D,
+    ) -> Result<(), D::Error> {
+        out.clear();
+        out.push_str(self.as_str());
+        Ok(())
+    }
+}
+
+impl<T, U, D> DeserializeInPlace<Vec<T>, D> for ArchivedVec<U>
+where
+    U: DeserializeInPlace<T, D> + Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_in_place(
+        &self,
+        out: &mut Vec<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        out.truncate(self.len());
+        let (existing, rest) = self.as_slice().split_at(out.len());
+        for (archived, value) in existing.iter().zip(out.iter_mut()) {
+            archived.deserialize_in_place(value, deserializer)?;
+        }
+        out.reserve(rest.len());
+        for archived in rest {
+            out.push(archived.deserialize(deserializer)?);
+        }
+        Ok(())
+    }
+}
+
+impl<T, U, D> DeserializeInPlace<Box<T>, D> for ArchivedBox<U>
+where
+    U: DeserializeInPlace<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_in_place(
+        &self,
+        out: &mut Box<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        self.get().deserialize_in_place(out, deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{boxed::Box, string::String, vec, vec::Vec},
+        api::{high::deserialize_in_place, test::to_archived},
+    };
+
+    #[test]
+    fn reuses_allocations() {
+        let mut out = (0..4)
+            .map(|_| String::with_capacity(64))
+            .collect::<Vec<_>>();
+        let addresses = out.iter().map(|s| s.as_ptr()).collect::<Vec<_>>();
+        let capacity = out.capacity();
+
+        let value = vec![String::from("hello"), String::from("world")];
+        to_archived(&value, |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(out, value);
+        assert_eq!(out.capacity(), capacity);
+        assert_eq!(out[0].as_ptr(), addresses[0]);
+        assert_eq!(out[1].as_ptr(), addresses[1]);
+
+        let value = vec![String::from("a"), String::from("b"), String::new()];
+        to_archived(&value, |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(out, value);
+        assert_eq!(out[0].as_ptr(), addresses[0]);
+    }
+
+    #[test]
+    fn reuses_box() {
+        let mut out = Box::new(Some(1u32));
+        let address = &*out as *const Option<u32>;
+
+        to_archived(&Box::new(Some(2u32)), |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(*out, Some(2));
+        assert_eq!(&*out as *const Option<u32>, address);
+
+        to_archived(&Box::new(None::<u32>), |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(*out, None);
+    }
+}
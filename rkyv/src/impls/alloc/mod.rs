@@ -1,6 +1,8 @@
+mod borrow;
 mod boxed;
 mod collections;
 mod ffi;
+mod in_place;
 mod rc;
 mod string;
 mod vec;
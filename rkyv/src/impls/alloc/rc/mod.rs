@@ -426,6 +426,63 @@ mod tests {
         assert!(to_bytes::<Failure>(&value).is_err());
     }
 
+    #[test]
+    fn serialize_cyclic_break_cycles() {
+        use rancor::{Fallible, Source};
+
+        use crate::{
+            de::Pooling,
+            ser::{Sharing, Writer},
+            with::BreakCycles,
+        };
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(
+            crate,
+            serialize_bounds(
+                __S: Sharing + Writer,
+                <__S as Fallible>::Error: Source,
+            ),
+            deserialize_bounds(
+                __D: Pooling,
+                <__D as Fallible>::Error: Source,
+            )
+        )]
+        #[cfg_attr(
+            feature = "bytecheck",
+            rkyv(bytecheck(bounds(
+                __C: crate::validation::ArchiveContext
+                    + crate::validation::SharedContext,
+                <__C as Fallible>::Error: Source,
+            ))),
+        )]
+        struct Inner {
+            #[rkyv(with = BreakCycles, omit_bounds)]
+            weak: Weak<Self>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(crate)]
+        struct Outer {
+            inner: Rc<Inner>,
+        }
+
+        let value = Outer {
+            inner: Rc::new_cyclic(|weak| Inner { weak: weak.clone() }),
+        };
+
+        // Weak back-references to a value which is still being serialized are
+        // serialized as null.
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        let archived = unsafe { access_unchecked::<ArchivedOuter>(&bytes) };
+        assert!(archived.inner.weak.upgrade().is_none());
+
+        let deserialized =
+            deserialize_using::<Outer, _, Panic>(archived, &mut Pool::new())
+                .unwrap();
+        assert!(deserialized.inner.weak.upgrade().is_none());
+    }
+
     #[cfg(all(
         feature = "bytecheck",
         not(feature = "big_endian"),
@@ -71,11 +71,73 @@ impl<T: PartialOrd<U>, U> PartialOrd<Vec<U>> for ArchivedVec<T> {
 
 #[cfg(test)]
 mod tests {
+    use rancor::{Fallible, Source};
+
     use crate::{
-        alloc::{vec, vec::Vec},
-        api::test::roundtrip,
+        alloc::{
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::test::{roundtrip, to_archived},
+        ser::{Allocator, Writer},
+        string::ArchivedString,
+        vec::{ArchivedVec, VecResolver},
+        Archive, Place, Serialize,
     };
 
+    // Archives the decimal representations of a range of numbers without
+    // collecting them first.
+    struct Numbers {
+        below: u32,
+        digits: usize,
+    }
+
+    impl Archive for Numbers {
+        type Archived = ArchivedVec<ArchivedString>;
+        type Resolver = (VecResolver, usize);
+
+        fn resolve(
+            &self,
+            (resolver, len): Self::Resolver,
+            out: Place<Self::Archived>,
+        ) {
+            ArchivedVec::resolve_from_len(len, resolver, out);
+        }
+    }
+
+    impl<S> Serialize<S> for Numbers
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        fn serialize(
+            &self,
+            serializer: &mut S,
+        ) -> Result<Self::Resolver, S::Error> {
+            let digits = self.digits;
+            ArchivedVec::serialize_from_single_pass_iter::<String, _, _>(
+                (0..self.below)
+                    .map(|i| i.to_string())
+                    .filter(move |s| s.len() == digits),
+                serializer,
+            )
+        }
+    }
+
+    #[test]
+    fn serialize_from_single_pass_iter() {
+        let value = Numbers {
+            below: 120,
+            digits: 2,
+        };
+        to_archived(&value, |archived| {
+            assert_eq!(archived.len(), 90);
+            assert_eq!(archived[0], "10");
+            assert_eq!(archived[89], "99");
+        });
+    }
+
     #[test]
     fn roundtrip_vec() {
         roundtrip(&Vec::<i32>::new());
@@ -102,4 +164,27 @@ mod tests {
         roundtrip(&Err::<(), _>(Vec::<i32>::new()));
         roundtrip(&Err::<(), _>(vec![1, 2, 3, 4]));
     }
+
+    #[test]
+    fn sealed_vec_mutation() {
+        use crate::Archived;
+
+        to_archived(&vec![5u32, 3, 4, 1, 2], |mut archived| {
+            *ArchivedVec::get_seal(archived.as_mut(), 0).unwrap() = 6.into();
+            assert!(ArchivedVec::get_seal(archived.as_mut(), 5).is_none());
+            assert_eq!(archived.as_slice(), [6u32, 3, 4, 1, 2]);
+
+            ArchivedVec::swap(archived.as_mut(), 0, 4);
+            assert_eq!(archived.as_slice(), [2u32, 3, 4, 1, 6]);
+
+            ArchivedVec::sort_unstable_by(archived.as_mut(), |a, b| a.cmp(b));
+            assert_eq!(archived.as_slice(), [1u32, 2, 3, 4, 6]);
+
+            ArchivedVec::fill(
+                archived.as_mut(),
+                Archived::<u32>::from_native(0),
+            );
+            assert_eq!(archived.as_slice(), [0u32; 5]);
+        });
+    }
 }
@@ -1,29 +1,42 @@
-use core::{marker::PhantomData, ops::ControlFlow};
+use core::{alloc::Layout, marker::PhantomData, ops::ControlFlow, ptr};
 
 use ptr_meta::Pointee;
-use rancor::{Fallible, Source};
+use rancor::{Fallible, ResultExt as _, Source};
 
 use crate::{
     alloc::{
         borrow::Cow,
         boxed::Box,
         collections::{BTreeMap, BTreeSet},
-        rc::Rc,
+        rc::{self, Rc},
+        string::String,
         vec::Vec,
     },
+    boxed::{ArchivedBox, BoxResolver},
+    bytes::{ArchivedBytes, BytesResolver},
     collections::{
         btree_map::{ArchivedBTreeMap, BTreeMapResolver},
         util::{Entry, EntryAdapter},
     },
+    compact::{ArchivedCompactString, ArchivedCompactVec, CompactVecResolver},
+    de::Arena,
     impls::core::with::RefWrapper,
     niche::option_box::{ArchivedOptionBox, OptionBoxResolver},
-    ser::{Allocator, Writer},
-    string::{ArchivedString, StringResolver},
+    rc::{ArchivedRcWeak, RcFlavor, RcWeakResolver},
+    reserved::{
+        ArchivedReservedString, ArchivedReservedVec, ReservedVecResolver,
+    },
+    ser::{Allocator, Sharing, Writer, WriterExt as _},
+    string::{
+        byte_string::{ArchivedByteString, ByteStringResolver},
+        ArchivedString, StringResolver,
+    },
     traits::LayoutRaw,
     vec::{ArchivedVec, VecResolver},
     with::{
-        ArchiveWith, AsOwned, AsVec, DeserializeWith, Map, MapKV, Niche,
-        SerializeWith, Unshare,
+        Align, ArchiveWith, AsByteString, AsBytes, AsOwned, AsVec, BreakCycles,
+        Compact, DeserializeWith, InArena, Map, MapKV, Niche, OutOfLine,
+        Reserve, SerializeWith, Unshare,
     },
     Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
     Place, Serialize, SerializeUnsized,
@@ -197,6 +210,52 @@ where
     }
 }
 
+impl<A, O> ArchiveWith<Box<O>> for Map<A>
+where
+    A: ArchiveWith<O>,
+{
+    type Archived = ArchivedBox<<A as ArchiveWith<O>>::Archived>;
+    type Resolver = BoxResolver;
+
+    fn resolve_with(
+        field: &Box<O>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        let value = RefWrapper::<'_, A, O>(field, PhantomData);
+        ArchivedBox::resolve_from_ref(&value, resolver, out)
+    }
+}
+
+impl<A, O, S> SerializeWith<Box<O>, S> for Map<A>
+where
+    S: Fallible + Writer + ?Sized,
+    A: ArchiveWith<O> + SerializeWith<O, S>,
+{
+    fn serialize_with(
+        field: &Box<O>,
+        s: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        let value = RefWrapper::<'_, A, O>(field, PhantomData);
+        ArchivedBox::serialize_from_ref(&value, s)
+    }
+}
+
+impl<A, O, D>
+    DeserializeWith<ArchivedBox<<A as ArchiveWith<O>>::Archived>, Box<O>, D>
+    for Map<A>
+where
+    A: ArchiveWith<O> + DeserializeWith<<A as ArchiveWith<O>>::Archived, O, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBox<<A as ArchiveWith<O>>::Archived>,
+        d: &mut D,
+    ) -> Result<Box<O>, D::Error> {
+        A::deserialize_with(field.get(), d).map(Box::new)
+    }
+}
+
 // AsOwned
 
 impl<'a, F: Archive + Clone> ArchiveWith<Cow<'a, F>> for AsOwned {
@@ -319,6 +378,208 @@ where
     }
 }
 
+// AsByteString
+
+impl ArchiveWith<Vec<u8>> for AsByteString {
+    type Archived = ArchivedByteString;
+    type Resolver = ByteStringResolver;
+
+    fn resolve_with(
+        field: &Vec<u8>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedByteString::resolve_from_bytes(field, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<Vec<u8>, S> for AsByteString
+where
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<u8>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedByteString::serialize_from_bytes(field, serializer)
+    }
+}
+
+impl<D> DeserializeWith<ArchivedByteString, Vec<u8>, D> for AsByteString
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedByteString,
+        _: &mut D,
+    ) -> Result<Vec<u8>, D::Error> {
+        Ok(field.as_bytes().to_vec())
+    }
+}
+
+// AsBytes
+
+impl<const ALIGN: usize> ArchiveWith<Vec<u8>> for AsBytes<ALIGN> {
+    type Archived = ArchivedBytes<ALIGN>;
+    type Resolver = BytesResolver;
+
+    fn resolve_with(
+        field: &Vec<u8>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBytes::resolve_from_bytes(field, resolver, out);
+    }
+}
+
+impl<S, const ALIGN: usize> SerializeWith<Vec<u8>, S> for AsBytes<ALIGN>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<u8>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBytes::<ALIGN>::serialize_from_bytes(field, serializer)
+    }
+}
+
+impl<D, const ALIGN: usize> DeserializeWith<ArchivedBytes<ALIGN>, Vec<u8>, D>
+    for AsBytes<ALIGN>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBytes<ALIGN>,
+        _: &mut D,
+    ) -> Result<Vec<u8>, D::Error> {
+        Ok(field.as_slice().to_vec())
+    }
+}
+
+// Align
+
+impl<const N: usize> ArchiveWith<Vec<u8>> for Align<N> {
+    type Archived = ArchivedVec<u8>;
+    type Resolver = VecResolver;
+
+    fn resolve_with(
+        field: &Vec<u8>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedVec::resolve_from_slice(field, resolver, out);
+    }
+}
+
+impl<S, const N: usize> SerializeWith<Vec<u8>, S> for Align<N>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<u8>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        assert!(N.is_power_of_two(), "N must be a power of 2");
+        let pos = serializer.align_and_write(N, field)?;
+        Ok(VecResolver::from_pos(pos))
+    }
+}
+
+impl<D, const N: usize> DeserializeWith<ArchivedVec<u8>, Vec<u8>, D>
+    for Align<N>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedVec<u8>,
+        _: &mut D,
+    ) -> Result<Vec<u8>, D::Error> {
+        Ok(field.as_slice().to_vec())
+    }
+}
+
+impl<const N: usize> ArchiveWith<Box<[u8]>> for Align<N> {
+    type Archived = ArchivedBox<[u8]>;
+    type Resolver = BoxResolver;
+
+    fn resolve_with(
+        field: &Box<[u8]>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBox::resolve_from_ref(&**field, resolver, out);
+    }
+}
+
+impl<S, const N: usize> SerializeWith<Box<[u8]>, S> for Align<N>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Box<[u8]>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        assert!(N.is_power_of_two(), "N must be a power of 2");
+        let pos = serializer.align_and_write(N, field)?;
+        Ok(BoxResolver::from_pos(pos))
+    }
+}
+
+impl<D, const N: usize> DeserializeWith<ArchivedBox<[u8]>, Box<[u8]>, D>
+    for Align<N>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBox<[u8]>,
+        _: &mut D,
+    ) -> Result<Box<[u8]>, D::Error> {
+        Ok(field.get().into())
+    }
+}
+
+// OutOfLine
+
+impl ArchiveWith<String> for OutOfLine {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedString::resolve_out_of_line(field, resolver, out);
+    }
+}
+
+impl<S> SerializeWith<String, S> for OutOfLine
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+    str: SerializeUnsized<S>,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_out_of_line(field, serializer)
+    }
+}
+
+impl<D> DeserializeWith<ArchivedString, String, D> for OutOfLine
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(String::from(field.as_str()))
+    }
+}
+
 // AsVec
 
 impl<K: Archive, V: Archive> ArchiveWith<BTreeMap<K, V>> for AsVec {
@@ -428,155 +689,611 @@ where
     }
 }
 
-// Niche
+// Compact
 
-impl<T> ArchiveWith<Option<Box<T>>> for Niche
-where
-    T: ArchiveUnsized + ?Sized,
-    ArchivedMetadata<T>: Default,
-{
-    type Archived = ArchivedOptionBox<T::Archived>;
-    type Resolver = OptionBoxResolver;
+impl<T: Archive> ArchiveWith<Vec<T>> for Compact {
+    type Archived = ArchivedCompactVec<T::Archived>;
+    type Resolver = CompactVecResolver;
 
     fn resolve_with(
-        field: &Option<Box<T>>,
+        _: &Vec<T>,
         resolver: Self::Resolver,
         out: Place<Self::Archived>,
     ) {
-        ArchivedOptionBox::resolve_from_option(field.as_deref(), resolver, out);
+        ArchivedCompactVec::resolve_from_resolver(resolver, out);
     }
 }
 
-impl<T, S> SerializeWith<Option<Box<T>>, S> for Niche
+impl<T, S> SerializeWith<Vec<T>, S> for Compact
 where
-    T: SerializeUnsized<S> + ?Sized,
-    S: Fallible + Writer + ?Sized,
-    ArchivedMetadata<T>: Default,
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
 {
     fn serialize_with(
-        field: &Option<Box<T>>,
+        field: &Vec<T>,
         serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        ArchivedOptionBox::serialize_from_option(field.as_deref(), serializer)
+        ArchivedCompactVec::serialize_from_slice(field.as_slice(), serializer)
     }
 }
 
-impl<T, D> DeserializeWith<ArchivedOptionBox<T::Archived>, Option<Box<T>>, D>
-    for Niche
+impl<T, D> DeserializeWith<ArchivedCompactVec<T::Archived>, Vec<T>, D>
+    for Compact
 where
-    T: ArchiveUnsized + LayoutRaw + Pointee + ?Sized,
-    T::Archived: DeserializeUnsized<T, D>,
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
     D: Fallible + ?Sized,
-    D::Error: Source,
 {
     fn deserialize_with(
-        field: &ArchivedOptionBox<T::Archived>,
+        field: &ArchivedCompactVec<T::Archived>,
         deserializer: &mut D,
-    ) -> Result<Option<Box<T>>, D::Error> {
-        if let Some(value) = field.as_ref() {
-            Ok(Some(value.deserialize(deserializer)?))
-        } else {
-            Ok(None)
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(field.len());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
         }
+        Ok(result)
     }
 }
 
-// Unshare
-
-#[cfg(target_has_atomic = "ptr")]
-impl<T: Archive> ArchiveWith<crate::alloc::sync::Arc<T>> for Unshare {
-    type Archived = T::Archived;
-    type Resolver = T::Resolver;
+impl ArchiveWith<String> for Compact {
+    type Archived = ArchivedCompactString;
+    type Resolver = CompactVecResolver;
 
     fn resolve_with(
-        x: &crate::alloc::sync::Arc<T>,
+        _: &String,
         resolver: Self::Resolver,
         out: Place<Self::Archived>,
     ) {
-        x.as_ref().resolve(resolver, out)
+        ArchivedCompactString::resolve_from_resolver(resolver, out);
     }
 }
 
-#[cfg(target_has_atomic = "ptr")]
-impl<T, S> SerializeWith<crate::alloc::sync::Arc<T>, S> for Unshare
+impl<S> SerializeWith<String, S> for Compact
 where
-    T: Serialize<S>,
-    S: Fallible + ?Sized,
+    S: Fallible + Allocator + Writer + ?Sized,
 {
     fn serialize_with(
-        x: &crate::alloc::sync::Arc<T>,
-        s: &mut S,
+        field: &String,
+        serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        x.as_ref().serialize(s)
+        ArchivedCompactString::serialize_from_str(field.as_str(), serializer)
     }
 }
 
-#[cfg(target_has_atomic = "ptr")]
-impl<A, T, D> DeserializeWith<A, crate::alloc::sync::Arc<T>, D> for Unshare
+impl<D> DeserializeWith<ArchivedCompactString, String, D> for Compact
 where
-    A: Deserialize<T, D>,
     D: Fallible + ?Sized,
 {
     fn deserialize_with(
-        x: &A,
-        d: &mut D,
-    ) -> Result<crate::alloc::sync::Arc<T>, D::Error> {
-        Ok(crate::alloc::sync::Arc::new(A::deserialize(x, d)?))
+        field: &ArchivedCompactString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        Ok(String::from(field.as_str()))
     }
 }
 
-impl<T: Archive> ArchiveWith<Rc<T>> for Unshare {
-    type Archived = T::Archived;
-    type Resolver = T::Resolver;
+// Reserve
+
+impl<T: Archive, const ADDITIONAL: usize> ArchiveWith<Vec<T>>
+    for Reserve<ADDITIONAL>
+{
+    type Archived = ArchivedReservedVec<T::Archived>;
+    type Resolver = ReservedVecResolver;
 
     fn resolve_with(
-        x: &Rc<T>,
+        field: &Vec<T>,
         resolver: Self::Resolver,
         out: Place<Self::Archived>,
     ) {
-        x.as_ref().resolve(resolver, out)
+        ArchivedReservedVec::resolve_from_slice(field, resolver, out);
     }
 }
 
-impl<T: Serialize<S>, S: Fallible + ?Sized> SerializeWith<Rc<T>, S>
-    for Unshare
+impl<T, S, const ADDITIONAL: usize> SerializeWith<Vec<T>, S>
+    for Reserve<ADDITIONAL>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
 {
     fn serialize_with(
-        x: &Rc<T>,
-        s: &mut S,
+        field: &Vec<T>,
+        serializer: &mut S,
     ) -> Result<Self::Resolver, S::Error> {
-        x.as_ref().serialize(s)
+        ArchivedReservedVec::serialize_from_slice(
+            field.as_slice(),
+            ADDITIONAL,
+            serializer,
+        )
     }
 }
 
-impl<A, T, D> DeserializeWith<A, Rc<T>, D> for Unshare
+impl<T, D, const ADDITIONAL: usize>
+    DeserializeWith<ArchivedReservedVec<T::Archived>, Vec<T>, D>
+    for Reserve<ADDITIONAL>
 where
-    A: Deserialize<T, D>,
+    T: Archive,
+    T::Archived: Deserialize<T, D>,
     D: Fallible + ?Sized,
 {
-    fn deserialize_with(x: &A, d: &mut D) -> Result<Rc<T>, D::Error> {
-        Ok(Rc::new(A::deserialize(x, d)?))
+    fn deserialize_with(
+        field: &ArchivedReservedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let mut result = Vec::with_capacity(field.capacity());
+        for value in field.iter() {
+            result.push(value.deserialize(deserializer)?);
+        }
+        Ok(result)
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use core::mem::size_of;
+impl<const ADDITIONAL: usize> ArchiveWith<String> for Reserve<ADDITIONAL> {
+    type Archived = ArchivedReservedString;
+    type Resolver = ReservedVecResolver;
 
-    use crate::{
-        alloc::{
-            borrow::Cow,
-            boxed::Box,
-            collections::{BTreeMap, BTreeSet},
-            string::{String, ToString},
-        },
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedReservedString::resolve_from_str(field, resolver, out);
+    }
+}
+
+impl<S, const ADDITIONAL: usize> SerializeWith<String, S>
+    for Reserve<ADDITIONAL>
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedReservedString::serialize_from_str(
+            field.as_str(),
+            ADDITIONAL,
+            serializer,
+        )
+    }
+}
+
+impl<D, const ADDITIONAL: usize>
+    DeserializeWith<ArchivedReservedString, String, D> for Reserve<ADDITIONAL>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedReservedString,
+        _: &mut D,
+    ) -> Result<String, D::Error> {
+        let mut result = String::with_capacity(field.capacity());
+        result.push_str(field.as_str());
+        Ok(result)
+    }
+}
+
+// Niche
+
+impl<T> ArchiveWith<Option<Box<T>>> for Niche
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedMetadata<T>: Default,
+{
+    type Archived = ArchivedOptionBox<T::Archived>;
+    type Resolver = OptionBoxResolver;
+
+    fn resolve_with(
+        field: &Option<Box<T>>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedOptionBox::resolve_from_option(field.as_deref(), resolver, out);
+    }
+}
+
+impl<T, S> SerializeWith<Option<Box<T>>, S> for Niche
+where
+    T: SerializeUnsized<S> + ?Sized,
+    S: Fallible + Writer + ?Sized,
+    ArchivedMetadata<T>: Default,
+{
+    fn serialize_with(
+        field: &Option<Box<T>>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedOptionBox::serialize_from_option(field.as_deref(), serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedOptionBox<T::Archived>, Option<Box<T>>, D>
+    for Niche
+where
+    T: ArchiveUnsized + LayoutRaw + Pointee + ?Sized,
+    T::Archived: DeserializeUnsized<T, D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedOptionBox<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Option<Box<T>>, D::Error> {
+        if let Some(value) = field.as_ref() {
+            Ok(Some(value.deserialize(deserializer)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+// Unshare
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T: Archive> ArchiveWith<crate::alloc::sync::Arc<T>> for Unshare {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    fn resolve_with(
+        x: &crate::alloc::sync::Arc<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        x.as_ref().resolve(resolver, out)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T, S> SerializeWith<crate::alloc::sync::Arc<T>, S> for Unshare
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+{
+    fn serialize_with(
+        x: &crate::alloc::sync::Arc<T>,
+        s: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        x.as_ref().serialize(s)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A, T, D> DeserializeWith<A, crate::alloc::sync::Arc<T>, D> for Unshare
+where
+    A: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        x: &A,
+        d: &mut D,
+    ) -> Result<crate::alloc::sync::Arc<T>, D::Error> {
+        Ok(crate::alloc::sync::Arc::new(A::deserialize(x, d)?))
+    }
+}
+
+impl<T: Archive> ArchiveWith<Rc<T>> for Unshare {
+    type Archived = T::Archived;
+    type Resolver = T::Resolver;
+
+    fn resolve_with(
+        x: &Rc<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        x.as_ref().resolve(resolver, out)
+    }
+}
+
+impl<T: Serialize<S>, S: Fallible + ?Sized> SerializeWith<Rc<T>, S>
+    for Unshare
+{
+    fn serialize_with(
+        x: &Rc<T>,
+        s: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        x.as_ref().serialize(s)
+    }
+}
+
+impl<A, T, D> DeserializeWith<A, Rc<T>, D> for Unshare
+where
+    A: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(x: &A, d: &mut D) -> Result<Rc<T>, D::Error> {
+        Ok(Rc::new(A::deserialize(x, d)?))
+    }
+}
+
+// BreakCycles
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T> ArchiveWith<crate::alloc::sync::Weak<T>> for BreakCycles
+where
+    T: ArchiveUnsized + ?Sized,
+{
+    type Archived = ArchivedRcWeak<T::Archived, crate::rc::ArcFlavor>;
+    type Resolver = RcWeakResolver;
+
+    fn resolve_with(
+        field: &crate::alloc::sync::Weak<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, out)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T, S> SerializeWith<crate::alloc::sync::Weak<T>, S> for BreakCycles
+where
+    T: SerializeUnsized<S> + ?Sized + 'static,
+    S: Fallible + Writer + Sharing + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &crate::alloc::sync::Weak<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedRcWeak::serialize_from_ref_or_null(
+            field.upgrade().as_ref().map(|v| v.as_ref()),
+            serializer,
+        )
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<T, D>
+    DeserializeWith<
+        ArchivedRcWeak<T::Archived, crate::rc::ArcFlavor>,
+        crate::alloc::sync::Weak<T>,
+        D,
+    > for BreakCycles
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedRcWeak<T::Archived, crate::rc::ArcFlavor>:
+        Deserialize<crate::alloc::sync::Weak<T>, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedRcWeak<T::Archived, crate::rc::ArcFlavor>,
+        deserializer: &mut D,
+    ) -> Result<crate::alloc::sync::Weak<T>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<T: ArchiveUnsized + ?Sized> ArchiveWith<rc::Weak<T>> for BreakCycles {
+    type Archived = ArchivedRcWeak<T::Archived, RcFlavor>;
+    type Resolver = RcWeakResolver;
+
+    fn resolve_with(
+        field: &rc::Weak<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, out)
+    }
+}
+
+impl<T, S> SerializeWith<rc::Weak<T>, S> for BreakCycles
+where
+    T: SerializeUnsized<S> + ?Sized + 'static,
+    S: Fallible + Writer + Sharing + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &rc::Weak<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedRcWeak::serialize_from_ref_or_null(
+            field.upgrade().as_ref().map(|v| v.as_ref()),
+            serializer,
+        )
+    }
+}
+
+impl<T, D>
+    DeserializeWith<ArchivedRcWeak<T::Archived, RcFlavor>, rc::Weak<T>, D>
+    for BreakCycles
+where
+    T: ArchiveUnsized + ?Sized,
+    ArchivedRcWeak<T::Archived, RcFlavor>: Deserialize<rc::Weak<T>, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedRcWeak<T::Archived, RcFlavor>,
+        deserializer: &mut D,
+    ) -> Result<rc::Weak<T>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+// InArena
+
+impl<T: ArchiveUnsized + ?Sized> ArchiveWith<Box<T>> for InArena {
+    type Archived = ArchivedBox<T::Archived>;
+    type Resolver = BoxResolver;
+
+    fn resolve_with(
+        field: &Box<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, out)
+    }
+}
+
+impl<T, S> SerializeWith<Box<T>, S> for InArena
+where
+    T: SerializeUnsized<S> + ?Sized,
+    S: Fallible + ?Sized,
+{
+    fn serialize_with(
+        field: &Box<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedBox<T::Archived>, Box<T>, D> for InArena
+where
+    T: ArchiveUnsized + LayoutRaw + ?Sized,
+    T::Archived: DeserializeUnsized<T, D>,
+    D: Fallible + Arena + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedBox<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Box<T>, D::Error> {
+        let metadata = field.get().deserialize_metadata();
+        let layout = T::layout_raw(metadata).into_error()?;
+        let data_address = if layout.size() > 0 {
+            deserializer.allocate(layout)?.as_ptr()
+        } else {
+            crate::polyfill::dangling(&layout).as_ptr()
+        };
+
+        let out = ptr_meta::from_raw_parts_mut(data_address.cast(), metadata);
+
+        unsafe {
+            field.get().deserialize_unsized(deserializer, out)?;
+        }
+        // SAFETY: The arena returned memory allocated by the global allocator
+        // with the layout of the box.
+        unsafe { Ok(Box::from_raw(out)) }
+    }
+}
+
+impl<T: Archive> ArchiveWith<Vec<T>> for InArena {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve_with(
+        field: &Vec<T>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, out)
+    }
+}
+
+impl<T, S> SerializeWith<Vec<T>, S> for InArena
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Vec<T>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<T, D> DeserializeWith<ArchivedVec<T::Archived>, Vec<T>, D> for InArena
+where
+    T: Archive,
+    [T::Archived]: DeserializeUnsized<[T], D>,
+    D: Fallible + Arena + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize_with(
+        field: &ArchivedVec<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<Vec<T>, D::Error> {
+        let metadata = field.as_slice().deserialize_metadata();
+        let layout = <[T] as LayoutRaw>::layout_raw(metadata).into_error()?;
+        let data_address = if layout.size() > 0 {
+            deserializer.allocate(layout)?.as_ptr()
+        } else {
+            crate::polyfill::dangling(&layout).as_ptr()
+        };
+        let out = ptr_meta::from_raw_parts_mut(data_address.cast(), metadata);
+        unsafe {
+            field.as_slice().deserialize_unsized(deserializer, out)?;
+        }
+        // SAFETY: The arena returned memory allocated by the global allocator
+        // with the layout of the slice.
+        unsafe { Ok(Box::<[T]>::from_raw(out).into()) }
+    }
+}
+
+impl ArchiveWith<String> for InArena {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    fn resolve_with(
+        field: &String,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field.resolve(resolver, out)
+    }
+}
+
+impl<S> SerializeWith<String, S> for InArena
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &String,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field.serialize(serializer)
+    }
+}
+
+impl<D> DeserializeWith<ArchivedString, String, D> for InArena
+where
+    D: Fallible + Arena + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedString,
+        deserializer: &mut D,
+    ) -> Result<String, D::Error> {
+        let bytes = field.as_str().as_bytes();
+        if bytes.is_empty() {
+            return Ok(String::new());
+        }
+
+        // The layout of a non-empty byte slice is always valid.
+        let layout = Layout::array::<u8>(bytes.len()).unwrap();
+        let ptr = deserializer.allocate(layout)?.as_ptr();
+        // SAFETY: `ptr` is valid for writes of `bytes.len()` bytes, and the
+        // copied bytes are valid UTF-8. The arena returned memory allocated by
+        // the global allocator with an array layout of `bytes.len()` bytes.
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), ptr, bytes.len());
+            Ok(String::from_raw_parts(ptr, bytes.len(), bytes.len()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::mem::size_of;
+
+    use crate::{
+        alloc::{
+            borrow::Cow,
+            boxed::Box,
+            collections::{BTreeMap, BTreeSet},
+            format,
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
         api::test::{roundtrip, roundtrip_with, to_archived},
         niche::niching::Null,
         with::{
-            AsOwned, AsVec, DefaultNiche, InlineAsBox, Map, MapKV, Niche,
-            NicheInto,
+            Align, AsByteString, AsBytes, AsOwned, AsVec, Compact,
+            DefaultNiche, InlineAsBox, Map, MapKV, MapResult, Niche, NicheInto,
+            OutOfLine,
         },
-        Archive, Deserialize, Serialize,
+        Archive, Archived, Deserialize, Serialize,
     };
 
     #[derive(Debug, Archive, Deserialize, Serialize, PartialEq)]
@@ -681,6 +1398,48 @@ mod tests {
         });
     }
 
+    #[test]
+    fn with_nested_map() {
+        use crate::result::ArchivedResult;
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(crate)]
+        struct Test<'a> {
+            #[rkyv(with = Map<Map<InlineAsBox>>)]
+            a: Vec<Option<&'a str>>,
+            #[rkyv(with = MapKV<InlineAsBox, Map<InlineAsBox>>)]
+            b: BTreeMap<&'a str, Vec<&'a str>>,
+            #[rkyv(with = Map<Map<InlineAsBox>>)]
+            c: Box<[&'a str; 2]>,
+            #[rkyv(with = MapResult<Map<InlineAsBox>, InlineAsBox>)]
+            d: Result<Vec<&'a str>, &'a str>,
+        }
+
+        let mut b = BTreeMap::new();
+        b.insert("foo", vec!["bar", "baz"]);
+
+        let value = Test {
+            a: vec![Some("hello"), None],
+            b,
+            c: Box::new(["left", "right"]),
+            d: Ok(vec!["ok"]),
+        };
+
+        to_archived(&value, |archived| {
+            assert_eq!(&**archived.a[0].as_ref().unwrap(), "hello");
+            assert!(archived.a[1].is_none());
+            let foo = archived.b.get("foo").unwrap();
+            assert_eq!(&*foo[0], "bar");
+            assert_eq!(&*foo[1], "baz");
+            assert_eq!(&*archived.c.get()[0], "left");
+            assert_eq!(&*archived.c.get()[1], "right");
+            match &archived.d {
+                ArchivedResult::Ok(values) => assert_eq!(&*values[0], "ok"),
+                ArchivedResult::Err(_) => panic!("expected `Ok`"),
+            }
+        });
+    }
+
     #[test]
     fn with_as_vec() {
         #[derive(Archive, Serialize, Deserialize)]
@@ -753,7 +1512,7 @@ mod tests {
         }
 
         #[derive(Archive, Serialize, Deserialize)]
-        #[rkyv(crate)]
+        #[rkyv(crate, no_auto_niche)]
         struct TestNoNiching {
             inner: Option<Box<String>>,
         }
@@ -831,4 +1590,193 @@ mod tests {
             assert_eq!(nichable.boxed.as_ref().to_native(), 727);
         });
     }
+
+    #[test]
+    fn roundtrip_compact() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = Compact)]
+            name: String,
+            #[rkyv(with = Compact)]
+            values: Vec<u32>,
+            #[rkyv(with = Compact)]
+            strings: Vec<String>,
+        }
+
+        assert!(
+            size_of::<ArchivedTest>() < size_of::<Archived<Vec<u32>>>() * 3
+        );
+
+        let value = Test {
+            name: "a".repeat(200),
+            values: (0..300).collect(),
+            strings: vec!["hello".to_string(), String::new()],
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.name, a.name.as_str());
+            assert_eq!(b.values.len(), 300);
+            assert_eq!(b.values[299], 299);
+            assert_eq!(b.strings.len(), 2);
+            assert_eq!(b.strings[0], "hello");
+        });
+
+        let empty = Test {
+            name: String::new(),
+            values: Vec::new(),
+            strings: Vec::new(),
+        };
+        roundtrip_with(&empty, |_, b| {
+            assert!(b.name.is_empty());
+            assert!(b.values.is_empty());
+            assert!(b.strings.is_empty());
+        });
+    }
+
+    #[test]
+    fn with_as_byte_string() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = AsByteString)]
+            short: Vec<u8>,
+            #[rkyv(with = AsByteString)]
+            long: Vec<u8>,
+        }
+
+        let value = Test {
+            short: b"a\xffb".to_vec(),
+            long: (0..=255).collect(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.short, b"a\xffb");
+            assert_eq!(b.short, a.short.as_slice());
+            assert_eq!(b.long.len(), 256);
+            assert_eq!(&b.long[250..], &[250, 251, 252, 253, 254, 255]);
+            assert!(b.short.to_str().is_err());
+            assert_eq!(format!("{:?}", b.short), r#"b"a\xffb""#);
+        });
+    }
+
+    #[test]
+    fn with_out_of_line() {
+        use core::mem::size_of;
+
+        use crate::string::ArchivedString;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = OutOfLine)]
+            empty: String,
+            #[rkyv(with = OutOfLine)]
+            short: String,
+            #[rkyv(with = OutOfLine)]
+            long: String,
+        }
+
+        fn is_out_of_line(string: &ArchivedString) -> bool {
+            let start = string as *const ArchivedString as usize;
+            let end = start + size_of::<ArchivedString>();
+            let bytes = string.as_ptr() as usize;
+            bytes < start || bytes >= end
+        }
+
+        let value = Test {
+            empty: String::new(),
+            short: "hi".to_string(),
+            long: "a string which is too long to be inlined".to_string(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.empty, a.empty);
+            assert_eq!(b.short, a.short);
+            assert_eq!(b.long, a.long);
+            assert!(is_out_of_line(&b.short));
+            assert!(is_out_of_line(&b.long));
+        });
+    }
+
+    #[test]
+    fn with_as_bytes() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            tag: u8,
+            #[rkyv(with = AsBytes<16>)]
+            payload: Vec<u8>,
+            #[rkyv(with = AsBytes)]
+            empty: Vec<u8>,
+        }
+
+        let value = Test {
+            tag: 42,
+            payload: vec![1, 2, 3, 4, 5],
+            empty: Vec::new(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.tag, a.tag);
+            assert_eq!(b.payload, a.payload);
+            assert_eq!(b.empty, a.empty);
+            assert_eq!(b.payload.as_ptr() as usize % 16, 0);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "bytecheck")]
+    fn with_as_bytes_64() {
+        use rancor::Failure;
+
+        use crate::{access, api::high::to_bytes_in, util::AlignedVec};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Test {
+            tag: u8,
+            #[rkyv(with = AsBytes<64>)]
+            image: Vec<u8>,
+        }
+
+        let value = Test {
+            tag: 1,
+            image: (0..200).collect(),
+        };
+        let bytes =
+            to_bytes_in::<_, Failure>(&value, AlignedVec::<64>::new()).unwrap();
+        let archived = access::<ArchivedTest, Failure>(&bytes).unwrap();
+        assert_eq!(archived.image, value.image);
+        assert_eq!(archived.image.as_ptr() as usize % 64, 0);
+
+        // An archive which is only aligned to 16 bytes may not place the
+        // payload on a 64-byte boundary, and is rejected.
+        let mut unaligned = AlignedVec::<64>::new();
+        unaligned.extend_from_slice(&[0; 16]);
+        unaligned.extend_from_slice(&bytes);
+        assert!(access::<ArchivedTest, Failure>(&unaligned[16..]).is_err());
+    }
+
+    #[test]
+    fn with_align() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            tag: u8,
+            #[rkyv(with = Align<16>)]
+            samples: Vec<u8>,
+            #[rkyv(with = Align<8>)]
+            boxed: Box<[u8]>,
+        }
+
+        let value = Test {
+            tag: 3,
+            samples: vec![0, 0, 128, 63, 0, 0, 0, 64],
+            boxed: vec![1, 2, 3].into_boxed_slice(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.tag, a.tag);
+            assert_eq!(b.samples, a.samples);
+            assert_eq!(b.boxed.get(), &*a.boxed);
+            assert_eq!(b.samples.as_ptr() as usize % 16, 0);
+            assert_eq!(b.boxed.get().as_ptr() as usize % 8, 0);
+        });
+    }
 }
@@ -0,0 +1,180 @@
+// Atomics are archived by loading their value with relaxed ordering. Their
+// archived forms are atomics as well, so an archive accessed mutably (e.g.
+// through `Seal::unseal_ref`) can be loaded from and stored to from multiple
+// threads.
+
+use core::sync::atomic::Ordering;
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicBool, AtomicI8, AtomicU8};
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+use core::sync::atomic::{AtomicI16, AtomicU16};
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+use core::sync::atomic::{AtomicI32, AtomicU32};
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+
+use rancor::Fallible;
+
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+use crate::primitive::{ArchivedAtomicI16, ArchivedAtomicU16};
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+use crate::primitive::{ArchivedAtomicI32, ArchivedAtomicU32};
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+use crate::primitive::{ArchivedAtomicI64, ArchivedAtomicU64};
+use crate::{
+    traits::NoUndef, Archive, Deserialize, Place, Portable, Serialize,
+};
+
+macro_rules! unsafe_impl_atomic {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            unsafe impl NoUndef for $ty {}
+            unsafe impl Portable for $ty {}
+        )*
+    };
+}
+
+#[cfg(target_has_atomic = "8")]
+unsafe_impl_atomic!(AtomicBool, AtomicI8, AtomicU8);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+unsafe_impl_atomic! {
+    rend::AtomicI16_be,
+    rend::AtomicI16_le,
+    rend::AtomicU16_be,
+    rend::AtomicU16_le,
+}
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+unsafe_impl_atomic! {
+    rend::AtomicI32_be,
+    rend::AtomicI32_le,
+    rend::AtomicU32_be,
+    rend::AtomicU32_le,
+}
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+unsafe_impl_atomic! {
+    rend::AtomicI64_be,
+    rend::AtomicI64_le,
+    rend::AtomicU64_be,
+    rend::AtomicU64_le,
+}
+
+macro_rules! impl_atomic {
+    ($atomic:ty, $archived:ty) => {
+        impl Archive for $atomic {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                out.write(<$archived>::new(self.load(Ordering::Relaxed)));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $atomic {
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$atomic, D> for $archived {
+            fn deserialize(&self, _: &mut D) -> Result<$atomic, D::Error> {
+                Ok(<$atomic>::new(self.load(Ordering::Relaxed)))
+            }
+        }
+    };
+}
+
+macro_rules! impl_atomics {
+    ($($atomic:ty, $archived:ty);* $(;)?) => {
+        $(
+            impl_atomic!($atomic, $archived);
+        )*
+    };
+}
+
+#[cfg(target_has_atomic = "8")]
+impl_atomics! {
+    AtomicBool, AtomicBool;
+    AtomicI8, AtomicI8;
+    AtomicU8, AtomicU8;
+}
+
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+impl_atomics! {
+    AtomicI16, ArchivedAtomicI16;
+    AtomicU16, ArchivedAtomicU16;
+}
+
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+impl_atomics! {
+    AtomicI32, ArchivedAtomicI32;
+    AtomicU32, ArchivedAtomicU32;
+}
+
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+impl_atomics! {
+    AtomicI64, ArchivedAtomicI64;
+    AtomicU64, ArchivedAtomicU64;
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::Ordering;
+
+    use crate::api::test::{deserialize, to_archived};
+
+    #[cfg(target_has_atomic = "8")]
+    #[test]
+    fn archive_atomic_bool() {
+        use core::sync::atomic::AtomicBool;
+
+        to_archived(&AtomicBool::new(true), |archived| {
+            assert!(archived.load(Ordering::Relaxed));
+            let value: AtomicBool = deserialize(&*archived);
+            assert!(value.load(Ordering::Relaxed));
+        });
+    }
+
+    #[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+    #[test]
+    fn archive_atomic_u32() {
+        use core::sync::atomic::AtomicU32;
+
+        to_archived(&AtomicU32::new(42), |archived| {
+            let archived = archived.unseal_ref();
+            assert_eq!(archived.load(Ordering::Relaxed), 42);
+            assert_eq!(archived.fetch_add(8, Ordering::Relaxed), 42);
+            archived.store(100, Ordering::Relaxed);
+
+            let value: AtomicU32 = deserialize(archived);
+            assert_eq!(value.load(Ordering::Relaxed), 100);
+        });
+    }
+
+    #[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+    #[test]
+    fn archive_atomic_counters() {
+        use core::sync::atomic::{AtomicI64, AtomicU64};
+
+        use crate::{Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Counters {
+            hits: AtomicU64,
+            balance: AtomicI64,
+        }
+
+        let value = Counters {
+            hits: AtomicU64::new(u64::MAX - 1),
+            balance: AtomicI64::new(-5),
+        };
+        to_archived(&value, |archived| {
+            let archived = archived.unseal_ref();
+            archived.hits.fetch_add(1, Ordering::Relaxed);
+            archived.balance.fetch_sub(5, Ordering::Relaxed);
+            assert_eq!(archived.hits.load(Ordering::Relaxed), u64::MAX);
+            assert_eq!(archived.balance.load(Ordering::Relaxed), -10);
+        });
+    }
+}
@@ -0,0 +1,111 @@
+use core::{
+    ffi::CStr,
+    num::{
+        NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
+        NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8, NonZeroUsize,
+    },
+};
+
+use rancor::Fallible;
+
+use crate::{
+    de::DeserializeBorrowed, ffi::ArchivedCString, option::ArchivedOption,
+    string::ArchivedString, vec::ArchivedVec, Archived, Deserialize,
+};
+
+macro_rules! impl_deserialize_borrowed_by_value {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl<'a, D: Fallible + ?Sized> DeserializeBorrowed<'a, $type, D>
+                for Archived<$type>
+            {
+                #[inline]
+                fn deserialize_borrowed(
+                    &'a self,
+                    deserializer: &mut D,
+                ) -> Result<$type, D::Error> {
+                    self.deserialize(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_deserialize_borrowed_by_value! {
+    (),
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    char,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+}
+
+impl<'a, D: Fallible + ?Sized> DeserializeBorrowed<'a, &'a str, D>
+    for ArchivedString
+{
+    #[inline]
+    fn deserialize_borrowed(&'a self, _: &mut D) -> Result<&'a str, D::Error> {
+        Ok(self.as_str())
+    }
+}
+
+impl<'a, T, D: Fallible + ?Sized> DeserializeBorrowed<'a, &'a [T], D>
+    for ArchivedVec<T>
+{
+    #[inline]
+    fn deserialize_borrowed(&'a self, _: &mut D) -> Result<&'a [T], D::Error> {
+        Ok(self.as_slice())
+    }
+}
+
+impl<'a, D: Fallible + ?Sized> DeserializeBorrowed<'a, &'a CStr, D>
+    for ArchivedCString
+{
+    #[inline]
+    fn deserialize_borrowed(&'a self, _: &mut D) -> Result<&'a CStr, D::Error> {
+        Ok(self.as_c_str())
+    }
+}
+
+impl<'a, T, U, D> DeserializeBorrowed<'a, Option<T>, D> for ArchivedOption<U>
+where
+    U: DeserializeBorrowed<'a, T, D>,
+    D: Fallible + ?Sized,
+{
+    #[inline]
+    fn deserialize_borrowed(
+        &'a self,
+        deserializer: &mut D,
+    ) -> Result<Option<T>, D::Error> {
+        match self {
+            ArchivedOption::Some(value) => {
+                Ok(Some(value.deserialize_borrowed(deserializer)?))
+            }
+            ArchivedOption::None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+    NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use rancor::Fallible;
+
+use crate::{
+    de::DeserializeInPlace, option::ArchivedOption, Archived, Deserialize,
+};
+
+macro_rules! impl_deserialize_in_place_by_value {
+    ($($type:ty),* $(,)?) => {
+        $(
+            impl<D: Fallible + ?Sized> DeserializeInPlace<$type, D>
+                for Archived<$type>
+            {
+                #[inline]
+                fn deserialize_in_place(
+                    &self,
+                    out: &mut $type,
+                    deserializer: &mut D,
+                ) -> Result<(), D::Error> {
+                    *out = self.deserialize(deserializer)?;
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_deserialize_in_place_by_value! {
+    (),
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    f32,
+    f64,
+    char,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+}
+
+impl<T, U, D, const N: usize> DeserializeInPlace<[T; N], D> for [U; N]
+where
+    U: DeserializeInPlace<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_in_place(
+        &self,
+        out: &mut [T; N],
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        for (archived, value) in self.iter().zip(out.iter_mut()) {
+            archived.deserialize_in_place(value, deserializer)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T, U, D> DeserializeInPlace<Option<T>, D> for ArchivedOption<U>
+where
+    U: DeserializeInPlace<T, D> + Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_in_place(
+        &self,
+        out: &mut Option<T>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        match (self, out) {
+            (ArchivedOption::Some(archived), Some(value)) => {
+                archived.deserialize_in_place(value, deserializer)
+            }
+            (ArchivedOption::Some(archived), out) => {
+                *out = Some(archived.deserialize(deserializer)?);
+                Ok(())
+            }
+            (ArchivedOption::None, out) => {
+                *out = None;
+                Ok(())
+            }
+        }
+    }
+}
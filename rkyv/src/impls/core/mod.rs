@@ -18,7 +18,10 @@ use crate::{
     Place, Portable, Serialize, SerializeUnsized,
 };
 
+mod atomic;
+mod borrow;
 mod ffi;
+mod in_place;
 mod net;
 mod ops;
 mod option;
@@ -195,12 +198,26 @@ impl_tuple!(
     ArchivedTuple13, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9,
     T10 10, T11 11, T12 12
 );
+impl_tuple!(
+    ArchivedTuple14, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9,
+    T10 10, T11 11, T12 12, T13 13
+);
+impl_tuple!(
+    ArchivedTuple15, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9,
+    T10 10, T11 11, T12 12, T13 13, T14 14
+);
+impl_tuple!(
+    ArchivedTuple16, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9,
+    T10 10, T11 11, T12 12, T13 13, T14 14, T15 15
+);
 
 // Arrays
 
 // SAFETY: `[T; N]` is a `T` array and so is portable as long as `T` is also
 // `Portable`.
-unsafe impl<T: Portable, const N: usize> Portable for [T; N] {}
+unsafe impl<T: Portable, const N: usize> Portable for [T; N] {
+    const ALWAYS_VALID: bool = T::ALWAYS_VALID;
+}
 
 impl<T: Archive, const N: usize> Archive for [T; N] {
     const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
@@ -211,9 +228,21 @@ impl<T: Archive, const N: usize> Archive for [T; N] {
     type Resolver = [T::Resolver; N];
 
     fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
-        for (i, (value, resolver)) in self.iter().zip(resolver).enumerate() {
-            let out_i = unsafe { out.index(i) };
-            value.resolve(resolver, out_i);
+        if T::COPY_OPTIMIZATION.is_enabled() {
+            // SAFETY: Copy optimization is only enabled for types which are
+            // bit-identical to their archived types and do not contain any
+            // uninit bytes, so the whole array can be copied at once.
+            unsafe {
+                out.ptr()
+                    .cast::<T>()
+                    .copy_from_nonoverlapping(self.as_ptr(), N);
+            }
+        } else {
+            for (i, (value, resolver)) in self.iter().zip(resolver).enumerate()
+            {
+                let out_i = unsafe { out.index(i) };
+                value.resolve(resolver, out_i);
+            }
         }
     }
 }
@@ -268,6 +297,23 @@ impl<T: Archive> ArchiveUnsized for [T] {
     fn archived_metadata(&self) -> ArchivedMetadata<Self> {
         ArchivedUsize::from_native(ptr_meta::metadata(self) as _)
     }
+
+    fn archived_bytes(&self) -> Option<(&[u8], usize)> {
+        if T::COPY_OPTIMIZATION.is_enabled() {
+            // SAFETY: Copy optimization is only enabled for types which do not
+            // contain any uninit bytes, so every byte of the slice is
+            // initialized.
+            let bytes = unsafe {
+                core::slice::from_raw_parts(
+                    self.as_ptr().cast::<u8>(),
+                    core::mem::size_of_val(self),
+                )
+            };
+            Some((bytes, core::mem::align_of::<T::Archived>()))
+        } else {
+            None
+        }
+    }
 }
 
 impl<T> ArchivePointee for [T] {
@@ -287,16 +333,14 @@ where
 {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
         if T::COPY_OPTIMIZATION.is_enabled() {
-            let result = serializer.align_for::<T::Archived>()?;
             let as_bytes = unsafe {
                 core::slice::from_raw_parts(
                     self.as_ptr().cast::<u8>(),
                     core::mem::size_of_val(self),
                 )
             };
-            serializer.write(as_bytes)?;
-
-            Ok(result)
+            serializer
+                .align_and_write(core::mem::align_of::<T::Archived>(), as_bytes)
         } else {
             use crate::util::SerVec;
 
@@ -374,6 +418,11 @@ impl ArchiveUnsized for str {
     fn archived_metadata(&self) -> ArchivedMetadata<Self> {
         ArchivedUsize::from_native(ptr_meta::metadata(self) as _)
     }
+
+    #[inline]
+    fn archived_bytes(&self) -> Option<(&[u8], usize)> {
+        Some((self.as_bytes(), 1))
+    }
 }
 
 impl ArchivePointee for str {
@@ -389,6 +438,9 @@ impl ArchivePointee for str {
 
 impl<S: Fallible + Writer + ?Sized> SerializeUnsized<S> for str {
     fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
+        // Strings have an alignment of 1 and store their length inline, so
+        // there is no padding or header to write alongside the bytes. A single
+        // `write` is already one write call, so `write_vectored` isn't needed.
         let result = serializer.pos();
         serializer.write(self.as_bytes())?;
         Ok(result)
@@ -538,7 +590,7 @@ mod tests {
     };
 
     use crate::{
-        api::test::{roundtrip, roundtrip_with},
+        api::test::{deserialize, roundtrip, roundtrip_with, to_archived},
         tuple::ArchivedTuple3,
     };
 
@@ -554,11 +606,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn roundtrip_tuple_16() {
+        type Wide = (
+            u8,
+            u16,
+            u32,
+            u64,
+            i8,
+            i16,
+            i32,
+            i64,
+            u8,
+            u16,
+            u32,
+            u64,
+            i8,
+            i16,
+            i32,
+            i64,
+        );
+
+        let value: Wide =
+            (0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15);
+        to_archived(&value, |archived| {
+            assert_eq!(archived.0, 0);
+            assert_eq!(archived.10, 10);
+            assert_eq!(archived.15, 15);
+            assert!(*archived == value);
+
+            let deserialized: Wide = deserialize(&*archived);
+            assert!(archived.unseal_ref() == &deserialized);
+        });
+    }
+
     #[test]
     fn roundtrip_array() {
         roundtrip(&[1, 2, 3, 4, 5, 6]);
         roundtrip(&[(); 0]);
         roundtrip(&[(), (), (), ()]);
+        roundtrip(&[1.5f32, -2.0, f32::MAX]);
+        roundtrip(&[[1.5f64, 2.5], [-3.5, 4.5]]);
     }
 
     #[test]
@@ -27,11 +27,19 @@ macro_rules! unsafe_impl_primitive {
     };
 }
 
+macro_rules! unsafe_impl_always_valid_primitive {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            unsafe impl NoUndef for $ty {}
+            unsafe impl Portable for $ty {
+                const ALWAYS_VALID: bool = true;
+            }
+        )*
+    };
+}
+
 unsafe_impl_primitive! {
-    (),
     bool,
-    i8,
-    u8,
     NonZeroI8,
     NonZeroU8,
     rend::NonZeroI16_be,
@@ -52,6 +60,30 @@ unsafe_impl_primitive! {
     rend::NonZeroU128_le,
     rend::char_be,
     rend::char_le,
+    rend::unaligned::NonZeroI16_ube,
+    rend::unaligned::NonZeroI16_ule,
+    rend::unaligned::NonZeroI32_ube,
+    rend::unaligned::NonZeroI32_ule,
+    rend::unaligned::NonZeroI64_ube,
+    rend::unaligned::NonZeroI64_ule,
+    rend::unaligned::NonZeroI128_ube,
+    rend::unaligned::NonZeroI128_ule,
+    rend::unaligned::NonZeroU16_ube,
+    rend::unaligned::NonZeroU16_ule,
+    rend::unaligned::NonZeroU32_ube,
+    rend::unaligned::NonZeroU32_ule,
+    rend::unaligned::NonZeroU64_ube,
+    rend::unaligned::NonZeroU64_ule,
+    rend::unaligned::NonZeroU128_ube,
+    rend::unaligned::NonZeroU128_ule,
+    rend::unaligned::char_ube,
+    rend::unaligned::char_ule,
+}
+
+unsafe_impl_always_valid_primitive! {
+    (),
+    i8,
+    u8,
     rend::f32_be,
     rend::f32_le,
     rend::f64_be,
@@ -72,24 +104,6 @@ unsafe_impl_primitive! {
     rend::u64_le,
     rend::u128_be,
     rend::u128_le,
-    rend::unaligned::NonZeroI16_ube,
-    rend::unaligned::NonZeroI16_ule,
-    rend::unaligned::NonZeroI32_ube,
-    rend::unaligned::NonZeroI32_ule,
-    rend::unaligned::NonZeroI64_ube,
-    rend::unaligned::NonZeroI64_ule,
-    rend::unaligned::NonZeroI128_ube,
-    rend::unaligned::NonZeroI128_ule,
-    rend::unaligned::NonZeroU16_ube,
-    rend::unaligned::NonZeroU16_ule,
-    rend::unaligned::NonZeroU32_ube,
-    rend::unaligned::NonZeroU32_ule,
-    rend::unaligned::NonZeroU64_ube,
-    rend::unaligned::NonZeroU64_ule,
-    rend::unaligned::NonZeroU128_ube,
-    rend::unaligned::NonZeroU128_ule,
-    rend::unaligned::char_ube,
-    rend::unaligned::char_ule,
     rend::unaligned::f32_ube,
     rend::unaligned::f32_ule,
     rend::unaligned::f64_ube,
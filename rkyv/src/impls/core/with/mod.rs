@@ -24,6 +24,7 @@ use rancor::Fallible;
 
 use crate::{
     boxed::{ArchivedBox, BoxResolver},
+    extensible::{ArchivedExtensible, ExtensibleResolver},
     niche::{
         niched_option::NichedOption,
         niching::{DefaultNiche, Niching},
@@ -38,10 +39,13 @@ use crate::{
     },
     option::ArchivedOption,
     primitive::{FixedNonZeroIsize, FixedNonZeroUsize},
+    result::ArchivedResult,
+    ser::Writer,
     traits::NoUndef,
     with::{
-        ArchiveWith, AsBox, DeserializeWith, Identity, Inline, InlineAsBox,
-        Map, MapNiche, Niche, NicheInto, SerializeWith, Skip, Unsafe,
+        ArchiveWith, AsBox, DeserializeWith, Extensible, Identity, Inline,
+        InlineAsBox, Map, MapNiche, MapResult, Niche, NicheInto, SerializeWith,
+        Skip, Unsafe,
     },
     Archive, ArchiveUnsized, Deserialize, Place, Serialize, SerializeUnsized,
 };
@@ -154,6 +158,48 @@ where
     }
 }
 
+// Extensible
+
+impl<F: Archive> ArchiveWith<F> for Extensible {
+    type Archived = ArchivedExtensible<F::Archived>;
+    type Resolver = ExtensibleResolver;
+
+    fn resolve_with(
+        _: &F,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedExtensible::resolve_from_resolver(resolver, out);
+    }
+}
+
+impl<F, S> SerializeWith<F, S> for Extensible
+where
+    F: Serialize<S>,
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &F,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedExtensible::serialize_from_ref(field, serializer)
+    }
+}
+
+impl<F, D> DeserializeWith<ArchivedExtensible<F::Archived>, F, D> for Extensible
+where
+    F: Archive,
+    F::Archived: Deserialize<F, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedExtensible<F::Archived>,
+        deserializer: &mut D,
+    ) -> Result<F, D::Error> {
+        field.get().deserialize(deserializer)
+    }
+}
+
 // Map
 
 // Copy-paste from Option's impls for the most part
@@ -255,6 +301,136 @@ struct ArchivedOptionVariantNone(ArchivedOptionTag);
 #[repr(C)]
 struct ArchivedOptionVariantSome<T>(ArchivedOptionTag, T);
 
+impl<A, O, const N: usize> ArchiveWith<[O; N]> for Map<A>
+where
+    A: ArchiveWith<O>,
+{
+    type Archived = [<A as ArchiveWith<O>>::Archived; N];
+    type Resolver = [<A as ArchiveWith<O>>::Resolver; N];
+
+    fn resolve_with(
+        field: &[O; N],
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        for (i, (value, resolver)) in field.iter().zip(resolver).enumerate() {
+            let out_i = unsafe { out.index(i) };
+            A::resolve_with(value, resolver, out_i);
+        }
+    }
+}
+
+impl<A, O, S, const N: usize> SerializeWith<[O; N], S> for Map<A>
+where
+    S: Fallible + ?Sized,
+    A: ArchiveWith<O> + SerializeWith<O, S>,
+{
+    fn serialize_with(
+        field: &[O; N],
+        s: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        field
+            .each_ref()
+            .map(|value| RefWrapper::<'_, A, O>(value, PhantomData))
+            .serialize(s)
+    }
+}
+
+impl<A, O, D, const N: usize>
+    DeserializeWith<[<A as ArchiveWith<O>>::Archived; N], [O; N], D> for Map<A>
+where
+    D: Fallible + ?Sized,
+    A: ArchiveWith<O> + DeserializeWith<<A as ArchiveWith<O>>::Archived, O, D>,
+{
+    fn deserialize_with(
+        field: &[<A as ArchiveWith<O>>::Archived; N],
+        d: &mut D,
+    ) -> Result<[O; N], D::Error> {
+        let mut result = core::mem::MaybeUninit::<[O; N]>::uninit();
+        let result_ptr = result.as_mut_ptr().cast::<O>();
+        for (i, value) in field.iter().enumerate() {
+            unsafe {
+                result_ptr.add(i).write(A::deserialize_with(value, d)?);
+            }
+        }
+        unsafe { Ok(result.assume_init()) }
+    }
+}
+
+// MapResult
+
+impl<A, B, T, U> ArchiveWith<Result<T, U>> for MapResult<A, B>
+where
+    A: ArchiveWith<T>,
+    B: ArchiveWith<U>,
+{
+    type Archived = ArchivedResult<
+        <A as ArchiveWith<T>>::Archived,
+        <B as ArchiveWith<U>>::Archived,
+    >;
+    type Resolver = Result<
+        <A as ArchiveWith<T>>::Resolver,
+        <B as ArchiveWith<U>>::Resolver,
+    >;
+
+    fn resolve_with(
+        field: &Result<T, U>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        field
+            .as_ref()
+            .map(|value| RefWrapper::<'_, A, T>(value, PhantomData))
+            .map_err(|err| RefWrapper::<'_, B, U>(err, PhantomData))
+            .resolve(resolver, out)
+    }
+}
+
+impl<A, B, T, U, S> SerializeWith<Result<T, U>, S> for MapResult<A, B>
+where
+    S: Fallible + ?Sized,
+    A: ArchiveWith<T> + SerializeWith<T, S>,
+    B: ArchiveWith<U> + SerializeWith<U, S>,
+{
+    fn serialize_with(
+        field: &Result<T, U>,
+        s: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        Ok(match field {
+            Ok(value) => Ok(A::serialize_with(value, s)?),
+            Err(err) => Err(B::serialize_with(err, s)?),
+        })
+    }
+}
+
+impl<A, B, T, U, D>
+    DeserializeWith<
+        ArchivedResult<
+            <A as ArchiveWith<T>>::Archived,
+            <B as ArchiveWith<U>>::Archived,
+        >,
+        Result<T, U>,
+        D,
+    > for MapResult<A, B>
+where
+    D: Fallible + ?Sized,
+    A: ArchiveWith<T> + DeserializeWith<<A as ArchiveWith<T>>::Archived, T, D>,
+    B: ArchiveWith<U> + DeserializeWith<<B as ArchiveWith<U>>::Archived, U, D>,
+{
+    fn deserialize_with(
+        field: &ArchivedResult<
+            <A as ArchiveWith<T>>::Archived,
+            <B as ArchiveWith<U>>::Archived,
+        >,
+        d: &mut D,
+    ) -> Result<Result<T, U>, D::Error> {
+        match field {
+            ArchivedResult::Ok(value) => Ok(Ok(A::deserialize_with(value, d)?)),
+            ArchivedResult::Err(err) => Ok(Err(B::deserialize_with(err, d)?)),
+        }
+    }
+}
+
 // Niche
 
 macro_rules! impl_nonzero_niche {
@@ -964,7 +1140,7 @@ mod tests {
         }
 
         #[derive(Archive, Serialize, Deserialize)]
-        #[rkyv(crate)]
+        #[rkyv(crate, no_auto_niche)]
         struct TestNoNiching {
             a: Option<NonZeroI8>,
             b: Option<NonZeroI32>,
@@ -1065,6 +1241,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn auto_niche_nonzero() {
+        use core::{
+            mem::size_of,
+            num::{NonZeroI64, NonZeroU16},
+        };
+
+        use crate::{
+            niche::option_nonzero::{
+                ArchivedOptionNonZeroI64, ArchivedOptionNonZeroU16,
+            },
+            option::ArchivedOption,
+        };
+
+        #[derive(Archive, Debug, Deserialize, PartialEq, Serialize)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct TestAuto {
+            a: Option<NonZeroU16>,
+            b: core::option::Option<NonZeroI64>,
+            #[rkyv(no_auto_niche)]
+            c: Option<NonZeroU16>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(crate)]
+        struct TestNiche {
+            #[rkyv(with = Niche)]
+            a: Option<NonZeroU16>,
+            #[rkyv(with = Niche)]
+            b: Option<NonZeroI64>,
+            #[rkyv(no_auto_niche)]
+            c: Option<NonZeroU16>,
+        }
+
+        #[derive(Archive, Serialize, Deserialize)]
+        #[rkyv(crate, no_auto_niche)]
+        struct TestNoAuto {
+            a: Option<NonZeroU16>,
+            b: Option<NonZeroI64>,
+            c: Option<NonZeroU16>,
+        }
+
+        assert_eq!(
+            size_of::<Archived<TestAuto>>(),
+            size_of::<Archived<TestNiche>>(),
+        );
+        assert!(
+            size_of::<Archived<TestAuto>>() < size_of::<Archived<TestNoAuto>>()
+        );
+
+        roundtrip(&TestAuto {
+            a: NonZeroU16::new(7),
+            b: NonZeroI64::new(-7),
+            c: NonZeroU16::new(7),
+        });
+        roundtrip(&TestAuto {
+            a: None,
+            b: None,
+            c: None,
+        });
+        to_archived(
+            &TestAuto {
+                a: None,
+                b: NonZeroI64::new(1),
+                c: None,
+            },
+            |archived| {
+                let _: &ArchivedOptionNonZeroU16 = &archived.a;
+                let _: &ArchivedOptionNonZeroI64 = &archived.b;
+                let _: &ArchivedOption<Archived<NonZeroU16>> = &archived.c;
+            },
+        );
+    }
+
     #[test]
     fn with_niche_float_nan() {
         #[derive(Archive, Serialize, Deserialize)]
@@ -1156,4 +1406,172 @@ mod tests {
             assert_eq!(archived.other, 10);
         });
     }
+
+    #[test]
+    fn with_map_array_and_result() {
+        use crate::{result::ArchivedResult, with::MapResult};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Test<'a> {
+            #[rkyv(with = Map<Inline>)]
+            a: [&'a u32; 3],
+            #[rkyv(with = MapResult<Inline, Identity>)]
+            b: Result<&'a u32, u8>,
+            #[rkyv(with = MapResult<Inline, Identity>)]
+            c: Result<&'a u32, u8>,
+        }
+
+        let (x, y, z) = (1, 2, 3);
+        let value = Test {
+            a: [&x, &y, &z],
+            b: Ok(&x),
+            c: Err(42),
+        };
+        to_archived(&value, |archived| {
+            assert_eq!(archived.a, [1, 2, 3]);
+            assert!(matches!(archived.b, ArchivedResult::Ok(ref v) if *v == 1));
+            assert!(matches!(archived.c, ArchivedResult::Err(42)));
+        });
+    }
+
+    #[test]
+    fn skip_fields() {
+        use core::{cell::Cell, mem::size_of};
+
+        fn seven() -> u32 {
+            7
+        }
+
+        #[derive(Archive, Debug, Deserialize, PartialEq, Serialize)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        struct Test {
+            a: u32,
+            #[rkyv(skip)]
+            cache: Cell<Option<u32>>,
+            #[rkyv(skip, default = seven)]
+            b: u32,
+        }
+
+        #[derive(Archive, Debug, Deserialize, PartialEq, Serialize)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        enum TestEnum {
+            A(u32, #[rkyv(skip)] Cell<Option<u32>>),
+            B {
+                #[rkyv(skip, default = seven)]
+                b: u32,
+            },
+        }
+
+        assert_eq!(size_of::<ArchivedTest>(), size_of::<Archived<u32>>());
+
+        let value = Test {
+            a: 42,
+            cache: Cell::new(None),
+            b: 7,
+        };
+        roundtrip(&value);
+
+        let value = Test {
+            a: 42,
+            cache: Cell::new(Some(1)),
+            b: 100,
+        };
+        to_archived(&value, |archived| {
+            assert_eq!(archived.a, 42);
+            let deserialized = deserialize::<Test>(&*archived);
+            assert_eq!(deserialized.a, 42);
+            assert_eq!(deserialized.cache.get(), None);
+            assert_eq!(deserialized.b, 7);
+        });
+
+        roundtrip(&TestEnum::A(1, Cell::new(None)));
+        roundtrip(&TestEnum::B { b: 7 });
+    }
+
+    #[test]
+    fn with_extensible() {
+        use crate::api::test::{to_archived_from_bytes, to_bytes};
+
+        mod v1 {
+            use crate::{with::Extensible, Archive, Deserialize, Serialize};
+
+            #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+            #[rkyv(crate, compare(PartialEq), derive(Debug))]
+            pub struct Config {
+                pub id: u32,
+            }
+
+            #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+            #[rkyv(crate, derive(Debug))]
+            pub struct Message {
+                #[rkyv(with = Extensible)]
+                pub config: Config,
+                pub flags: u8,
+            }
+        }
+
+        mod v2 {
+            use crate::{with::Extensible, Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[rkyv(crate)]
+            pub struct Config {
+                pub id: u32,
+                pub extra: u64,
+            }
+
+            #[derive(Archive, Serialize)]
+            #[rkyv(crate)]
+            pub struct Message {
+                #[rkyv(with = Extensible)]
+                pub config: Config,
+                pub flags: u8,
+            }
+        }
+
+        roundtrip_with(
+            &v1::Message {
+                config: v1::Config { id: 1 },
+                flags: 2,
+            },
+            |a, b| {
+                assert_eq!(
+                    b.config.size(),
+                    core::mem::size_of_val(b.config.get())
+                );
+                assert_eq!(*b.config.get(), a.config);
+                assert_eq!(b.flags, a.flags);
+                assert_eq!(deserialize::<v1::Message>(b), *a);
+            },
+        );
+
+        let value = v2::Message {
+            config: v2::Config { id: 42, extra: 7 },
+            flags: 3,
+        };
+        to_bytes(&value, |bytes| {
+            to_archived_from_bytes::<v1::Message>(bytes, |archived| {
+                assert!(
+                    archived.config.size()
+                        > core::mem::size_of_val(archived.config.get())
+                );
+                assert_eq!(archived.config.id, 42);
+                assert_eq!(archived.flags, 3);
+            });
+        });
+
+        #[cfg(feature = "bytecheck")]
+        {
+            use crate::{api::low::access, rancor::Failure};
+
+            let value = v1::Message {
+                config: v1::Config { id: 1 },
+                flags: 2,
+            };
+            to_bytes(&value, |bytes| {
+                access::<v2::ArchivedMessage, Failure>(bytes).unwrap_err();
+            });
+        }
+    }
 }
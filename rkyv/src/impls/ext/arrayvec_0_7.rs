@@ -1,9 +1,14 @@
-use arrayvec_0_7::ArrayVec;
-use rancor::Fallible;
+use core::{error::Error, fmt};
+
+use arrayvec_0_7::{ArrayString, ArrayVec};
+use rancor::{fail, Fallible, Source};
 
 use crate::{
+    inline_vec::{ArchivedInlineVec, InlineVecResolver},
     ser::{Allocator, Writer},
+    string::{ArchivedString, StringResolver},
     vec::{ArchivedVec, VecResolver},
+    with::{ArchiveWith, DeserializeWith, InlineCapacity, SerializeWith},
     Archive, Archived, Deserialize, Place, Serialize,
 };
 
@@ -75,10 +80,151 @@ where
     }
 }
 
+impl<T, const CAP: usize> ArchiveWith<ArrayVec<T, CAP>> for InlineCapacity
+where
+    T: Archive,
+{
+    type Archived = ArchivedInlineVec<Archived<T>, CAP>;
+    type Resolver = InlineVecResolver<T::Resolver, CAP>;
+
+    fn resolve_with(
+        field: &ArrayVec<T, CAP>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedInlineVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+impl<T, S, const CAP: usize> SerializeWith<ArrayVec<T, CAP>, S>
+    for InlineCapacity
+where
+    T: Serialize<S>,
+    S: Fallible + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &ArrayVec<T, CAP>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedInlineVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const CAP: usize>
+    DeserializeWith<ArchivedInlineVec<Archived<T>, CAP>, ArrayVec<T, CAP>, D>
+    for InlineCapacity
+where
+    T: Archive,
+    Archived<T>: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedInlineVec<Archived<T>, CAP>,
+        deserializer: &mut D,
+    ) -> Result<ArrayVec<T, CAP>, D::Error> {
+        let mut result = ArrayVec::new();
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+impl<const CAP: usize> Archive for ArrayString<CAP> {
+    type Archived = ArchivedString;
+    type Resolver = StringResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedString::resolve_from_str(self, resolver, out);
+    }
+}
+
+impl<S, const CAP: usize> Serialize<S> for ArrayString<CAP>
+where
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedString::serialize_from_str(self, serializer)
+    }
+}
+
+impl<D, const CAP: usize> Deserialize<ArrayString<CAP>, D> for ArchivedString
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(
+        &self,
+        _deserializer: &mut D,
+    ) -> Result<ArrayString<CAP>, D::Error> {
+        #[derive(Debug)]
+        struct CapacityExceeded {
+            len: usize,
+            capacity: usize,
+        }
+
+        impl fmt::Display for CapacityExceeded {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "archived string of length {} exceeds the capacity {} of \
+                     the ArrayString",
+                    self.len, self.capacity,
+                )
+            }
+        }
+
+        impl Error for CapacityExceeded {}
+
+        match ArrayString::from(self.as_str()) {
+            Ok(result) => Ok(result),
+            Err(_) => fail!(CapacityExceeded {
+                len: self.len(),
+                capacity: CAP,
+            }),
+        }
+    }
+}
+
+impl<const CAP: usize> PartialEq<ArrayString<CAP>> for ArchivedString {
+    fn eq(&self, other: &ArrayString<CAP>) -> bool {
+        other.as_str() == self.as_str()
+    }
+}
+
+impl<const CAP: usize> PartialOrd<ArrayString<CAP>> for ArchivedString {
+    fn partial_cmp(
+        &self,
+        other: &ArrayString<CAP>,
+    ) -> Option<::core::cmp::Ordering> {
+        Some(self.as_str().cmp(other.as_str()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ArrayVec;
-    use crate::api::test::roundtrip_with;
+    use super::{ArrayString, ArrayVec};
+    use crate::{
+        api::{
+            deserialize_using,
+            test::{roundtrip, roundtrip_with, to_archived},
+        },
+        with::InlineCapacity,
+        Archive, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, derive(Debug))]
+    struct InlineValues {
+        #[rkyv(with = InlineCapacity)]
+        values: ArrayVec<i32, 4>,
+    }
 
     #[test]
     fn roundtrip_array_vec() {
@@ -86,4 +232,36 @@ mod tests {
             assert_eq!(**a, **b)
         });
     }
+
+    #[test]
+    fn roundtrip_inline_array_vec() {
+        let mut values = ArrayVec::new();
+        values.push(10);
+        values.push(20);
+        let value = InlineValues { values };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.values.capacity(), 4);
+            assert_eq!(*a.values, *b.values);
+        });
+    }
+
+    #[test]
+    fn roundtrip_array_string() {
+        roundtrip(&ArrayString::<16>::from("hello world").unwrap());
+        roundtrip(&ArrayString::<4>::new());
+    }
+
+    #[test]
+    fn array_string_capacity_exceeded() {
+        use rancor::Failure;
+
+        let value = ArrayString::<16>::from("hello world").unwrap();
+        to_archived(&value, |archived| {
+            let result = deserialize_using::<ArrayString<4>, _, Failure>(
+                &*archived,
+                &mut (),
+            );
+            assert!(result.is_err());
+        });
+    }
 }
@@ -0,0 +1,117 @@
+use bitflags_2::Flags;
+use rancor::Fallible;
+
+use crate::{
+    flags::bitflags_2::{ArchiveBits, ArchivedFlags},
+    with::{ArchiveWith, AsBits, DeserializeWith, SerializeWith},
+    Place,
+};
+
+impl<F> ArchiveWith<F> for AsBits
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+{
+    type Archived = ArchivedFlags<F>;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve_with(field: &F, _: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedFlags::emplace(field, out);
+    }
+}
+
+impl<F, S> SerializeWith<F, S> for AsBits
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+    S: Fallible + ?Sized,
+{
+    fn serialize_with(_: &F, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<F, D> DeserializeWith<ArchivedFlags<F>, F, D> for AsBits
+where
+    F: Flags,
+    F::Bits: ArchiveBits,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedFlags<F>,
+        _: &mut D,
+    ) -> Result<F, D::Error> {
+        Ok(field.to_flags())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitflags_2::bitflags;
+
+    use crate::{
+        api::test::{roundtrip, to_archived},
+        with::AsBits,
+        Archive, Deserialize, Serialize,
+    };
+
+    bitflags! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        struct Permissions: u16 {
+            const READ = 0b001;
+            const WRITE = 0b010;
+            const EXECUTE = 0b100;
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, compare(PartialEq), derive(Debug))]
+    struct File {
+        #[rkyv(with = AsBits)]
+        permissions: Permissions,
+    }
+
+    #[test]
+    fn roundtrip_bitflags() {
+        roundtrip(&File {
+            permissions: Permissions::READ | Permissions::EXECUTE,
+        });
+        roundtrip(&File {
+            permissions: Permissions::empty(),
+        });
+        roundtrip(&File {
+            permissions: Permissions::all(),
+        });
+    }
+
+    #[test]
+    fn archived_bitflags() {
+        let value = File {
+            permissions: Permissions::READ | Permissions::WRITE,
+        };
+        to_archived(&value, |archived| {
+            let permissions = &archived.permissions;
+            assert_eq!(permissions.bits(), 0b011);
+            assert!(permissions.contains(Permissions::WRITE));
+            assert!(!permissions.contains(Permissions::all()));
+            assert!(permissions.intersects(Permissions::all()));
+            assert!(!permissions.is_empty());
+            assert!(!permissions.is_all());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "bytecheck")]
+    fn reject_unknown_bits() {
+        use rancor::Failure;
+
+        use crate::{access, to_bytes};
+
+        let bytes = to_bytes::<Failure>(&0b1000u16).unwrap();
+        assert!(access::<ArchivedFile, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes::<Failure>(&0b0111u16).unwrap();
+        assert!(access::<ArchivedFile, Failure>(&bytes).is_ok());
+    }
+}
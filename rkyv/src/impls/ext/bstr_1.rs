@@ -0,0 +1,81 @@
+use bstr_1::{BStr, BString};
+use rancor::Fallible;
+
+use crate::{
+    ser::Writer,
+    string::byte_string::{ArchivedByteString, ByteStringResolver},
+    Archive, Deserialize, Place, Serialize,
+};
+
+impl Archive for BString {
+    type Archived = ArchivedByteString;
+    type Resolver = ByteStringResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedByteString::resolve_from_bytes(self, resolver, out);
+    }
+}
+
+impl<S: Fallible + Writer + ?Sized> Serialize<S> for BString {
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedByteString::serialize_from_bytes(self, serializer)
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<BString, D> for ArchivedByteString {
+    fn deserialize(&self, _: &mut D) -> Result<BString, D::Error> {
+        Ok(BString::from(self.as_bytes()))
+    }
+}
+
+impl PartialEq<BStr> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &BStr) -> bool {
+        self.as_bytes() == &**other
+    }
+}
+
+impl PartialEq<ArchivedByteString> for BStr {
+    #[inline]
+    fn eq(&self, other: &ArchivedByteString) -> bool {
+        other.eq(self)
+    }
+}
+
+impl PartialEq<BString> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &BString) -> bool {
+        self.as_bytes() == other.as_slice()
+    }
+}
+
+impl PartialEq<ArchivedByteString> for BString {
+    #[inline]
+    fn eq(&self, other: &ArchivedByteString) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bstr_1::{BString, ByteSlice as _};
+
+    use crate::api::test::{roundtrip, to_archived};
+
+    #[test]
+    fn roundtrip_bstring() {
+        roundtrip(&BString::from(""));
+        roundtrip(&BString::from(&b"hello\xffworld"[..]));
+        roundtrip(&BString::from("a".repeat(100)));
+
+        let value = BString::from(&b"foo\xffbar"[..]);
+        to_archived(&value, |archived| {
+            assert_eq!(archived.as_bstr(), value.as_bstr());
+            assert_eq!(archived.as_bstr().find("bar"), Some(4));
+        });
+    }
+}
@@ -2,8 +2,10 @@ use bytes_1::{Bytes, BytesMut};
 use rancor::Fallible;
 
 use crate::{
+    bytes::{ArchivedBytes, BytesResolver},
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
+    with::{ArchiveWith, AsBytes, DeserializeWith, SerializeWith},
     Archive, Archived, Deserialize, Place, Serialize,
 };
 
@@ -34,6 +36,19 @@ impl<D: Fallible + ?Sized> Deserialize<Bytes, D> for ArchivedVec<Archived<u8>> {
     }
 }
 
+/// Converts an `AlignedVec` into `Bytes` without copying.
+///
+/// The `Bytes` takes ownership of the aligned allocation, so archives can be
+/// passed to APIs which take `Bytes` and then accessed again in place. A
+/// [`CheckedArchive<Bytes, T>`](crate::api::high::CheckedArchive) holds the
+/// `Bytes` and accesses the archive without validating it again.
+#[cfg(feature = "alloc")]
+impl<const A: usize> From<crate::util::AlignedVec<A>> for Bytes {
+    fn from(vec: crate::util::AlignedVec<A>) -> Self {
+        Bytes::from_owner(vec)
+    }
+}
+
 impl<T: Archive> PartialEq<Bytes> for ArchivedVec<T>
 where
     Bytes: PartialEq<[T]>,
@@ -43,13 +58,100 @@ where
     }
 }
 
+impl<const ALIGN: usize> ArchiveWith<Bytes> for AsBytes<ALIGN> {
+    type Archived = ArchivedBytes<ALIGN>;
+    type Resolver = BytesResolver;
+
+    fn resolve_with(
+        field: &Bytes,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedBytes::resolve_from_bytes(field, resolver, out);
+    }
+}
+
+impl<S, const ALIGN: usize> SerializeWith<Bytes, S> for AsBytes<ALIGN>
+where
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize_with(
+        field: &Bytes,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedBytes::<ALIGN>::serialize_from_bytes(field, serializer)
+    }
+}
+
+impl<D, const ALIGN: usize> DeserializeWith<ArchivedBytes<ALIGN>, Bytes, D>
+    for AsBytes<ALIGN>
+where
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedBytes<ALIGN>,
+        _: &mut D,
+    ) -> Result<Bytes, D::Error> {
+        Ok(Bytes::copy_from_slice(field.as_slice()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Bytes;
-    use crate::{alloc::vec, api::test::roundtrip};
+    use crate::{
+        alloc::vec,
+        api::test::{roundtrip, roundtrip_with},
+        with::AsBytes,
+        Archive, Deserialize, Serialize,
+    };
 
     #[test]
     fn roundtrip_bytes() {
         roundtrip(&Bytes::from(vec![10, 20, 40, 80]));
     }
+
+    #[test]
+    fn with_as_bytes() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            tag: u8,
+            #[rkyv(with = AsBytes<16>)]
+            payload: Bytes,
+        }
+
+        let value = Test {
+            tag: 7,
+            payload: Bytes::from(vec![10, 20, 40, 80]),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.tag, a.tag);
+            assert_eq!(b.payload.as_slice(), &a.payload[..]);
+            assert_eq!(b.payload.as_ptr() as usize % 16, 0);
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn aligned_vec_into_bytes() {
+        use rancor::Panic;
+
+        use crate::{
+            alloc::string::ToString,
+            api::high::{to_bytes, CheckedArchive},
+            string::ArchivedString,
+            vec::ArchivedVec,
+        };
+
+        let value = vec!["a".to_string(), "b".to_string()];
+        let vec = to_bytes::<Panic>(&value).unwrap();
+        let ptr = vec.as_ptr();
+
+        let bytes = Bytes::from(vec);
+        assert_eq!(bytes.as_ptr(), ptr);
+        type Root = ArchivedVec<ArchivedString>;
+        let archive = CheckedArchive::<_, Root>::new::<Panic>(bytes).unwrap();
+        assert_eq!(archive[1], "b");
+    }
 }
@@ -0,0 +1,162 @@
+use chrono_0_4::{
+    DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc,
+};
+use rancor::Fallible;
+
+use crate::{
+    time::chrono_0_4::{
+        ArchivedNaiveDate, ArchivedNaiveDateTime, ArchivedNaiveTime,
+        ArchivedTimeDelta,
+    },
+    Archive, Deserialize, Place, Serialize,
+};
+
+#[rustfmt::skip]
+macro_rules! impl_chrono {
+    (
+        $ty:ty,
+        $archived:ty,
+        $value:ident => $emplace:expr,
+        $this:ident => $native:expr $(,)?
+    ) => {
+        impl Archive for $ty {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                let $value = self;
+                <$archived>::emplace($emplace, out);
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $ty {
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$ty, D> for $archived {
+            fn deserialize(&self, _: &mut D) -> Result<$ty, D::Error> {
+                let $this = self;
+                Ok($native)
+            }
+        }
+
+        impl PartialEq<$ty> for $archived {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                let $this = self;
+                $native == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $ty {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                other.eq(self)
+            }
+        }
+    };
+}
+
+impl_chrono!(
+    NaiveDate,
+    ArchivedNaiveDate,
+    value => value,
+    this => this.as_naive_date(),
+);
+impl_chrono!(
+    NaiveTime,
+    ArchivedNaiveTime,
+    value => value,
+    this => this.as_naive_time(),
+);
+impl_chrono!(
+    NaiveDateTime,
+    ArchivedNaiveDateTime,
+    value => value,
+    this => this.as_naive_date_time(),
+);
+impl_chrono!(
+    DateTime<Utc>,
+    ArchivedNaiveDateTime,
+    value => &value.naive_utc(),
+    this => this.as_date_time_utc(),
+);
+impl_chrono!(
+    TimeDelta,
+    ArchivedTimeDelta,
+    value => value,
+    this => this.as_time_delta(),
+);
+
+#[cfg(test)]
+mod tests {
+    use chrono_0_4::{
+        DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc,
+    };
+
+    use crate::{
+        api::test::{roundtrip, to_archived},
+        time::chrono_0_4::ArchivedNaiveDate,
+    };
+
+    #[test]
+    fn roundtrip_chrono() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let time =
+            NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap();
+        let date_time = NaiveDateTime::new(date, time);
+
+        roundtrip(&date);
+        roundtrip(&NaiveDate::MIN);
+        roundtrip(&NaiveDate::MAX);
+        roundtrip(&time);
+        roundtrip(&date_time);
+        roundtrip(&DateTime::<Utc>::from_naive_utc_and_offset(date_time, Utc));
+    }
+
+    #[test]
+    fn roundtrip_time_delta() {
+        for delta in [
+            TimeDelta::zero(),
+            TimeDelta::milliseconds(1_500),
+            TimeDelta::milliseconds(-1_500),
+            TimeDelta::nanoseconds(-1),
+            TimeDelta::seconds(i64::MAX / 1_000),
+            TimeDelta::seconds(-i64::MAX / 1_000),
+        ] {
+            roundtrip(&delta);
+            to_archived(&delta, |archived| {
+                assert_eq!(archived.num_seconds(), delta.num_seconds());
+                assert_eq!(archived.subsec_nanos(), delta.subsec_nanos());
+            });
+        }
+    }
+
+    #[test]
+    fn archived_dates_are_ordered() {
+        let a = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap();
+        let b = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        to_archived(&(a, b), |archived| {
+            let (a, b): (&ArchivedNaiveDate, _) = (&archived.0, &archived.1);
+            assert!(a < b);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "bytecheck")]
+    fn reject_out_of_range() {
+        use rancor::Failure;
+
+        use crate::{access, time::chrono_0_4::ArchivedNaiveTime, to_bytes};
+
+        // 86,400 seconds from midnight is past the end of the day.
+        let bytes = to_bytes::<Failure>(&(86_400u32, 0u32)).unwrap();
+        assert!(access::<ArchivedNaiveTime, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes::<Failure>(&(86_399u32, 0u32)).unwrap();
+        assert!(access::<ArchivedNaiveTime, Failure>(&bytes).is_ok());
+    }
+}
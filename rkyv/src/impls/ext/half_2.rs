@@ -0,0 +1,91 @@
+use half_2::{bf16, f16};
+use rancor::Fallible;
+
+use crate::{
+    primitive::half_2::{ArchivedBf16, ArchivedF16},
+    traits::{CopyOptimization, NoUndef},
+    Archive, Deserialize, Place, Serialize,
+};
+
+#[cfg(any(
+    all(not(feature = "big_endian"), target_endian = "little"),
+    all(feature = "big_endian", target_endian = "big"),
+))]
+const HALF_IS_TRIVIALLY_COPYABLE: bool = true;
+#[cfg(any(
+    all(feature = "big_endian", target_endian = "little"),
+    all(not(feature = "big_endian"), target_endian = "big"),
+))]
+const HALF_IS_TRIVIALLY_COPYABLE: bool = false;
+
+macro_rules! impl_half {
+    ($archived:ident, $ty:ty) => {
+        // SAFETY: The archived half-precision types are transparent wrappers
+        // around an archived `u16`, which has no undefined bytes.
+        unsafe impl NoUndef for $archived {}
+
+        impl Archive for $ty {
+            const COPY_OPTIMIZATION: CopyOptimization<Self> = unsafe {
+                CopyOptimization::enable_if(HALF_IS_TRIVIALLY_COPYABLE)
+            };
+
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                out.write(<$archived>::from_native(*self));
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $ty {
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$ty, D> for $archived {
+            fn deserialize(&self, _: &mut D) -> Result<$ty, D::Error> {
+                Ok(self.to_native())
+            }
+        }
+    };
+}
+
+impl_half!(ArchivedF16, f16);
+impl_half!(ArchivedBf16, bf16);
+
+#[cfg(test)]
+mod tests {
+    use half_2::{bf16, f16};
+
+    use crate::api::test::{roundtrip, to_archived};
+
+    #[test]
+    fn roundtrip_f16() {
+        roundtrip(&f16::from_f32(1.5));
+        roundtrip(&f16::MAX);
+        roundtrip(&f16::NEG_INFINITY);
+        roundtrip(&f16::MIN_POSITIVE_SUBNORMAL);
+        roundtrip(&[f16::ONE, f16::ZERO, f16::NEG_ONE, f16::EPSILON]);
+    }
+
+    #[test]
+    fn roundtrip_bf16() {
+        roundtrip(&bf16::from_f32(-3.25));
+        roundtrip(&bf16::MIN);
+        roundtrip(&bf16::INFINITY);
+        roundtrip(&[bf16::ONE, bf16::ZERO, bf16::NEG_ONE, bf16::EPSILON]);
+    }
+
+    #[test]
+    fn archived_half_bits() {
+        let value = (f16::from_f32(0.1), bf16::NAN);
+        to_archived(&value, |archived| {
+            assert_eq!(archived.0.to_bits(), value.0.to_bits());
+            assert_eq!(archived.0.to_native(), value.0);
+            assert!(archived.1.to_native().is_nan());
+            assert_ne!(archived.1, value.1);
+        });
+    }
+}
@@ -107,4 +107,24 @@ mod tests {
             }
         });
     }
+
+    #[test]
+    fn index_map_preserves_order() {
+        let mut value =
+            IndexMap::with_hasher(BuildHasherDefault::<FxHasher64>::default());
+        for (i, key) in ["d", "a", "c", "b"].into_iter().enumerate() {
+            value.insert(String::from(key), i as u32);
+        }
+
+        roundtrip_with(&value, |a, b| {
+            assert!(a
+                .keys()
+                .map(String::as_str)
+                .eq(b.keys().map(|k| k.as_str())));
+            assert_eq!(b.first().unwrap().0, "d");
+            assert_eq!(b.last().unwrap().0, "b");
+            assert_eq!(b.get_index_of("c"), Some(2));
+            assert_eq!(b.get_index(1).unwrap().0, "a");
+        });
+    }
 }
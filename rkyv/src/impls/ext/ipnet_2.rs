@@ -0,0 +1,217 @@
+use ipnet_2::{IpNet, Ipv4Net, Ipv6Net};
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    net::ipnet_2::{ArchivedIpNet, ArchivedIpv4Net, ArchivedIpv6Net},
+    traits::NoUndef,
+    Archive, Deserialize, Place, Serialize,
+};
+
+// Ipv4Net
+
+impl Archive for Ipv4Net {
+    type Archived = ArchivedIpv4Net;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedIpv4Net::emplace(self, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Ipv4Net {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Ipv4Net, D> for ArchivedIpv4Net {
+    fn deserialize(&self, _: &mut D) -> Result<Ipv4Net, D::Error> {
+        Ok(self.as_ipv4net())
+    }
+}
+
+impl PartialEq<Ipv4Net> for ArchivedIpv4Net {
+    #[inline]
+    fn eq(&self, other: &Ipv4Net) -> bool {
+        self.as_ipv4net() == *other
+    }
+}
+
+impl PartialEq<ArchivedIpv4Net> for Ipv4Net {
+    #[inline]
+    fn eq(&self, other: &ArchivedIpv4Net) -> bool {
+        other.eq(self)
+    }
+}
+
+// Ipv6Net
+
+impl Archive for Ipv6Net {
+    type Archived = ArchivedIpv6Net;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedIpv6Net::emplace(self, out);
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for Ipv6Net {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<Ipv6Net, D> for ArchivedIpv6Net {
+    fn deserialize(&self, _: &mut D) -> Result<Ipv6Net, D::Error> {
+        Ok(self.as_ipv6net())
+    }
+}
+
+impl PartialEq<Ipv6Net> for ArchivedIpv6Net {
+    #[inline]
+    fn eq(&self, other: &Ipv6Net) -> bool {
+        self.as_ipv6net() == *other
+    }
+}
+
+impl PartialEq<ArchivedIpv6Net> for Ipv6Net {
+    #[inline]
+    fn eq(&self, other: &ArchivedIpv6Net) -> bool {
+        other.eq(self)
+    }
+}
+
+// IpNet
+
+#[allow(dead_code)]
+#[repr(u8)]
+enum ArchivedIpNetTag {
+    V4,
+    V6,
+}
+
+// SAFETY: `ArchivedIpNetTag` is `repr(u8)` and so always consists of a single
+// well-defined byte.
+unsafe impl NoUndef for ArchivedIpNetTag {}
+
+#[repr(C)]
+struct ArchivedIpNetVariantV4(ArchivedIpNetTag, ArchivedIpv4Net);
+
+#[repr(C)]
+struct ArchivedIpNetVariantV6(ArchivedIpNetTag, ArchivedIpv6Net);
+
+impl Archive for IpNet {
+    type Archived = ArchivedIpNet;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        match self {
+            IpNet::V4(net) => {
+                let out =
+                    unsafe { out.cast_unchecked::<ArchivedIpNetVariantV4>() };
+                munge!(let ArchivedIpNetVariantV4(tag, out_net) = out);
+                tag.write(ArchivedIpNetTag::V4);
+                ArchivedIpv4Net::emplace(net, out_net);
+            }
+            IpNet::V6(net) => {
+                let out =
+                    unsafe { out.cast_unchecked::<ArchivedIpNetVariantV6>() };
+                munge!(let ArchivedIpNetVariantV6(tag, out_net) = out);
+                tag.write(ArchivedIpNetTag::V6);
+                ArchivedIpv6Net::emplace(net, out_net);
+            }
+        }
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for IpNet {
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<IpNet, D> for ArchivedIpNet {
+    fn deserialize(&self, _: &mut D) -> Result<IpNet, D::Error> {
+        Ok(self.as_ipnet())
+    }
+}
+
+impl PartialEq<IpNet> for ArchivedIpNet {
+    #[inline]
+    fn eq(&self, other: &IpNet) -> bool {
+        self.as_ipnet() == *other
+    }
+}
+
+impl PartialEq<ArchivedIpNet> for IpNet {
+    #[inline]
+    fn eq(&self, other: &ArchivedIpNet) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use ipnet_2::{IpNet, Ipv4Net, Ipv6Net};
+
+    use crate::api::test::{roundtrip, to_archived};
+
+    #[test]
+    fn roundtrip_ipnet() {
+        let v4 = Ipv4Net::new(Ipv4Addr::new(10, 1, 2, 3), 16).unwrap();
+        let v6 = Ipv6Net::new(Ipv6Addr::new(0xfd00, 0, 0, 0, 0, 0, 0, 1), 8)
+            .unwrap();
+
+        roundtrip(&v4);
+        roundtrip(&v6);
+        roundtrip(&IpNet::V4(v4));
+        roundtrip(&IpNet::V6(v6));
+        roundtrip(&Ipv4Net::new(Ipv4Addr::UNSPECIFIED, 0).unwrap());
+        roundtrip(&Ipv6Net::new(Ipv6Addr::LOCALHOST, 128).unwrap());
+    }
+
+    #[test]
+    fn archived_ipnet_contains() {
+        let nets: [IpNet; 3] = [
+            "10.0.0.0/8".parse().unwrap(),
+            "192.168.1.0/24".parse().unwrap(),
+            "fd00::/8".parse().unwrap(),
+        ];
+        to_archived(&nets, |archived| {
+            let addr = |s: &str| s.parse::<IpAddr>().unwrap();
+
+            assert!(archived[0].contains(&addr("10.255.0.1")));
+            assert!(!archived[0].contains(&addr("11.0.0.1")));
+            assert!(archived[1].contains(&addr("192.168.1.200")));
+            assert!(!archived[1].contains(&addr("192.168.2.1")));
+            assert!(archived[2].contains(&addr("fd12::1")));
+            assert!(!archived[2].contains(&addr("10.0.0.1")));
+
+            assert!(archived[0].contains_net(&"10.4.0.0/16".parse().unwrap()));
+            assert!(
+                !archived[1].contains_net(&"192.168.0.0/16".parse().unwrap())
+            );
+            assert_eq!(archived[1].network(), addr("192.168.1.0"));
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "bytecheck")]
+    fn reject_long_prefix() {
+        use rancor::Failure;
+
+        use crate::{access, net::ipnet_2::ArchivedIpv4Net, to_bytes};
+
+        let bytes = to_bytes::<Failure>(&[10u8, 0, 0, 0, 33]).unwrap();
+        assert!(access::<ArchivedIpv4Net, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes::<Failure>(&[10u8, 0, 0, 0, 32]).unwrap();
+        assert!(access::<ArchivedIpv4Net, Failure>(&bytes).is_ok());
+    }
+}
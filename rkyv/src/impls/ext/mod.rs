@@ -8,14 +8,24 @@
 
 #[cfg(feature = "arrayvec-0_7")]
 mod arrayvec_0_7;
+#[cfg(feature = "bitflags-2")]
+mod bitflags_2;
+#[cfg(feature = "bstr-1")]
+mod bstr_1;
 #[cfg(feature = "bytes-1")]
 mod bytes_1;
+#[cfg(feature = "chrono-0_4")]
+mod chrono_0_4;
+#[cfg(feature = "half-2")]
+mod half_2;
 #[cfg(feature = "hashbrown-0_14")]
 mod hashbrown_0_14;
 #[cfg(feature = "hashbrown-0_15")]
 mod hashbrown_0_15;
 #[cfg(feature = "indexmap-2")]
 mod indexmap_2;
+#[cfg(feature = "ipnet-2")]
+mod ipnet_2;
 #[cfg(feature = "smallvec-1")]
 mod smallvec_1;
 #[cfg(feature = "smol_str-0_2")]
@@ -24,6 +34,8 @@ mod smolstr_0_2;
 mod smolstr_0_3;
 #[cfg(feature = "thin-vec-0_2")]
 mod thin_vec_0_2;
+#[cfg(feature = "time-0_3")]
+mod time_0_3;
 #[cfg(feature = "tinyvec-1")]
 mod tinyvec_1;
 #[cfg(feature = "triomphe-0_1")]
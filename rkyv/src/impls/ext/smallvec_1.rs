@@ -1,9 +1,11 @@
-use rancor::Fallible;
+use rancor::{Fallible, Source};
 use smallvec_1::{Array, SmallVec};
 
 use crate::{
+    inline_vec::{ArchivedInlineVec, InlineVecResolver},
     ser::{Allocator, Writer},
     vec::{ArchivedVec, VecResolver},
+    with::{ArchiveWith, DeserializeWith, InlineCapacity, SerializeWith},
     Archive, Archived, Deserialize, Place, Serialize,
 };
 
@@ -78,15 +80,100 @@ where
     }
 }
 
+impl<T, const N: usize> ArchiveWith<SmallVec<[T; N]>> for InlineCapacity
+where
+    T: Archive,
+    [T; N]: Array<Item = T>,
+{
+    type Archived = ArchivedInlineVec<Archived<T>, N>;
+    type Resolver = InlineVecResolver<T::Resolver, N>;
+
+    fn resolve_with(
+        field: &SmallVec<[T; N]>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedInlineVec::resolve_from_slice(field.as_slice(), resolver, out);
+    }
+}
+
+impl<T, S, const N: usize> SerializeWith<SmallVec<[T; N]>, S> for InlineCapacity
+where
+    T: Serialize<S>,
+    [T; N]: Array<Item = T>,
+    S: Fallible + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &SmallVec<[T; N]>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedInlineVec::serialize_from_slice(field.as_slice(), serializer)
+    }
+}
+
+impl<T, D, const N: usize>
+    DeserializeWith<ArchivedInlineVec<Archived<T>, N>, SmallVec<[T; N]>, D>
+    for InlineCapacity
+where
+    T: Archive,
+    Archived<T>: Deserialize<T, D>,
+    [T; N]: Array<Item = T>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedInlineVec<Archived<T>, N>,
+        deserializer: &mut D,
+    ) -> Result<SmallVec<[T; N]>, D::Error> {
+        let mut result = SmallVec::new();
+        for item in field.as_slice() {
+            result.push(item.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use smallvec_1::{smallvec, SmallVec};
 
-    use crate::api::test::roundtrip_with;
+    use crate::{
+        api::test::roundtrip_with, with::InlineCapacity, Archive, Deserialize,
+        Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, derive(Debug))]
+    struct InlineValues {
+        #[rkyv(with = InlineCapacity)]
+        values: SmallVec<[i32; 4]>,
+    }
 
     #[test]
     fn roundtrip_small_vec() {
         let value: SmallVec<[i32; 4]> = smallvec![10, 20, 40, 80];
         roundtrip_with(&value, |a, b| assert_eq!(**a, **b));
     }
+
+    #[test]
+    fn roundtrip_inline_small_vec() {
+        let value = InlineValues {
+            values: smallvec![10, 20, 40],
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.values.capacity(), 4);
+            assert_eq!(*a.values, *b.values);
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn spilled_small_vec_fails() {
+        use rancor::Failure;
+
+        let value = InlineValues {
+            values: smallvec![10, 20, 40, 80, 160],
+        };
+        assert!(crate::to_bytes::<Failure>(&value).is_err());
+    }
 }
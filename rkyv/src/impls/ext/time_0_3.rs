@@ -0,0 +1,151 @@
+use rancor::Fallible;
+use time_0_3::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time};
+
+use crate::{
+    time::time_0_3::{
+        ArchivedDate, ArchivedDuration, ArchivedOffsetDateTime,
+        ArchivedPrimitiveDateTime, ArchivedTime,
+    },
+    Archive, Deserialize, Place, Serialize,
+};
+
+macro_rules! impl_time {
+    ($ty:ty, $archived:ty, $this:ident => $native:expr $(,)?) => {
+        impl Archive for $ty {
+            type Archived = $archived;
+            type Resolver = ();
+
+            #[inline]
+            fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+                <$archived>::emplace(self, out);
+            }
+        }
+
+        impl<S: Fallible + ?Sized> Serialize<S> for $ty {
+            fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+                Ok(())
+            }
+        }
+
+        impl<D: Fallible + ?Sized> Deserialize<$ty, D> for $archived {
+            fn deserialize(&self, _: &mut D) -> Result<$ty, D::Error> {
+                let $this = self;
+                Ok($native)
+            }
+        }
+
+        impl PartialEq<$ty> for $archived {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                let $this = self;
+                $native == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $ty {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                other.eq(self)
+            }
+        }
+    };
+}
+
+impl_time!(Date, ArchivedDate, this => this.as_date());
+impl_time!(Time, ArchivedTime, this => this.as_time());
+impl_time!(
+    PrimitiveDateTime,
+    ArchivedPrimitiveDateTime,
+    this => this.as_primitive_date_time(),
+);
+impl_time!(
+    OffsetDateTime,
+    ArchivedOffsetDateTime,
+    this => this.as_offset_date_time(),
+);
+impl_time!(Duration, ArchivedDuration, this => this.as_duration());
+
+#[cfg(test)]
+mod tests {
+    use time_0_3::{Date, Duration, Month, PrimitiveDateTime, Time, UtcOffset};
+
+    use crate::{
+        api::test::{roundtrip, to_archived},
+        time::time_0_3::ArchivedDate,
+    };
+
+    #[test]
+    fn roundtrip_time() {
+        let date = Date::from_calendar_date(2024, Month::February, 29).unwrap();
+        let time = Time::from_hms_nano(23, 59, 59, 999_999_999).unwrap();
+        let date_time = PrimitiveDateTime::new(date, time);
+        let offset = UtcOffset::from_hms(-5, -30, 0).unwrap();
+
+        roundtrip(&date);
+        roundtrip(&Date::MIN);
+        roundtrip(&Date::MAX);
+        roundtrip(&time);
+        roundtrip(&Time::MIDNIGHT);
+        roundtrip(&date_time);
+        roundtrip(&date_time.assume_utc());
+        roundtrip(&date_time.assume_offset(offset));
+
+        to_archived(&date_time.assume_offset(offset), |archived| {
+            assert_eq!(archived.offset_seconds(), offset.whole_seconds());
+            assert_eq!(archived.local(), &date_time);
+        });
+    }
+
+    #[test]
+    fn roundtrip_duration() {
+        for duration in [
+            Duration::ZERO,
+            Duration::milliseconds(1_500),
+            Duration::milliseconds(-1_500),
+            Duration::nanoseconds(-1),
+            Duration::MAX,
+            Duration::MIN,
+        ] {
+            roundtrip(&duration);
+        }
+    }
+
+    #[test]
+    fn archived_dates_are_ordered() {
+        let a = Date::from_calendar_date(1969, Month::December, 31).unwrap();
+        let b = Date::from_calendar_date(1970, Month::January, 1).unwrap();
+        to_archived(&(a, b), |archived| {
+            let (a, b): (&ArchivedDate, _) = (&archived.0, &archived.1);
+            assert!(a < b);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "bytecheck")]
+    fn reject_out_of_range() {
+        use rancor::Failure;
+
+        use crate::{
+            access,
+            time::time_0_3::{ArchivedDuration, ArchivedTime},
+            to_bytes,
+        };
+
+        let bytes = to_bytes::<Failure>(&i32::MAX).unwrap();
+        assert!(access::<ArchivedDate, Failure>(&bytes).is_err());
+
+        // Hour 24 is past the end of the day.
+        let bytes = to_bytes::<Failure>(&(86_400u32, 0u32)).unwrap();
+        assert!(access::<ArchivedTime, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes::<Failure>(&(0u32, 1_000_000_000u32)).unwrap();
+        assert!(access::<ArchivedTime, Failure>(&bytes).is_err());
+
+        // Seconds and nanoseconds must have the same sign.
+        let bytes = to_bytes::<Failure>(&(1i64, -1i32)).unwrap();
+        assert!(access::<ArchivedDuration, Failure>(&bytes).is_err());
+
+        let bytes = to_bytes::<Failure>(&(-1i64, -1i32)).unwrap();
+        assert!(access::<ArchivedDuration, Failure>(&bytes).is_ok());
+    }
+}
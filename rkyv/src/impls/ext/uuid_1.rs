@@ -2,7 +2,9 @@ use rancor::Fallible;
 use uuid_1::Uuid;
 
 use crate::{
-    traits::CopyOptimization, Archive, Deserialize, Place, Portable, Serialize,
+    niche::niching::{Niching, Zero},
+    traits::CopyOptimization,
+    Archive, Deserialize, Place, Portable, Serialize,
 };
 
 // SAFETY: `Uuid` has the same ABI has `Bytes`, and so is `Portable` when
@@ -37,10 +39,34 @@ impl<D: Fallible + ?Sized> Deserialize<Uuid, D> for Uuid {
     }
 }
 
+// Niching a `Uuid` into zero uses the nil UUID to represent `None`. Because the
+// nil UUID is a valid value, this is only used when explicitly requested with
+// `NicheInto<Zero>`, and `Some(Uuid::nil())` will be archived as `None`.
+impl Niching<Uuid> for Zero {
+    unsafe fn is_niched(niched: *const Uuid) -> bool {
+        // SAFETY: The caller has guaranteed that `niched` is non-null,
+        // properly-aligned, and safe for reads. All bit patterns are valid for
+        // `Uuid`.
+        unsafe { (*niched).is_nil() }
+    }
+
+    fn resolve_niched(out: Place<Uuid>) {
+        // SAFETY: The nil UUID is fully-initialized.
+        unsafe {
+            out.write_unchecked(Uuid::nil());
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Uuid;
-    use crate::api::test::roundtrip;
+    use crate::{
+        api::test::{roundtrip, roundtrip_with},
+        niche::{niched_option::NichedOption, niching::Zero},
+        with::NicheInto,
+        Archive, Archived, Deserialize, Serialize,
+    };
 
     #[test]
     fn roundtrip_uuid() {
@@ -48,4 +74,27 @@ mod tests {
             &Uuid::parse_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap(),
         )
     }
+
+    #[test]
+    fn niche_uuid() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Record {
+            #[rkyv(with = NicheInto<Zero>)]
+            id: Option<Uuid>,
+        }
+
+        assert_eq!(
+            size_of::<NichedOption<Uuid, Zero>>(),
+            size_of::<Archived<Uuid>>(),
+        );
+
+        let id =
+            Uuid::parse_str("f9168c5e-ceb2-4faa-b6bf-329bf39fa1e4").unwrap();
+        for value in [Some(id), None] {
+            roundtrip_with(&Record { id: value }, |a, b| {
+                assert_eq!(a.id, b.id.as_ref().copied());
+            });
+        }
+    }
 }
@@ -804,4 +804,612 @@ mod alloc_tests {
             }),
         });
     }
+
+    #[test]
+    fn recursive_field_bounds() {
+        use crate::ser::Allocator;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(
+            crate,
+            bytecheck(bounds(__C: crate::validation::ArchiveContext)),
+            compare(PartialEq),
+            derive(Debug)
+        )]
+        pub struct Tree<T> {
+            value: T,
+            #[rkyv(
+                omit_bounds,
+                serialize_bounds(__S: Allocator + Writer),
+                deserialize_bounds(__D::Error: Source)
+            )]
+            children: Vec<Tree<T>>,
+        }
+
+        roundtrip(&Tree {
+            value: 1u32,
+            children: vec![
+                Tree {
+                    value: 2,
+                    children: Vec::new(),
+                },
+                Tree {
+                    value: 3,
+                    children: vec![Tree {
+                        value: 4,
+                        children: Vec::new(),
+                    }],
+                },
+            ],
+        });
+    }
+
+    #[test]
+    fn container_omit_bounds() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(
+            crate,
+            omit_bounds,
+            archive_bounds(T: Archive),
+            serialize_bounds(T: Serialize<__S>),
+            deserialize_bounds(T::Archived: Deserialize<T, __D>),
+            derive(Debug)
+        )]
+        #[cfg_attr(
+            feature = "bytecheck",
+            rkyv(bytecheck(bounds(
+                T::Archived: crate::bytecheck::CheckBytes<__C>,
+            ))),
+        )]
+        pub struct Wrapper<T> {
+            a: T,
+            b: T,
+        }
+
+        impl<T: Archive> PartialEq<Wrapper<T>> for ArchivedWrapper<T>
+        where
+            T::Archived: PartialEq<T>,
+        {
+            fn eq(&self, other: &Wrapper<T>) -> bool {
+                self.a == other.a && self.b == other.b
+            }
+        }
+
+        roundtrip(&Wrapper {
+            a: "a".to_string(),
+            b: "b".to_string(),
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn previous_versions() {
+        use rancor::Panic;
+
+        use crate::api::test::{deserialize, to_bytes};
+
+        mod v1 {
+            use crate::{alloc::string::String, Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[rkyv(crate)]
+            pub struct Example {
+                pub id: u32,
+                pub name: String,
+            }
+        }
+
+        mod v2 {
+            use crate::{
+                alloc::{string::String, vec::Vec},
+                Archive, Serialize,
+            };
+
+            #[derive(Archive, Serialize)]
+            #[rkyv(crate)]
+            pub struct Example {
+                pub id: u32,
+                pub name: String,
+                pub tags: Vec<u32>,
+            }
+        }
+
+        mod v3 {
+            use crate::{
+                alloc::{string::String, vec::Vec},
+                Archive, Deserialize, Serialize,
+            };
+
+            #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+            #[rkyv(crate, compare(PartialEq), derive(Debug))]
+            pub struct Example {
+                pub id: u32,
+                pub name: String,
+                #[rkyv(since = 2)]
+                pub tags: Vec<u32>,
+                #[rkyv(since = 3, default = default_scale)]
+                pub scale: u16,
+            }
+
+            fn default_scale() -> u16 {
+                100
+            }
+        }
+
+        let value = v1::Example {
+            id: 1,
+            name: "first".to_string(),
+        };
+        to_bytes(&value, |bytes| {
+            let archived =
+                crate::access::<v3::ArchivedExampleV1, Panic>(bytes).unwrap();
+            assert_eq!(
+                deserialize::<v3::Example>(archived),
+                v3::Example {
+                    id: 1,
+                    name: "first".to_string(),
+                    tags: Vec::new(),
+                    scale: 100,
+                },
+            );
+        });
+
+        let value = v2::Example {
+            id: 2,
+            name: "second".to_string(),
+            tags: vec![1, 2, 3],
+        };
+        to_bytes(&value, |bytes| {
+            let archived =
+                crate::access::<v3::ArchivedExampleV2, Panic>(bytes).unwrap();
+            assert_eq!(
+                deserialize::<v3::Example>(archived),
+                v3::Example {
+                    id: 2,
+                    name: "second".to_string(),
+                    tags: vec![1, 2, 3],
+                    scale: 100,
+                },
+            );
+        });
+
+        roundtrip(&v3::Example {
+            id: 3,
+            name: "third".to_string(),
+            tags: vec![4, 5],
+            scale: 50,
+        });
+
+        #[derive(Archive)]
+        #[rkyv(crate)]
+        #[allow(dead_code)]
+        struct Skipped {
+            id: u32,
+            #[rkyv(since = 3)]
+            extra: u32,
+        }
+
+        // Version 2 didn't add any fields, but still gets an archived type.
+        assert_eq!(
+            core::mem::size_of::<ArchivedSkippedV1>(),
+            core::mem::size_of::<ArchivedSkippedV2>(),
+        );
+    }
+
+    #[test]
+    fn derive_hash_and_ord() {
+        use core::cmp::Ordering;
+
+        use crate::{
+            alloc::collections::BTreeSet,
+            hash::{hash_value, FxHasher64},
+        };
+
+        #[derive(Archive, Serialize, Debug, PartialEq, PartialOrd)]
+        #[rkyv(
+            crate,
+            derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord),
+            compare(PartialEq, PartialOrd)
+        )]
+        struct Key {
+            name: String,
+            tags: BTreeSet<u32>,
+            pair: (u8, Option<i16>),
+            result: Result<u32, String>,
+        }
+
+        let a = Key {
+            name: "a".to_string(),
+            tags: [1, 2, 3].into_iter().collect(),
+            pair: (1, Some(-1)),
+            result: Ok(10),
+        };
+        let b = Key {
+            name: "a".to_string(),
+            tags: [1, 2, 4].into_iter().collect(),
+            pair: (1, None),
+            result: Err("b".to_string()),
+        };
+        let value = vec![a, b];
+
+        to_archived(&value, |archived| {
+            let (x, y) = (&archived[0], &archived[1]);
+
+            assert_eq!(x.cmp(y), Ordering::Less);
+            assert_eq!(y.cmp(x), Ordering::Greater);
+            assert_ne!(
+                hash_value::<_, FxHasher64>(x),
+                hash_value::<_, FxHasher64>(y),
+            );
+
+            assert_eq!(x.partial_cmp(&value[0]), Some(Ordering::Equal));
+            assert_eq!(x.partial_cmp(&value[1]), Some(Ordering::Less));
+            assert_eq!(y.partial_cmp(&value[0]), Some(Ordering::Greater));
+            assert!(*x < value[1]);
+        });
+    }
+
+    #[test]
+    fn derive_accessors() {
+        mod player {
+            use crate::{alloc::string::String, Archive, Serialize};
+
+            #[derive(Archive, Serialize)]
+            #[rkyv(crate, accessors)]
+            pub struct Player {
+                name: String,
+                score: u32,
+                alive: bool,
+                #[rkyv(skip)]
+                #[allow(dead_code)]
+                cache: Option<u32>,
+            }
+
+            impl Player {
+                pub fn new(name: &str, score: u32) -> Self {
+                    Self {
+                        name: name.into(),
+                        score,
+                        alive: true,
+                        cache: None,
+                    }
+                }
+            }
+        }
+
+        to_archived(&player::Player::new("alice", 1234), |archived| {
+            assert_eq!(archived.name(), "alice");
+            let score: u32 = archived.score();
+            assert_eq!(score, 1234);
+            assert!(archived.alive());
+        });
+    }
+
+    #[test]
+    fn derive_sealed_variants() {
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, sealed_variants)]
+        enum Shape {
+            Point,
+            Circle(u32),
+            Rect { width: u32, height: u32 },
+        }
+
+        to_archived(
+            &Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+            |mut archived| {
+                match ArchivedShape::as_sealed_variants(archived.as_mut()) {
+                    ArchivedShapeSealed::Rect { mut width, height } => {
+                        assert_eq!(*height, 4);
+                        *width = 10.into();
+                    }
+                    _ => panic!("expected a rect"),
+                }
+                match &*archived {
+                    ArchivedShape::Rect { width, height } => {
+                        assert_eq!(*width, 10);
+                        assert_eq!(*height, 4);
+                    }
+                    _ => panic!("expected a rect"),
+                }
+            },
+        );
+
+        to_archived(&Shape::Circle(5), |mut archived| {
+            match ArchivedShape::as_sealed_variants(archived.as_mut()) {
+                ArchivedShapeSealed::Circle(mut radius) => *radius = 6.into(),
+                _ => panic!("expected a circle"),
+            }
+            assert!(matches!(&*archived, ArchivedShape::Circle(r) if *r == 6));
+        });
+
+        to_archived(&Shape::Point, |archived| {
+            assert!(matches!(
+                ArchivedShape::as_sealed_variants(archived),
+                ArchivedShapeSealed::Point,
+            ));
+        });
+    }
+
+    // Multibyte enum tags are not available with the `unaligned` feature.
+    #[cfg(not(feature = "unaligned"))]
+    #[test]
+    fn enum_discriminants_and_tags() {
+        use crate::api::test::to_bytes;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, compare(PartialEq), derive(Debug))]
+        enum Small {
+            A = 3,
+            B,
+            C = 7,
+        }
+
+        #[derive(
+            Archive, Serialize, Deserialize, Debug, PartialEq, PartialOrd,
+        )]
+        #[rkyv(
+            crate,
+            tag = u16,
+            compare(PartialEq, PartialOrd),
+            derive(Debug)
+        )]
+        #[repr(u16)]
+        enum Opcode {
+            Nop,
+            Load = 0x100,
+            Store(u32),
+        }
+
+        roundtrip(&Small::A);
+        roundtrip(&Small::B);
+        roundtrip(&Small::C);
+        to_bytes(&Small::C, |bytes| assert_eq!(bytes, [7]));
+
+        roundtrip(&Opcode::Nop);
+        roundtrip(&Opcode::Load);
+        roundtrip(&Opcode::Store(42));
+        to_bytes(&Opcode::Load, |bytes| {
+            let tag = if cfg!(feature = "big_endian") {
+                0x100u16.to_be_bytes()
+            } else {
+                0x100u16.to_le_bytes()
+            };
+            assert_eq!(bytes[..2], tag);
+        });
+
+        to_archived(&Opcode::Load, |archived| {
+            assert!(Opcode::Nop < *archived);
+            assert!(Opcode::Store(0) > *archived);
+        });
+
+        #[cfg(feature = "bytecheck")]
+        {
+            use rancor::Failure;
+
+            use crate::{access, to_bytes};
+
+            let bytes = to_bytes::<Failure>(&4u8).unwrap();
+            let archived = access::<ArchivedSmall, Failure>(&bytes).unwrap();
+            assert_eq!(*archived, Small::B);
+
+            let bytes = to_bytes::<Failure>(&5u8).unwrap();
+            assert!(access::<ArchivedSmall, Failure>(&bytes).is_err());
+
+            let bytes = to_bytes::<Failure>(&(0x101u16, 0u16, 7u32)).unwrap();
+            let archived = access::<ArchivedOpcode, Failure>(&bytes).unwrap();
+            assert_eq!(*archived, Opcode::Store(7));
+
+            // Tags between declared discriminants are invalid.
+            let bytes = to_bytes::<Failure>(&(2u16, 0u16, 0u32)).unwrap();
+            assert!(access::<ArchivedOpcode, Failure>(&bytes).is_err());
+
+            let bytes = to_bytes::<Failure>(&(0x102u16, 0u16, 0u32)).unwrap();
+            assert!(access::<ArchivedOpcode, Failure>(&bytes).is_err());
+        }
+    }
+
+    #[test]
+    fn derive_deserialize_fields() {
+        use rancor::{Panic, Strategy};
+
+        use crate::{de::Pool, with::AsBox};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, deserialize_fields)]
+        struct Record {
+            id: u32,
+            name: String,
+            #[rkyv(with = AsBox)]
+            tags: Vec<String>,
+        }
+
+        let value = Record {
+            id: 7,
+            name: "a record".to_string(),
+            tags: vec!["a".to_string(), "b".to_string()],
+        };
+        to_archived(&value, |archived| {
+            let mut pool = Pool::new();
+            let deserializer = Strategy::<_, Panic>::wrap(&mut pool);
+
+            assert_eq!(archived.deserialize_id(deserializer).unwrap(), 7);
+            let name: String = archived.deserialize_name(deserializer).unwrap();
+            assert_eq!(name, "a record");
+            let tags: Vec<String> =
+                archived.deserialize_tags(deserializer).unwrap();
+            assert_eq!(tags, ["a", "b"]);
+        });
+    }
+
+    #[test]
+    fn derive_deserialize_borrowed() {
+        use rancor::Panic;
+
+        use crate::{api::high::deserialize_borrowed, de::DeserializeBorrowed};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Record {
+            id: u32,
+            name: String,
+            data: Vec<u8>,
+            notes: Option<String>,
+        }
+
+        #[derive(DeserializeBorrowed, Debug, PartialEq)]
+        #[rkyv(crate, borrow_from = ArchivedRecord)]
+        struct RecordRef<'a> {
+            id: u32,
+            name: &'a str,
+            data: &'a [u8],
+            notes: Option<&'a str>,
+            #[rkyv(skip)]
+            extra: u32,
+        }
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        enum Event {
+            Named { name: String, count: u32 },
+            Data(Vec<u8>, String),
+            Empty,
+        }
+
+        #[derive(DeserializeBorrowed, Debug, PartialEq)]
+        #[rkyv(crate, borrow_from = ArchivedEvent)]
+        enum EventRef<'a> {
+            Named { name: &'a str },
+            Data(&'a [u8]),
+            Empty,
+        }
+
+        let record = Record {
+            id: 3,
+            name: "borrowed".to_string(),
+            data: vec![1, 2, 3],
+            notes: Some("a note".to_string()),
+        };
+        to_archived(&record, |archived| {
+            let borrowed: RecordRef<'_> =
+                deserialize_borrowed::<_, Panic>(&*archived).unwrap();
+            assert_eq!(
+                borrowed,
+                RecordRef {
+                    id: 3,
+                    name: "borrowed",
+                    data: &[1, 2, 3],
+                    notes: Some("a note"),
+                    extra: 0,
+                }
+            );
+            assert_eq!(borrowed.name.as_ptr(), archived.name.as_ptr());
+        });
+
+        let event = Event::Named {
+            name: "event".to_string(),
+            count: 2,
+        };
+        to_archived(&event, |archived| {
+            let borrowed: EventRef<'_> =
+                deserialize_borrowed::<_, Panic>(&*archived).unwrap();
+            assert_eq!(borrowed, EventRef::Named { name: "event" });
+        });
+
+        to_archived(&Event::Data(vec![4, 5], "ignored".to_string()), |a| {
+            let borrowed: EventRef<'_> =
+                deserialize_borrowed::<_, Panic>(&*a).unwrap();
+            assert_eq!(borrowed, EventRef::Data(&[4, 5]));
+        });
+
+        to_archived(&Event::Empty, |archived| {
+            let borrowed: EventRef<'_> =
+                deserialize_borrowed::<_, Panic>(&*archived).unwrap();
+            assert_eq!(borrowed, EventRef::Empty);
+        });
+    }
+
+    #[test]
+    fn derive_deserialize_in_place() {
+        use rancor::Panic;
+
+        use crate::{api::high::deserialize_in_place, de::DeserializeInPlace};
+
+        #[derive(Archive, Serialize, DeserializeInPlace, Debug, PartialEq)]
+        #[rkyv(crate)]
+        struct Record {
+            name: String,
+            values: Vec<u32>,
+            #[rkyv(skip)]
+            cache: u32,
+        }
+
+        #[derive(
+            Archive,
+            Serialize,
+            Deserialize,
+            DeserializeInPlace,
+            Debug,
+            PartialEq,
+        )]
+        #[rkyv(crate)]
+        enum Message {
+            Empty,
+            Text(String),
+            Values { values: Vec<u32> },
+        }
+
+        let mut out = Record {
+            name: String::with_capacity(64),
+            values: Vec::with_capacity(64),
+            cache: 7,
+        };
+        let name = out.name.as_ptr();
+        let values = out.values.as_ptr();
+
+        let value = Record {
+            name: "hello".to_string(),
+            values: vec![1, 2, 3],
+            cache: 0,
+        };
+        to_archived(&value, |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(out.name, "hello");
+        assert_eq!(out.values, [1, 2, 3]);
+        assert_eq!(out.cache, 7);
+        assert_eq!(out.name.as_ptr(), name);
+        assert_eq!(out.values.as_ptr(), values);
+
+        let mut out = Message::Text(String::with_capacity(64));
+        let Message::Text(ref text) = out else {
+            unreachable!()
+        };
+        let text = text.as_ptr();
+
+        to_archived(&Message::Text("hi".to_string()), |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        match out {
+            Message::Text(ref s) => {
+                assert_eq!(s, "hi");
+                assert_eq!(s.as_ptr(), text);
+            }
+            _ => panic!("expected text"),
+        }
+
+        let value = Message::Values { values: vec![4, 5] };
+        to_archived(&value, |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(out, value);
+
+        to_archived(&Message::Empty, |archived| {
+            deserialize_in_place::<_, Panic>(&*archived, &mut out).unwrap();
+        });
+        assert_eq!(out, Message::Empty);
+    }
 }
@@ -8,6 +8,7 @@ use rancor::{Fallible, Source};
 
 use crate::{
     collections::swiss_table::map::{ArchivedHashMap, HashMapResolver},
+    de::DeserializeInPlace,
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -74,6 +75,33 @@ where
     }
 }
 
+impl<K, V, D, S> DeserializeInPlace<HashMap<K, V, S>, D>
+    for ArchivedHashMap<K::Archived, V::Archived>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    D: Fallible + ?Sized,
+    S: BuildHasher,
+{
+    fn deserialize_in_place(
+        &self,
+        out: &mut HashMap<K, V, S>,
+        deserializer: &mut D,
+    ) -> Result<(), D::Error> {
+        out.clear();
+        out.reserve(self.len());
+        for (k, v) in self.iter() {
+            out.insert(
+                k.deserialize(deserializer)?,
+                v.deserialize(deserializer)?,
+            );
+        }
+        Ok(())
+    }
+}
+
 impl<K, V, AK, AV, S> PartialEq<HashMap<K, V, S>> for ArchivedHashMap<AK, AV>
 where
     K: Hash + Eq + Borrow<AK>,
@@ -226,6 +254,40 @@ mod tests {
         });
     }
 
+    #[test]
+    fn get_by_hash_and_index() {
+        let mut hash_map = HashMap::new();
+        hash_map.insert("hello".to_string(), 1);
+        hash_map.insert("foo".to_string(), 2);
+        hash_map.insert("baz".to_string(), 3);
+
+        to_archived(&hash_map, |archived| {
+            type Map = Archived<HashMap<String, i32>>;
+
+            let hash = Map::hash_key("foo");
+            let (key, value) = archived
+                .get_key_value_by_hash(hash, |k| k.as_bytes() == b"foo")
+                .unwrap();
+            assert_eq!(key, "foo");
+            assert_eq!(*value, 2);
+            assert!(archived.get_key_value_by_hash(hash, |_| false).is_none());
+
+            for (key, value) in archived.iter() {
+                let index = archived.get_index_of(key.as_str()).unwrap();
+                assert_eq!(
+                    archived
+                        .get_index_by_hash(Map::hash_key(key.as_str()), |k| {
+                            k == key
+                        }),
+                    Some(index),
+                );
+                assert_eq!(archived.get_index(index), Some((key, value)));
+            }
+            assert!(archived.get_index_of("missing").is_none());
+            assert!(archived.get_index(archived.capacity()).is_none());
+        });
+    }
+
     #[test]
     fn get_seal() {
         let mut hash_map: HashMap<_, _, RandomState> = HashMap::default();
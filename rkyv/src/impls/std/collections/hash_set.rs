@@ -7,7 +7,10 @@ use std::collections::HashSet;
 use rancor::{Fallible, Source};
 
 use crate::{
-    collections::swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    collections::{
+        set_ops::Contains,
+        swiss_table::set::{ArchivedHashSet, HashSetResolver},
+    },
     ser::{Allocator, Writer},
     Archive, Deserialize, Place, Serialize,
 };
@@ -88,11 +91,22 @@ impl<K: Hash + Eq + Borrow<AK>, AK: Hash + Eq, S: BuildHasher>
     }
 }
 
+impl<K, Q, S> Contains<Q> for HashSet<K, S>
+where
+    K: Borrow<Q> + Hash + Eq,
+    Q: Hash + Eq + ?Sized,
+    S: BuildHasher,
+{
+    fn contains_value(&self, value: &Q) -> bool {
+        self.contains(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
 
-    use crate::api::test::{roundtrip, roundtrip_with};
+    use crate::api::test::{roundtrip, roundtrip_with, to_archived};
 
     #[test]
     fn roundtrip_hash_set() {
@@ -122,4 +136,43 @@ mod tests {
         value.insert(());
         roundtrip(&value);
     }
+
+    #[test]
+    fn hash_set_algebra() {
+        fn sorted<'a>(iter: impl Iterator<Item = &'a str>) -> Vec<&'a str> {
+            let mut result = iter.collect::<Vec<_>>();
+            result.sort();
+            result
+        }
+
+        let a = ["a", "b", "c", "d"].map(String::from);
+        let a = a.into_iter().collect::<HashSet<_>>();
+        let b = ["c", "d", "e"].map(String::from);
+        let b = b.into_iter().collect::<HashSet<_>>();
+
+        to_archived(&(a, b.clone()), |archived| {
+            let (a, archived_b) = (&archived.0, &archived.1);
+
+            let i = sorted(a.intersection(archived_b).map(|k| k.as_str()));
+            assert_eq!(i, ["c", "d"]);
+            let d = sorted(a.difference(archived_b).map(|k| k.as_str()));
+            assert_eq!(d, ["a", "b"]);
+            let u = sorted(a.union::<str, _, _>(archived_b.iter()));
+            assert_eq!(u, ["a", "b", "c", "d", "e"]);
+            let u = sorted(a.union::<str, _, _>(&b));
+            assert_eq!(u, ["a", "b", "c", "d", "e"]);
+
+            let i = sorted(a.intersection::<str, _>(&b).map(|k| k.as_str()));
+            assert_eq!(i, ["c", "d"]);
+            let d = sorted(a.difference::<str, _>(&b).map(|k| k.as_str()));
+            assert_eq!(d, ["a", "b"]);
+
+            assert!(!a.is_subset(archived_b));
+            assert!(!a.is_disjoint::<str, _>(&b));
+            let c = ["c"].map(String::from).into_iter().collect::<HashSet<_>>();
+            assert_eq!(archived_b.difference::<str, _>(&c).count(), 2);
+            assert!(a.is_subset(a));
+            assert!(a.difference(a).next().is_none());
+        });
+    }
 }
@@ -0,0 +1,166 @@
+use core::{error::Error, fmt};
+use std::{ffi::OsString, path::PathBuf};
+
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    ffi::{ArchivedOsString, OsStringResolver},
+    path::{ArchivedPathBuf, PathBufResolver},
+    ser::Writer,
+    Archive, Deserialize, Place, Serialize,
+};
+
+#[derive(Debug)]
+struct UnsupportedPlatform {
+    type_name: &'static str,
+}
+
+impl fmt::Display for UnsupportedPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "archived `{}` was encoded for a different platform",
+            self.type_name,
+        )
+    }
+}
+
+impl Error for UnsupportedPlatform {}
+
+// OsString
+
+impl Archive for OsString {
+    type Archived = ArchivedOsString;
+    type Resolver = OsStringResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedOsString::resolve_from_os_str(self, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for OsString
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedOsString::serialize_from_os_str(self, serializer)
+    }
+}
+
+impl<D> Deserialize<OsString, D> for ArchivedOsString
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<OsString, D::Error> {
+        match self.to_os_string() {
+            Some(os_string) => Ok(os_string),
+            None => fail!(UnsupportedPlatform {
+                type_name: "OsString",
+            }),
+        }
+    }
+}
+
+// PathBuf
+
+impl Archive for PathBuf {
+    type Archived = ArchivedPathBuf;
+    type Resolver = PathBufResolver;
+
+    #[inline]
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedPathBuf::resolve_from_path(self, resolver, out);
+    }
+}
+
+impl<S> Serialize<S> for PathBuf
+where
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedPathBuf::serialize_from_path(self, serializer)
+    }
+}
+
+impl<D> Deserialize<PathBuf, D> for ArchivedPathBuf
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<PathBuf, D::Error> {
+        match self.to_path_buf() {
+            Some(path_buf) => Ok(path_buf),
+            None => fail!(UnsupportedPlatform {
+                type_name: "PathBuf",
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ffi::OsString, path::PathBuf};
+
+    use crate::{
+        api::test::{roundtrip, to_archived},
+        ffi::OsStrEncoding,
+    };
+
+    #[test]
+    fn roundtrip_os_string() {
+        roundtrip(&OsString::new());
+        roundtrip(&OsString::from("hello world"));
+        roundtrip(&PathBuf::from("/usr/local/bin"));
+
+        let path = PathBuf::from("dir/file.txt");
+        to_archived(&path, |archived| {
+            assert_eq!(archived.encoding(), OsStrEncoding::Utf8);
+            assert_eq!(archived.to_str(), Some("dir/file.txt"));
+            assert_eq!(archived.as_path(), Some(path.as_path()));
+        });
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn roundtrip_non_utf8_unix() {
+        use std::os::unix::ffi::OsStringExt as _;
+
+        let os_string = OsString::from_vec(vec![b'a', 0xff, b'b']);
+        roundtrip(&os_string);
+        roundtrip(&PathBuf::from(os_string.clone()));
+
+        to_archived(&os_string, |archived| {
+            assert_eq!(archived.encoding(), OsStrEncoding::Unix);
+            assert_eq!(archived.to_str(), None);
+            assert_eq!(archived.as_os_str(), Some(os_string.as_os_str()));
+            assert_eq!(archived.to_string_lossy(), "a\u{fffd}b");
+        });
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn roundtrip_non_utf8_windows() {
+        use std::os::windows::ffi::OsStringExt as _;
+
+        // An unpaired surrogate is not valid UTF-8.
+        let os_string = OsString::from_wide(&[0x61, 0xd800, 0x62]);
+        roundtrip(&os_string);
+        roundtrip(&PathBuf::from(os_string.clone()));
+
+        to_archived(&os_string, |archived| {
+            assert_eq!(archived.encoding(), OsStrEncoding::Wtf8);
+            assert_eq!(archived.as_bytes(), b"a\xed\xa0\x80b");
+            assert_eq!(archived.to_os_string(), Some(os_string.clone()));
+        });
+    }
+}
@@ -1,3 +1,5 @@
 mod collections;
+mod ffi;
 mod net;
+mod time;
 mod with;
@@ -0,0 +1,130 @@
+use core::{error::Error, fmt};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rancor::{fail, Fallible, Source};
+
+use crate::{time::ArchivedSystemTime, Archive, Deserialize, Place, Serialize};
+
+/// Returns the number of seconds since the Unix epoch (rounded down) and the
+/// subsecond nanoseconds of the given time, or `None` if the number of seconds
+/// does not fit in an `i64`.
+fn to_unix_time(time: &SystemTime) -> Option<(i64, u32)> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => {
+            Some((i64::try_from(after.as_secs()).ok()?, after.subsec_nanos()))
+        }
+        Err(e) => {
+            let before = e.duration();
+            let secs = 0i64.checked_sub_unsigned(before.as_secs())?;
+            match before.subsec_nanos() {
+                0 => Some((secs, 0)),
+                nanos => Some((secs.checked_sub(1)?, 1_000_000_000 - nanos)),
+            }
+        }
+    }
+}
+
+impl Archive for SystemTime {
+    type Archived = ArchivedSystemTime;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, _: Self::Resolver, out: Place<Self::Archived>) {
+        // We already checked that the time fits during serialize
+        let (secs, nanos) = to_unix_time(self).unwrap();
+        ArchivedSystemTime::emplace(secs, nanos, out);
+    }
+}
+
+impl<S> Serialize<S> for SystemTime
+where
+    S: Fallible + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(&self, _: &mut S) -> Result<Self::Resolver, S::Error> {
+        #[derive(Debug)]
+        struct SystemTimeOverflow;
+
+        impl fmt::Display for SystemTimeOverflow {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "`SystemTime` is too far from the Unix epoch")
+            }
+        }
+
+        impl Error for SystemTimeOverflow {}
+
+        if to_unix_time(self).is_none() {
+            fail!(SystemTimeOverflow);
+        }
+        Ok(())
+    }
+}
+
+impl<D> Deserialize<SystemTime, D> for ArchivedSystemTime
+where
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, _: &mut D) -> Result<SystemTime, D::Error> {
+        #[derive(Debug)]
+        struct SystemTimeOutOfRange;
+
+        impl fmt::Display for SystemTimeOutOfRange {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "archived `SystemTime` is out of range for this platform",
+                )
+            }
+        }
+
+        impl Error for SystemTimeOutOfRange {}
+
+        match self.to_system_time() {
+            Some(time) => Ok(time),
+            None => fail!(SystemTimeOutOfRange),
+        }
+    }
+}
+
+impl PartialEq<SystemTime> for ArchivedSystemTime {
+    #[inline]
+    fn eq(&self, other: &SystemTime) -> bool {
+        to_unix_time(other) == Some((self.unix_secs(), self.subsec_nanos()))
+    }
+}
+
+impl PartialEq<ArchivedSystemTime> for SystemTime {
+    #[inline]
+    fn eq(&self, other: &ArchivedSystemTime) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use crate::api::test::{roundtrip, to_archived};
+
+    #[test]
+    fn roundtrip_system_time() {
+        roundtrip(&UNIX_EPOCH);
+        roundtrip(&SystemTime::now());
+        roundtrip(&(UNIX_EPOCH + Duration::new(1_700_000_000, 123)));
+        roundtrip(&(UNIX_EPOCH - Duration::new(1, 500_000_000)));
+        roundtrip(&(UNIX_EPOCH - Duration::new(86_400, 0)));
+    }
+
+    #[test]
+    fn system_time_before_epoch() {
+        let before = UNIX_EPOCH - Duration::new(1, 500_000_000);
+        let after = UNIX_EPOCH + Duration::new(0, 1);
+        to_archived(&(before, after), |archived| {
+            assert_eq!(archived.0.unix_secs(), -2);
+            assert_eq!(archived.0.subsec_nanos(), 500_000_000);
+            assert!(archived.0 < archived.1);
+        });
+    }
+}
@@ -1,4 +1,8 @@
-use core::{error::Error, fmt, hash::BuildHasher};
+use core::{
+    error::Error,
+    fmt,
+    hash::{BuildHasher, Hasher},
+};
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
@@ -15,7 +19,9 @@ use rancor::{Fallible, OptionExt, ResultExt, Source};
 
 use crate::{
     collections::{
-        swiss_table::{ArchivedHashMap, HashMapResolver},
+        swiss_table::{
+            ArchivedHashMap, ArchivedHashSet, HashMapResolver, HashSetResolver,
+        },
         util::{Entry, EntryAdapter},
     },
     ffi::{ArchivedCString, CStringResolver},
@@ -24,10 +30,11 @@ use crate::{
     ser::{Allocator, Writer},
     string::{ArchivedString, StringResolver},
     time::ArchivedDuration,
+    util::SerVec,
     vec::{ArchivedVec, VecResolver},
     with::{
         ArchiveWith, AsOwned, AsString, AsUnixTime, AsVec, DeserializeWith,
-        Lock, MapKV, SerializeWith,
+        HashWith, LoadFactor, Lock, MapKV, SerializeWith, Sorted,
     },
     Archive, Deserialize, Place, Serialize, SerializeUnsized,
 };
@@ -463,6 +470,400 @@ where
     }
 }
 
+// Sorted
+
+impl<K, V: Archive, H> ArchiveWith<HashMap<K, V, H>> for Sorted
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, V, H, S> SerializeWith<HashMap<K, V, H>, S> for Sorted
+where
+    K: Serialize<S> + Hash + Ord,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        type Map<K, V> = ArchivedHashMap<K, V>;
+
+        SerVec::with_capacity(
+            serializer,
+            field.len(),
+            |entries, serializer| {
+                for entry in field.iter() {
+                    entries.push(entry);
+                }
+                entries.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+
+                Map::serialize_from_iter::<_, _, _, K, V, _>(
+                    entries.iter().copied(),
+                    (7, 8),
+                    serializer,
+                )
+            },
+        )?
+    }
+}
+
+impl<K, V, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived>,
+        HashMap<K, V, H>,
+        D,
+    > for Sorted
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, H>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<T, H> ArchiveWith<HashSet<T, H>> for Sorted
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashSet<T::Archived>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<T, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::<T::Archived>::resolve_from_len(
+            field.len(),
+            (7, 8),
+            resolver,
+            out,
+        );
+    }
+}
+
+impl<T, H, S> SerializeWith<HashSet<T, H>, S> for Sorted
+where
+    T: Serialize<S> + Hash + Ord,
+    T::Archived: Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<T, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        SerVec::with_capacity(serializer, field.len(), |keys, serializer| {
+            for key in field.iter() {
+                keys.push(key);
+            }
+            keys.sort_unstable();
+
+            ArchivedHashSet::<T::Archived>::serialize_from_iter::<_, T, _>(
+                keys.iter().copied(),
+                (7, 8),
+                serializer,
+            )
+        })?
+    }
+}
+
+impl<T, H, D> DeserializeWith<ArchivedHashSet<T::Archived>, HashSet<T, H>, D>
+    for Sorted
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, D> + Hash + Eq,
+    H: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<T::Archived>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<T, H>, D::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+// HashWith
+
+impl<K, V, B, H> ArchiveWith<HashMap<K, V, B>> for HashWith<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+    H: Hasher + Default,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived, H>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, B>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<K, V, B, H, S> SerializeWith<HashMap<K, V, B>, S> for HashWith<H>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    H: Hasher + Default,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, B>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashMap::<_, _, H>::serialize_from_iter::<_, _, _, K, V, _>(
+            field.iter(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, B, H, D>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived, H>,
+        HashMap<K, V, B>,
+        D,
+    > for HashWith<H>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, D>,
+    V: Archive,
+    V::Archived: Deserialize<V, D>,
+    B: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashMap<K, V, B>, D::Error> {
+        let mut result =
+            HashMap::with_capacity_and_hasher(field.len(), B::default());
+        for (k, v) in field.iter() {
+            result.insert(
+                k.deserialize(deserializer)?,
+                v.deserialize(deserializer)?,
+            );
+        }
+        Ok(result)
+    }
+}
+
+impl<T, B, H> ArchiveWith<HashSet<T, B>> for HashWith<H>
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Hash + Eq,
+    H: Hasher + Default,
+{
+    type Archived = ArchivedHashSet<T::Archived, H>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<T, B>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::resolve_from_len(field.len(), (7, 8), resolver, out);
+    }
+}
+
+impl<T, B, H, S> SerializeWith<HashSet<T, B>, S> for HashWith<H>
+where
+    T: Serialize<S> + Hash + Eq,
+    T::Archived: Hash + Eq,
+    H: Hasher + Default,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<T, B>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashSet::<_, H>::serialize_from_iter::<_, T, _>(
+            field.iter(),
+            (7, 8),
+            serializer,
+        )
+    }
+}
+
+impl<T, B, H, D>
+    DeserializeWith<ArchivedHashSet<T::Archived, H>, HashSet<T, B>, D>
+    for HashWith<H>
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, D>,
+    B: BuildHasher + Default,
+    D: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<T::Archived, H>,
+        deserializer: &mut D,
+    ) -> Result<HashSet<T, B>, D::Error> {
+        let mut result =
+            HashSet::with_capacity_and_hasher(field.len(), B::default());
+        for k in field.iter() {
+            result.insert(k.deserialize(deserializer)?);
+        }
+        Ok(result)
+    }
+}
+
+// LoadFactor
+
+impl<K, V, H, const N: usize, const D: usize> ArchiveWith<HashMap<K, V, H>>
+    for LoadFactor<N, D>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Archive,
+{
+    type Archived = ArchivedHashMap<K::Archived, V::Archived>;
+    type Resolver = HashMapResolver;
+
+    fn resolve_with(
+        field: &HashMap<K, V, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashMap::resolve_from_len(field.len(), (N, D), resolver, out);
+    }
+}
+
+impl<K, V, H, S, const N: usize, const D: usize>
+    SerializeWith<HashMap<K, V, H>, S> for LoadFactor<N, D>
+where
+    K: Serialize<S> + Hash + Eq,
+    K::Archived: Hash + Eq,
+    V: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashMap<K, V, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashMap::<_, _>::serialize_from_iter::<_, _, _, K, V, _>(
+            field.iter(),
+            (N, D),
+            serializer,
+        )
+    }
+}
+
+impl<K, V, H, De, const N: usize, const D: usize>
+    DeserializeWith<
+        ArchivedHashMap<K::Archived, V::Archived>,
+        HashMap<K, V, H>,
+        De,
+    > for LoadFactor<N, D>
+where
+    K: Archive + Hash + Eq,
+    K::Archived: Deserialize<K, De> + Hash + Eq,
+    V: Archive,
+    V::Archived: Deserialize<V, De>,
+    H: BuildHasher + Default,
+    De: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashMap<K::Archived, V::Archived>,
+        deserializer: &mut De,
+    ) -> Result<HashMap<K, V, H>, De::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
+impl<T, H, const N: usize, const D: usize> ArchiveWith<HashSet<T, H>>
+    for LoadFactor<N, D>
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Hash + Eq,
+{
+    type Archived = ArchivedHashSet<T::Archived>;
+    type Resolver = HashSetResolver;
+
+    fn resolve_with(
+        field: &HashSet<T, H>,
+        resolver: Self::Resolver,
+        out: Place<Self::Archived>,
+    ) {
+        ArchivedHashSet::<T::Archived>::resolve_from_len(
+            field.len(),
+            (N, D),
+            resolver,
+            out,
+        );
+    }
+}
+
+impl<T, H, S, const N: usize, const D: usize> SerializeWith<HashSet<T, H>, S>
+    for LoadFactor<N, D>
+where
+    T: Serialize<S> + Hash + Eq,
+    T::Archived: Hash + Eq,
+    S: Fallible + Allocator + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize_with(
+        field: &HashSet<T, H>,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        ArchivedHashSet::<T::Archived>::serialize_from_iter::<_, T, _>(
+            field.iter(),
+            (N, D),
+            serializer,
+        )
+    }
+}
+
+impl<T, H, De, const N: usize, const D: usize>
+    DeserializeWith<ArchivedHashSet<T::Archived>, HashSet<T, H>, De>
+    for LoadFactor<N, D>
+where
+    T: Archive + Hash + Eq,
+    T::Archived: Deserialize<T, De> + Hash + Eq,
+    H: BuildHasher + Default,
+    De: Fallible + ?Sized,
+{
+    fn deserialize_with(
+        field: &ArchivedHashSet<T::Archived>,
+        deserializer: &mut De,
+    ) -> Result<HashSet<T, H>, De::Error> {
+        field.deserialize(deserializer)
+    }
+}
+
 // UnixTimestamp
 
 impl ArchiveWith<SystemTime> for AsUnixTime {
@@ -560,9 +961,12 @@ mod tests {
     };
 
     use crate::{
-        alloc::collections::HashMap,
+        alloc::collections::{HashMap, HashSet},
         api::test::{roundtrip_with, to_archived},
-        with::{AsString, InlineAsBox, Lock, MapKV},
+        to_bytes,
+        with::{
+            AsString, HashWith, InlineAsBox, LoadFactor, Lock, MapKV, Sorted,
+        },
         Archive, Deserialize, Serialize,
     };
 
@@ -613,6 +1017,107 @@ mod tests {
         });
     }
 
+    #[test]
+    fn with_sorted_is_deterministic() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = Sorted)]
+            map: HashMap<String, u32>,
+            #[rkyv(with = Sorted)]
+            set: HashSet<u32>,
+        }
+
+        let forward = Test {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).collect(),
+        };
+        let reverse = Test {
+            map: (0..100).rev().map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).rev().collect(),
+        };
+
+        let forward_bytes = to_bytes::<rancor::Error>(&forward).unwrap();
+        let reverse_bytes = to_bytes::<rancor::Error>(&reverse).unwrap();
+        assert_eq!(forward_bytes, reverse_bytes);
+
+        roundtrip_with(&forward, |a, b| {
+            assert_eq!(b.map.len(), a.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(&b.map[key.as_str()], value);
+            }
+            assert_eq!(b.set.len(), a.set.len());
+        });
+    }
+
+    #[test]
+    fn with_hasher() {
+        use std::hash::DefaultHasher;
+
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = HashWith<DefaultHasher>)]
+            map: HashMap<String, u32>,
+            #[rkyv(with = HashWith<DefaultHasher>)]
+            set: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).collect(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert_eq!(b.map.len(), a.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(&b.map[key.as_str()], value);
+            }
+            assert_eq!(b.set.len(), a.set.len());
+            for key in a.set.iter() {
+                assert!(b.set.contains(key));
+            }
+        });
+    }
+
+    #[test]
+    fn with_load_factor() {
+        #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+        #[rkyv(crate, derive(Debug))]
+        struct Test {
+            #[rkyv(with = LoadFactor<1, 4>)]
+            map: HashMap<String, u32>,
+            #[rkyv(with = LoadFactor<1, 1>)]
+            set: HashSet<u32>,
+        }
+
+        let value = Test {
+            map: (0..100).map(|i| (i.to_string(), i)).collect(),
+            set: (0..100).collect(),
+        };
+        roundtrip_with(&value, |a, b| {
+            assert!(b.map.capacity() >= 4 * a.map.len());
+            for (key, value) in a.map.iter() {
+                assert_eq!(&b.map[key.as_str()], value);
+            }
+            assert_eq!(b.set.len(), a.set.len());
+            for key in a.set.iter() {
+                assert!(b.set.contains(key));
+            }
+        });
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Invalid {
+            #[rkyv(with = LoadFactor<2, 1>)]
+            set: HashSet<u32>,
+        }
+
+        let value = Invalid {
+            set: (0..100).collect(),
+        };
+        assert!(to_bytes::<rancor::Error>(&value).is_err());
+    }
+
     #[test]
     fn with_btree_map_mapkv() {
         #[derive(Archive, Serialize, Deserialize)]
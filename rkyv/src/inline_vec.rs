@@ -0,0 +1,245 @@
+//! An archived vector with inline fixed-capacity storage.
+//!
+//! [`ArchivedVec`](crate::vec::ArchivedVec) stores its elements out of line
+//! behind a relative pointer. [`ArchivedInlineVec`] instead stores up to `N`
+//! elements directly inside of the archived value next to its length, so it
+//! has a fixed size and can be read without following a pointer. This mirrors
+//! fixed-capacity containers like `ArrayVec` and the inline storage of
+//! `SmallVec`.
+//!
+//! This type is used with the [`InlineCapacity`](crate::with::InlineCapacity)
+//! wrapper.
+
+use core::{borrow::Borrow, error::Error, fmt, mem::MaybeUninit, ops::Deref};
+
+use munge::munge;
+use rancor::{fail, Fallible, Source};
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    Archive, Place, Portable, Serialize,
+};
+
+/// An archived vector which stores up to `N` elements inline.
+///
+/// The elements past the length of the archived vector are zeroed and are
+/// never read.
+#[derive(Portable)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedInlineVec<T, const N: usize> {
+    len: ArchivedUsize,
+    items: [MaybeUninit<T>; N],
+}
+
+impl<T, const N: usize> ArchivedInlineVec<T, N> {
+    /// Returns a pointer to the first element of the archived vec.
+    pub fn as_ptr(&self) -> *const T {
+        self.items.as_ptr().cast()
+    }
+
+    /// Returns the number of elements in the archived vec.
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived vec is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the archived vec can hold.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Gets the elements of the archived vec as a sealed mutable slice.
+    pub fn as_slice_seal(this: Seal<'_, Self>) -> Seal<'_, [T]> {
+        let len = this.len();
+        munge!(let Self { items, .. } = this);
+        let slice = unsafe {
+            core::slice::from_raw_parts_mut(
+                items.unseal_unchecked().as_mut_ptr().cast::<T>(),
+                len,
+            )
+        };
+        Seal::new(slice)
+    }
+
+    /// Resolves an archived inline vec from a given slice.
+    pub fn resolve_from_slice<U: Archive<Archived = T>>(
+        slice: &[U],
+        resolver: InlineVecResolver<U::Resolver, N>,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedInlineVec { len, items } = out);
+        len.write(ArchivedUsize::from_native(slice.len() as FixedUsize));
+        let resolvers = resolver.resolvers.into_iter().flatten();
+        for (i, (value, resolver)) in slice.iter().zip(resolvers).enumerate() {
+            // SAFETY: The resolver only holds resolvers for slices with at
+            // most `N` elements, so `i` is in-bounds for `items`.
+            let out_i = unsafe { items.index(i).cast_unchecked::<T>() };
+            value.resolve(resolver, out_i);
+        }
+    }
+
+    /// Serializes the elements of an archived inline vec from a given slice.
+    ///
+    /// Fails if the slice has more than `N` elements.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        serializer: &mut S,
+    ) -> Result<InlineVecResolver<U::Resolver, N>, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + ?Sized,
+        S::Error: Source,
+    {
+        if slice.len() > N {
+            fail!(InlineCapacityExceeded {
+                len: slice.len(),
+                cap: N,
+            });
+        }
+
+        let mut resolvers = core::array::from_fn(|_| None);
+        for (value, resolver) in slice.iter().zip(resolvers.iter_mut()) {
+            *resolver = Some(value.serialize(serializer)?);
+        }
+        Ok(InlineVecResolver { resolvers })
+    }
+}
+
+impl<T, const N: usize> AsRef<[T]> for ArchivedInlineVec<T, N> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T, const N: usize> Borrow<[T]> for ArchivedInlineVec<T, N> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ArchivedInlineVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T, const N: usize> Deref for ArchivedInlineVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U, const N: usize> PartialEq<[U]>
+    for ArchivedInlineVec<T, N>
+{
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for [`ArchivedInlineVec`].
+pub struct InlineVecResolver<R, const N: usize> {
+    resolvers: [Option<R>; N],
+}
+
+#[derive(Debug)]
+struct InlineCapacityExceeded {
+    len: usize,
+    cap: usize,
+}
+
+impl fmt::Display for InlineCapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tried to archive {} elements inline, but the archived inline vec \
+             only has capacity for {} elements",
+            self.len, self.cap,
+        )
+    }
+}
+
+impl Error for InlineCapacityExceeded {}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt, ptr::addr_of};
+
+    use bytecheck::{
+        rancor::{fail, Fallible, Source},
+        CheckBytes,
+    };
+
+    use crate::{
+        inline_vec::ArchivedInlineVec,
+        primitive::ArchivedUsize,
+        validation::{check_slice, ArchiveContext},
+        Portable,
+    };
+
+    #[derive(Debug)]
+    struct LengthExceedsCapacity {
+        len: usize,
+        cap: usize,
+    }
+
+    impl fmt::Display for LengthExceedsCapacity {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived inline vec has length {} but only has capacity for \
+                 {} elements",
+                self.len, self.cap,
+            )
+        }
+    }
+
+    impl Error for LengthExceedsCapacity {}
+
+    // SAFETY: `check_bytes` only returns `Ok` if the length is at most `N` and
+    // every element within the length is valid.
+    unsafe impl<T, C, const N: usize> CheckBytes<C> for ArchivedInlineVec<T, N>
+    where
+        T: Portable + CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        unsafe fn check_bytes(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<(), C::Error> {
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for an archived inline vec, so its length
+            // is also aligned and points to enough bytes.
+            unsafe {
+                ArchivedUsize::check_bytes(addr_of!((*value).len), context)?;
+            }
+            // SAFETY: The length was just checked.
+            let len = unsafe { (*value).len() };
+            if len > N {
+                fail!(LengthExceedsCapacity { len, cap: N });
+            }
+            context.claim_elements(len)?;
+
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for an archived inline vec, so its first
+            // `len` elements are also aligned and point to enough bytes.
+            let items = unsafe { addr_of!((*value).items) };
+            let ptr = core::ptr::slice_from_raw_parts(items.cast::<T>(), len);
+            unsafe { check_slice(ptr, context) }
+        }
+    }
+}
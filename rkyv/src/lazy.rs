@@ -0,0 +1,384 @@
+//! Values which are only deserialized when they are first accessed.
+
+use core::{cell::OnceCell, fmt, marker::PhantomData};
+
+use munge::munge;
+use rancor::{Fallible, Source};
+
+use crate::{
+    api::{
+        access_unchecked,
+        high::{
+            from_bytes_unchecked, to_bytes, HighDeserializer, HighSerializer,
+        },
+    },
+    primitive::FixedUsize,
+    ser::{allocator::ArenaHandle, Writer, WriterExt as _},
+    util::AlignedVec,
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+/// A value which is only deserialized when it is first accessed.
+///
+/// When a `Lazy` is deserialized, it copies the archived bytes of its value
+/// instead of deserializing it. The value is only deserialized from those bytes
+/// the first time it is accessed with [`get`](Self::get),
+/// [`get_mut`](Self::get_mut), or [`into_inner`](Self::into_inner). This makes
+/// deserializing large values which are rarely used much cheaper. An unloaded
+/// `Lazy` can also be serialized again without deserializing its value.
+///
+/// A `Lazy` is archived as an [`ArchivedLazy`], which holds its value as a
+/// separate archive nested inside of the outer one.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access, deserialize, lazy::Lazy, rancor::Error, to_bytes, Archive,
+///     Deserialize, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize, Deserialize)]
+/// struct Message {
+///     id: u32,
+///     attachment: Lazy<Vec<u8>>,
+/// }
+///
+/// let message = Message {
+///     id: 42,
+///     attachment: Lazy::new(vec![0; 1024]),
+/// };
+/// let bytes = to_bytes::<Error>(&message).unwrap();
+/// let archived = access::<ArchivedMessage, Error>(&bytes).unwrap();
+///
+/// let mut message = deserialize::<Message, Error>(archived).unwrap();
+/// assert_eq!(message.id, 42);
+/// assert!(!message.attachment.is_loaded());
+///
+/// let attachment = message.attachment.get::<Error>().unwrap();
+/// assert_eq!(attachment.len(), 1024);
+/// assert!(message.attachment.is_loaded());
+/// ```
+pub struct Lazy<T> {
+    value: OnceCell<T>,
+    // Invariant: if `value` is empty, then `bytes` contains an archive with a
+    // valid `T::Archived` at its root. If both are present, then they hold
+    // the same value.
+    bytes: Option<AlignedVec>,
+}
+
+impl<T> Lazy<T> {
+    /// Returns a new `Lazy` which has already been loaded with the given value.
+    pub fn new(value: T) -> Self {
+        Self {
+            value: OnceCell::from(value),
+            bytes: None,
+        }
+    }
+
+    /// Returns whether the value has been deserialized.
+    pub fn is_loaded(&self) -> bool {
+        self.value.get().is_some()
+    }
+
+    /// Returns the archived value, if the value was deserialized from an
+    /// archive.
+    pub fn archived(&self) -> Option<&T::Archived>
+    where
+        T: Archive,
+    {
+        // SAFETY: `bytes` always contains a valid archive of `T`.
+        self.bytes
+            .as_ref()
+            .map(|bytes| unsafe { access_unchecked::<T::Archived>(bytes) })
+    }
+
+    /// Returns a reference to the value, deserializing it if it has not been
+    /// loaded yet.
+    pub fn get<E>(&self) -> Result<&T, E>
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, HighDeserializer<E>>,
+    {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = self.load::<E>()?;
+        Ok(self.value.get_or_init(|| value))
+    }
+
+    /// Returns a mutable reference to the value, deserializing it if it has
+    /// not been loaded yet.
+    ///
+    /// The archived bytes of the value are discarded, since the value may be
+    /// modified.
+    pub fn get_mut<E>(&mut self) -> Result<&mut T, E>
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, HighDeserializer<E>>,
+    {
+        if self.value.get().is_none() {
+            let value = self.load::<E>()?;
+            self.value = OnceCell::from(value);
+        }
+        self.bytes = None;
+        Ok(self.value.get_mut().unwrap())
+    }
+
+    /// Returns the value, deserializing it if it has not been loaded yet.
+    pub fn into_inner<E>(mut self) -> Result<T, E>
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, HighDeserializer<E>>,
+    {
+        match self.value.take() {
+            Some(value) => Ok(value),
+            None => self.load::<E>(),
+        }
+    }
+
+    fn load<E>(&self) -> Result<T, E>
+    where
+        T: Archive,
+        T::Archived: Deserialize<T, HighDeserializer<E>>,
+    {
+        // The value is only missing if the bytes are present.
+        let bytes = self.bytes.as_ref().unwrap();
+        // SAFETY: `bytes` always contains a valid archive of `T`.
+        unsafe { from_bytes_unchecked::<T, E>(bytes) }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => f.debug_tuple("Lazy").field(&"<unloaded>").finish(),
+        }
+    }
+}
+
+impl<T> From<T> for Lazy<T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+/// An archived [`Lazy`].
+///
+/// The archived value is stored as a separate archive nested inside of the
+/// outer archive. It can still be accessed without deserializing it.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedLazy<T> {
+    bytes: ArchivedVec<u8>,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Portable> ArchivedLazy<T> {
+    /// Returns a reference to the archived value.
+    pub fn get(&self) -> &T {
+        // SAFETY: The nested archive always has a valid `T` at its root.
+        unsafe { access_unchecked::<T>(self.bytes.as_slice()) }
+    }
+
+    /// Returns the bytes of the nested archive.
+    pub fn as_bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+}
+
+impl<T: Portable + fmt::Debug> fmt::Debug for ArchivedLazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// The resolver for [`ArchivedLazy`].
+pub struct LazyResolver {
+    pos: FixedUsize,
+    len: FixedUsize,
+}
+
+impl<T: Archive> Archive for Lazy<T> {
+    type Archived = ArchivedLazy<T::Archived>;
+    type Resolver = LazyResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedLazy { bytes, _phantom: _ } = out);
+        ArchivedVec::resolve_from_len(
+            resolver.len as usize,
+            VecResolver::from_pos(resolver.pos as usize),
+            bytes,
+        );
+    }
+}
+
+type NestedSerializer<'a, E> = HighSerializer<AlignedVec, ArenaHandle<'a>, E>;
+
+impl<T, S> Serialize<S> for Lazy<T>
+where
+    T: Archive + for<'a> Serialize<NestedSerializer<'a, S::Error>>,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        let serialized;
+        let bytes = match (&self.bytes, self.value.get()) {
+            (Some(bytes), _) => bytes,
+            (None, Some(value)) => {
+                serialized = to_bytes::<S::Error>(value)?;
+                &serialized
+            }
+            (None, None) => unreachable!(),
+        };
+
+        let pos = serializer.align(AlignedVec::<16>::ALIGNMENT)?;
+        serializer.write(bytes)?;
+        Ok(LazyResolver {
+            pos: pos as FixedUsize,
+            len: bytes.len() as FixedUsize,
+        })
+    }
+}
+
+impl<T, D> Deserialize<Lazy<T>, D> for ArchivedLazy<T::Archived>
+where
+    T: Archive,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, _: &mut D) -> Result<Lazy<T>, D::Error> {
+        let mut bytes = AlignedVec::with_capacity(self.bytes.len());
+        bytes.extend_from_slice(self.bytes.as_slice());
+        Ok(Lazy {
+            value: OnceCell::new(),
+            bytes: Some(bytes),
+        })
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::fail;
+
+    use crate::{
+        api::high::{access, HighValidator},
+        lazy::ArchivedLazy,
+        util::AlignedVec,
+        validation::ArchiveContext,
+        Portable,
+    };
+
+    #[derive(Debug)]
+    struct UnalignedLazyError;
+
+    impl fmt::Display for UnalignedLazyError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "the nested archive of a lazy value is not aligned")
+        }
+    }
+
+    impl Error for UnalignedLazyError {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedLazy<T>
+    where
+        T: Portable + for<'a> CheckBytes<HighValidator<'a, C::Error>>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let bytes = self.bytes.as_slice();
+            if (bytes.as_ptr() as usize) % AlignedVec::<16>::ALIGNMENT != 0 {
+                fail!(UnalignedLazyError);
+            }
+            access::<T, C::Error>(bytes)?;
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use super::Lazy;
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        api::test::{deserialize, to_archived},
+        Archive, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug)]
+    #[rkyv(crate)]
+    struct Message {
+        id: u32,
+        attachment: Lazy<Vec<String>>,
+    }
+
+    #[test]
+    fn loads_on_access() {
+        let value = Message {
+            id: 1,
+            attachment: Lazy::new(vec!["a".into(), "b".into()]),
+        };
+
+        to_archived(&value, |archived| {
+            assert_eq!(archived.id, 1);
+            assert_eq!(archived.attachment.get().len(), 2);
+
+            let mut message = deserialize::<Message>(&*archived);
+            assert!(!message.attachment.is_loaded());
+            assert_eq!(message.attachment.archived().unwrap()[1].as_str(), "b");
+
+            let attachment = message.attachment.get::<Panic>().unwrap();
+            assert_eq!(attachment, &["a", "b"]);
+            assert!(message.attachment.is_loaded());
+
+            message
+                .attachment
+                .get_mut::<Panic>()
+                .unwrap()
+                .push("c".into());
+            assert!(message.attachment.archived().is_none());
+            assert_eq!(
+                message.attachment.into_inner::<Panic>().unwrap(),
+                ["a", "b", "c"],
+            );
+        });
+    }
+
+    #[test]
+    fn reserializes_unloaded() {
+        let value = Message {
+            id: 2,
+            attachment: Lazy::new(vec!["hello".into()]),
+        };
+
+        to_archived(&value, |archived| {
+            let message = deserialize::<Message>(&*archived);
+            assert!(!message.attachment.is_loaded());
+
+            to_archived(&message, |archived| {
+                assert_eq!(archived.id, 2);
+                assert_eq!(archived.attachment.get()[0].as_str(), "hello");
+            });
+        });
+    }
+}
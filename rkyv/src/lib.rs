@@ -117,7 +117,9 @@
 //!
 //! - `pointer_width_16`: Serializes `isize` and `usize` as 16-bit integers.
 //!   This is intended to be used only for small data sizes and may not handle
-//!   large amounts of data.
+//!   large amounts of data. Serializing data which requires relative pointer
+//!   offsets larger than 32 KiB fails with an error that suggests a larger
+//!   pointer width.
 //! - `pointer_width_32`: Serializes `isize` and `usize` as 32-bit integers.
 //!   This is a good choice for most data, and balances the storage overhead
 //!   with support for large data sizes.
@@ -125,6 +127,14 @@
 //!   This is intended to be used only for extremely large data sizes and may
 //!   cause unnecessary data bloat for smaller amounts of data.
 //!
+//! The pointer width changes the layout of archived types, so it can't be
+//! selected at runtime. The high-level API and the I/O, memory-mapped, and
+//! compressing writers fail with an error when an archive grows past
+//! [`MAX_ARCHIVE_LEN`](ser::writer::MAX_ARCHIVE_LEN) instead of producing an
+//! archive with relative pointers that can't be represented. Other serializers
+//! can wrap their writer in a [`LimitedWriter`](ser::writer::LimitedWriter) to
+//! get the same check.
+//!
 //! ### Functionality
 //!
 //! These features enable more built-in functionality and provide more powerful
@@ -143,15 +153,27 @@
 //! disabling these features does not change rkyv's serialized format.
 //!
 //! - [`arrayvec-0_7`](https://docs.rs/arrayvec/0.7)
+//! - [`bitflags-2`](https://docs.rs/bitflags/2)
+//! - [`bstr-1`](https://docs.rs/bstr/1)
 //! - [`bytes-1`](https://docs.rs/bytes/1)
+//! - [`chrono-0_4`](https://docs.rs/chrono/0.4)
+//! - [`half-2`](https://docs.rs/half/2)
 //! - [`hashbrown-0_14`](https://docs.rs/hashbrown/0.14)
 //! - [`hashbrown-0_15`](https://docs.rs/hashbrown/0.15)
 //! - [`indexmap-2`](https://docs.rs/indexmap/2)
+//! - [`ipnet-2`](https://docs.rs/ipnet/2)
+//! - [`lz4_flex-0_11`](https://docs.rs/lz4_flex/0.11)
+//! - [`memmap2-0_9`](https://docs.rs/memmap2/0.9)
+//! - [`rayon-1`](https://docs.rs/rayon/1)
+//! - [`serde-1`](https://docs.rs/serde/1)
+//! - [`serde_json-1`](https://docs.rs/serde_json/1)
 //! - [`smallvec-1`](https://docs.rs/smallvec/1)
 //! - [`smol_str-0_2`](https://docs.rs/smol_str/0.2)
 //! - [`smol_str-0_3`](https://docs.rs/smol_str/0.3)
 //! - [`thin-vec-0_2`](https://docs.rs/thin-vec/0.2)
+//! - [`time-0_3`](https://docs.rs/time/0.3)
 //! - [`tinyvec-1`](https://docs.rs/tinyvec/1)
+//! - [`tokio-1`](https://docs.rs/tokio/1)
 //! - [`triomphe-0_1`](https://docs.rs/triomphe/0.1)
 //! - [`uuid-1`](https://docs.rs/uuid/1)
 //!
@@ -217,27 +239,49 @@ mod alias;
 mod _macros;
 pub mod api;
 pub mod boxed;
+pub mod bytes;
 pub mod collections;
+pub mod compact;
 pub mod de;
+#[cfg(all(feature = "serde_json-1", feature = "bytecheck"))]
+pub mod debug;
+#[cfg(feature = "alloc")]
+pub mod diff;
+pub mod extensible;
 pub mod ffi;
+pub mod flags;
 mod fmt;
 pub mod hash;
 mod impls;
+pub mod inline_vec;
+#[cfg(feature = "alloc")]
+pub mod lazy;
 pub mod net;
 pub mod niche;
 pub mod ops;
 pub mod option;
+pub mod patch;
+pub mod path;
 pub mod place;
 mod polyfill;
 pub mod primitive;
+#[cfg(feature = "alloc")]
+pub mod raw;
 pub mod rc;
 pub mod rel_ptr;
+pub mod reserved;
 pub mod result;
+pub mod schema;
 pub mod seal;
 pub mod ser;
+#[cfg(feature = "serde-1")]
+pub mod serde;
 mod simd;
 pub mod string;
 pub mod time;
+pub mod trailing;
+#[cfg(feature = "alloc")]
+pub mod trait_object;
 pub mod traits;
 pub mod tuple;
 pub mod util;
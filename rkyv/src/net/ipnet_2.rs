@@ -0,0 +1,316 @@
+//! Archived versions of `ipnet` types.
+
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use ipnet_2::{IpNet, Ipv4Net, Ipv6Net};
+use munge::munge;
+
+use crate::{
+    net::{ArchivedIpv4Addr, ArchivedIpv6Addr},
+    Place, Portable,
+};
+
+/// An archived [`Ipv4Net`].
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedIpv4Net {
+    addr: ArchivedIpv4Addr,
+    prefix_len: u8,
+}
+
+impl ArchivedIpv4Net {
+    /// Returns the address of the network.
+    #[inline]
+    pub const fn addr(&self) -> &ArchivedIpv4Addr {
+        &self.addr
+    }
+
+    /// Returns the prefix length of the network.
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network mask.
+    ///
+    /// See [`Ipv4Net::netmask`] for more details.
+    #[inline]
+    pub const fn netmask(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.mask())
+    }
+
+    /// Returns the network address, truncating the address to the prefix
+    /// length.
+    ///
+    /// See [`Ipv4Net::network`] for more details.
+    #[inline]
+    pub const fn network(&self) -> Ipv4Addr {
+        Ipv4Addr::from_bits(self.addr.as_ipv4().to_bits() & self.mask())
+    }
+
+    /// Returns `true` if this network contains the given address.
+    #[inline]
+    pub const fn contains(&self, addr: &Ipv4Addr) -> bool {
+        addr.to_bits() & self.mask() == self.network().to_bits()
+    }
+
+    /// Returns `true` if this network contains the given network.
+    #[inline]
+    pub fn contains_net(&self, net: &Ipv4Net) -> bool {
+        net.prefix_len() >= self.prefix_len && self.contains(&net.addr())
+    }
+
+    /// Returns an [`Ipv4Net`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix length is out of range. This can only happen if
+    /// the archive was accessed without validation.
+    #[inline]
+    pub fn as_ipv4net(&self) -> Ipv4Net {
+        Ipv4Net::new(self.addr.as_ipv4(), self.prefix_len)
+            .expect("archived `Ipv4Net` prefix length is out of range")
+    }
+
+    /// Emplaces an `ArchivedIpv4Net` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &Ipv4Net, out: Place<Self>) {
+        munge!(let ArchivedIpv4Net { addr, prefix_len } = out);
+        ArchivedIpv4Addr::emplace(value.addr().octets(), addr);
+        prefix_len.write(value.prefix_len());
+    }
+
+    const fn mask(&self) -> u32 {
+        match self.prefix_len {
+            0 => 0,
+            len @ 1..32 => u32::MAX << (32 - len as u32),
+            _ => u32::MAX,
+        }
+    }
+}
+
+/// An archived [`Ipv6Net`].
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedIpv6Net {
+    addr: ArchivedIpv6Addr,
+    prefix_len: u8,
+}
+
+impl ArchivedIpv6Net {
+    /// Returns the address of the network.
+    #[inline]
+    pub const fn addr(&self) -> &ArchivedIpv6Addr {
+        &self.addr
+    }
+
+    /// Returns the prefix length of the network.
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        self.prefix_len
+    }
+
+    /// Returns the network mask.
+    ///
+    /// See [`Ipv6Net::netmask`] for more details.
+    #[inline]
+    pub const fn netmask(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.mask())
+    }
+
+    /// Returns the network address, truncating the address to the prefix
+    /// length.
+    ///
+    /// See [`Ipv6Net::network`] for more details.
+    #[inline]
+    pub const fn network(&self) -> Ipv6Addr {
+        Ipv6Addr::from_bits(self.addr.as_ipv6().to_bits() & self.mask())
+    }
+
+    /// Returns `true` if this network contains the given address.
+    #[inline]
+    pub const fn contains(&self, addr: &Ipv6Addr) -> bool {
+        addr.to_bits() & self.mask() == self.network().to_bits()
+    }
+
+    /// Returns `true` if this network contains the given network.
+    #[inline]
+    pub fn contains_net(&self, net: &Ipv6Net) -> bool {
+        net.prefix_len() >= self.prefix_len && self.contains(&net.addr())
+    }
+
+    /// Returns an [`Ipv6Net`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix length is out of range. This can only happen if
+    /// the archive was accessed without validation.
+    #[inline]
+    pub fn as_ipv6net(&self) -> Ipv6Net {
+        Ipv6Net::new(self.addr.as_ipv6(), self.prefix_len)
+            .expect("archived `Ipv6Net` prefix length is out of range")
+    }
+
+    /// Emplaces an `ArchivedIpv6Net` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &Ipv6Net, out: Place<Self>) {
+        munge!(let ArchivedIpv6Net { addr, prefix_len } = out);
+        ArchivedIpv6Addr::emplace(value.addr().octets(), addr);
+        prefix_len.write(value.prefix_len());
+    }
+
+    const fn mask(&self) -> u128 {
+        match self.prefix_len {
+            0 => 0,
+            len @ 1..128 => u128::MAX << (128 - len as u32),
+            _ => u128::MAX,
+        }
+    }
+}
+
+/// An archived [`IpNet`].
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(u8)]
+pub enum ArchivedIpNet {
+    /// An IPv4 network.
+    V4(ArchivedIpv4Net),
+    /// An IPv6 network.
+    V6(ArchivedIpv6Net),
+}
+
+impl ArchivedIpNet {
+    /// Returns the prefix length of the network.
+    #[inline]
+    pub const fn prefix_len(&self) -> u8 {
+        match self {
+            ArchivedIpNet::V4(net) => net.prefix_len(),
+            ArchivedIpNet::V6(net) => net.prefix_len(),
+        }
+    }
+
+    /// Returns the network address, truncating the address to the prefix
+    /// length.
+    #[inline]
+    pub const fn network(&self) -> IpAddr {
+        match self {
+            ArchivedIpNet::V4(net) => IpAddr::V4(net.network()),
+            ArchivedIpNet::V6(net) => IpAddr::V6(net.network()),
+        }
+    }
+
+    /// Returns `true` if this network contains the given address.
+    ///
+    /// Networks never contain addresses from a different address family.
+    #[inline]
+    pub const fn contains(&self, addr: &IpAddr) -> bool {
+        match (self, addr) {
+            (ArchivedIpNet::V4(net), IpAddr::V4(addr)) => net.contains(addr),
+            (ArchivedIpNet::V6(net), IpAddr::V6(addr)) => net.contains(addr),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this network contains the given network.
+    #[inline]
+    pub fn contains_net(&self, net: &IpNet) -> bool {
+        match (self, net) {
+            (ArchivedIpNet::V4(this), IpNet::V4(net)) => this.contains_net(net),
+            (ArchivedIpNet::V6(this), IpNet::V6(net)) => this.contains_net(net),
+            _ => false,
+        }
+    }
+
+    /// Returns an [`IpNet`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the prefix length is out of range. This can only happen if
+    /// the archive was accessed without validation.
+    #[inline]
+    pub fn as_ipnet(&self) -> IpNet {
+        match self {
+            ArchivedIpNet::V4(net) => IpNet::V4(net.as_ipv4net()),
+            ArchivedIpNet::V6(net) => IpNet::V6(net.as_ipv6net()),
+        }
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use rancor::fail;
+
+    use super::{ArchivedIpv4Net, ArchivedIpv6Net};
+
+    /// An error resulting from an archived network with a prefix length that is
+    /// too long for its address family.
+    #[derive(Debug)]
+    pub struct PrefixLenError {
+        prefix_len: u8,
+        max: u8,
+    }
+
+    impl fmt::Display for PrefixLenError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "network prefix length {} is greater than the maximum of {}",
+                self.prefix_len, self.max,
+            )
+        }
+    }
+
+    impl Error for PrefixLenError {}
+
+    fn check_prefix_len<E: Source>(prefix_len: u8, max: u8) -> Result<(), E> {
+        if prefix_len > max {
+            fail!(PrefixLenError { prefix_len, max });
+        }
+        Ok(())
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedIpv4Net
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            check_prefix_len(self.prefix_len, 32)
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedIpv6Net
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            check_prefix_len(self.prefix_len, 128)
+        }
+    }
+}
@@ -1,5 +1,8 @@
 //! Archived versions of network types.
 
+#[cfg(feature = "ipnet-2")]
+pub mod ipnet_2;
+
 use core::net::{
     IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6,
 };
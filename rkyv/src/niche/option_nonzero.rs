@@ -4,7 +4,8 @@ use core::{
     cmp, fmt, hash,
     num::{
         NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8,
-        NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8,
+        NonZeroIsize, NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64,
+        NonZeroU8, NonZeroUsize,
     },
 };
 
@@ -204,10 +205,42 @@ macro_rules! impl_archived_option_nonzero {
             }
         }
 
+        impl PartialEq<Option<$nz>> for $ar {
+            #[inline]
+            fn eq(&self, other: &Option<$nz>) -> bool {
+                self.as_ref().map(|x| x.get()) == other.map(|x| x.get())
+            }
+        }
+
+        impl PartialEq<$ar> for Option<$nz> {
+            #[inline]
+            fn eq(&self, other: &$ar) -> bool {
+                other.eq(self)
+            }
+        }
+
         unsafe impl NoUndef for $ar {}
     };
 }
 
+macro_rules! impl_archived_option_nonzero_size {
+    ($ar:ident, $nz:ty, $ne:ty) => {
+        impl PartialEq<Option<$nz>> for $ar {
+            #[inline]
+            fn eq(&self, other: &Option<$nz>) -> bool {
+                self.as_ref().map(|x| x.get() as $ne) == other.map(|x| x.get())
+            }
+        }
+
+        impl PartialEq<$ar> for Option<$nz> {
+            #[inline]
+            fn eq(&self, other: &$ar) -> bool {
+                other.eq(self)
+            }
+        }
+    };
+}
+
 impl_archived_option_nonzero!(ArchivedOptionNonZeroI8, NonZeroI8, i8);
 impl_archived_option_nonzero!(ArchivedOptionNonZeroI16, NonZeroI16, i16);
 impl_archived_option_nonzero!(ArchivedOptionNonZeroI32, NonZeroI32, i32);
@@ -221,6 +254,12 @@ pub type ArchivedOptionNonZeroIsize = match_pointer_width!(
     ArchivedOptionNonZeroI64,
 );
 
+impl_archived_option_nonzero_size!(
+    ArchivedOptionNonZeroIsize,
+    NonZeroIsize,
+    isize
+);
+
 impl_archived_option_nonzero!(ArchivedOptionNonZeroU8, NonZeroU8, u8);
 impl_archived_option_nonzero!(ArchivedOptionNonZeroU16, NonZeroU16, u16);
 impl_archived_option_nonzero!(ArchivedOptionNonZeroU32, NonZeroU32, u32);
@@ -234,6 +273,12 @@ pub type ArchivedOptionNonZeroUsize = match_pointer_width!(
     ArchivedOptionNonZeroU64,
 );
 
+impl_archived_option_nonzero_size!(
+    ArchivedOptionNonZeroUsize,
+    NonZeroUsize,
+    usize
+);
+
 /// An iterator over a reference to the `Some` variant of an
 /// `ArchivedOptionNonZero` integer.
 ///
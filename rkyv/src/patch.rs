@@ -0,0 +1,161 @@
+//! Patching fields of archived values in place.
+//!
+//! Archived values can be mutated through a [`Seal`], which only allows the
+//! parts of the value that can't be invalidated to be written. [`Assign`]
+//! writes native values like integers, floats, and `bool`s into those parts,
+//! converting them to their archived form.
+//!
+//! Archived types generated by the derive macro get patching methods when
+//! `#[rkyv(patch)]` is added to the derive input. For structs, each field gets
+//! a `{field}_seal` method which projects a `Seal` of the struct to a `Seal` of
+//! the field, and a `set_{field}` method which assigns a new value to the
+//! field. Projections can be chained to reach nested fields, and are checked
+//! at compile time. Enums without fields implement [`NoUndef`] and can be
+//! assigned from their native variants, which rewrites the tag in place.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{access_mut, rancor::Error, to_bytes, Archive, Serialize};
+//!
+//! #[derive(Archive, Serialize)]
+//! #[rkyv(patch)]
+//! struct Stats {
+//!     hits: u64,
+//!     mode: Mode,
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[rkyv(patch)]
+//! enum Mode {
+//!     Running,
+//!     Paused,
+//! }
+//!
+//! #[derive(Archive, Serialize)]
+//! #[rkyv(patch)]
+//! struct Server {
+//!     name: String,
+//!     stats: Stats,
+//! }
+//!
+//! let server = Server {
+//!     name: "primary".to_string(),
+//!     stats: Stats {
+//!         hits: 41,
+//!         mode: Mode::Running,
+//!     },
+//! };
+//! let mut bytes = to_bytes::<Error>(&server).unwrap();
+//!
+//! let archived = access_mut::<ArchivedServer, Error>(&mut bytes).unwrap();
+//! let mut stats = ArchivedServer::stats_seal(archived);
+//! ArchivedStats::set_hits(stats.as_mut(), 42u64);
+//! ArchivedStats::set_mode(stats, Mode::Paused);
+//!
+//! let archived = rkyv::access::<ArchivedServer, Error>(&bytes).unwrap();
+//! assert_eq!(archived.stats.hits, 42);
+//! assert!(matches!(archived.stats.mode, ArchivedMode::Paused));
+//! ```
+
+use crate::{seal::Seal, traits::NoUndef};
+
+/// A sealed archived value which can be assigned a new value of type `T`.
+///
+/// This is implemented for every archived type which can be written through a
+/// `Seal` and can be converted from `T`.
+pub trait Assign<T> {
+    /// Assigns `value` to the sealed archived value.
+    fn assign(this: Seal<'_, Self>, value: T);
+}
+
+impl<T: NoUndef + Unpin, U: Into<T>> Assign<U> for T {
+    #[inline]
+    fn assign(mut this: Seal<'_, Self>, value: U) {
+        *this = value.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Assign;
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        api::test::to_archived,
+        vec::ArchivedVec,
+        Archive, Archived, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, patch)]
+    struct Counters {
+        total: u32,
+        enabled: bool,
+        #[rkyv(skip)]
+        _cache: u8,
+        window: [i16; 3],
+        state: State,
+        label: String,
+    }
+
+    #[derive(Archive, Serialize, PartialEq, Debug)]
+    #[rkyv(crate, patch, compare(PartialEq), derive(Debug))]
+    enum State {
+        Idle,
+        Busy,
+        Stopped,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate, patch)]
+    struct Node {
+        counters: Counters,
+        children: Vec<Counters>,
+    }
+
+    fn counters(total: u32) -> Counters {
+        Counters {
+            total,
+            enabled: false,
+            _cache: 0,
+            window: [0; 3],
+            state: State::Idle,
+            label: String::from("counters"),
+        }
+    }
+
+    #[test]
+    fn patch_fields() {
+        let value = Node {
+            counters: counters(1),
+            children: vec![counters(2), counters(3)],
+        };
+
+        to_archived(&value, |mut archived| {
+            let mut counters = ArchivedNode::counters_seal(archived.as_mut());
+            ArchivedCounters::set_total(counters.as_mut(), 10u32);
+            ArchivedCounters::set_enabled(counters.as_mut(), true);
+            ArchivedCounters::set_state(counters.as_mut(), State::Busy);
+            let mut window = ArchivedCounters::window_seal(counters.as_mut());
+            window[1] = 7i16.into();
+
+            let children = ArchivedNode::children_seal(archived.as_mut());
+            let mut child = ArchivedVec::as_slice_seal(children).index(1);
+            ArchivedCounters::set_state(child.as_mut(), State::Stopped);
+            <Archived<u32> as Assign<u32>>::assign(
+                ArchivedCounters::total_seal(child),
+                30,
+            );
+
+            assert_eq!(archived.counters.total, 10);
+            assert!(archived.counters.enabled);
+            assert_eq!(archived.counters.window[1], 7);
+            assert_eq!(archived.counters.state, State::Busy);
+            assert_eq!(archived.counters.label, "counters");
+            assert_eq!(archived.children[0].total, 2);
+            assert_eq!(archived.children[0].state, State::Idle);
+            assert_eq!(archived.children[1].total, 30);
+            assert_eq!(archived.children[1].state, State::Stopped);
+        });
+    }
+}
@@ -0,0 +1,157 @@
+//! Archived versions of path types.
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use munge::munge;
+#[cfg(feature = "std")]
+use rancor::{Fallible, Source};
+
+use crate::{
+    ffi::{ArchivedOsString, OsStrEncoding, OsStringResolver},
+    Portable,
+};
+#[cfg(feature = "std")]
+use crate::{ser::Writer, Place};
+
+/// An archived [`PathBuf`](std::path::PathBuf).
+///
+/// Paths are archived the same way as their underlying OS strings. See
+/// [`ArchivedOsString`] for how archived paths can be used on platforms other
+/// than the one they were serialized on.
+#[derive(Eq, Hash, Ord, PartialEq, PartialOrd, Portable)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedPathBuf {
+    inner: ArchivedOsString,
+}
+
+impl ArchivedPathBuf {
+    /// Returns the underlying archived OS string of the path.
+    #[inline]
+    pub fn as_archived_os_str(&self) -> &ArchivedOsString {
+        &self.inner
+    }
+
+    /// Returns the encoding of the archived path.
+    #[inline]
+    pub fn encoding(&self) -> OsStrEncoding {
+        self.inner.encoding()
+    }
+
+    /// Returns the encoded bytes of the archived path.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_bytes()
+    }
+
+    /// Returns the archived path as a `str` if it is valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Option<&str> {
+        self.inner.to_str()
+    }
+
+    /// Returns the archived path as a `Path` without copying, if its encoding
+    /// can be used on this platform.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn as_path(&self) -> Option<&std::path::Path> {
+        self.inner.as_os_str().map(std::path::Path::new)
+    }
+
+    /// Returns the archived path as a `PathBuf`, if its encoding can be used on
+    /// this platform.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_path_buf(&self) -> Option<std::path::PathBuf> {
+        self.inner.to_os_string().map(std::path::PathBuf::from)
+    }
+
+    /// Converts the archived path to a `str`, replacing any invalid sequences
+    /// with the replacement character on every platform.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn to_string_lossy(&self) -> crate::alloc::borrow::Cow<'_, str> {
+        self.inner.to_string_lossy()
+    }
+
+    /// Resolves an archived path from the given path and parameters.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn resolve_from_path(
+        path: &std::path::Path,
+        resolver: PathBufResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedPathBuf { inner } = out);
+        ArchivedOsString::resolve_from_os_str(
+            path.as_os_str(),
+            resolver.inner,
+            inner,
+        );
+    }
+
+    /// Serializes a path.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn serialize_from_path<S>(
+        path: &std::path::Path,
+        serializer: &mut S,
+    ) -> Result<PathBufResolver, S::Error>
+    where
+        S: Fallible + Writer + ?Sized,
+        S::Error: Source,
+    {
+        Ok(PathBufResolver {
+            inner: ArchivedOsString::serialize_from_os_str(
+                path.as_os_str(),
+                serializer,
+            )?,
+        })
+    }
+}
+
+impl fmt::Debug for ArchivedPathBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<std::path::Path> for ArchivedPathBuf {
+    #[inline]
+    fn eq(&self, other: &std::path::Path) -> bool {
+        self.inner == *other.as_os_str()
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ArchivedPathBuf> for std::path::Path {
+    #[inline]
+    fn eq(&self, other: &ArchivedPathBuf) -> bool {
+        other.eq(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<std::path::PathBuf> for ArchivedPathBuf {
+    #[inline]
+    fn eq(&self, other: &std::path::PathBuf) -> bool {
+        self.eq(other.as_path())
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq<ArchivedPathBuf> for std::path::PathBuf {
+    #[inline]
+    fn eq(&self, other: &ArchivedPathBuf) -> bool {
+        other.eq(self.as_path())
+    }
+}
+
+/// The resolver for `PathBuf`.
+pub struct PathBufResolver {
+    inner: OsStringResolver,
+}
@@ -0,0 +1,114 @@
+//! Archived versions of `half` floating-point types.
+
+use core::{cmp::Ordering, fmt};
+
+use half_2::{bf16, f16};
+
+use crate::{primitive::ArchivedU16, Portable};
+
+macro_rules! define_archived_half {
+    ($archived:ident, $ty:ident) => {
+        #[doc = concat!("An archived [`", stringify!($ty), "`].")]
+        /// The value is stored as its raw bits with the archive's endianness.
+        #[derive(Clone, Copy, Default, Portable)]
+        #[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+        #[rkyv(crate)]
+        #[repr(transparent)]
+        pub struct $archived {
+            bits: ArchivedU16,
+        }
+
+        impl $archived {
+            /// Returns an archived value equal to `value`.
+            #[inline]
+            pub const fn from_native(value: $ty) -> Self {
+                Self {
+                    bits: ArchivedU16::from_native(value.to_bits()),
+                }
+            }
+
+            /// Returns the native value of the archived value.
+            #[inline]
+            pub const fn to_native(&self) -> $ty {
+                $ty::from_bits(self.bits.to_native())
+            }
+
+            /// Returns the raw bits of the value.
+            #[inline]
+            pub const fn to_bits(&self) -> u16 {
+                self.bits.to_native()
+            }
+        }
+
+        impl fmt::Debug for $archived {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Debug::fmt(&self.to_native(), f)
+            }
+        }
+
+        impl fmt::Display for $archived {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.to_native(), f)
+            }
+        }
+
+        impl From<$ty> for $archived {
+            #[inline]
+            fn from(value: $ty) -> Self {
+                Self::from_native(value)
+            }
+        }
+
+        impl From<$archived> for $ty {
+            #[inline]
+            fn from(value: $archived) -> Self {
+                value.to_native()
+            }
+        }
+
+        impl PartialEq for $archived {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.to_native() == other.to_native()
+            }
+        }
+
+        impl PartialEq<$ty> for $archived {
+            #[inline]
+            fn eq(&self, other: &$ty) -> bool {
+                self.to_native() == *other
+            }
+        }
+
+        impl PartialEq<$archived> for $ty {
+            #[inline]
+            fn eq(&self, other: &$archived) -> bool {
+                *self == other.to_native()
+            }
+        }
+
+        impl PartialOrd for $archived {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                self.to_native().partial_cmp(&other.to_native())
+            }
+        }
+
+        impl PartialOrd<$ty> for $archived {
+            #[inline]
+            fn partial_cmp(&self, other: &$ty) -> Option<Ordering> {
+                self.to_native().partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$archived> for $ty {
+            #[inline]
+            fn partial_cmp(&self, other: &$archived) -> Option<Ordering> {
+                self.partial_cmp(&other.to_native())
+            }
+        }
+    };
+}
+
+define_archived_half!(ArchivedF16, f16);
+define_archived_half!(ArchivedBf16, bf16);
@@ -1,6 +1,9 @@
 //! Definitions of archived primitives and type aliases based on enabled
 //! features.
 
+#[cfg(feature = "half-2")]
+pub mod half_2;
+
 // Unaligned big-endian
 #[cfg(all(feature = "unaligned", feature = "big_endian"))]
 use crate::rend::unaligned::{
@@ -208,3 +211,86 @@ pub type ArchivedNonZeroUsize = match_pointer_width!(
     ArchivedNonZeroU32,
     ArchivedNonZeroU64
 );
+
+// Atomics are always aligned, so multibyte archived atomics are unavailable
+// with the `unaligned` feature.
+
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+define_archived_primitive!(
+    ArchivedAtomicI16: AtomicI16,
+    crate::rend::AtomicI16_le,
+    crate::rend::AtomicI16_be
+);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "16"))]
+define_archived_primitive!(
+    ArchivedAtomicU16: AtomicU16,
+    crate::rend::AtomicU16_le,
+    crate::rend::AtomicU16_be
+);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+define_archived_primitive!(
+    ArchivedAtomicI32: AtomicI32,
+    crate::rend::AtomicI32_le,
+    crate::rend::AtomicI32_be
+);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "32"))]
+define_archived_primitive!(
+    ArchivedAtomicU32: AtomicU32,
+    crate::rend::AtomicU32_le,
+    crate::rend::AtomicU32_be
+);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+define_archived_primitive!(
+    ArchivedAtomicI64: AtomicI64,
+    crate::rend::AtomicI64_le,
+    crate::rend::AtomicI64_be
+);
+#[cfg(all(not(feature = "unaligned"), target_has_atomic = "64"))]
+define_archived_primitive!(
+    ArchivedAtomicU64: AtomicU64,
+    crate::rend::AtomicU64_le,
+    crate::rend::AtomicU64_be
+);
+
+macro_rules! define_archived_tag_fns {
+    ($($name:ident: $ty:ty),* $(,)?) => {
+        $(
+            #[doc = concat!(
+                "Converts a `",
+                stringify!($ty),
+                "` enum tag between its native and archived representations.",
+            )]
+            ///
+            /// Multibyte enum tags are stored with the byte order of the
+            /// archive. This conversion is its own inverse, so it is also used
+            /// to recover the native value of an archived tag.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the `unaligned` feature is enabled, since multibyte
+            /// enum tags are always aligned. This function is called in const
+            /// contexts by the derive macro, so this surfaces as a compile
+            /// error.
+            #[doc(hidden)]
+            #[inline]
+            pub const fn $name(tag: $ty) -> $ty {
+                if cfg!(feature = "unaligned") {
+                    panic!(
+                        "enums with multibyte tags cannot be archived with \
+                         the `unaligned` feature enabled"
+                    );
+                }
+                if cfg!(feature = "big_endian") {
+                    tag.to_be()
+                } else {
+                    tag.to_le()
+                }
+            }
+        )*
+    };
+}
+
+define_archived_tag_fns! {
+    archived_tag_u16: u16,
+    archived_tag_u32: u32,
+}
@@ -0,0 +1,369 @@
+//! Pre-serialized archives which can be embedded in other archives.
+
+use core::{fmt, marker::PhantomData};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    api::access_unchecked,
+    primitive::{ArchivedUsize, FixedUsize},
+    ser::{Writer, WriterExt as _},
+    util::AlignedVec,
+    Archive, Deserialize, Place, Portable, RelPtr, Serialize,
+};
+
+/// An archive of a `T` which has already been serialized.
+///
+/// `T` is the archived type at the root of the archive. When a `RawArchived`
+/// is serialized, its bytes are copied into the outer archive verbatim instead
+/// of serializing the value again. The bytes are written at the alignment of
+/// an [`AlignedVec`] so that the nested archive stays properly aligned. This
+/// makes it cheap to wrap archives received from elsewhere in an envelope.
+///
+/// A `RawArchived` is archived as an [`ArchivedRawArchived`], which holds the
+/// nested archive and can access its root without deserializing it.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access, rancor::Error, raw::RawArchived, to_bytes, Archive, Archived,
+///     Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Envelope {
+///     sender: String,
+///     payload: RawArchived<Archived<Vec<u32>>>,
+/// }
+///
+/// // A payload which was serialized upstream
+/// let payload = to_bytes::<Error>(&vec![1u32, 2, 3]).unwrap();
+///
+/// let envelope = Envelope {
+///     sender: "upstream".to_string(),
+///     payload: RawArchived::new::<Error>(payload).unwrap(),
+/// };
+/// let bytes = to_bytes::<Error>(&envelope).unwrap();
+///
+/// let archived = access::<ArchivedEnvelope, Error>(&bytes).unwrap();
+/// assert_eq!(archived.payload.get().as_slice(), [1, 2, 3]);
+/// ```
+pub struct RawArchived<T> {
+    // Invariant: `bytes` contains an archive with a valid `T` at its root.
+    bytes: AlignedVec,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Portable> RawArchived<T> {
+    /// Validates the given archive and returns a `RawArchived` which contains
+    /// it.
+    #[cfg(feature = "bytecheck")]
+    pub fn new<E>(bytes: AlignedVec) -> Result<Self, E>
+    where
+        T: for<'a> bytecheck::CheckBytes<
+            crate::api::high::HighValidator<'a, E>,
+        >,
+        E: rancor::Source,
+    {
+        crate::api::high::access::<T, E>(&bytes)?;
+        // SAFETY: We just validated that `bytes` has a valid `T` at its root.
+        Ok(unsafe { Self::new_unchecked(bytes) })
+    }
+
+    /// Returns a `RawArchived` which contains the given archive without
+    /// validating it.
+    ///
+    /// # Safety
+    ///
+    /// `bytes` must contain an archive with a valid `T` at its root.
+    pub unsafe fn new_unchecked(bytes: AlignedVec) -> Self {
+        Self {
+            bytes,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the archived value at the root of the archive.
+    pub fn get(&self) -> &T {
+        // SAFETY: `bytes` always contains a valid archive of `T`.
+        unsafe { access_unchecked::<T>(&self.bytes) }
+    }
+
+    /// Returns the bytes of the archive.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Consumes the `RawArchived` and returns the underlying buffer.
+    pub fn into_inner(self) -> AlignedVec {
+        self.bytes
+    }
+}
+
+impl<T: Portable + fmt::Debug> fmt::Debug for RawArchived<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawArchived").field(self.get()).finish()
+    }
+}
+
+/// An archived [`RawArchived`].
+///
+/// The archived value is stored as a separate archive nested inside of the
+/// outer archive. The nested archive is validated along with the outer archive
+/// and counts against the same [limits](crate::validation::ValidatorConfig).
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedRawArchived<T> {
+    ptr: RelPtr<u8>,
+    len: ArchivedUsize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: Portable> ArchivedRawArchived<T> {
+    /// Returns a reference to the archived value.
+    pub fn get(&self) -> &T {
+        // SAFETY: The nested archive always has a valid `T` at its root.
+        unsafe { access_unchecked::<T>(self.as_bytes()) }
+    }
+
+    /// Returns the bytes of the nested archive.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: `ptr` always points to `len` bytes of the nested archive.
+        unsafe {
+            core::slice::from_raw_parts(
+                self.ptr.as_ptr(),
+                self.len.to_native() as usize,
+            )
+        }
+    }
+}
+
+impl<T: Portable + fmt::Debug> fmt::Debug for ArchivedRawArchived<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+/// The resolver for [`ArchivedRawArchived`].
+pub struct RawArchivedResolver {
+    pos: FixedUsize,
+    len: FixedUsize,
+}
+
+impl<T: Portable> Archive for RawArchived<T> {
+    type Archived = ArchivedRawArchived<T>;
+    type Resolver = RawArchivedResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedRawArchived { ptr, len, _phantom: _ } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+        usize::resolve(&(resolver.len as usize), (), len);
+    }
+}
+
+impl<T, S> Serialize<S> for RawArchived<T>
+where
+    T: Portable,
+    S: Fallible + Writer + ?Sized,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        let pos = serializer.align(AlignedVec::<16>::ALIGNMENT)?;
+        serializer.write(&self.bytes)?;
+        Ok(RawArchivedResolver {
+            pos: pos as FixedUsize,
+            len: self.bytes.len() as FixedUsize,
+        })
+    }
+}
+
+impl<T, D> Deserialize<RawArchived<T>, D> for ArchivedRawArchived<T>
+where
+    T: Portable,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, _: &mut D) -> Result<RawArchived<T>, D::Error> {
+        let mut bytes = AlignedVec::with_capacity(self.as_bytes().len());
+        bytes.extend_from_slice(self.as_bytes());
+        // SAFETY: The nested archive always has a valid `T` at its root.
+        Ok(unsafe { RawArchived::new_unchecked(bytes) })
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{alloc::Layout, error::Error, fmt, mem::size_of};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes, Verify,
+    };
+    use rancor::{fail, ResultExt as _};
+
+    use crate::{
+        api::root_position,
+        raw::ArchivedRawArchived,
+        util::AlignedVec,
+        validation::{ArchiveContext, ArchiveContextExt},
+        Portable,
+    };
+
+    #[derive(Debug)]
+    struct UnalignedRawArchivedError;
+
+    impl fmt::Display for UnalignedRawArchivedError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "the nested archive of a raw archived value is not aligned"
+            )
+        }
+    }
+
+    impl Error for UnalignedRawArchivedError {}
+
+    #[derive(Debug)]
+    struct RawArchivedTooShortError {
+        len: usize,
+        root_size: usize,
+    }
+
+    impl fmt::Display for RawArchivedTooShortError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "the nested archive of a raw archived value is {} bytes long, \
+                 but its root is {} bytes long",
+                self.len, self.root_size,
+            )
+        }
+    }
+
+    impl Error for RawArchivedTooShortError {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedRawArchived<T>
+    where
+        T: Portable + CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let start = self.ptr.as_ptr_wrapping();
+            let len = self.len.to_native() as usize;
+            if (start as usize) % AlignedVec::<16>::ALIGNMENT != 0 {
+                fail!(UnalignedRawArchivedError);
+            }
+            if len < size_of::<T>() {
+                fail!(RawArchivedTooShortError {
+                    len,
+                    root_size: size_of::<T>(),
+                });
+            }
+            let layout = Layout::from_size_align(len, 1).into_error()?;
+            context.check_subtree_ptr(start, &layout)?;
+
+            // Claim an empty subtree at the start of the nested archive. This
+            // moves the start of the subtree range up to the nested archive, so
+            // that its root can only point to data inside of it.
+            context.in_subtree_raw(start, Layout::new::<()>(), |_| Ok(()))?;
+
+            // The nested archive is validated with the outer context, so its
+            // contents count against the limits of the outer validator. Its
+            // root ends at the end of the nested archive, so the outer archive
+            // can't point into the nested archive after this returns.
+            let root = start.wrapping_add(root_position::<T>(len)).cast::<T>();
+            context.in_subtree_raw(root.cast(), Layout::new::<T>(), |context| {
+                // SAFETY: `in_subtree_raw` has guaranteed that `root` is
+                // properly aligned and points to enough bytes for a `T`.
+                unsafe { T::check_bytes(root, context) }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use super::RawArchived;
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        api::test::{deserialize, to_archived},
+        to_bytes,
+        util::AlignedVec,
+        Archive, Archived, Deserialize, Serialize,
+    };
+
+    type Payload = Archived<Vec<String>>;
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[rkyv(crate)]
+    struct Envelope {
+        tag: u8,
+        payload: RawArchived<Payload>,
+    }
+
+    #[test]
+    fn embeds_archive_verbatim() {
+        let payload: Vec<String> = vec!["a".into(), "b".into()];
+        let bytes = to_bytes::<Panic>(&payload).unwrap();
+        // SAFETY: `bytes` was just serialized from a `Vec<String>`.
+        let payload = unsafe { RawArchived::<Payload>::new_unchecked(bytes) };
+        assert_eq!(payload.get()[1], "b");
+
+        let value = Envelope { tag: 3, payload };
+        to_archived(&value, |archived| {
+            assert_eq!(archived.tag, 3);
+            assert_eq!(archived.payload.get()[0], "a");
+            assert_eq!(archived.payload.as_bytes(), value.payload.as_bytes());
+            assert_eq!(
+                archived.payload.as_bytes().as_ptr() as usize
+                    % AlignedVec::<16>::ALIGNMENT,
+                0,
+            );
+
+            let envelope = deserialize::<Envelope>(&*archived);
+            assert_eq!(envelope.payload.get()[1], "b");
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn nested_archive_limits() {
+        use rancor::Failure;
+
+        use crate::{api::high::access_with, validation::ValidatorConfig};
+
+        let payload: Vec<String> = vec!["a".into(), "b".into(), "c".into()];
+        let bytes = to_bytes::<Panic>(&payload).unwrap();
+        let value = Envelope {
+            tag: 3,
+            payload: RawArchived::new::<Panic>(bytes).unwrap(),
+        };
+        let bytes = to_bytes::<Panic>(&value).unwrap();
+
+        let config = ValidatorConfig {
+            max_elements: Some(3),
+            ..ValidatorConfig::default()
+        };
+        let archived =
+            access_with::<ArchivedEnvelope, Failure>(&bytes, &config).unwrap();
+        assert_eq!(archived.payload.get()[2], "c");
+
+        let config = ValidatorConfig {
+            max_elements: Some(2),
+            ..ValidatorConfig::default()
+        };
+        access_with::<ArchivedEnvelope, Failure>(&bytes, &config).unwrap_err();
+    }
+}
@@ -94,8 +94,10 @@ impl<T: ArchivePointee + ?Sized, F> ArchivedRc<T, F> {
         U: SerializeUnsized<S> + ?Sized,
         S: Fallible + Writer + Sharing + ?Sized,
         S::Error: Source,
+        T: 'static,
+        F: 'static,
     {
-        let pos = serializer.serialize_shared(value)?;
+        let pos = serializer.serialize_shared_for::<Self, _>(value)?;
 
         // The positions of serialized `Rc` values must be unique. If we didn't
         // write any data by serializing `value`, pad the serializer by a byte
@@ -222,6 +224,13 @@ impl RcResolver {
 /// An archived `rc::Weak`.
 ///
 /// This is essentially just an optional [`ArchivedRc`].
+///
+/// A weak pointer is serialized as a shared pointer to its target if the
+/// target is still alive. If the target is currently being serialized (for
+/// example, a back-reference from a child to its parent) then serialization
+/// fails, since archived pointers can only point to data which has already been
+/// written. The [`BreakCycles`](crate::with::BreakCycles) wrapper serializes
+/// these weak pointers as null instead.
 #[derive(Portable)]
 #[rkyv(crate)]
 #[repr(transparent)]
@@ -265,15 +274,15 @@ impl<T: ArchivePointee + ?Sized, F> ArchivedRcWeak<T, F> {
         resolver: RcWeakResolver,
         out: Place<Self>,
     ) {
-        match value {
-            None => {
+        match (value, resolver.inner) {
+            (Some(value), Some(inner)) => {
+                let out = unsafe { out.cast_unchecked::<ArchivedRc<T, F>>() };
+                ArchivedRc::resolve_from_ref(value, inner, out);
+            }
+            _ => {
                 munge!(let ArchivedRcWeak { ptr, _phantom: _ } = out);
                 RelPtr::emplace_invalid(ptr);
             }
-            Some(value) => {
-                let out = unsafe { out.cast_unchecked::<ArchivedRc<T, F>>() };
-                ArchivedRc::resolve_from_ref(value, resolver.inner, out);
-            }
         }
     }
 
@@ -286,15 +295,49 @@ impl<T: ArchivePointee + ?Sized, F> ArchivedRcWeak<T, F> {
         U: SerializeUnsized<S, Archived = T> + ?Sized,
         S: Fallible + Writer + Sharing + ?Sized,
         S::Error: Source,
+        T: 'static,
+        F: 'static,
     {
-        Ok(match value {
-            None => RcWeakResolver {
-                inner: RcResolver { pos: 0 },
-            },
-            Some(r) => RcWeakResolver {
-                inner: ArchivedRc::<T, F>::serialize_from_ref(r, serializer)?,
-            },
-        })
+        let inner = value
+            .map(|r| ArchivedRc::<T, F>::serialize_from_ref(r, serializer))
+            .transpose()?;
+        Ok(RcWeakResolver { inner })
+    }
+
+    /// Serializes an archived `Weak` from a given optional reference, or as
+    /// null if it points to a value which is still being serialized.
+    ///
+    /// This is used by the [`BreakCycles`](crate::with::BreakCycles) wrapper.
+    pub fn serialize_from_ref_or_null<U, S>(
+        value: Option<&U>,
+        serializer: &mut S,
+    ) -> Result<RcWeakResolver, S::Error>
+    where
+        U: SerializeUnsized<S, Archived = T> + ?Sized,
+        S: Fallible + Writer + Sharing + ?Sized,
+        S::Error: Source,
+        T: 'static,
+        F: 'static,
+    {
+        let inner = match value {
+            None => None,
+            Some(r) => {
+                match serializer.serialize_weak::<ArchivedRc<T, F>, _>(r)? {
+                    None => None,
+                    Some(pos) => {
+                        // See `ArchivedRc::serialize_from_ref` for why the
+                        // position must be unique.
+                        if serializer.pos() == pos {
+                            serializer.pad(1)?;
+                        }
+                        Some(RcResolver {
+                            pos: pos as FixedUsize,
+                        })
+                    }
+                }
+            }
+        };
+        Ok(RcWeakResolver { inner })
     }
 }
 
@@ -308,7 +351,7 @@ impl<T: ArchivePointee + fmt::Debug + ?Sized, F> fmt::Debug
 
 /// The resolver for `rc::Weak`.
 pub struct RcWeakResolver {
-    inner: RcResolver,
+    inner: Option<RcResolver>,
 }
 
 #[cfg(feature = "bytecheck")]
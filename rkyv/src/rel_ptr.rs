@@ -1,4 +1,11 @@
 //! Relative pointer implementations and options.
+//!
+//! Relative pointers are the building blocks of archived data structures. A
+//! [`RawRelPtr`] stores only an offset, and a [`RelPtr`] pairs an offset with
+//! the archived metadata of its pointee. Both can be written into a [`Place`]
+//! during resolution, or created directly from a pair of positions when the
+//! bytes of an archive are laid out by hand. A pointer with an offset of `1` is
+//! *invalid* and can be used as a null pointer by nullable data structures.
 
 use core::{
     error::Error,
@@ -31,6 +38,31 @@ impl fmt::Display for IsizeOverflow {
 
 impl Error for IsizeOverflow {}
 
+#[derive(Debug)]
+struct OffsetOverflow {
+    offset: isize,
+    bits: u32,
+}
+
+impl fmt::Display for OffsetOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            concat!(
+                "relative pointer offset {} does not fit in a {}-bit offset\n",
+                "help: the archive is too large for the enabled pointer width \
+                 ({}-bit). Enabling a larger `pointer_width_*` feature will \
+                 allow larger archives.",
+            ),
+            self.offset,
+            self.bits,
+            core::mem::size_of::<crate::primitive::FixedUsize>() * 8,
+        )
+    }
+}
+
+impl Error for OffsetOverflow {}
+
 /// A offset that can be used with [`RawRelPtr`].
 pub trait Offset: Copy + NoUndef {
     /// Creates a new offset between a `from` position and a `to` position.
@@ -48,7 +80,13 @@ macro_rules! impl_offset_single_byte {
                 // computed offsets cannot overflow an isize, which is why we're
                 // using signed_offset instead of `checked_sub` for unsized
                 // types.
-                Self::try_from(value).into_error()
+                match Self::try_from(value) {
+                    Ok(offset) => Ok(offset),
+                    Err(_) => fail!(OffsetOverflow {
+                        offset: value,
+                        bits: <$ty>::BITS,
+                    }),
+                }
             }
 
             #[inline]
@@ -72,9 +110,13 @@ macro_rules! impl_offset_multi_byte {
                 // computed offsets cannot overflow an isize, which is why we're
                 // using signed_offset instead of `checked_sub` for unsized
                 // types.
-                Ok(<$archived>::from_native(
-                    <$ty>::try_from(value).into_error()?,
-                ))
+                match <$ty>::try_from(value) {
+                    Ok(offset) => Ok(<$archived>::from_native(offset)),
+                    Err(_) => fail!(OffsetOverflow {
+                        offset: value,
+                        bits: <$ty>::BITS,
+                    }),
+                }
             }
 
             #[inline]
@@ -191,6 +233,44 @@ impl<O: Offset> RawRelPtr<O> {
         Self::try_emplace::<Panic>(to, out).always_ok()
     }
 
+    /// Attempts to create a new `RawRelPtr` which points from the `from`
+    /// position to the `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    pub fn try_new<E: Source>(from: usize, to: usize) -> Result<Self, E> {
+        Ok(Self {
+            offset: O::from_isize(signed_offset(from, to)?)?,
+            _phantom: PhantomPinned,
+        })
+    }
+
+    /// Creates a new `RawRelPtr` which points from the `from` position to the
+    /// `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    ///
+    /// # Panics
+    ///
+    /// - If the offset between `from` and `to` does not fit in an `isize`
+    /// - If the offset between `from` and `to` exceeds the offset storage
+    pub fn new(from: usize, to: usize) -> Self {
+        Self::try_new::<Panic>(from, to).always_ok()
+    }
+
+    /// Attempts to create a new invalid `RawRelPtr`.
+    pub fn try_new_invalid<E: Source>() -> Result<Self, E> {
+        Self::try_new(0, 1)
+    }
+
+    /// Creates a new invalid `RawRelPtr`.
+    ///
+    /// # Panics
+    ///
+    /// - If an offset of `1` exceeds the offset storage
+    pub fn new_invalid() -> Self {
+        Self::try_new_invalid::<Panic>().always_ok()
+    }
+
     /// Gets the base pointer for the pointed-to relative pointer.
     pub fn base_raw(this: *mut Self) -> *mut u8 {
         this.cast()
@@ -281,6 +361,19 @@ impl<O: Offset> RawRelPtr<O> {
         unsafe { Self::is_invalid_raw(this.cast_mut()) }
     }
 
+    /// Returns the position this relative pointer points to if it is located
+    /// at the `from` position.
+    ///
+    /// Returns `None` if the relative pointer is invalid or the position it
+    /// points to would overflow a `usize`.
+    pub fn target_pos(&self, from: usize) -> Option<usize> {
+        if self.is_invalid() {
+            None
+        } else {
+            from.checked_add_signed(self.offset())
+        }
+    }
+
     /// Calculates the memory address being pointed to by this relative pointer.
     ///
     /// # Safety
@@ -410,6 +503,27 @@ impl<T, O: Offset> RelPtr<T, O> {
     pub fn emplace(to: usize, out: Place<Self>) {
         Self::try_emplace::<Panic>(to, out).always_ok()
     }
+
+    /// Attempts to create a new relative pointer which points from the `from`
+    /// position to the `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    pub fn try_new<E: Source>(from: usize, to: usize) -> Result<Self, E> {
+        Self::try_new_unsized(from, to, ())
+    }
+
+    /// Creates a new relative pointer which points from the `from` position to
+    /// the `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    ///
+    /// # Panics
+    ///
+    /// - If the offset between `from` and `to` does not fit in an `isize`
+    /// - If the offset between `from` and `to` exceeds the offset storage
+    pub fn new(from: usize, to: usize) -> Self {
+        Self::try_new::<Panic>(from, to).always_ok()
+    }
 }
 
 impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
@@ -457,6 +571,57 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
         Self::try_emplace_unsized::<Panic>(to, metadata, out).always_ok()
     }
 
+    /// Attempts to create a new relative pointer with the given metadata which
+    /// points from the `from` position to the `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    pub fn try_new_unsized<E: Source>(
+        from: usize,
+        to: usize,
+        metadata: T::ArchivedMetadata,
+    ) -> Result<Self, E> {
+        Ok(Self {
+            raw_ptr: RawRelPtr::try_new(from, to)?,
+            metadata,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Creates a new relative pointer with the given metadata which points from
+    /// the `from` position to the `to` position.
+    ///
+    /// The returned pointer is only valid once it is written at `from`.
+    ///
+    /// # Panics
+    ///
+    /// - If the offset between `from` and `to` does not fit in an `isize`
+    /// - If the offset between `from` and `to` exceeds the offset storage
+    pub fn new_unsized(
+        from: usize,
+        to: usize,
+        metadata: T::ArchivedMetadata,
+    ) -> Self {
+        Self::try_new_unsized::<Panic>(from, to, metadata).always_ok()
+    }
+
+    /// Attempts to create a new invalid relative pointer with default metadata.
+    pub fn try_new_invalid<E: Source>() -> Result<Self, E> {
+        Ok(Self {
+            raw_ptr: RawRelPtr::try_new_invalid()?,
+            metadata: Default::default(),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Creates a new invalid relative pointer with default metadata.
+    ///
+    /// # Panics
+    ///
+    /// - If an offset of `1` exceeds the offset storage
+    pub fn new_invalid() -> Self {
+        Self::try_new_invalid::<Panic>().always_ok()
+    }
+
     /// Gets the base pointer for the pointed-to relative pointer.
     pub fn base_raw(this: *mut Self) -> *mut u8 {
         RawRelPtr::<O>::base_raw(this.cast())
@@ -561,6 +726,15 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
         self.raw_ptr.is_invalid()
     }
 
+    /// Returns the position this relative pointer points to if it is located
+    /// at the `from` position.
+    ///
+    /// Returns `None` if the relative pointer is invalid or the position it
+    /// points to would overflow a `usize`.
+    pub fn target_pos(&self, from: usize) -> Option<usize> {
+        self.raw_ptr.target_pos(from)
+    }
+
     /// Gets the metadata of the relative pointer.
     pub fn metadata(&self) -> &T::ArchivedMetadata {
         &self.metadata
@@ -622,6 +796,41 @@ impl<T: ArchivePointee + ?Sized, O: Offset> RelPtr<T, O> {
             metadata,
         )
     }
+
+    /// Returns a reference to the value pointed to by this relative pointer,
+    /// or `None` if it is invalid.
+    ///
+    /// # Safety
+    ///
+    /// If the relative pointer is not invalid, it must point to a valid `T`
+    /// located in the same allocated object as it.
+    pub unsafe fn as_ref(&self) -> Option<&T> {
+        if self.is_invalid() {
+            None
+        } else {
+            // SAFETY: The caller has guaranteed that valid relative pointers
+            // point to a valid `T` in the same allocated object.
+            Some(unsafe { &*self.as_ptr() })
+        }
+    }
+
+    /// Returns a sealed mutable reference to the value pointed to by this
+    /// relative pointer, or `None` if it is invalid.
+    ///
+    /// # Safety
+    ///
+    /// If the relative pointer is not invalid, it must point to a valid `T`
+    /// located in the same allocated object as it, which is not aliased by any
+    /// other references.
+    pub unsafe fn as_mut<'a>(this: Seal<'a, Self>) -> Option<Seal<'a, T>> {
+        if this.is_invalid() {
+            None
+        } else {
+            // SAFETY: The caller has guaranteed that valid relative pointers
+            // point to a valid and unaliased `T` in the same allocated object.
+            Some(Seal::new(unsafe { &mut *Self::as_mut_ptr(this) }))
+        }
+    }
 }
 
 impl<T: ArchivePointee + ?Sized, O: fmt::Debug> fmt::Debug for RelPtr<T, O>
@@ -641,3 +850,58 @@ impl<T: ArchivePointee + ?Sized, O: Offset> fmt::Pointer for RelPtr<T, O> {
         fmt::Pointer::fmt(&self.as_ptr_wrapping(), f)
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use rancor::Error;
+
+    use super::Offset;
+    use crate::{alloc::string::ToString, primitive::ArchivedI16};
+
+    #[test]
+    fn offset_overflow_diagnostic() {
+        assert!(ArchivedI16::from_isize::<Error>(i16::MAX as isize).is_ok());
+
+        let error = ArchivedI16::from_isize::<Error>(i16::MAX as isize + 1)
+            .unwrap_err();
+        let message = error.to_string();
+        assert!(message.contains("16-bit offset"));
+        assert!(message.contains("pointer_width"));
+    }
+
+    #[test]
+    fn new_from_positions() {
+        use core::mem::offset_of;
+
+        use super::RelPtr;
+        use crate::primitive::{ArchivedI32, ArchivedU32};
+
+        type Ptr = RelPtr<ArchivedU32, ArchivedI32>;
+
+        #[repr(C)]
+        struct Node {
+            next: Ptr,
+            value: ArchivedU32,
+        }
+
+        let node = Node {
+            next: Ptr::new(0, offset_of!(Node, value)),
+            value: ArchivedU32::from_native(42),
+        };
+        assert!(!node.next.is_invalid());
+        assert_eq!(
+            node.next.target_pos(16),
+            Some(16 + offset_of!(Node, value))
+        );
+        // SAFETY: `node.next` points to `node.value`.
+        assert_eq!(unsafe { node.next.as_ref() }, Some(&node.value));
+
+        let null = Ptr::new_invalid();
+        assert!(null.is_invalid());
+        assert_eq!(null.target_pos(16), None);
+        // SAFETY: `null` is invalid.
+        assert!(unsafe { null.as_ref() }.is_none());
+
+        assert!(Ptr::try_new::<Error>(0, i32::MAX as usize + 1).is_err());
+    }
+}
@@ -0,0 +1,479 @@
+//! Archived collections with reserved capacity for in-place growth.
+//!
+//! [`ArchivedVec`](crate::vec::ArchivedVec) and
+//! [`ArchivedString`](crate::string::ArchivedString) are serialized with
+//! exactly as much space as their elements need, so they can't grow without
+//! serializing the archive again. The types in this module reserve additional
+//! zeroed capacity after their elements and store a mutable length, so a
+//! bounded number of elements can be pushed through a [`Seal`] after the
+//! archive has been written. This suits archives which are mostly appended to,
+//! like logs in memory-mapped files.
+//!
+//! Only elements which can be written through a `Seal` can be pushed, which
+//! excludes elements that contain relative pointers.
+//!
+//! These types are used with the [`Reserve`](crate::with::Reserve) wrapper.
+
+use core::{borrow::Borrow, fmt, mem::size_of, ops::Deref, str};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    primitive::{ArchivedUsize, FixedUsize},
+    seal::Seal,
+    ser::{Allocator, Writer, WriterExt as _},
+    traits::NoUndef,
+    Place, Portable, RelPtr, Serialize, SerializeUnsized,
+};
+
+/// An archived [`Vec`] with reserved capacity and a mutable length.
+///
+/// This is the archived representation of a `Vec` with the
+/// [`Reserve`](crate::with::Reserve) wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedReservedVec<T> {
+    ptr: RelPtr<T>,
+    len: ArchivedUsize,
+    cap: ArchivedUsize,
+}
+
+impl<T> ArchivedReservedVec<T> {
+    /// Returns a pointer to the first element of the archived vec.
+    pub fn as_ptr(&self) -> *const T {
+        unsafe { self.ptr.as_ptr() }
+    }
+
+    /// Returns the number of elements in the archived vec.
+    pub fn len(&self) -> usize {
+        self.len.to_native() as usize
+    }
+
+    /// Returns whether the archived vec is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of elements the archived vec can hold.
+    pub fn capacity(&self) -> usize {
+        self.cap.to_native() as usize
+    }
+
+    /// Gets the elements of the archived vec as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.as_ptr(), self.len()) }
+    }
+
+    /// Gets the elements of the archived vec as a sealed mutable slice.
+    pub fn as_slice_seal(this: Seal<'_, Self>) -> Seal<'_, [T]> {
+        let len = this.len();
+        munge!(let Self { ptr, .. } = this);
+        let slice = unsafe {
+            core::slice::from_raw_parts_mut(RelPtr::as_mut_ptr(ptr), len)
+        };
+        Seal::new(slice)
+    }
+
+    /// Appends an element to the end of the archived vec.
+    ///
+    /// Returns the element back if the archived vec is already at capacity.
+    pub fn push(this: Seal<'_, Self>, value: T) -> Result<(), T>
+    where
+        T: NoUndef + Unpin,
+    {
+        let index = this.len();
+        if index == this.capacity() {
+            return Err(value);
+        }
+
+        munge!(let Self { ptr, mut len, .. } = this);
+        // SAFETY: `index` is less than the capacity of the archived vec, so the
+        // element at `index` is located in its reserved capacity. `T` is
+        // `NoUndef` and `Unpin`, so it can be written without invalidating
+        // the archive.
+        unsafe {
+            RelPtr::as_mut_ptr(ptr).add(index).write(value);
+        }
+        *len = ArchivedUsize::from_native((index + 1) as FixedUsize);
+        Ok(())
+    }
+
+    /// Shortens the archived vec to the given length.
+    ///
+    /// If `new_len` is greater than or equal to the current length, this has
+    /// no effect. The removed elements are not dropped.
+    pub fn truncate(this: Seal<'_, Self>, new_len: usize) {
+        if new_len < this.len() {
+            munge!(let Self { mut len, .. } = this);
+            *len = ArchivedUsize::from_native(new_len as FixedUsize);
+        }
+    }
+
+    /// Removes all elements from the archived vec.
+    pub fn clear(this: Seal<'_, Self>) {
+        Self::truncate(this, 0);
+    }
+
+    /// Resolves an archived reserved vec from a given slice.
+    pub fn resolve_from_slice<U>(
+        slice: &[U],
+        resolver: ReservedVecResolver,
+        out: Place<Self>,
+    ) {
+        Self::resolve_from_len(slice.len(), resolver, out);
+    }
+
+    /// Resolves an archived reserved vec from a given length.
+    pub fn resolve_from_len(
+        len: usize,
+        resolver: ReservedVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedReservedVec { ptr, len: out_len, cap } = out);
+        RelPtr::emplace(resolver.pos as usize, ptr);
+        out_len.write(ArchivedUsize::from_native(len as FixedUsize));
+        cap.write(ArchivedUsize::from_native(resolver.cap));
+    }
+
+    /// Serializes an archived reserved vec from a given slice, reserving
+    /// capacity for `additional` more elements.
+    pub fn serialize_from_slice<U, S>(
+        slice: &[U],
+        additional: usize,
+        serializer: &mut S,
+    ) -> Result<ReservedVecResolver, S::Error>
+    where
+        U: Serialize<S, Archived = T>,
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        let pos = slice.serialize_unsized(serializer)?;
+        // The reserved capacity immediately follows the elements.
+        serializer.pad(additional * size_of::<T>())?;
+        Ok(ReservedVecResolver {
+            pos: pos as FixedUsize,
+            cap: (slice.len() + additional) as FixedUsize,
+        })
+    }
+}
+
+impl<T> AsRef<[T]> for ArchivedReservedVec<T> {
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Borrow<[T]> for ArchivedReservedVec<T> {
+    fn borrow(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for ArchivedReservedVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<T> Deref for ArchivedReservedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T: PartialEq<U>, U> PartialEq<[U]> for ArchivedReservedVec<T> {
+    fn eq(&self, other: &[U]) -> bool {
+        self.as_slice().eq(other)
+    }
+}
+
+/// The resolver for [`ArchivedReservedVec`].
+pub struct ReservedVecResolver {
+    pos: FixedUsize,
+    cap: FixedUsize,
+}
+
+/// An archived [`String`] with reserved capacity and a mutable length.
+///
+/// This is the archived representation of a `String` with the
+/// [`Reserve`](crate::with::Reserve) wrapper.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedReservedString {
+    bytes: ArchivedReservedVec<u8>,
+}
+
+impl ArchivedReservedString {
+    /// Extracts a string slice containing the entire archived string.
+    pub fn as_str(&self) -> &str {
+        // SAFETY: Archived reserved strings always contain valid UTF-8.
+        unsafe { str::from_utf8_unchecked(self.bytes.as_slice()) }
+    }
+
+    /// Returns the length of the archived string in bytes.
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns whether the archived string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Returns the number of bytes the archived string can hold.
+    pub fn capacity(&self) -> usize {
+        self.bytes.capacity()
+    }
+
+    /// Appends a string slice to the end of the archived string.
+    ///
+    /// Returns the string slice back if it doesn't fit in the remaining
+    /// capacity of the archived string.
+    pub fn push_str<'s>(
+        this: Seal<'_, Self>,
+        s: &'s str,
+    ) -> Result<(), &'s str> {
+        if this.capacity() - this.len() < s.len() {
+            return Err(s);
+        }
+
+        munge!(let Self { mut bytes } = this);
+        for &byte in s.as_bytes() {
+            // The remaining capacity was checked above.
+            let _ = ArchivedReservedVec::push(bytes.as_mut(), byte);
+        }
+        Ok(())
+    }
+
+    /// Appends a character to the end of the archived string.
+    ///
+    /// Returns the character back if it doesn't fit in the remaining capacity
+    /// of the archived string.
+    pub fn push(this: Seal<'_, Self>, ch: char) -> Result<(), char> {
+        Self::push_str(this, ch.encode_utf8(&mut [0; 4])).map_err(|_| ch)
+    }
+
+    /// Removes all characters from the archived string.
+    pub fn clear(this: Seal<'_, Self>) {
+        munge!(let Self { bytes } = this);
+        ArchivedReservedVec::clear(bytes);
+    }
+
+    /// Resolves an archived reserved string from a given `str`.
+    pub fn resolve_from_str(
+        value: &str,
+        resolver: ReservedVecResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedReservedString { bytes } = out);
+        ArchivedReservedVec::resolve_from_len(value.len(), resolver, bytes);
+    }
+
+    /// Serializes an archived reserved string from a given `str`, reserving
+    /// capacity for `additional` more bytes.
+    pub fn serialize_from_str<S>(
+        value: &str,
+        additional: usize,
+        serializer: &mut S,
+    ) -> Result<ReservedVecResolver, S::Error>
+    where
+        S: Fallible + Allocator + Writer + ?Sized,
+    {
+        ArchivedReservedVec::serialize_from_slice(
+            value.as_bytes(),
+            additional,
+            serializer,
+        )
+    }
+}
+
+impl AsRef<str> for ArchivedReservedString {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for ArchivedReservedString {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Debug for ArchivedReservedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for ArchivedReservedString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Deref for ArchivedReservedString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for ArchivedReservedString {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ArchivedReservedString {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{fail, Fallible, Source},
+        CheckBytes, Verify,
+    };
+
+    use crate::{
+        reserved::{ArchivedReservedString, ArchivedReservedVec},
+        validation::{check_slice, ArchiveContext, ArchiveContextExt},
+        Portable,
+    };
+
+    #[derive(Debug)]
+    struct LengthExceedsCapacity {
+        len: usize,
+        cap: usize,
+    }
+
+    impl fmt::Display for LengthExceedsCapacity {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived reserved vec has length {} but only has capacity \
+                 for {} elements",
+                self.len, self.cap,
+            )
+        }
+    }
+
+    impl Error for LengthExceedsCapacity {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedReservedVec<T>
+    where
+        T: Portable + CheckBytes<C>,
+        C: Fallible + ArchiveContext + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len();
+            let cap = self.capacity();
+            if len > cap {
+                fail!(LengthExceedsCapacity { len, cap });
+            }
+            context.claim_elements(cap)?;
+
+            let start = self.ptr.as_ptr_wrapping();
+            let reserved = core::ptr::slice_from_raw_parts(start, cap);
+            context.in_subtree(reserved, |context| {
+                let ptr = core::ptr::slice_from_raw_parts(start, len);
+                // SAFETY: `in_subtree` has guaranteed that `reserved` is
+                // properly aligned and points to enough bytes for `cap`
+                // elements, and `len` is at most `cap`.
+                unsafe { check_slice(ptr, context) }
+            })
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedReservedString
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let ptr = self.bytes.as_slice() as *const [u8] as *const str;
+            // SAFETY: `ptr` points to the bytes of the string, which have
+            // already been checked.
+            unsafe { str::check_bytes(ptr, context) }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc", feature = "bytecheck"))]
+mod tests {
+    use munge::munge;
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        api::high::{access, access_mut, to_bytes},
+        reserved::{ArchivedReservedString, ArchivedReservedVec},
+        with::Reserve,
+        Archive, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate)]
+    struct Log {
+        #[rkyv(with = Reserve<2>)]
+        entries: Vec<u32>,
+        #[rkyv(with = Reserve<4>)]
+        name: String,
+    }
+
+    #[test]
+    fn push_within_capacity() {
+        let value = Log {
+            entries: vec![1, 2],
+            name: String::from("log"),
+        };
+        let mut bytes = to_bytes::<Panic>(&value).unwrap();
+
+        {
+            let archived =
+                access_mut::<ArchivedLog, Panic>(&mut bytes).unwrap();
+            munge!(let ArchivedLog { mut entries, mut name } = archived);
+            assert_eq!(entries.capacity(), 4);
+            assert!(
+                ArchivedReservedVec::push(entries.as_mut(), 3.into()).is_ok()
+            );
+            assert!(
+                ArchivedReservedVec::push(entries.as_mut(), 4.into()).is_ok()
+            );
+            assert!(
+                ArchivedReservedVec::push(entries.as_mut(), 5.into()).is_err()
+            );
+
+            assert!(
+                ArchivedReservedString::push_str(name.as_mut(), "-01").is_ok()
+            );
+            assert!(ArchivedReservedString::push(name.as_mut(), 'é').is_err());
+            assert!(ArchivedReservedString::push(name.as_mut(), '!').is_ok());
+        }
+
+        let archived = access::<ArchivedLog, Panic>(&bytes).unwrap();
+        assert_eq!(archived.entries.as_slice(), [1u32, 2, 3, 4]);
+        assert_eq!(archived.name, "log-01!");
+        assert_eq!(archived.name.len(), archived.name.capacity());
+    }
+}
@@ -198,3 +198,22 @@ where
         }
     }
 }
+
+impl<T, U, E, F> PartialOrd<Result<T, E>> for ArchivedResult<U, F>
+where
+    U: PartialOrd<T>,
+    F: PartialOrd<E>,
+{
+    fn partial_cmp(&self, other: &Result<T, E>) -> Option<Ordering> {
+        match (self, other) {
+            (ArchivedResult::Ok(self_value), Ok(other_value)) => {
+                self_value.partial_cmp(other_value)
+            }
+            (ArchivedResult::Ok(_), Err(_)) => Some(Ordering::Less),
+            (ArchivedResult::Err(_), Ok(_)) => Some(Ordering::Greater),
+            (ArchivedResult::Err(self_err), Err(other_err)) => {
+                self_err.partial_cmp(other_err)
+            }
+        }
+    }
+}
@@ -0,0 +1,660 @@
+//! Machine-readable descriptions of the layouts of archived types.
+//!
+//! Archived types which implement [`Reflect`] describe their layout with a
+//! [`TypeSchema`]. Schemas list the names, offsets, and types of fields, the
+//! variants and tags of enums, and the types that archived types point to.
+//! Tools like archive viewers and bindings generators can use schemas instead
+//! of reading the generated code.
+//!
+//! Archived types generated by the derive macro implement `Reflect` when
+//! `#[rkyv(schema)]` is added to the derive input.
+//!
+//! Schemas can also be reduced to a [`fingerprint`], which changes whenever
+//! the layout of the archived type changes. Framed archives can record the
+//! fingerprint of their root type so that incompatible archives are rejected
+//! when they are accessed with [`access_versioned`].
+//!
+//! [`access_versioned`]: crate::api::framed::access_versioned
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     schema::{Reflect, SchemaKind},
+//!     Archive,
+//! };
+//!
+//! #[derive(Archive)]
+//! #[rkyv(schema)]
+//! struct Point {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let schema = &ArchivedPoint::SCHEMA;
+//! assert_eq!(schema.name, "ArchivedPoint");
+//! assert_eq!(schema.size, 8);
+//!
+//! let SchemaKind::Struct(fields) = schema.kind else {
+//!     panic!("expected a struct");
+//! };
+//! assert_eq!(fields[1].name, "y");
+//! assert_eq!(fields[1].offset, 4);
+//! assert_eq!(fields[1].schema.get().name, "f32");
+//! ```
+
+use core::{fmt, marker::PhantomData, mem};
+
+use crate::{
+    boxed::ArchivedBox,
+    option::ArchivedOption,
+    primitive::{
+        ArchivedChar, ArchivedF32, ArchivedF64, ArchivedI128, ArchivedI16,
+        ArchivedI32, ArchivedI64, ArchivedNonZeroI128, ArchivedNonZeroI16,
+        ArchivedNonZeroI32, ArchivedNonZeroI64, ArchivedNonZeroU128,
+        ArchivedNonZeroU16, ArchivedNonZeroU32, ArchivedNonZeroU64,
+        ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64, FixedUsize,
+    },
+    rc::{ArchivedRc, ArchivedRcWeak},
+    string::ArchivedString,
+    vec::ArchivedVec,
+    ArchivePointee,
+};
+
+/// An archived type which can describe its layout.
+pub trait Reflect {
+    /// The layout of the type.
+    const SCHEMA: TypeSchema;
+}
+
+/// The layout of an archived type.
+#[derive(Clone, Copy, Debug)]
+pub struct TypeSchema {
+    /// The name of the type.
+    ///
+    /// Generic types are named without their generic parameters.
+    pub name: &'static str,
+    /// The size of the type in bytes.
+    ///
+    /// Unsized types like slices have a size of zero.
+    pub size: usize,
+    /// The alignment of the type in bytes.
+    pub align: usize,
+    /// What kind of type this is.
+    pub kind: SchemaKind,
+}
+
+/// The kind of an archived type, along with any types it contains.
+#[derive(Clone, Copy, Debug)]
+pub enum SchemaKind {
+    /// A primitive like an integer, float, `bool`, or `char`.
+    Primitive,
+    /// A struct with the given fields.
+    Struct(&'static [FieldSchema]),
+    /// An enum with the given tag type and variants.
+    Enum {
+        /// The type of the tag at the start of the enum.
+        tag: SchemaRef,
+        /// The variants of the enum.
+        variants: &'static [VariantSchema],
+    },
+    /// An array of a fixed number of elements.
+    Array {
+        /// The type of the elements.
+        element: SchemaRef,
+        /// The number of elements.
+        len: usize,
+    },
+    /// An unsized slice of elements.
+    Slice(SchemaRef),
+    /// An unsized UTF-8 string slice.
+    Str,
+    /// An [`ArchivedString`].
+    String,
+    /// An [`ArchivedVec`] of the given element type.
+    Vec(SchemaRef),
+    /// An [`ArchivedOption`] of the given type.
+    Option(SchemaRef),
+    /// A relative pointer to a value of the given type, like an
+    /// [`ArchivedBox`] or [`ArchivedRc`].
+    Pointer(SchemaRef),
+}
+
+/// A reference to the schema of another type.
+///
+/// Schemas refer to the types they contain lazily so that recursive types can
+/// be described.
+#[derive(Clone, Copy)]
+pub struct SchemaRef(fn() -> &'static TypeSchema);
+
+impl SchemaRef {
+    /// Returns a reference to the schema of `T`.
+    pub const fn of<T: Reflect + ?Sized>() -> Self {
+        Self(schema_of::<T>)
+    }
+
+    /// Returns the referenced schema.
+    pub fn get(&self) -> &'static TypeSchema {
+        (self.0)()
+    }
+}
+
+fn schema_of<T: Reflect + ?Sized>() -> &'static TypeSchema {
+    &T::SCHEMA
+}
+
+impl fmt::Debug for SchemaRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SchemaRef").field(&self.get().name).finish()
+    }
+}
+
+/// A field of a struct or enum variant.
+#[derive(Clone, Copy, Debug)]
+pub struct FieldSchema {
+    /// The name of the field. Unnamed fields are named by their index.
+    pub name: &'static str,
+    /// The offset of the field from the start of the struct or enum, in bytes.
+    pub offset: usize,
+    /// The type of the field.
+    pub schema: SchemaRef,
+}
+
+/// A variant of an enum.
+#[derive(Clone, Copy, Debug)]
+pub struct VariantSchema {
+    /// The name of the variant.
+    pub name: &'static str,
+    /// The native value of the tag for the variant.
+    pub tag: u32,
+    /// The fields of the variant.
+    pub fields: &'static [FieldSchema],
+}
+
+macro_rules! impl_primitive {
+    ($($ty:ty: $name:literal),* $(,)?) => {
+        $(
+            impl Reflect for $ty {
+                const SCHEMA: TypeSchema = TypeSchema {
+                    name: $name,
+                    size: mem::size_of::<$ty>(),
+                    align: mem::align_of::<$ty>(),
+                    kind: SchemaKind::Primitive,
+                };
+            }
+        )*
+    };
+}
+
+impl_primitive!(
+    bool: "bool",
+    i8: "i8",
+    u8: "u8",
+    core::num::NonZeroI8: "NonZeroI8",
+    core::num::NonZeroU8: "NonZeroU8",
+    ArchivedI16: "i16",
+    ArchivedI32: "i32",
+    ArchivedI64: "i64",
+    ArchivedI128: "i128",
+    ArchivedU16: "u16",
+    ArchivedU32: "u32",
+    ArchivedU64: "u64",
+    ArchivedU128: "u128",
+    ArchivedF32: "f32",
+    ArchivedF64: "f64",
+    ArchivedChar: "char",
+    ArchivedNonZeroI16: "NonZeroI16",
+    ArchivedNonZeroI32: "NonZeroI32",
+    ArchivedNonZeroI64: "NonZeroI64",
+    ArchivedNonZeroI128: "NonZeroI128",
+    ArchivedNonZeroU16: "NonZeroU16",
+    ArchivedNonZeroU32: "NonZeroU32",
+    ArchivedNonZeroU64: "NonZeroU64",
+    ArchivedNonZeroU128: "NonZeroU128",
+);
+
+impl Reflect for () {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "()",
+        size: 0,
+        align: 1,
+        kind: SchemaKind::Struct(&[]),
+    };
+}
+
+impl<T: ?Sized> Reflect for PhantomData<T> {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "PhantomData",
+        size: 0,
+        align: 1,
+        kind: SchemaKind::Struct(&[]),
+    };
+}
+
+impl<T: Reflect, const N: usize> Reflect for [T; N] {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "array",
+        size: mem::size_of::<[T; N]>(),
+        align: mem::align_of::<[T; N]>(),
+        kind: SchemaKind::Array {
+            element: SchemaRef::of::<T>(),
+            len: N,
+        },
+    };
+}
+
+impl<T: Reflect> Reflect for [T] {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "slice",
+        size: 0,
+        align: mem::align_of::<T>(),
+        kind: SchemaKind::Slice(SchemaRef::of::<T>()),
+    };
+}
+
+impl Reflect for str {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "str",
+        size: 0,
+        align: 1,
+        kind: SchemaKind::Str,
+    };
+}
+
+impl Reflect for ArchivedString {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedString",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::String,
+    };
+}
+
+impl<T: Reflect> Reflect for ArchivedVec<T> {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedVec",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::Vec(SchemaRef::of::<T>()),
+    };
+}
+
+impl<T: Reflect> Reflect for ArchivedOption<T> {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedOption",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::Option(SchemaRef::of::<T>()),
+    };
+}
+
+impl<T: ArchivePointee + Reflect + ?Sized> Reflect for ArchivedBox<T> {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedBox",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::Pointer(SchemaRef::of::<T>()),
+    };
+}
+
+impl<T: ArchivePointee + Reflect + ?Sized, F> Reflect for ArchivedRc<T, F> {
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedRc",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::Pointer(SchemaRef::of::<T>()),
+    };
+}
+
+impl<T, F> Reflect for ArchivedRcWeak<T, F>
+where
+    T: ArchivePointee + Reflect + ?Sized,
+{
+    const SCHEMA: TypeSchema = TypeSchema {
+        name: "ArchivedRcWeak",
+        size: mem::size_of::<Self>(),
+        align: mem::align_of::<Self>(),
+        kind: SchemaKind::Pointer(SchemaRef::of::<T>()),
+    };
+}
+
+/// Returns a fingerprint of the archived layout of `T`.
+///
+/// The fingerprint is a 64-bit FNV-1a hash of the schema of `T` and every type
+/// it contains or points to, along with the endianness, alignment, and pointer
+/// width of the enabled format control features. It covers the names, sizes,
+/// alignments, and offsets of types and fields, the order of fields, and the
+/// names and tags of enum variants. It is stable across compilations and
+/// platforms as long as the archived layout is the same.
+///
+/// Recursive types are hashed once. A struct or enum with the same name and
+/// shape as one of the types that contains it is hashed as a reference back
+/// to that type.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{schema::fingerprint, Archive};
+///
+/// #[derive(Archive)]
+/// #[rkyv(schema)]
+/// struct Point {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Archive)]
+/// #[rkyv(schema)]
+/// struct Point3 {
+///     x: f32,
+///     y: f32,
+///     z: f32,
+/// }
+///
+/// assert_eq!(
+///     fingerprint::<ArchivedPoint>(),
+///     fingerprint::<ArchivedPoint>()
+/// );
+/// assert_ne!(
+///     fingerprint::<ArchivedPoint>(),
+///     fingerprint::<ArchivedPoint3>()
+/// );
+/// ```
+pub fn fingerprint<T: Reflect + ?Sized>() -> u64 {
+    let mut hasher = Fingerprinter::new();
+    hasher.write_u8(cfg!(feature = "big_endian") as u8);
+    hasher.write_u8(cfg!(feature = "unaligned") as u8);
+    hasher.write_usize(mem::size_of::<FixedUsize>());
+    hasher.write_schema(&T::SCHEMA, None);
+    hasher.0
+}
+
+/// The types which contain the type currently being hashed.
+struct Ancestors<'a> {
+    schema: &'static TypeSchema,
+    parent: Option<&'a Ancestors<'a>>,
+}
+
+struct Fingerprinter(u64);
+
+impl Fingerprinter {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.write(&[value]);
+    }
+
+    fn write_usize(&mut self, value: usize) {
+        self.write(&(value as u64).to_le_bytes());
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_usize(value.len());
+        self.write(value.as_bytes());
+    }
+
+    fn write_fields(
+        &mut self,
+        fields: &[FieldSchema],
+        ancestors: &Ancestors<'_>,
+    ) {
+        self.write_usize(fields.len());
+        for field in fields {
+            self.write_str(field.name);
+            self.write_usize(field.offset);
+            self.write_schema(field.schema.get(), Some(ancestors));
+        }
+    }
+
+    fn write_schema(
+        &mut self,
+        schema: &'static TypeSchema,
+        parent: Option<&Ancestors<'_>>,
+    ) {
+        if let SchemaKind::Struct(_) | SchemaKind::Enum { .. } = schema.kind {
+            let mut depth = 0;
+            let mut ancestor = parent;
+            while let Some(a) = ancestor {
+                if same_shape(a.schema, schema) {
+                    self.write_u8(0xff);
+                    self.write_usize(depth);
+                    return;
+                }
+                depth += 1;
+                ancestor = a.parent;
+            }
+        }
+
+        self.write_str(schema.name);
+        self.write_usize(schema.size);
+        self.write_usize(schema.align);
+
+        let ancestors = Ancestors { schema, parent };
+        match schema.kind {
+            SchemaKind::Primitive => self.write_u8(0),
+            SchemaKind::Struct(fields) => {
+                self.write_u8(1);
+                self.write_fields(fields, &ancestors);
+            }
+            SchemaKind::Enum { tag, variants } => {
+                self.write_u8(2);
+                self.write_schema(tag.get(), Some(&ancestors));
+                self.write_usize(variants.len());
+                for variant in variants {
+                    self.write_str(variant.name);
+                    self.write(&variant.tag.to_le_bytes());
+                    self.write_fields(variant.fields, &ancestors);
+                }
+            }
+            SchemaKind::Array { element, len } => {
+                self.write_u8(3);
+                self.write_usize(len);
+                self.write_schema(element.get(), Some(&ancestors));
+            }
+            SchemaKind::Slice(element) => {
+                self.write_u8(4);
+                self.write_schema(element.get(), Some(&ancestors));
+            }
+            SchemaKind::Str => self.write_u8(5),
+            SchemaKind::String => self.write_u8(6),
+            SchemaKind::Vec(element) => {
+                self.write_u8(7);
+                self.write_schema(element.get(), Some(&ancestors));
+            }
+            SchemaKind::Option(inner) => {
+                self.write_u8(8);
+                self.write_schema(inner.get(), Some(&ancestors));
+            }
+            SchemaKind::Pointer(pointee) => {
+                self.write_u8(9);
+                self.write_schema(pointee.get(), Some(&ancestors));
+            }
+        }
+    }
+}
+
+/// Returns whether two schemas have the same name, layout, and fields without
+/// comparing the types of their fields beyond their names and layouts.
+fn same_shape(a: &TypeSchema, b: &TypeSchema) -> bool {
+    fn same_fields(a: &[FieldSchema], b: &[FieldSchema]) -> bool {
+        a.len() == b.len()
+            && a.iter().zip(b).all(|(a, b)| {
+                let (a_schema, b_schema) = (a.schema.get(), b.schema.get());
+                a.name == b.name
+                    && a.offset == b.offset
+                    && a_schema.name == b_schema.name
+                    && a_schema.size == b_schema.size
+                    && a_schema.align == b_schema.align
+            })
+    }
+
+    if a.name != b.name || a.size != b.size || a.align != b.align {
+        return false;
+    }
+
+    match (a.kind, b.kind) {
+        (SchemaKind::Struct(a), SchemaKind::Struct(b)) => same_fields(a, b),
+        (
+            SchemaKind::Enum { variants: a, .. },
+            SchemaKind::Enum { variants: b, .. },
+        ) => {
+            a.len() == b.len()
+                && a.iter().zip(b).all(|(a, b)| {
+                    a.name == b.name
+                        && a.tag == b.tag
+                        && same_fields(a.fields, b.fields)
+                })
+        }
+        _ => false,
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use core::mem::{align_of, offset_of};
+
+    use super::{fingerprint, Reflect, SchemaKind, TypeSchema};
+    use crate::{
+        alloc::{boxed::Box, string::String, vec::Vec},
+        boxed::ArchivedBox,
+        Archive, Archived,
+    };
+
+    fn fields(schema: &TypeSchema) -> Vec<(&'static str, usize, &str)> {
+        let SchemaKind::Struct(fields) = schema.kind else {
+            panic!("expected a struct");
+        };
+        fields
+            .iter()
+            .map(|f| (f.name, f.offset, f.schema.get().name))
+            .collect()
+    }
+
+    #[derive(Archive)]
+    #[rkyv(crate, schema)]
+    struct Example {
+        id: u8,
+        value: u32,
+        name: String,
+        #[rkyv(skip)]
+        _cache: u64,
+        tree: Tree,
+    }
+
+    #[derive(Archive)]
+    #[rkyv(
+        crate,
+        schema,
+        bytecheck(bounds(__C: crate::validation::ArchiveContext)),
+    )]
+    enum Tree {
+        Leaf,
+        Node(u16, #[rkyv(omit_bounds)] Box<Tree>),
+        Named {
+            #[rkyv(omit_bounds)]
+            children: Vec<Tree>,
+        },
+    }
+
+    #[test]
+    fn struct_schema() {
+        let schema = &ArchivedExample::SCHEMA;
+        assert_eq!(schema.name, "ArchivedExample");
+        assert_eq!(schema.size, core::mem::size_of::<ArchivedExample>());
+        assert_eq!(
+            fields(schema),
+            [
+                ("id", offset_of!(ArchivedExample, id), "u8"),
+                ("value", offset_of!(ArchivedExample, value), "u32"),
+                ("name", offset_of!(ArchivedExample, name), "ArchivedString"),
+                ("_cache", offset_of!(ArchivedExample, _cache), "()"),
+                ("tree", offset_of!(ArchivedExample, tree), "ArchivedTree"),
+            ],
+        );
+    }
+
+    #[test]
+    fn enum_schema() {
+        let schema = &ArchivedTree::SCHEMA;
+        let SchemaKind::Enum { tag, variants } = schema.kind else {
+            panic!("expected an enum");
+        };
+        assert_eq!(tag.get().name, "u8");
+        assert_eq!(variants.len(), 3);
+
+        assert_eq!(variants[0].name, "Leaf");
+        assert_eq!(variants[0].tag, 0);
+        assert!(variants[0].fields.is_empty());
+
+        let node = &variants[1];
+        assert_eq!(node.name, "Node");
+        assert_eq!(node.tag, 1);
+        assert_eq!(node.fields[0].name, "0");
+        assert_eq!(node.fields[0].offset, align_of::<Archived<u16>>());
+        assert_eq!(node.fields[1].name, "1");
+        assert_eq!(
+            node.fields[1].offset,
+            (node.fields[0].offset + 2)
+                .next_multiple_of(align_of::<ArchivedBox<ArchivedTree>>()),
+        );
+
+        // Recursive types refer back to themselves.
+        let SchemaKind::Pointer(pointee) = node.fields[1].schema.get().kind
+        else {
+            panic!("expected a pointer");
+        };
+        assert_eq!(pointee.get().name, "ArchivedTree");
+
+        let SchemaKind::Vec(element) = variants[2].fields[0].schema.get().kind
+        else {
+            panic!("expected a vec");
+        };
+        assert_eq!(element.get().name, "ArchivedTree");
+        assert_eq!(<Archived<u32> as Reflect>::SCHEMA.size, 4);
+    }
+
+    mod v2 {
+        use super::Tree;
+        use crate::{alloc::string::String, Archive};
+
+        #[derive(Archive)]
+        #[rkyv(crate, schema)]
+        pub(super) struct Example {
+            id: u8,
+            count: u32,
+            name: String,
+            #[rkyv(skip)]
+            _cache: u64,
+            tree: Tree,
+        }
+    }
+
+    #[test]
+    fn fingerprints() {
+        let example = fingerprint::<ArchivedExample>();
+        assert_eq!(example, fingerprint::<ArchivedExample>());
+        assert_ne!(example, fingerprint::<v2::ArchivedExample>());
+        assert_ne!(example, fingerprint::<ArchivedTree>());
+
+        assert_ne!(
+            fingerprint::<Archived<Vec<u8>>>(),
+            fingerprint::<Archived<Vec<u16>>>(),
+        );
+        assert_ne!(
+            fingerprint::<Archived<Vec<Vec<Vec<u8>>>>>(),
+            fingerprint::<Archived<Vec<Vec<Vec<u16>>>>>(),
+        );
+    }
+}
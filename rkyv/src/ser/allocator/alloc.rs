@@ -1,5 +1,5 @@
 use core::{
-    alloc::Layout,
+    alloc::{GlobalAlloc, Layout},
     marker::PhantomData,
     mem::{align_of, size_of, ManuallyDrop},
     ptr::{slice_from_raw_parts_mut, NonNull},
@@ -15,11 +15,31 @@ struct Block {
     next_size: usize,
 }
 
+/// The global allocator.
+///
+/// This is the default backing allocator for [`Arena`]. It forwards all
+/// allocations to the registered `#[global_allocator]`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GlobalAllocator;
+
+unsafe impl GlobalAlloc for GlobalAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: The caller has guaranteed that `layout` has non-zero size.
+        unsafe { alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: The caller has guaranteed that `ptr` was allocated by this
+        // allocator with the given layout.
+        unsafe { dealloc(ptr, layout) }
+    }
+}
+
 impl Block {
-    fn alloc(size: usize) -> NonNull<Self> {
+    fn alloc<B: GlobalAlloc>(size: usize, backing: &B) -> NonNull<Self> {
         debug_assert!(size >= size_of::<Self>());
         let layout = Layout::from_size_align(size, align_of::<Self>()).unwrap();
-        let ptr = unsafe { alloc(layout).cast::<Self>() };
+        let ptr = unsafe { backing.alloc(layout).cast::<Self>() };
         let Some(ptr) = NonNull::new(ptr) else {
             handle_alloc_error(layout)
         };
@@ -34,12 +54,16 @@ impl Block {
         ptr
     }
 
-    unsafe fn dealloc(ptr: NonNull<Self>, size: usize) {
+    unsafe fn dealloc<B: GlobalAlloc>(
+        ptr: NonNull<Self>,
+        size: usize,
+        backing: &B,
+    ) {
         let layout = unsafe {
             Layout::from_size_align(size, align_of::<Self>()).unwrap_unchecked()
         };
         unsafe {
-            dealloc(ptr.as_ptr().cast(), layout);
+            backing.dealloc(ptr.as_ptr().cast(), layout);
         }
     }
 
@@ -69,19 +93,27 @@ impl Block {
 ///
 /// Reusing the same arena for multiple serializations will reduce the number of
 /// global allocations, which can save a considerable amount of time.
-pub struct Arena {
+///
+/// Arena blocks are allocated from a backing allocator `B`, which defaults to
+/// the [`GlobalAllocator`]. Any type implementing [`GlobalAlloc`] (for example
+/// a jemalloc pool, a hugepage allocator, or a per-thread slab) can be used
+/// with [`new_in`](Arena::new_in) and
+/// [`with_capacity_in`](Arena::with_capacity_in).
+pub struct Arena<B: GlobalAlloc = GlobalAllocator> {
     head_ptr: NonNull<Block>,
+    backing: B,
 }
 
-// SAFETY: Arena is safe to send to other threads
-unsafe impl Send for Arena {}
+// SAFETY: Arena is safe to send to other threads as long as its backing
+// allocator is.
+unsafe impl<B: GlobalAlloc + Send> Send for Arena<B> {}
 
-impl Drop for Arena {
+impl<B: GlobalAlloc> Drop for Arena<B> {
     fn drop(&mut self) {
         self.shrink();
         let head_size = unsafe { self.head_ptr.as_ref().next_size };
         unsafe {
-            Block::dealloc(self.head_ptr, head_size);
+            Block::dealloc(self.head_ptr, head_size, &self.backing);
         }
     }
 }
@@ -92,14 +124,53 @@ impl Arena {
 
     /// Creates a new `Arena` with the default capacity.
     pub fn new() -> Self {
-        Self::with_capacity(Self::DEFAULT_CAPACITY)
+        Self::new_in(GlobalAllocator)
     }
 
     /// Creates a new `Arena` with at least the requested capacity.
     pub fn with_capacity(cap: usize) -> Self {
+        Self::with_capacity_in(cap, GlobalAllocator)
+    }
+
+    /// Consumes the `Arena`, returning a raw pointer.
+    pub fn into_raw(self) -> NonNull<()> {
+        let this = ManuallyDrop::new(self);
+        this.head_ptr.cast()
+    }
+
+    /// Constructs an arena from a raw pointer.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must have been returned from `into_raw`. `from_raw` takes
+    /// ownership over the pointer, and so `from_raw` must not be called on the
+    /// same pointer more than once.
+    pub unsafe fn from_raw(raw: NonNull<()>) -> Self {
+        Self {
+            head_ptr: raw.cast(),
+            backing: GlobalAllocator,
+        }
+    }
+}
+
+impl<B: GlobalAlloc> Arena<B> {
+    /// Creates a new `Arena` with the default capacity which allocates its
+    /// blocks from the given backing allocator.
+    pub fn new_in(backing: B) -> Self {
+        Self::with_capacity_in(<Arena>::DEFAULT_CAPACITY, backing)
+    }
+
+    /// Creates a new `Arena` with at least the requested capacity which
+    /// allocates its blocks from the given backing allocator.
+    pub fn with_capacity_in(cap: usize, backing: B) -> Self {
         let head_size = (cap + size_of::<Block>()).next_power_of_two();
-        let head_ptr = Block::alloc(head_size);
-        Self { head_ptr }
+        let head_ptr = Block::alloc(head_size, &backing);
+        Self { head_ptr, backing }
+    }
+
+    /// Returns a reference to the backing allocator of the arena.
+    pub fn backing(&self) -> &B {
+        &self.backing
     }
 
     /// Cleans up allocated blocks which are no longer in use.
@@ -125,7 +196,7 @@ impl Arena {
             if next_ptr == self.head_ptr {
                 // End of the loop. Free the head block.
                 unsafe {
-                    Block::dealloc(next_ptr, next_size);
+                    Block::dealloc(next_ptr, next_size, &self.backing);
                 }
 
                 // Loop the head back on itself.
@@ -137,7 +208,7 @@ impl Arena {
             }
 
             unsafe {
-                Block::dealloc(current_ptr, current_size);
+                Block::dealloc(current_ptr, current_size, &self.backing);
             }
 
             current_ptr = next_ptr;
@@ -162,35 +233,17 @@ impl Arena {
     /// Acquires a handle to the arena.
     ///
     /// The returned handle has exclusive allocation rights in the arena.
-    pub fn acquire(&mut self) -> ArenaHandle<'_> {
+    pub fn acquire(&mut self) -> ArenaHandle<'_, B> {
         self.shrink();
 
         ArenaHandle {
             tail_ptr: self.head_ptr,
             tail_size: unsafe { self.head_ptr.as_ref().next_size },
             used: size_of::<Block>(),
+            backing: &self.backing,
             _phantom: PhantomData,
         }
     }
-
-    /// Consumes the `Arena`, returning a raw pointer.
-    pub fn into_raw(self) -> NonNull<()> {
-        let this = ManuallyDrop::new(self);
-        this.head_ptr.cast()
-    }
-
-    /// Constructs an arena from a raw pointer.
-    ///
-    /// # Safety
-    ///
-    /// `raw` must have been returned from `into_raw`. `from_raw` takes
-    /// ownership over the pointer, and so `from_raw` must not be called on the
-    /// same pointer more than once.
-    pub unsafe fn from_raw(raw: NonNull<()>) -> Self {
-        Self {
-            head_ptr: raw.cast(),
-        }
-    }
 }
 
 impl Default for Arena {
@@ -200,17 +253,19 @@ impl Default for Arena {
 }
 
 /// A handle which can allocate within an arena.
-pub struct ArenaHandle<'a> {
+pub struct ArenaHandle<'a, B: GlobalAlloc = GlobalAllocator> {
     tail_ptr: NonNull<Block>,
     tail_size: usize,
     used: usize,
-    _phantom: PhantomData<&'a mut Arena>,
+    backing: &'a B,
+    _phantom: PhantomData<&'a mut Arena<B>>,
 }
 
-// SAFETY: ArenaHandle is safe to send to other threads
-unsafe impl Send for ArenaHandle<'_> {}
+// SAFETY: ArenaHandle is safe to send to other threads as long as its backing
+// allocator can be shared between threads.
+unsafe impl<B: GlobalAlloc + Sync> Send for ArenaHandle<'_, B> {}
 
-unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
+unsafe impl<B: GlobalAlloc, E> Allocator<E> for ArenaHandle<'_, B> {
     unsafe fn push_alloc(
         &mut self,
         layout: Layout,
@@ -226,7 +281,7 @@ unsafe impl<E> Allocator<E> for ArenaHandle<'_> {
                 (size_of::<Block>() + layout.size() + layout.align())
                     .next_power_of_two(),
             );
-            let next = Block::alloc(size);
+            let next = Block::alloc(size, self.backing);
             unsafe {
                 Block::push_next(self.tail_ptr, next);
             }
@@ -320,4 +375,45 @@ mod tests {
                 .always_ok();
         }
     }
+
+    #[test]
+    fn custom_backing() {
+        use core::{
+            alloc::GlobalAlloc,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        use crate::ser::allocator::GlobalAllocator;
+
+        static LIVE: AtomicUsize = AtomicUsize::new(0);
+        static TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+        struct Counting;
+
+        unsafe impl GlobalAlloc for Counting {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                LIVE.fetch_add(1, Ordering::Relaxed);
+                TOTAL.fetch_add(1, Ordering::Relaxed);
+                unsafe { GlobalAllocator.alloc(layout) }
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                LIVE.fetch_sub(1, Ordering::Relaxed);
+                unsafe { GlobalAllocator.dealloc(ptr, layout) }
+            }
+        }
+
+        let mut arena = Arena::with_capacity_in(2, Counting);
+        let value = vec![vec![1, 2], vec![3, 4], vec![5, 6], vec![7, 8]];
+        to_bytes_in_with_alloc::<_, _, Panic>(
+            &value,
+            AlignedVec::<16>::new(),
+            arena.acquire(),
+        )
+        .unwrap();
+        assert!(TOTAL.load(Ordering::Relaxed) > 1);
+
+        drop(arena);
+        assert_eq!(LIVE.load(Ordering::Relaxed), 0);
+    }
 }
@@ -98,3 +98,93 @@ where
         Ok(())
     }
 }
+
+/// A fixed-size memory space for sub-allocating scratch space, stored inline.
+///
+/// This is a convenient alternative to managing a byte slice for a
+/// [`SubAllocator`] when the amount of scratch space required is known at
+/// compile time.
+///
+/// `StackAllocator` does not implement [`Allocator`] itself. Instead,
+/// [`acquire`](StackAllocator::acquire) is the entry point for allocating: it
+/// returns a [`SubAllocator`] which implements `Allocator` and borrows the
+/// buffer, so the buffer can't be moved while scratch space is allocated from
+/// it. Pass the acquired sub-allocator anywhere an `Allocator` is expected.
+///
+/// # Examples
+///
+/// ```
+/// use core::mem::MaybeUninit;
+///
+/// use rkyv::{
+///     api::low::to_bytes_in_with_alloc,
+///     rancor::Failure,
+///     ser::{allocator::StackAllocator, writer::Buffer},
+///     util::Align,
+/// };
+///
+/// let value = [[1u8, 2], [3, 4]];
+/// let mut output = Align([MaybeUninit::<u8>::uninit(); 64]);
+/// let mut scratch = StackAllocator::<128>::new();
+/// let buffer = to_bytes_in_with_alloc::<_, _, Failure>(
+///     &value,
+///     Buffer::from(&mut *output),
+///     scratch.acquire(),
+/// )
+/// .unwrap();
+/// assert_eq!(&*buffer, &[1, 2, 3, 4]);
+/// ```
+#[derive(Debug)]
+pub struct StackAllocator<const N: usize> {
+    bytes: [MaybeUninit<u8>; N],
+}
+
+impl<const N: usize> StackAllocator<N> {
+    /// Creates a new stack allocator.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [MaybeUninit::uninit(); N],
+        }
+    }
+
+    /// Acquires a sub-allocator for the buffer.
+    ///
+    /// The returned sub-allocator starts with the entire buffer free, and has
+    /// exclusive allocation rights in it.
+    pub fn acquire(&mut self) -> SubAllocator<'_> {
+        SubAllocator::new(&mut self.bytes)
+    }
+}
+
+impl<const N: usize> Default for StackAllocator<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::alloc::Layout;
+
+    use rancor::{Failure, Panic, ResultExt as _};
+
+    use crate::ser::{allocator::StackAllocator, Allocator};
+
+    #[test]
+    fn stack_allocator() {
+        let mut scratch = StackAllocator::<48>::new();
+        let mut alloc = scratch.acquire();
+        let layout = Layout::from_size_align(32, 8).unwrap();
+
+        unsafe {
+            let a =
+                Allocator::<Panic>::push_alloc(&mut alloc, layout).always_ok();
+            assert_eq!(a.as_ptr().cast::<u8>() as usize % 8, 0);
+            Allocator::<Failure>::push_alloc(&mut alloc, layout)
+                .expect_err("allocating past the end of the buffer must fail");
+            Allocator::<Panic>::pop_alloc(&mut alloc, a.cast(), layout)
+                .always_ok();
+            Allocator::<Panic>::push_alloc(&mut alloc, layout).always_ok();
+        }
+    }
+}
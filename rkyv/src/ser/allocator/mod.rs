@@ -3,6 +3,8 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod core;
+#[cfg(feature = "std")]
+mod std;
 
 use ::core::{alloc::Layout, ptr::NonNull};
 use rancor::{Fallible, Strategy};
@@ -10,6 +12,8 @@ use rancor::{Fallible, Strategy};
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
 pub use self::core::*;
+#[cfg(feature = "std")]
+pub use self::std::*;
 
 /// A serializer that can allocate scratch space.
 ///
@@ -73,6 +77,10 @@ pub struct AllocationStats {
     pub max_allocations: usize,
     /// Returns the maximum alignment of requested allocations.
     pub max_alignment: usize,
+    /// Returns the total number of allocations that were made.
+    pub total_allocations: usize,
+    /// Returns the size in bytes of the largest single allocation.
+    pub max_allocation_size: usize,
 }
 
 impl AllocationStats {
@@ -105,6 +113,9 @@ impl AllocationStats {
         self.max_allocations =
             usize::max(self.allocations, self.max_allocations);
         self.max_alignment = usize::max(self.max_alignment, layout.align());
+        self.total_allocations += 1;
+        self.max_allocation_size =
+            usize::max(self.max_allocation_size, layout.size());
     }
 
     #[inline]
@@ -115,6 +126,10 @@ impl AllocationStats {
 }
 
 /// A passthrough allocator that tracks usage.
+///
+/// The recorded [`AllocationStats`] can be used to size a fixed scratch
+/// buffer, like the one backing a [`SubAllocator`], for environments that
+/// can't allocate.
 pub struct AllocationTracker<T> {
     inner: T,
     stats: AllocationStats,
@@ -131,14 +146,32 @@ impl<T> AllocationTracker<T> {
                 max_bytes_allocated: 0,
                 max_allocations: 0,
                 max_alignment: 1,
+                total_allocations: 0,
+                max_allocation_size: 0,
             },
         }
     }
 
+    /// Returns the allocation stats accumulated so far.
+    pub fn stats(&self) -> &AllocationStats {
+        &self.stats
+    }
+
+    /// Returns a reference to the wrapped allocator.
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
     /// Returns the allocation stats accumulated during serialization.
     pub fn into_stats(self) -> AllocationStats {
         self.stats
     }
+
+    /// Consumes the allocation tracker and returns the wrapped allocator and
+    /// the allocation stats accumulated during serialization.
+    pub fn into_parts(self) -> (T, AllocationStats) {
+        (self.inner, self.stats)
+    }
 }
 
 unsafe impl<T: Allocator<E>, E> Allocator<E> for AllocationTracker<T> {
@@ -217,6 +250,8 @@ mod tests {
         assert_eq!(stats.max_alignment, 1);
         assert_eq!(stats.min_arena_capacity(), 0);
         assert_eq!(stats.min_arena_capacity_max_error(), 0);
+        assert_eq!(stats.total_allocations, 0);
+        assert_eq!(stats.max_allocation_size, 0);
     }
 
     #[cfg(feature = "alloc")]
@@ -241,5 +276,7 @@ mod tests {
         assert_ne!(stats.max_bytes_allocated, 0);
         assert_eq!(stats.max_allocations, 1);
         assert_ne!(stats.min_arena_capacity(), 0);
+        assert_eq!(stats.total_allocations, 1);
+        assert_eq!(stats.max_allocation_size, stats.max_bytes_allocated);
     }
 }
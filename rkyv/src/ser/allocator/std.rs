@@ -0,0 +1,165 @@
+use core::ops::{Deref, DerefMut};
+use std::sync::Mutex;
+
+use crate::ser::allocator::Arena;
+
+/// A thread-safe pool of reusable [`Arena`]s.
+///
+/// Checking out an arena from the pool reuses a warmed arena if one is
+/// available, which avoids regrowing a fresh arena for every serialization.
+/// Arenas are returned to the pool when the [`PooledArena`] is dropped.
+///
+/// The pool can be configured to cap the memory it retains: at most
+/// `max_arenas` arenas are kept idle, and arenas which have grown beyond
+/// `max_capacity` bytes are freed instead of being returned to the pool.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_with_alloc, rancor::Error,
+///     ser::allocator::ArenaPool,
+/// };
+///
+/// let pool = ArenaPool::new();
+/// for i in 0..10 {
+///     let mut arena = pool.checkout();
+///     let value = vec![i.to_string(); 4];
+///     to_bytes_with_alloc::<_, Error>(&value, arena.acquire()).unwrap();
+/// }
+/// assert_eq!(pool.idle(), 1);
+/// ```
+pub struct ArenaPool {
+    arenas: Mutex<Vec<Arena>>,
+    max_arenas: usize,
+    max_capacity: usize,
+}
+
+impl ArenaPool {
+    /// Creates a new arena pool which retains any number of arenas of any
+    /// capacity.
+    pub fn new() -> Self {
+        Self::with_limits(usize::MAX, usize::MAX)
+    }
+
+    /// Creates a new arena pool which retains at most `max_arenas` idle arenas
+    /// and frees arenas with a capacity greater than `max_capacity` bytes.
+    pub fn with_limits(max_arenas: usize, max_capacity: usize) -> Self {
+        Self {
+            arenas: Mutex::new(Vec::new()),
+            max_arenas,
+            max_capacity,
+        }
+    }
+
+    /// Checks out an arena from the pool, creating a new arena if none are
+    /// idle.
+    pub fn checkout(&self) -> PooledArena<'_> {
+        let arena = self.lock().pop().unwrap_or_default();
+        PooledArena {
+            arena: Some(arena),
+            pool: self,
+        }
+    }
+
+    /// Returns the number of idle arenas in the pool.
+    pub fn idle(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Frees all idle arenas in the pool.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<Arena>> {
+        // The pool is never left in an inconsistent state, so it's safe to
+        // ignore poisoning.
+        self.arenas.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn release(&self, mut arena: Arena) {
+        if arena.shrink() > self.max_capacity {
+            return;
+        }
+        let mut arenas = self.lock();
+        if arenas.len() < self.max_arenas {
+            arenas.push(arena);
+        }
+    }
+}
+
+impl Default for ArenaPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An [`Arena`] checked out from an [`ArenaPool`].
+///
+/// The arena is returned to the pool when this is dropped.
+pub struct PooledArena<'a> {
+    arena: Option<Arena>,
+    pool: &'a ArenaPool,
+}
+
+impl Deref for PooledArena<'_> {
+    type Target = Arena;
+
+    fn deref(&self) -> &Self::Target {
+        self.arena.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledArena<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.arena.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledArena<'_> {
+    fn drop(&mut self) {
+        if let Some(arena) = self.arena.take() {
+            self.pool.release(arena);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{api::high::to_bytes_with_alloc, ser::allocator::ArenaPool};
+
+    #[test]
+    fn reuse_pooled_arenas() {
+        let pool = ArenaPool::with_limits(2, usize::MAX);
+
+        let a = pool.checkout();
+        let b = pool.checkout();
+        let c = pool.checkout();
+        drop((a, b, c));
+        assert_eq!(pool.idle(), 2);
+
+        let mut arena = pool.checkout();
+        assert_eq!(pool.idle(), 1);
+        let value = vec![vec![0u8; 4096]; 4];
+        to_bytes_with_alloc::<_, Panic>(&value, arena.acquire()).unwrap();
+        drop(arena);
+        assert_eq!(pool.idle(), 2);
+
+        pool.clear();
+        assert_eq!(pool.idle(), 0);
+    }
+
+    #[test]
+    fn cap_retained_capacity() {
+        let pool = ArenaPool::with_limits(usize::MAX, 1024);
+
+        let mut arena = pool.checkout();
+        let value = vec![vec![1u32]; 1024];
+        to_bytes_with_alloc::<_, Panic>(&value, arena.acquire()).unwrap();
+        assert!(arena.capacity() > 1024);
+        drop(arena);
+        assert_eq!(pool.idle(), 0);
+    }
+}
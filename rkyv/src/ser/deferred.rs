@@ -0,0 +1,116 @@
+use core::{alloc::Layout, ptr::NonNull};
+
+use rancor::Fallible;
+
+use crate::{
+    alloc::vec::Vec,
+    ser::{
+        sharing::{SharedValue, SharingState},
+        Allocator, Positional, Sharing, Writer,
+    },
+};
+
+/// A serializer adapter which buffers everything written to it so that it can
+/// be written to the underlying serializer later.
+///
+/// The buffered bytes are positioned as if they would be written at `base`
+/// in the underlying serializer. Allocation and pointer sharing are forwarded
+/// to the underlying serializer, so shared pointers may refer to values
+/// serialized on either side.
+///
+/// This is used to write values before their dependencies, for example to
+/// serialize the elements of a vector from a single pass over an iterator.
+#[derive(Debug)]
+pub struct Deferred<'a, S: ?Sized> {
+    inner: &'a mut S,
+    base: usize,
+    bytes: Vec<u8>,
+}
+
+impl<'a, S: ?Sized> Deferred<'a, S> {
+    /// Returns a new `Deferred` which buffers bytes as if they were written
+    /// starting at `base` in the given serializer.
+    pub fn new(inner: &'a mut S, base: usize) -> Self {
+        Self {
+            inner,
+            base,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Returns the position in the underlying serializer where the buffered
+    /// bytes must be written.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    /// Consumes the `Deferred` and returns the buffered bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+impl<S: Fallible + ?Sized> Fallible for Deferred<'_, S> {
+    type Error = S::Error;
+}
+
+impl<S: ?Sized> Positional for Deferred<'_, S> {
+    fn pos(&self) -> usize {
+        self.base + self.bytes.len()
+    }
+}
+
+impl<S: ?Sized, E> Writer<E> for Deferred<'_, S> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.bytes.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+unsafe impl<S: Allocator<E> + ?Sized, E> Allocator<E> for Deferred<'_, S> {
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        // SAFETY: The safety requirements for `S::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        unsafe { self.inner.push_alloc(layout) }
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `S::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        unsafe { self.inner.pop_alloc(ptr, layout) }
+    }
+}
+
+impl<S: Sharing<E> + ?Sized, E> Sharing<E> for Deferred<'_, S> {
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        self.inner.start_sharing(address)
+    }
+
+    fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.inner.finish_sharing(address, pos)
+    }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        self.inner.start_sharing_value(address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        self.inner.finish_sharing_value(address, archived, pos)
+    }
+}
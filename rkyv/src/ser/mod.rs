@@ -1,17 +1,25 @@
 //! Serialization traits and adapters.
 
 pub mod allocator;
+#[cfg(feature = "alloc")]
+mod deferred;
+#[cfg(feature = "rayon-1")]
+pub mod parallel;
 pub mod sharing;
 pub mod writer;
 
 use ::core::{alloc::Layout, ptr::NonNull};
 
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::deferred::Deferred;
 #[doc(inline)]
 pub use self::{
     allocator::Allocator,
     sharing::{Sharing, SharingExt},
     writer::{Positional, Writer, WriterExt},
 };
+use crate::ser::sharing::Unshare;
 
 /// A serializer built from composeable pieces.
 #[derive(Debug, Default)]
@@ -24,6 +32,47 @@ pub struct Serializer<W, A, S> {
     pub sharing: S,
 }
 
+impl Serializer<(), (), Unshare> {
+    /// Returns a builder for a serializer.
+    ///
+    /// Pointer sharing defaults to [`Unshare`] if it is not specified. A
+    /// writer must always be provided: [`finish`](SerializerBuilder::finish)
+    /// is only available once one has been set with
+    /// [`writer`](SerializerBuilder::writer). If no allocator is set, the
+    /// serializer doesn't implement [`Allocator`], so serializing values which
+    /// need scratch space (like a `Vec<String>`) fails to compile instead of
+    /// failing at runtime.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rkyv::{
+    ///     api::serialize_using,
+    ///     rancor::Error,
+    ///     ser::{allocator::Arena, Serializer},
+    ///     util::AlignedVec,
+    /// };
+    ///
+    /// let mut arena = Arena::new();
+    /// let mut serializer = Serializer::build()
+    ///     .writer(AlignedVec::<16>::new())
+    ///     .arena(&mut arena)
+    ///     .share()
+    ///     .finish();
+    ///
+    /// let value = vec!["a".to_string(), "b".to_string()];
+    /// serialize_using::<_, Error>(&value, &mut serializer).unwrap();
+    /// let bytes = serializer.into_writer();
+    /// ```
+    pub fn build() -> SerializerBuilder<(), (), Unshare> {
+        SerializerBuilder {
+            writer: (),
+            allocator: (),
+            sharing: Unshare,
+        }
+    }
+}
+
 impl<W, A, S> Serializer<W, A, S> {
     /// Creates a new serializer from a writer, allocator, and pointer sharing.
     pub fn new(writer: W, allocator: A, sharing: S) -> Self {
@@ -57,6 +106,10 @@ impl<W: Writer<E>, A, S, E> Writer<E> for Serializer<W, A, S> {
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         self.writer.write(bytes)
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        self.writer.write_vectored(bufs)
+    }
 }
 
 unsafe impl<W, A: Allocator<E>, S, E> Allocator<E> for Serializer<W, A, S> {
@@ -88,4 +141,87 @@ impl<W, A, S: Sharing<E>, E> Sharing<E> for Serializer<W, A, S> {
     fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
         self.sharing.finish_sharing(address, pos)
     }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<sharing::SharedValue<'_>>,
+    ) -> sharing::SharingState {
+        self.sharing.start_sharing_value(address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<sharing::SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        self.sharing.finish_sharing_value(address, archived, pos)
+    }
+}
+
+/// A builder for a [`Serializer`].
+///
+/// See [`Serializer::build`] for more information.
+#[derive(Debug)]
+pub struct SerializerBuilder<W, A, S> {
+    writer: W,
+    allocator: A,
+    sharing: S,
+}
+
+impl<W, A, S> SerializerBuilder<W, A, S> {
+    /// Sets the writer of the serializer.
+    pub fn writer<W2>(self, writer: W2) -> SerializerBuilder<W2, A, S> {
+        SerializerBuilder {
+            writer,
+            allocator: self.allocator,
+            sharing: self.sharing,
+        }
+    }
+
+    /// Sets the allocator of the serializer.
+    pub fn allocator<A2>(self, allocator: A2) -> SerializerBuilder<W, A2, S> {
+        SerializerBuilder {
+            writer: self.writer,
+            allocator,
+            sharing: self.sharing,
+        }
+    }
+
+    /// Sets the allocator of the serializer to a handle to the given arena.
+    #[cfg(feature = "alloc")]
+    pub fn arena(
+        self,
+        arena: &mut allocator::Arena,
+    ) -> SerializerBuilder<W, allocator::ArenaHandle<'_>, S> {
+        self.allocator(arena.acquire())
+    }
+
+    /// Sets the pointer sharing of the serializer.
+    pub fn sharing<S2>(self, sharing: S2) -> SerializerBuilder<W, A, S2> {
+        SerializerBuilder {
+            writer: self.writer,
+            allocator: self.allocator,
+            sharing,
+        }
+    }
+
+    /// Sets the pointer sharing of the serializer to [`Share`](sharing::Share).
+    #[cfg(feature = "alloc")]
+    pub fn share(self) -> SerializerBuilder<W, A, sharing::Share> {
+        self.sharing(sharing::Share::new())
+    }
+
+    /// Sets the pointer sharing of the serializer to [`Unshare`].
+    pub fn unshare(self) -> SerializerBuilder<W, A, Unshare> {
+        self.sharing(Unshare)
+    }
+}
+
+impl<W: Positional, A, S> SerializerBuilder<W, A, S> {
+    /// Builds the serializer.
+    pub fn finish(self) -> Serializer<W, A, S> {
+        Serializer::new(self.writer, self.allocator, self.sharing)
+    }
 }
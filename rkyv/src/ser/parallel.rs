@@ -0,0 +1,388 @@
+//! Parallel serialization for large vectors.
+//!
+//! Serializing a vector is usually a single pass over its elements which
+//! writes the out-of-line data of each element followed by the elements
+//! themselves. When a vector has many independent elements, the out-of-line
+//! data of each element can be serialized on its own as long as its final
+//! position in the archive is known ahead of time.
+//!
+//! [`serialize_vec`] splits the elements of a slice into chunks and serializes
+//! them on the rayon thread pool in two passes. The first pass measures how
+//! many bytes the out-of-line data of each chunk takes up, which determines
+//! where each chunk will be written. The second pass serializes each chunk into
+//! its own buffer at its final position and resolves its elements. Finally,
+//! the buffers are stitched together in order.
+//!
+//! Each chunk is serialized with its own arena and pointer sharing, so shared
+//! pointers are only deduplicated within a chunk. Sharing pointers between
+//! threads would make the layout of each chunk depend on which thread happened
+//! to serialize a shared value first, so values which are shared between
+//! chunks are serialized once for each chunk they appear in.
+//!
+//! The out-of-line data of each chunk starts on a 64-byte boundary. Padding
+//! inside of a chunk only depends on the position of the chunk modulo the
+//! largest alignment used inside of it, so this lets the first pass measure
+//! each chunk at position zero. Chunks which contain data aligned to more than
+//! 64 bytes (or the alignment of the archived element, if greater) fail to
+//! serialize with an error.
+
+use core::{
+    error::Error,
+    fmt,
+    mem::{align_of, size_of},
+};
+
+use rancor::{fail, Fallible, Source, Strategy};
+use rayon_1::{
+    iter::{
+        IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator,
+    },
+    slice::ParallelSlice,
+};
+
+use crate::{
+    alloc::vec::Vec,
+    api::{high::HighSerializer, serialize_using},
+    ser::{
+        allocator::ArenaHandle, sharing::Share, writer::LimitedWriter,
+        Positional, Serializer, Writer, WriterExt,
+    },
+    util::{with_arena, AlignedVec},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Place, Serialize,
+};
+
+/// The serializer used to serialize each chunk of a parallel vector.
+pub type ParallelSerializer<'a, E> =
+    HighSerializer<ChunkWriter, ArenaHandle<'a>, E>;
+
+const MAX_ALIGNMENT: usize = 64;
+
+/// Returns the alignment of the out-of-line data of each chunk of a vector of
+/// `T`.
+const fn chunk_alignment<T: Archive>() -> usize {
+    if align_of::<T::Archived>() > MAX_ALIGNMENT {
+        align_of::<T::Archived>()
+    } else {
+        MAX_ALIGNMENT
+    }
+}
+
+#[derive(Debug)]
+struct ChunkSizeMismatch {
+    expected: usize,
+    actual: usize,
+    align: usize,
+}
+
+impl fmt::Display for ChunkSizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "a chunk of a parallel vector serialized to {} bytes, but {} \
+             bytes were reserved for it\nhelp: chunks of parallel vectors are \
+             aligned to {} bytes, so data inside of them can't be aligned to \
+             more than that",
+            self.actual, self.expected, self.align,
+        )
+    }
+}
+
+impl Error for ChunkSizeMismatch {}
+
+/// A writer which buffers the bytes of a chunk of a parallel vector.
+///
+/// The buffered bytes are positioned as if they were written at the final
+/// position of the chunk in the archive. This is created by [`serialize_vec`]
+/// for each chunk of elements, and can't be constructed directly.
+#[derive(Debug)]
+pub struct ChunkWriter {
+    base: usize,
+    len: usize,
+    bytes: Option<Vec<u8>>,
+}
+
+impl ChunkWriter {
+    /// Returns a writer which only counts the bytes written to it.
+    ///
+    /// The writer starts at position zero, which has the same padding as the
+    /// final position of every chunk because chunks are aligned to
+    /// [`chunk_alignment`].
+    fn sizing() -> Self {
+        Self {
+            base: 0,
+            len: 0,
+            bytes: None,
+        }
+    }
+
+    /// Returns a writer which buffers bytes as if they were written starting
+    /// at `base`.
+    fn new(base: usize) -> Self {
+        Self {
+            base,
+            len: 0,
+            bytes: Some(Vec::new()),
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        self.bytes.unwrap_or_default()
+    }
+}
+
+impl Positional for ChunkWriter {
+    fn pos(&self) -> usize {
+        self.base + self.len
+    }
+}
+
+impl<E> Writer<E> for ChunkWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.len += bytes.len();
+        if let Some(buffer) = &mut self.bytes {
+            buffer.extend_from_slice(bytes);
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the out-of-line data of a chunk of elements into the given
+/// writer, returning the writer and the resolvers for the elements.
+fn serialize_chunk<T, E>(
+    values: &[T],
+    writer: ChunkWriter,
+) -> Result<(ChunkWriter, Vec<T::Resolver>), E>
+where
+    T: for<'a> Serialize<ParallelSerializer<'a, E>>,
+    E: Source,
+{
+    with_arena(|arena| {
+        let mut serializer = Serializer::new(
+            LimitedWriter::new(writer),
+            arena.acquire(),
+            Share::new(),
+        );
+        let strategy = Strategy::<_, E>::wrap(&mut serializer);
+        let mut resolvers = Vec::with_capacity(values.len());
+        for value in values {
+            resolvers.push(value.serialize(strategy)?);
+        }
+        Ok((serializer.into_writer().into_inner(), resolvers))
+    })
+}
+
+/// Serializes the elements of a slice as an archived vector, serializing
+/// chunks of elements in parallel on the rayon thread pool.
+///
+/// The returned resolver can be used to resolve an [`ArchivedVec`] the same as
+/// the resolver returned from
+/// [`serialize_from_slice`](ArchivedVec::serialize_from_slice). Each element is
+/// serialized twice: once to measure its size, and once to write it. Elements
+/// must serialize to the same number of bytes each time.
+///
+/// See the [module docs](crate::ser::parallel) for how pointers are shared.
+pub fn serialize_vec<T, S>(
+    values: &[T],
+    serializer: &mut S,
+) -> Result<VecResolver, S::Error>
+where
+    T: Sync + for<'a> Serialize<ParallelSerializer<'a, S::Error>>,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source + Send,
+{
+    let chunk_size = values
+        .len()
+        .div_ceil(rayon_1::current_num_threads() * 4)
+        .max(1);
+
+    let sizes = values
+        .par_chunks(chunk_size)
+        .map(|chunk| {
+            serialize_chunk::<T, S::Error>(chunk, ChunkWriter::sizing())
+                .map(|(writer, _)| writer.len)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bases = Vec::with_capacity(sizes.len());
+    let chunk_align = chunk_alignment::<T>();
+    let mut end = serializer.align(chunk_align)?;
+    for size in sizes.iter() {
+        bases.push(end);
+        end = (end + size).next_multiple_of(chunk_align);
+    }
+    let pos = end.next_multiple_of(align_of::<T::Archived>());
+    let chunk_len = chunk_size * size_of::<T::Archived>();
+
+    let chunks = values
+        .par_chunks(chunk_size)
+        .zip(bases.par_iter())
+        .enumerate()
+        .map(|(index, (chunk, &base))| {
+            let (writer, resolvers) =
+                serialize_chunk::<T, S::Error>(chunk, ChunkWriter::new(base))?;
+            if writer.len != sizes[index] {
+                fail!(ChunkSizeMismatch {
+                    expected: sizes[index],
+                    actual: writer.len,
+                    align: chunk_align,
+                });
+            }
+
+            let mut elements = ChunkWriter::new(pos + index * chunk_len);
+            for (value, resolver) in chunk.iter().zip(resolvers) {
+                // SAFETY: `pos` is aligned for `T::Archived`, and each element
+                // is written at a multiple of its size after `pos`.
+                unsafe {
+                    WriterExt::<S::Error>::resolve_aligned(
+                        &mut elements,
+                        value,
+                        resolver,
+                    )?;
+                }
+            }
+
+            Ok((writer.into_bytes(), elements.into_bytes()))
+        })
+        .collect::<Result<Vec<_>, S::Error>>()?;
+
+    for (base, (dependencies, _)) in bases.iter().zip(chunks.iter()) {
+        serializer.pad(base - serializer.pos())?;
+        serializer.write(dependencies)?;
+    }
+    serializer.pad(pos - serializer.pos())?;
+    for (_, elements) in chunks.iter() {
+        serializer.write(elements)?;
+    }
+
+    Ok(VecResolver::from_pos(pos))
+}
+
+struct ParallelVec<'a, T>(&'a [T]);
+
+impl<T: Archive> Archive for ParallelVec<'_, T> {
+    type Archived = ArchivedVec<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        ArchivedVec::resolve_from_slice(self.0, resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for ParallelVec<'_, T>
+where
+    T: Sync + for<'a> Serialize<ParallelSerializer<'a, S::Error>>,
+    S: Fallible + Writer + ?Sized,
+    S::Error: Source + Send,
+{
+    fn serialize(
+        &self,
+        serializer: &mut S,
+    ) -> Result<Self::Resolver, S::Error> {
+        serialize_vec(self.0, serializer)
+    }
+}
+
+/// Serializes a slice as an archived vector at the root of an archive,
+/// serializing its elements in parallel.
+///
+/// The archived vector can be accessed the same as if the slice had been
+/// serialized as a `Vec` with [`to_bytes`](crate::to_bytes), and can be
+/// validated in parallel with
+/// [`access_vec`](crate::validation::parallel::access_vec).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{access, rancor::Error, ser::parallel::to_bytes_vec, Archived};
+///
+/// let value = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+/// let bytes = to_bytes_vec::<_, Error>(&value).unwrap();
+///
+/// let archived = access::<Archived<Vec<String>>, Error>(&bytes).unwrap();
+/// assert_eq!(archived.len(), 10_000);
+/// assert_eq!(archived[1234], "1234");
+/// ```
+pub fn to_bytes_vec<T, E>(values: &[T]) -> Result<AlignedVec, E>
+where
+    T: Sync + for<'a> Serialize<ParallelSerializer<'a, E>>,
+    E: Source + Send,
+{
+    let mut bytes = AlignedVec::new();
+    serialize_using::<_, E>(&ParallelVec(values), &mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(all(test, feature = "bytecheck"))]
+mod tests {
+    use rancor::{Error, Panic};
+
+    use super::to_bytes_vec;
+    use crate::{
+        access,
+        alloc::{format, string::String, sync::Arc, vec, vec::Vec},
+        util::AlignedVec,
+        with::Align,
+        Archive, Archived, Serialize,
+    };
+
+    #[test]
+    fn serializes_chunks_in_order() {
+        let value = (0..1000)
+            .map(|i| format!("a string long enough to be out of line {i}"))
+            .collect::<Vec<String>>();
+
+        let bytes = to_bytes_vec::<_, Panic>(&value).unwrap();
+        let archived = access::<Archived<Vec<String>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), value.len());
+        for (archived, value) in archived.iter().zip(value.iter()) {
+            assert_eq!(archived, value);
+        }
+    }
+
+    #[test]
+    fn shared_pointers() {
+        let shared = Arc::new(String::from("shared"));
+        let value = (0..1000).map(|_| shared.clone()).collect::<Vec<_>>();
+
+        let bytes = to_bytes_vec::<_, Panic>(&value).unwrap();
+        let archived =
+            access::<Archived<Vec<Arc<String>>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), value.len());
+        assert!(archived
+            .iter()
+            .all(|archived| archived.as_str() == "shared"));
+    }
+
+    #[test]
+    fn over_aligned() {
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate, attr(repr(C, align(32))))]
+        struct Element {
+            #[rkyv(with = Align<64>)]
+            bytes: Vec<u8>,
+        }
+
+        let value = (0..1000)
+            .map(|i| Element {
+                bytes: vec![i as u8; i % 7 + 1],
+            })
+            .collect::<Vec<_>>();
+
+        let mut bytes = AlignedVec::<64>::new();
+        bytes.extend_from_slice(&to_bytes_vec::<_, Panic>(&value).unwrap());
+        let archived = access::<Archived<Vec<Element>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.len(), value.len());
+        for (archived, value) in archived.iter().zip(value.iter()) {
+            assert_eq!(archived.bytes.as_ptr() as usize % 64, 0);
+            assert_eq!(archived.bytes.as_slice(), value.bytes.as_slice());
+        }
+    }
+
+    #[test]
+    fn empty() {
+        let bytes = to_bytes_vec::<u32, Panic>(&[]).unwrap();
+        let archived = access::<Archived<Vec<u32>>, Error>(&bytes).unwrap();
+        assert!(archived.is_empty());
+    }
+}
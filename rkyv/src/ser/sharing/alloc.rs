@@ -1,15 +1,24 @@
-use core::{error::Error, fmt, hash::BuildHasherDefault};
+use core::{any::TypeId, error::Error, fmt, hash::BuildHasherDefault};
 
 use hashbrown::hash_map::{Entry, HashMap};
 use rancor::{fail, Source};
 
 use crate::{
+    alloc::boxed::Box,
     hash::FxHasher64,
-    ser::{sharing::SharingState, Sharing},
+    ser::{
+        sharing::{SharedValue, SharingState},
+        Sharing,
+    },
 };
 
 /// A shared pointer strategy that shares serializations of the same shared
 /// pointer.
+///
+/// Although shared pointers are looked up by address, the serialized output
+/// does not depend on those addresses. Shared values are always written the
+/// first time they are encountered in serialization order, so identical inputs
+/// produce identical bytes across runs.
 #[derive(Debug, Default)]
 pub struct Share {
     shared_address_to_pos:
@@ -94,3 +103,197 @@ impl<E: Source> Sharing<E> for Share {
         }
     }
 }
+
+/// A shared pointer strategy that shares serializations of shared pointers
+/// with identical values.
+///
+/// Like [`Share`], this shares serializations of the same shared pointer. In
+/// addition, shared values whose archived bytes are identical to their native
+/// bytes (like `str` and slices of primitives) are deduplicated by content, so
+/// two distinct `Arc<str>` with the same contents serialize to a single
+/// archived copy. Values are only deduplicated with other values shared by the
+/// same kind of pointer to the same archived type, so an `Rc<str>` and an
+/// `Arc<str>` with the same contents are still archived separately.
+#[derive(Debug, Default)]
+pub struct DedupByValue {
+    share: Share,
+    // Maps archived pointer types and alignments to the positions of archived
+    // values by their bytes.
+    value_to_pos: HashMap<
+        (TypeId, usize),
+        HashMap<Box<[u8]>, usize, BuildHasherDefault<FxHasher64>>,
+        BuildHasherDefault<FxHasher64>,
+    >,
+}
+
+impl DedupByValue {
+    /// Creates a new value-deduplicating shared pointer unifier.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the shared pointer unifier for reuse.
+    pub fn clear(&mut self) {
+        self.share.clear();
+        self.value_to_pos.clear();
+    }
+}
+
+impl<E: Source> Sharing<E> for DedupByValue {
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        Sharing::<E>::start_sharing(&mut self.share, address)
+    }
+
+    fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.share.finish_sharing(address, pos)
+    }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        if let Some(value) = archived {
+            let pos = self
+                .value_to_pos
+                .get(&(value.type_id, value.align))
+                .and_then(|values| values.get(value.bytes));
+            if let Some(pos) = pos {
+                return SharingState::Finished(*pos);
+            }
+        }
+        Sharing::<E>::start_sharing(self, address)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        Sharing::<E>::finish_sharing(self, address, pos)?;
+        if let Some(value) = archived {
+            self.value_to_pos
+                .entry((value.type_id, value.align))
+                .or_default()
+                .insert(Box::from(value.bytes), pos);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        access_unchecked,
+        alloc::{sync::Arc, vec, vec::Vec},
+        api::serialize_using,
+        ser::{
+            allocator::Arena,
+            sharing::{DedupByValue, Share},
+            Serializer, Sharing,
+        },
+        util::AlignedVec,
+        Archived,
+    };
+
+    fn serialize_with<S>(value: &Vec<Arc<str>>, sharing: S) -> AlignedVec
+    where
+        S: Sharing<Panic>,
+    {
+        let mut arena = Arena::new();
+        let mut serializer =
+            Serializer::new(AlignedVec::<16>::new(), arena.acquire(), sharing);
+        serialize_using::<_, Panic>(value, &mut serializer).unwrap();
+        serializer.into_writer()
+    }
+
+    #[test]
+    fn share_is_deterministic() {
+        fn make(padding: usize) -> Vec<Arc<str>> {
+            // Allocate some unrelated data to perturb the addresses of the
+            // shared pointers.
+            let _padding = vec![0u8; padding];
+            let a: Arc<str> = Arc::from("first");
+            let b: Arc<str> = Arc::from("second");
+            vec![b.clone(), a.clone(), b, a.clone(), a]
+        }
+
+        let expected = serialize_with(&make(0), Share::new());
+        for padding in [1, 17, 4096] {
+            let bytes = serialize_with(&make(padding), Share::new());
+            assert_eq!(bytes.as_slice(), expected.as_slice());
+        }
+    }
+
+    #[test]
+    fn dedup_by_value() {
+        let value: Vec<Arc<str>> = vec![
+            Arc::from("a long shared string"),
+            Arc::from("a long shared string"),
+            Arc::from("another string"),
+        ];
+        assert!(!Arc::ptr_eq(&value[0], &value[1]));
+
+        let shared = serialize_with(&value, Share::new());
+        let deduped = serialize_with(&value, DedupByValue::new());
+        assert!(deduped.len() < shared.len());
+
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<Arc<str>>>>(&deduped) };
+        assert_eq!(&*archived[0], "a long shared string");
+        assert_eq!(&*archived[1], "a long shared string");
+        assert_eq!(&*archived[2], "another string");
+        assert_eq!(
+            archived[0].get() as *const str,
+            archived[1].get() as *const str,
+        );
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn dedup_by_value_mixed_pointers() {
+        use rancor::Error;
+
+        use crate::{access, alloc::rc::Rc, Archive, Serialize};
+
+        #[derive(Archive, Serialize)]
+        #[rkyv(crate)]
+        struct Mixed {
+            rc: Rc<str>,
+            arc: Arc<str>,
+            bytes: Arc<[u8]>,
+            other_arc: Arc<str>,
+        }
+
+        let value = Mixed {
+            rc: Rc::from("shared"),
+            arc: Arc::from("shared"),
+            bytes: Arc::from(b"shared".as_slice()),
+            other_arc: Arc::from("shared"),
+        };
+
+        let mut arena = Arena::new();
+        let mut serializer = Serializer::new(
+            AlignedVec::<16>::new(),
+            arena.acquire(),
+            DedupByValue::new(),
+        );
+        serialize_using::<_, Error>(&value, &mut serializer).unwrap();
+        let bytes = serializer.into_writer();
+
+        let archived = access::<ArchivedMixed, Error>(&bytes).unwrap();
+        assert_eq!(&*archived.rc, "shared");
+        assert_eq!(&*archived.arc, "shared");
+        assert_eq!(&*archived.bytes, b"shared");
+        assert_eq!(&*archived.other_arc, "shared");
+
+        let address = |value: &str| value.as_ptr() as usize;
+        assert_eq!(address(&archived.arc), address(&archived.other_arc));
+        assert_ne!(address(&archived.rc), address(&archived.arc));
+        assert_ne!(archived.bytes.as_ptr() as usize, address(&archived.arc));
+    }
+}
@@ -4,13 +4,13 @@
 mod alloc;
 mod core;
 
-use ::core::{error::Error, fmt};
+use ::core::{any::TypeId, error::Error, fmt};
 use rancor::{fail, Fallible, Source, Strategy};
 
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
 pub use self::core::*;
-use crate::SerializeUnsized;
+use crate::{ArchiveUnsized, SerializeUnsized};
 
 /// The result of starting to serialize a shared pointer.
 pub enum SharingState {
@@ -26,6 +26,24 @@ pub enum SharingState {
     Finished(usize),
 }
 
+/// The archived bytes of a shared value.
+///
+/// Sharing strategies like [`DedupByValue`](crate::ser::sharing::DedupByValue)
+/// use this to recognize identical values at different addresses. Values are
+/// only identical if they are shared by the same archived pointer type as well
+/// as having the same bytes and alignment, since validation requires that
+/// every pointer to a shared value has the same type.
+#[derive(Clone, Copy, Debug)]
+pub struct SharedValue<'a> {
+    /// The `TypeId` of the archived shared pointer, which includes both the
+    /// archived type and the pointer flavor.
+    pub type_id: TypeId,
+    /// The archived bytes of the value.
+    pub bytes: &'a [u8],
+    /// The alignment of the archived value.
+    pub align: usize,
+}
+
 /// A shared pointer serialization strategy.
 ///
 /// This trait is required to serialize `Rc` and `Arc`.
@@ -37,6 +55,37 @@ pub trait Sharing<E = <Self as Fallible>::Error> {
     ///
     /// Returns an error if the given address was not pending.
     fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E>;
+
+    /// Starts sharing the value associated with the given address and
+    /// archived bytes.
+    ///
+    /// `archived` is built from the result of calling
+    /// [`archived_bytes`](crate::ArchiveUnsized::archived_bytes) on the value.
+    /// By default, this ignores the archived bytes and calls
+    /// [`start_sharing`](Sharing::start_sharing).
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        let _ = archived;
+        self.start_sharing(address)
+    }
+
+    /// Finishes sharing the value associated with the given address and
+    /// archived bytes.
+    ///
+    /// By default, this ignores the archived bytes and calls
+    /// [`finish_sharing`](Sharing::finish_sharing).
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        let _ = archived;
+        self.finish_sharing(address, pos)
+    }
 }
 
 impl<T, E> Sharing<E> for &mut T
@@ -50,6 +99,23 @@ where
     fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
         T::finish_sharing(*self, address, pos)
     }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        T::start_sharing_value(*self, address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        T::finish_sharing_value(*self, address, archived, pos)
+    }
 }
 
 impl<T, E> Sharing<E> for Strategy<T, E>
@@ -63,18 +129,38 @@ where
     fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
         T::finish_sharing(self, address, pos)
     }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        T::start_sharing_value(self, address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        T::finish_sharing_value(self, address, archived, pos)
+    }
 }
 
 #[derive(Debug)]
-struct CyclicSharedPointerError;
+struct CyclicSharedPointerError {
+    address: usize,
+    type_name: &'static str,
+}
 
 impl fmt::Display for CyclicSharedPointerError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "encountered cyclic shared pointers while serializing\nhelp: \
-             change your serialization strategy to `Unshare` or use the \
-             `Unshare` wrapper type to break the cycle",
+            "encountered cyclic shared pointers while serializing: the shared \
+             `{}` at address {:#x} is part of a reference cycle",
+            self.type_name, self.address,
         )
     }
 }
@@ -103,10 +189,127 @@ pub trait SharingExt<E>: Sharing<E> {
                 self.finish_sharing(addr, pos)?;
                 Ok(pos)
             }
-            SharingState::Pending => fail!(CyclicSharedPointerError),
+            SharingState::Pending => fail!(CyclicSharedPointerError {
+                address: addr,
+                type_name: ::core::any::type_name::<T>(),
+            }),
+            SharingState::Finished(pos) => Ok(pos),
+        }
+    }
+
+    /// Serializes the given shared value for the archived shared pointer type
+    /// `P` and returns its position.
+    ///
+    /// Like [`serialize_shared`](SharingExt::serialize_shared), but also passes
+    /// the archived bytes of the value to the sharing strategy. Values shared
+    /// by different archived pointer types are never deduplicated with each
+    /// other.
+    fn serialize_shared_for<P, T>(
+        &mut self,
+        value: &T,
+    ) -> Result<usize, <Self as Fallible>::Error>
+    where
+        P: ?Sized + 'static,
+        T: SerializeUnsized<Self> + ?Sized,
+        Self: Fallible<Error = E>,
+        E: Source,
+    {
+        let addr = value as *const T as *const () as usize;
+        let archived = shared_value::<P, T>(value);
+        match self.start_sharing_value(addr, archived) {
+            SharingState::Started => {
+                let pos = value.serialize_unsized(self)?;
+                self.finish_sharing_value(addr, archived, pos)?;
+                Ok(pos)
+            }
+            SharingState::Pending => fail!(CyclicSharedPointerError {
+                address: addr,
+                type_name: ::core::any::type_name::<T>(),
+            }),
             SharingState::Finished(pos) => Ok(pos),
         }
     }
+
+    /// Serializes the given value for a weak pointer of the archived shared
+    /// pointer type `P` and returns its position. If the value has already
+    /// been serialized then it returns the position of the previously added
+    /// value.
+    ///
+    /// Returns `None` if the value is currently being serialized, which occurs
+    /// when a weak pointer refers back to one of its ancestors.
+    fn serialize_weak<P, T>(
+        &mut self,
+        value: &T,
+    ) -> Result<Option<usize>, <Self as Fallible>::Error>
+    where
+        P: ?Sized + 'static,
+        T: SerializeUnsized<Self> + ?Sized,
+        Self: Fallible<Error = E>,
+        E: Source,
+    {
+        let addr = value as *const T as *const () as usize;
+        let archived = shared_value::<P, T>(value);
+        match self.start_sharing_value(addr, archived) {
+            SharingState::Started => {
+                let pos = value.serialize_unsized(self)?;
+                self.finish_sharing_value(addr, archived, pos)?;
+                Ok(Some(pos))
+            }
+            SharingState::Pending => Ok(None),
+            SharingState::Finished(pos) => Ok(Some(pos)),
+        }
+    }
 }
 
 impl<S, E> SharingExt<E> for S where S: Sharing<E> + ?Sized {}
+
+fn shared_value<P, T>(value: &T) -> Option<SharedValue<'_>>
+where
+    P: ?Sized + 'static,
+    T: ArchiveUnsized + ?Sized,
+{
+    value.archived_bytes().map(|(bytes, align)| SharedValue {
+        type_id: TypeId::of::<P>(),
+        bytes,
+        align,
+    })
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use rancor::{Error, Panic, Strategy};
+
+    use crate::{
+        alloc::{format, string::ToString},
+        ser::{
+            sharing::{Share, SharingState},
+            writer::SizeWriter,
+            Serializer, Sharing, SharingExt as _,
+        },
+        Archive, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate)]
+    struct Cyclic;
+
+    #[test]
+    fn cyclic_error_names_type() {
+        let value = Cyclic;
+        let addr = &value as *const Cyclic as usize;
+        let mut serializer =
+            Serializer::new(SizeWriter::new(), (), Share::new());
+
+        // Simulate encountering the value again while it is being serialized.
+        assert!(matches!(
+            Sharing::<Panic>::start_sharing(&mut serializer, addr),
+            SharingState::Started,
+        ));
+        let message = Strategy::<_, Error>::wrap(&mut serializer)
+            .serialize_shared(&value)
+            .unwrap_err()
+            .to_string();
+        assert!(message.contains("Cyclic"));
+        assert!(message.contains(&format!("{addr:#x}")));
+    }
+}
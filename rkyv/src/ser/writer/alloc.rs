@@ -16,6 +16,14 @@ impl<E> Writer<E> for Vec<u8> {
         self.extend_from_slice(bytes);
         Ok(())
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        self.reserve(bufs.iter().map(|buf| buf.len()).sum());
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(())
+    }
 }
 
 impl<const A: usize> Positional for AlignedVec<A> {
@@ -30,4 +38,12 @@ impl<E, const A: usize> Writer<E> for AlignedVec<A> {
         self.extend_from_slice(bytes);
         Ok(())
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        self.reserve(bufs.iter().map(|buf| buf.len()).sum());
+        for buf in bufs {
+            self.extend_from_slice(buf);
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,140 @@
+use crate::ser::{Positional, Writer};
+
+/// Wraps a [`Writer`] and coalesces small writes into an internal buffer of
+/// `N` bytes before forwarding them.
+///
+/// This is useful when the underlying writer is expensive to call, like an
+/// [`IoWriter`](crate::ser::writer::IoWriter) backed by a file or socket.
+/// Writes which are at least as large as the buffer bypass it entirely.
+///
+/// Buffered bytes are not written when a `BufferedWriter` is dropped. Call
+/// [`flush`](BufferedWriter::flush) or
+/// [`into_inner`](BufferedWriter::into_inner) after serializing.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in, rancor::Error, ser::writer::BufferedWriter,
+/// };
+///
+/// let writer = BufferedWriter::<_, 64>::new(Vec::new());
+/// let writer = to_bytes_in::<_, Error>(&42u32, writer).unwrap();
+/// let bytes = writer.into_inner::<Error>().unwrap();
+/// assert_eq!(bytes, [42, 0, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct BufferedWriter<W, const N: usize> {
+    inner: W,
+    buffer: [u8; N],
+    len: usize,
+}
+
+impl<W, const N: usize> BufferedWriter<W, N> {
+    /// Creates a new buffered writer wrapping the given writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: [0; N],
+            len: 0,
+        }
+    }
+
+    /// Returns the bytes which are currently buffered.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+
+    /// Writes all buffered bytes to the internal writer.
+    pub fn flush<E>(&mut self) -> Result<(), E>
+    where
+        W: Writer<E>,
+    {
+        if self.len > 0 {
+            self.inner.write(&self.buffer[..self.len])?;
+            self.len = 0;
+        }
+        Ok(())
+    }
+
+    /// Writes all buffered bytes to the internal writer and returns it.
+    pub fn into_inner<E>(mut self) -> Result<W, E>
+    where
+        W: Writer<E>,
+    {
+        self.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Positional, const N: usize> Positional for BufferedWriter<W, N> {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.inner.pos() + self.len
+    }
+}
+
+impl<W: Writer<E>, E, const N: usize> Writer<E> for BufferedWriter<W, N> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        if bytes.len() > N - self.len {
+            self.flush()?;
+        }
+
+        if bytes.len() >= N {
+            self.inner.write(bytes)
+        } else {
+            self.buffer[self.len..self.len + bytes.len()]
+                .copy_from_slice(bytes);
+            self.len += bytes.len();
+            Ok(())
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        alloc::vec::Vec,
+        api::high::to_bytes_in,
+        ser::{writer::BufferedWriter, Positional, Writer},
+    };
+
+    #[derive(Default)]
+    struct CountingWriter {
+        bytes: Vec<u8>,
+        writes: usize,
+    }
+
+    impl Positional for CountingWriter {
+        fn pos(&self) -> usize {
+            self.bytes.len()
+        }
+    }
+
+    impl<E> Writer<E> for CountingWriter {
+        fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+            self.bytes.extend_from_slice(bytes);
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn coalesces_writes() {
+        let value = (0..16u8)
+            .map(|i| (i, i as u32, i as u64))
+            .collect::<Vec<_>>();
+
+        let unbuffered =
+            to_bytes_in::<_, Panic>(&value, CountingWriter::default()).unwrap();
+
+        let writer = BufferedWriter::<_, 128>::new(CountingWriter::default());
+        let writer = to_bytes_in::<_, Panic>(&value, writer).unwrap();
+        assert_eq!(writer.pos(), unbuffered.bytes.len());
+        let buffered = writer.into_inner::<Panic>().unwrap();
+
+        assert_eq!(buffered.bytes, unbuffered.bytes);
+        assert!(buffered.writes < unbuffered.writes);
+    }
+}
@@ -0,0 +1,202 @@
+use core::hash::Hasher;
+
+use crate::ser::{Positional, Writer};
+
+const BLOCK_LEN: usize = 64;
+
+/// Wraps a [`Writer`] and feeds every written byte through a [`Hasher`].
+///
+/// Any hasher may be used, including CRC32 and xxHash implementations which
+/// implement [`Hasher`]. The digest of all bytes written so far can be read at
+/// any time with [`digest`](ChecksumWriter::digest), or appended to the output
+/// as a trailer with [`write_trailer`](ChecksumWriter::write_trailer).
+///
+/// Some hashers (like [`FxHasher64`](crate::hash::FxHasher64)) return different
+/// results depending on how their input is split between calls to
+/// [`Hasher::write`]. To keep the digest independent of how serialization
+/// splits its writes, written bytes are collected into 64-byte blocks before
+/// they are fed to the hasher. The digest is the same as hashing all of the
+/// written bytes with a single call to [`Hasher::write`] for any hasher which
+/// processes its input in blocks of a size that divides 64 bytes.
+///
+/// # Examples
+/// ```
+/// use core::hash::Hasher as _;
+///
+/// use rkyv::{
+///     api::high::to_bytes_in, hash::FxHasher64, rancor::Error,
+///     ser::writer::ChecksumWriter,
+/// };
+///
+/// let writer = ChecksumWriter::new(Vec::new(), FxHasher64::default());
+/// let writer = to_bytes_in::<_, Error>(&42u32, writer).unwrap();
+/// let digest = writer.digest();
+/// let (bytes, _) = writer.into_inner();
+/// assert_eq!(bytes, [42, 0, 0, 0]);
+///
+/// let mut hasher = FxHasher64::default();
+/// hasher.write(&bytes);
+/// assert_eq!(hasher.finish(), digest);
+/// ```
+#[derive(Debug)]
+pub struct ChecksumWriter<W, H> {
+    inner: W,
+    hasher: H,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+}
+
+impl<W, H> ChecksumWriter<W, H> {
+    /// Creates a new checksum writer from a writer and hasher.
+    pub fn new(inner: W, hasher: H) -> Self {
+        Self {
+            inner,
+            hasher,
+            block: [0; BLOCK_LEN],
+            block_len: 0,
+        }
+    }
+}
+
+impl<W, H: Hasher> ChecksumWriter<W, H> {
+    /// Returns the digest of all of the bytes written so far.
+    pub fn digest(&self) -> u64
+    where
+        H: Clone,
+    {
+        let mut hasher = self.hasher.clone();
+        hasher.write(&self.block[..self.block_len]);
+        hasher.finish()
+    }
+
+    /// Writes the digest of all of the bytes written so far to the internal
+    /// writer as eight little-endian bytes.
+    ///
+    /// The trailer is not fed through the hasher. Returns the digest that was
+    /// written.
+    pub fn write_trailer<E>(&mut self) -> Result<u64, E>
+    where
+        W: Writer<E>,
+        H: Clone,
+    {
+        let digest = self.digest();
+        self.inner.write(&digest.to_le_bytes())?;
+        Ok(digest)
+    }
+
+    /// Consumes the checksum writer and returns the internal writer and
+    /// hasher.
+    ///
+    /// All of the bytes written so far are fed to the returned hasher.
+    pub fn into_inner(mut self) -> (W, H) {
+        self.hasher.write(&self.block[..self.block_len]);
+        (self.inner, self.hasher)
+    }
+
+    fn hash(&mut self, mut bytes: &[u8]) {
+        if self.block_len != 0 {
+            let len = usize::min(BLOCK_LEN - self.block_len, bytes.len());
+            let (head, rest) = bytes.split_at(len);
+            self.block[self.block_len..self.block_len + len]
+                .copy_from_slice(head);
+            self.block_len += len;
+            bytes = rest;
+
+            if self.block_len < BLOCK_LEN {
+                return;
+            }
+            self.hasher.write(&self.block);
+            self.block_len = 0;
+        }
+
+        let (blocks, rest) =
+            bytes.split_at(bytes.len() - bytes.len() % BLOCK_LEN);
+        if !blocks.is_empty() {
+            self.hasher.write(blocks);
+        }
+        self.block[..rest.len()].copy_from_slice(rest);
+        self.block_len = rest.len();
+    }
+}
+
+impl<W: Positional, H> Positional for ChecksumWriter<W, H> {
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<W: Writer<E>, H: Hasher, E> Writer<E> for ChecksumWriter<W, H> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write(bytes)?;
+        self.hash(bytes);
+        Ok(())
+    }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        self.inner.write_vectored(bufs)?;
+        for buf in bufs {
+            self.hash(buf);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use core::hash::Hasher as _;
+
+    use rancor::Panic;
+
+    use crate::{
+        alloc::{string::String, vec::Vec},
+        api::high::to_bytes_in,
+        hash::FxHasher64,
+        ser::{writer::ChecksumWriter, Writer},
+    };
+
+    fn hash(bytes: &[u8]) -> u64 {
+        let mut hasher = FxHasher64::default();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn checksum_writer() {
+        let value = (0..100u32)
+            .map(|i| (i as u8, String::from("abc")))
+            .collect::<Vec<_>>();
+        let writer = ChecksumWriter::new(Vec::new(), FxHasher64::default());
+        let mut writer = to_bytes_in::<_, Panic>(&value, writer).unwrap();
+
+        let digest = writer.write_trailer::<Panic>().unwrap();
+        let (bytes, _) = writer.into_inner();
+        let (archive, trailer) = bytes.split_at(bytes.len() - 8);
+
+        assert_eq!(hash(archive), digest);
+        assert_eq!(trailer, digest.to_le_bytes());
+    }
+
+    #[test]
+    fn split_writes() {
+        let bytes = (0..1000u32).map(|i| i as u8).collect::<Vec<_>>();
+        let expected = hash(&bytes);
+
+        for split in [1, 3, 7, 13, 64, 100] {
+            let mut writer =
+                ChecksumWriter::new(Vec::new(), FxHasher64::default());
+            for chunk in bytes.chunks(split) {
+                Writer::<Panic>::write(&mut writer, chunk).unwrap();
+            }
+            assert_eq!(writer.digest(), expected);
+
+            let (written, hasher) = writer.into_inner();
+            assert_eq!(written, bytes);
+            assert_eq!(hasher.finish(), expected);
+        }
+
+        let mut writer = ChecksumWriter::new(Vec::new(), FxHasher64::default());
+        let bufs = bytes.chunks(5).collect::<Vec<_>>();
+        Writer::<Panic>::write_vectored(&mut writer, &bufs).unwrap();
+        assert_eq!(writer.digest(), expected);
+    }
+}
@@ -123,6 +123,20 @@ impl<'a> From<&'a mut [MaybeUninit<u8>]> for Buffer<'a> {
     }
 }
 
+impl<'a> Buffer<'a> {
+    /// Consumes the buffer and returns the initialized prefix of the backing
+    /// memory, which holds all of the bytes written so far.
+    ///
+    /// A buffer created from a slice of [`MaybeUninit<u8>`] never reads or
+    /// initializes bytes past those which are written, so the backing memory
+    /// does not need to be zeroed before serializing.
+    pub fn into_initialized(self) -> &'a mut [u8] {
+        // SAFETY: The first `len` bytes of the buffer have been initialized by
+        // `write`, and the returned slice borrows the backing memory for `'a`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
 impl Deref for Buffer<'_> {
     type Target = [u8];
 
@@ -166,6 +180,53 @@ impl<E: Source> Writer<E> for Buffer<'_> {
     }
 }
 
+/// A writer which discards all written bytes and only counts them.
+///
+/// Serializing with a `SizeWriter` runs all of the usual serialization logic,
+/// so its final position is exactly the number of bytes a real serialization
+/// pass would produce.
+///
+/// # Examples
+///
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in,
+///     rancor::Error,
+///     ser::{writer::SizeWriter, Positional},
+/// };
+///
+/// let value = vec![1u32, 2, 3, 4];
+/// let writer = to_bytes_in::<_, Error>(&value, SizeWriter::new()).unwrap();
+/// let bytes = rkyv::to_bytes::<Error>(&value).unwrap();
+/// assert_eq!(writer.pos(), bytes.len());
+/// ```
+#[derive(Debug, Default)]
+pub struct SizeWriter {
+    len: usize,
+}
+
+impl SizeWriter {
+    /// Creates a new size writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Positional for SizeWriter {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.len
+    }
+}
+
+impl<E> Writer<E> for SizeWriter {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.len += bytes.len();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use core::mem::MaybeUninit;
@@ -202,4 +263,21 @@ mod tests {
             .iter()
             .all(|&b| b == 0));
     }
+
+    #[test]
+    fn into_initialized() {
+        use crate::{access_unchecked, util::Align, Archived};
+
+        let mut bytes = Align([MaybeUninit::<u8>::uninit(); 64]);
+        let mut serializer =
+            Serializer::new(Buffer::from(&mut bytes[..]), (), ());
+        serialize_using::<_, Panic>(&(1u32, 2u32), &mut serializer).unwrap();
+        let initialized = serializer.into_writer().into_initialized();
+        assert_eq!(initialized.len(), 8);
+
+        let archived =
+            unsafe { access_unchecked::<Archived<(u32, u32)>>(initialized) };
+        assert_eq!(archived.0, 1);
+        assert_eq!(archived.1, 2);
+    }
 }
@@ -0,0 +1,71 @@
+use rancor::Source;
+
+use crate::ser::{writer::check_archive_len, Positional, Writer};
+
+/// Wraps a [`Writer`] and fails when the archive grows past
+/// [`MAX_ARCHIVE_LEN`](crate::ser::writer::MAX_ARCHIVE_LEN).
+///
+/// In-memory writers like `Vec<u8>` and `AlignedVec` accept any error type, so
+/// they can't report an archive which has outgrown the enabled pointer width.
+/// Wrapping them in a `LimitedWriter` fails with an error that suggests a
+/// larger `pointer_width_*` feature before any bytes past the limit are
+/// written. The [high-level API](crate::api::high) wraps its writers in a
+/// `LimitedWriter` automatically.
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     api::low::to_bytes_in_with_alloc,
+///     rancor::Error,
+///     ser::{allocator::SubAllocator, writer::LimitedWriter},
+///     util::AlignedVec,
+/// };
+///
+/// let writer = LimitedWriter::new(AlignedVec::<16>::new());
+/// let writer = to_bytes_in_with_alloc::<_, _, Error>(
+///     &42u32,
+///     writer,
+///     SubAllocator::empty(),
+/// )
+/// .unwrap();
+/// assert_eq!(writer.into_inner().as_slice(), &[42, 0, 0, 0]);
+/// ```
+#[derive(Debug)]
+pub struct LimitedWriter<W> {
+    inner: W,
+}
+
+impl<W> LimitedWriter<W> {
+    /// Creates a new limited writer from a writer.
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Consumes the limited writer and returns the internal writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Positional> Positional for LimitedWriter<W> {
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<W, E> Writer<E> for LimitedWriter<W>
+where
+    W: Writer<E>,
+    E: Source,
+{
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        check_archive_len::<E>(self.inner.pos(), bytes.len())?;
+        self.inner.write(bytes)
+    }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        let len = bufs.iter().map(|buf| buf.len()).sum();
+        check_archive_len::<E>(self.inner.pos(), len)?;
+        self.inner.write_vectored(bufs)
+    }
+}
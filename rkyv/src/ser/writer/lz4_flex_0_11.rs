@@ -0,0 +1,69 @@
+use std::io;
+
+use lz4_flex_0_11::frame::FrameEncoder;
+use rancor::{ResultExt as _, Source};
+
+use crate::ser::{writer::check_archive_len, Positional, Writer};
+
+/// Wraps a type that implements [`io::Write`] and compresses all written
+/// bytes into it as an LZ4 frame.
+///
+/// Positions reported by the writer are positions in the uncompressed
+/// archive. Call [`finish`](CompressWriter::finish) after serializing to write
+/// the end of the frame. The compressed archive can be decompressed into an
+/// aligned buffer for access with
+/// [`decompress_lz4`](crate::util::decompress_lz4).
+///
+/// # Examples
+/// ```
+/// use rkyv::{
+///     api::high::to_bytes_in, rancor::Error, ser::writer::CompressWriter,
+///     util::decompress_lz4,
+/// };
+///
+/// let value = vec![0u32; 1024];
+/// let writer =
+///     to_bytes_in::<_, Error>(&value, CompressWriter::new(Vec::new()))
+///         .unwrap();
+/// let compressed = writer.finish::<Error>().unwrap();
+/// assert!(compressed.len() < 4096);
+///
+/// let bytes = decompress_lz4::<16, _>(compressed.as_slice()).unwrap();
+/// let archived =
+///     rkyv::access::<rkyv::Archived<Vec<u32>>, Error>(&bytes).unwrap();
+/// assert_eq!(archived.len(), 1024);
+/// ```
+pub struct CompressWriter<W: io::Write> {
+    inner: FrameEncoder<W>,
+    pos: usize,
+}
+
+impl<W: io::Write> CompressWriter<W> {
+    /// Creates a new compressing writer from a writer.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: FrameEncoder::new(inner),
+            pos: 0,
+        }
+    }
+
+    /// Writes the end of the LZ4 frame and returns the internal writer.
+    pub fn finish<E: Source>(self) -> Result<W, E> {
+        self.inner.finish().into_error()
+    }
+}
+
+impl<W: io::Write> Positional for CompressWriter<W> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<W: io::Write, E: Source> Writer<E> for CompressWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        check_archive_len::<E>(self.pos, bytes.len())?;
+        io::Write::write_all(&mut self.inner, bytes).into_error()?;
+        self.pos += bytes.len();
+        Ok(())
+    }
+}
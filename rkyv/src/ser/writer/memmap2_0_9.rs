@@ -0,0 +1,177 @@
+use std::{fs::File, io};
+
+use memmap2_0_9::MmapMut;
+use rancor::{ResultExt as _, Source};
+
+use crate::ser::{writer::check_archive_len, Positional, Writer};
+
+const MIN_CAPACITY: usize = 4096;
+
+/// A writer which writes directly into a memory-mapped file, growing the
+/// file and its mapping as needed.
+///
+/// Call [`finish`](MmapWriter::finish) after serializing to flush the mapping
+/// and truncate the file to the number of bytes written.
+///
+/// Page-aligned mappings are aligned to at least 16 bytes, so archives written
+/// with an `MmapWriter` are properly aligned when the file is mapped again for
+/// reading.
+#[derive(Debug)]
+pub struct MmapWriter {
+    file: File,
+    mmap: Option<MmapMut>,
+    pos: usize,
+}
+
+impl MmapWriter {
+    /// Creates a new writer which writes to the beginning of the given file.
+    ///
+    /// The file must be opened for reading and writing. Any existing contents
+    /// of the file are discarded.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated, or mapped by any other code
+    /// (including other processes) while the writer is alive.
+    pub unsafe fn new(file: File) -> io::Result<Self> {
+        // SAFETY: The caller has guaranteed that the file is not modified by
+        // any other code while the writer is alive.
+        unsafe { Self::with_capacity(file, 0) }
+    }
+
+    /// Creates a new writer which writes to the beginning of the given file,
+    /// reserving space for at least `capacity` bytes up front.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, truncated, or mapped by any other code
+    /// (including other processes) while the writer is alive.
+    pub unsafe fn with_capacity(
+        file: File,
+        capacity: usize,
+    ) -> io::Result<Self> {
+        file.set_len(0)?;
+        let mut result = Self {
+            file,
+            mmap: None,
+            pos: 0,
+        };
+        if capacity > 0 {
+            result.remap(capacity)?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the number of bytes the writer can hold without growing the
+    /// file.
+    pub fn capacity(&self) -> usize {
+        self.mmap.as_ref().map_or(0, |mmap| mmap.len())
+    }
+
+    /// Flushes all written bytes to the file, truncates the file to the
+    /// number of bytes written, and returns it.
+    pub fn finish(mut self) -> io::Result<File> {
+        if let Some(mmap) = self.mmap.take() {
+            mmap.flush()?;
+        }
+        self.file.set_len(self.pos as u64)?;
+        Ok(self.file)
+    }
+
+    fn remap(&mut self, capacity: usize) -> io::Result<()> {
+        if let Some(mmap) = self.mmap.take() {
+            mmap.flush()?;
+        }
+        self.file.set_len(capacity as u64)?;
+        // SAFETY: The caller of `with_capacity` guaranteed that the file is not
+        // modified by any other code while this writer is alive.
+        self.mmap = Some(unsafe { MmapMut::map_mut(&self.file)? });
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) -> io::Result<()> {
+        let required = self.pos.checked_add(additional).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                "mmap writer capacity overflowed",
+            )
+        })?;
+        if required > self.capacity() {
+            let capacity = required
+                .checked_next_power_of_two()
+                .unwrap_or(required)
+                .max(MIN_CAPACITY);
+            self.remap(capacity)?;
+        }
+        Ok(())
+    }
+}
+
+impl Positional for MmapWriter {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<E: Source> Writer<E> for MmapWriter {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        check_archive_len::<E>(self.pos, bytes.len())?;
+        self.reserve(bytes.len()).into_error()?;
+        // `reserve` always leaves a mapping which can hold `pos + len` bytes.
+        let mmap = self.mmap.as_mut().unwrap();
+        mmap[self.pos..self.pos + bytes.len()].copy_from_slice(bytes);
+        self.pos += bytes.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, fs::OpenOptions};
+
+    use rancor::Panic;
+
+    use crate::{
+        access_unchecked,
+        api::high::to_bytes_in,
+        ser::{writer::MmapWriter, Positional as _},
+        util::AlignedVec,
+        Archived,
+    };
+
+    #[test]
+    fn mmap_writer() {
+        let path = std::env::temp_dir()
+            .join(format!("rkyv_mmap_writer_{}", std::process::id()));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+
+        // SAFETY: The file was just created and is not shared.
+        let writer = unsafe { MmapWriter::new(file).unwrap() };
+        let value = (0..2000u32).collect::<Vec<_>>();
+        let writer = to_bytes_in::<_, Panic>(&value, writer).unwrap();
+        let len = writer.pos();
+        assert!(writer.capacity() >= len);
+        writer.finish().unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(bytes.len(), len);
+
+        let mut aligned = AlignedVec::<16>::new();
+        aligned.extend_from_slice(&bytes);
+        // SAFETY: The bytes were serialized from a `Vec<u32>`.
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<u32>>>(&aligned) };
+        assert_eq!(archived.len(), 2000);
+        assert_eq!(archived[1999], 1999);
+    }
+}
@@ -2,17 +2,84 @@
 
 #[cfg(feature = "alloc")]
 mod alloc;
+mod buffered;
+mod checksum;
 mod core;
+mod limited;
+#[cfg(feature = "lz4_flex-0_11")]
+mod lz4_flex_0_11;
+#[cfg(feature = "memmap2-0_9")]
+mod memmap2_0_9;
 #[cfg(feature = "std")]
 mod std;
+#[cfg(feature = "tokio-1")]
+mod tokio_1;
 
-use ::core::mem;
-use rancor::{Fallible, Strategy};
+use ::core::{error::Error, fmt, mem};
+use rancor::{fail, Fallible, Source, Strategy};
 
-pub use self::core::*;
+#[cfg(feature = "lz4_flex-0_11")]
+pub use self::lz4_flex_0_11::*;
+#[cfg(feature = "memmap2-0_9")]
+pub use self::memmap2_0_9::*;
 #[cfg(feature = "std")]
 pub use self::std::*;
-use crate::{Archive, ArchiveUnsized, Place, RelPtr};
+#[cfg(feature = "tokio-1")]
+pub use self::tokio_1::*;
+pub use self::{buffered::*, checksum::*, core::*, limited::*};
+use crate::{
+    primitive::{FixedIsize, FixedUsize},
+    Archive, ArchiveUnsized, Place, RelPtr,
+};
+
+/// The maximum length of an archive with the enabled pointer width.
+///
+/// Relative pointers can only span as many bytes as fit in a signed offset of
+/// the enabled pointer width, so archives which are longer than this may
+/// contain pointers which cannot be represented. The pointer width is chosen
+/// at compile time with the `pointer_width_*` features because it changes the
+/// layout of archived types.
+pub const MAX_ARCHIVE_LEN: usize =
+    if mem::size_of::<FixedIsize>() < mem::size_of::<usize>() {
+        FixedIsize::MAX as usize
+    } else {
+        isize::MAX as usize
+    };
+
+#[derive(Debug)]
+struct ArchiveTooLarge {
+    len: usize,
+}
+
+impl fmt::Display for ArchiveTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "archive length {} exceeds the maximum of {} bytes for {}-bit \
+             offsets\nhelp: enabling a larger `pointer_width_*` feature will \
+             allow larger archives.",
+            self.len,
+            MAX_ARCHIVE_LEN,
+            mem::size_of::<FixedUsize>() * 8,
+        )
+    }
+}
+
+impl Error for ArchiveTooLarge {}
+
+/// Checks that writing `additional` bytes at `pos` keeps the archive within
+/// [`MAX_ARCHIVE_LEN`].
+pub(crate) fn check_archive_len<E: Source>(
+    pos: usize,
+    additional: usize,
+) -> Result<(), E> {
+    match pos.checked_add(additional) {
+        Some(len) if len <= MAX_ARCHIVE_LEN => Ok(()),
+        len => fail!(ArchiveTooLarge {
+            len: len.unwrap_or(usize::MAX),
+        }),
+    }
+}
 
 /// A writer that knows its current position.
 pub trait Positional {
@@ -59,6 +126,19 @@ where
 pub trait Writer<E = <Self as Fallible>::Error>: Positional {
     /// Attempts to write the given bytes to the serializer.
     fn write(&mut self, bytes: &[u8]) -> Result<(), E>;
+
+    /// Attempts to write each of the given byte slices to the serializer, in
+    /// order.
+    ///
+    /// The default implementation calls [`write`](Writer::write) once for each
+    /// slice. Writers backed by sockets or files can override this to submit
+    /// all of the slices at once.
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        for buf in bufs {
+            self.write(buf)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T, E> Writer<E> for &mut T
@@ -68,6 +148,10 @@ where
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         T::write(*self, bytes)
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        T::write_vectored(*self, bufs)
+    }
 }
 
 impl<T, E> Writer<E> for Strategy<T, E>
@@ -77,16 +161,23 @@ where
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
         T::write(self, bytes)
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        T::write_vectored(self, bufs)
+    }
 }
 
 /// Helper methods for [`Writer`].
 pub trait WriterExt<E>: Writer<E> {
     /// Advances the given number of bytes as padding.
-    fn pad(&mut self, padding: usize) -> Result<(), E> {
+    fn pad(&mut self, mut padding: usize) -> Result<(), E> {
         const MAX_ZEROS: usize = 32;
         const ZEROS: [u8; MAX_ZEROS] = [0; MAX_ZEROS];
-        debug_assert!(padding < MAX_ZEROS);
 
+        while padding > MAX_ZEROS {
+            self.write(&ZEROS)?;
+            padding -= MAX_ZEROS;
+        }
         self.write(&ZEROS[0..padding])
     }
 
@@ -105,6 +196,34 @@ pub trait WriterExt<E>: Writer<E> {
         self.align(mem::align_of::<T>())
     }
 
+    /// Aligns the position of the serializer to the given alignment and then
+    /// writes the given bytes.
+    ///
+    /// The padding and the bytes are submitted together with a single call to
+    /// [`write_vectored`](Writer::write_vectored). Returns the position of the
+    /// written bytes.
+    fn align_and_write(
+        &mut self,
+        align: usize,
+        bytes: &[u8],
+    ) -> Result<usize, E> {
+        const MAX_ZEROS: usize = 32;
+        const ZEROS: [u8; MAX_ZEROS] = [0; MAX_ZEROS];
+
+        let mask = align - 1;
+        debug_assert_eq!(align & mask, 0);
+
+        let padding = (align - (self.pos() & mask)) & mask;
+        let pos = self.pos() + padding;
+        if padding > MAX_ZEROS {
+            self.pad(padding)?;
+            self.write(bytes)?;
+        } else {
+            self.write_vectored(&[&ZEROS[0..padding], bytes])?;
+        }
+        Ok(pos)
+    }
+
     /// Resolves the given value with its resolver and writes the archived type.
     ///
     /// Returns the position of the written archived type.
@@ -201,4 +320,76 @@ mod tests {
 
         assert_eq!(writer.capacity(), 4);
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn align_and_write() {
+        use rancor::Panic;
+
+        use crate::{
+            alloc::vec::Vec,
+            ser::{Writer, WriterExt},
+        };
+
+        let mut writer = Vec::new();
+        Writer::<Panic>::write(&mut writer, &[1]).unwrap();
+        let pos = WriterExt::<Panic>::align_and_write(&mut writer, 4, &[2, 3]);
+        assert_eq!(pos.unwrap(), 4);
+        assert_eq!(&writer[..], &[1, 0, 0, 0, 2, 3]);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn align_and_write_large() {
+        use rancor::Panic;
+
+        use crate::{
+            alloc::vec::Vec,
+            ser::{Writer, WriterExt},
+        };
+
+        let mut writer = Vec::new();
+        Writer::<Panic>::write(&mut writer, &[1]).unwrap();
+        let pos = WriterExt::<Panic>::align_and_write(&mut writer, 64, &[2]);
+        assert_eq!(pos.unwrap(), 64);
+        assert_eq!(writer.len(), 65);
+        assert!(writer[1..64].iter().all(|&b| b == 0));
+        assert_eq!(writer[64], 2);
+    }
+
+    #[test]
+    fn archive_too_large() {
+        use rancor::Failure;
+
+        use super::{check_archive_len, MAX_ARCHIVE_LEN};
+
+        assert!(check_archive_len::<Failure>(0, MAX_ARCHIVE_LEN).is_ok());
+        assert!(check_archive_len::<Failure>(MAX_ARCHIVE_LEN, 1).is_err());
+        assert!(check_archive_len::<Failure>(usize::MAX, 1).is_err());
+    }
+
+    #[cfg(all(feature = "alloc", feature = "pointer_width_16"))]
+    #[test]
+    fn vec_too_large() {
+        use rancor::Failure;
+
+        use super::{LimitedWriter, MAX_ARCHIVE_LEN};
+        use crate::{alloc::vec, ser::Writer};
+
+        let mut writer = LimitedWriter::new(vec![0u8; MAX_ARCHIVE_LEN]);
+        assert!(Writer::<Failure>::write(&mut writer, &[1]).is_err());
+        assert_eq!(writer.into_inner().len(), MAX_ARCHIVE_LEN);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "pointer_width_16"))]
+    #[test]
+    fn to_bytes_too_large() {
+        use rancor::Failure;
+
+        use super::MAX_ARCHIVE_LEN;
+        use crate::{alloc::vec, to_bytes};
+
+        assert!(to_bytes::<Failure>(&vec![0u8; MAX_ARCHIVE_LEN]).is_err());
+        assert!(to_bytes::<Failure>(&vec![0u8; MAX_ARCHIVE_LEN / 2]).is_ok());
+    }
 }
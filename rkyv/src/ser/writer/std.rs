@@ -2,7 +2,7 @@ use std::io;
 
 use rancor::{ResultExt as _, Source};
 
-use crate::ser::{Positional, Writer};
+use crate::ser::{writer::check_archive_len, Positional, Writer};
 
 /// Wraps a type that implements [`io::Write`](std::io::Write) and equips it
 /// with [`Writer`].
@@ -40,6 +40,19 @@ impl<W> IoWriter<W> {
         Self { inner, pos }
     }
 
+    /// Returns a reference to the internal writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the internal writer.
+    ///
+    /// Writing directly to the internal writer will desynchronize it from the
+    /// position tracked by the `IoWriter`.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
     /// Consumes the serializer and returns the internal writer used to create
     /// it.
     pub fn into_inner(self) -> W {
@@ -55,10 +68,35 @@ impl<W> Positional for IoWriter<W> {
 
 impl<W: io::Write, E: Source> Writer<E> for IoWriter<W> {
     fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        check_archive_len::<E>(self.pos, bytes.len())?;
         self.inner.write_all(bytes).into_error()?;
         self.pos += bytes.len();
         Ok(())
     }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), E> {
+        let len = bufs.iter().map(|buf| buf.len()).sum::<usize>();
+        check_archive_len::<E>(self.pos, len)?;
+        let mut slices = bufs
+            .iter()
+            .map(|buf| io::IoSlice::new(buf))
+            .collect::<Vec<_>>();
+        let mut slices = &mut slices[..];
+        io::IoSlice::advance_slices(&mut slices, 0);
+        while !slices.is_empty() {
+            match self.inner.write_vectored(slices) {
+                Ok(0) => {
+                    return Err(io::Error::from(io::ErrorKind::WriteZero))
+                        .into_error();
+                }
+                Ok(n) => io::IoSlice::advance_slices(&mut slices, n),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e).into_error(),
+            }
+        }
+        self.pos += len;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,282 @@
+use core::{alloc::Layout, future::Future, ptr::NonNull};
+
+use rancor::{Fallible, ResultExt as _, Source, Strategy};
+use tokio_1::io::{AsyncWrite, AsyncWriteExt as _};
+
+use crate::{
+    alloc::vec::Vec,
+    ser::{
+        sharing::{SharedValue, SharingState},
+        Allocator, Positional, Sharing, Writer,
+    },
+    SerializeUnsized,
+};
+
+/// A type that asynchronously writes bytes to some output.
+///
+/// This is the asynchronous counterpart to [`Writer`]. Serialization itself is
+/// synchronous, so an `AsyncWriter` is driven by an [`AsyncSerializer`] which
+/// stages bytes and flushes them when awaited.
+pub trait AsyncWriter<E = <Self as Fallible>::Error>: Positional {
+    /// Attempts to write the given bytes to the output.
+    fn write_async(
+        &mut self,
+        bytes: &[u8],
+    ) -> impl Future<Output = Result<(), E>>;
+
+    /// Flushes any bytes buffered by the output.
+    fn flush_async(&mut self) -> impl Future<Output = Result<(), E>>;
+}
+
+/// Wraps a type that implements [`AsyncWrite`] and equips it with
+/// [`AsyncWriter`].
+#[derive(Debug)]
+pub struct TokioWriter<W> {
+    inner: W,
+    pos: usize,
+}
+
+impl<W> TokioWriter<W> {
+    /// Creates a new writer from an async sink.
+    pub fn new(inner: W) -> Self {
+        Self::with_pos(inner, 0)
+    }
+
+    /// Creates a new writer from an async sink, and assumes that the
+    /// underlying sink is currently at the given position.
+    pub fn with_pos(inner: W, pos: usize) -> Self {
+        Self { inner, pos }
+    }
+
+    /// Consumes the writer and returns the internal sink used to create it.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W> Positional for TokioWriter<W> {
+    fn pos(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<W: AsyncWrite + Unpin, E: Source> AsyncWriter<E> for TokioWriter<W> {
+    async fn write_async(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.inner.write_all(bytes).await.into_error()?;
+        self.pos += bytes.len();
+        Ok(())
+    }
+
+    async fn flush_async(&mut self) -> Result<(), E> {
+        self.inner.flush().await.into_error()
+    }
+}
+
+/// A serializer which writes to an [`AsyncWriter`].
+///
+/// Bytes written during serialization are staged in an internal buffer and
+/// written to the underlying writer when [`flush`](AsyncSerializer::flush) is
+/// awaited. Each call to [`serialize`](AsyncSerializer::serialize) flushes
+/// once it completes, so serializing a sequence of values one at a time only
+/// ever stages a single value in memory and awaits backpressure between
+/// values.
+#[derive(Debug)]
+pub struct AsyncSerializer<W, A, S> {
+    writer: W,
+    buffer: Vec<u8>,
+    /// The allocator of the serializer.
+    pub allocator: A,
+    /// The pointer sharing of the serializer.
+    pub sharing: S,
+}
+
+impl<W, A, S> AsyncSerializer<W, A, S> {
+    /// Creates a new async serializer from a writer, allocator, and pointer
+    /// sharing.
+    pub fn new(writer: W, allocator: A, sharing: S) -> Self {
+        Self {
+            writer,
+            buffer: Vec::new(),
+            allocator,
+            sharing,
+        }
+    }
+
+    /// Returns the number of bytes staged but not yet flushed to the writer.
+    pub fn staged_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Consumes the serializer and returns the components.
+    ///
+    /// Any staged bytes which have not been flushed are discarded.
+    pub fn into_raw_parts(self) -> (W, A, S) {
+        (self.writer, self.allocator, self.sharing)
+    }
+
+    /// Consumes the serializer and returns the writer.
+    ///
+    /// The allocator, pointer sharing, and any staged bytes which have not
+    /// been flushed are discarded.
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: AsyncWriter<E>, A, S, E> AsyncSerializer<W, A, S>
+where
+    A: Allocator<E>,
+    S: Sharing<E>,
+    E: Source,
+{
+    /// Writes all staged bytes to the underlying writer and flushes it.
+    pub async fn flush(&mut self) -> Result<(), E> {
+        if !self.buffer.is_empty() {
+            self.writer.write_async(&self.buffer).await?;
+            self.buffer.clear();
+        }
+        self.writer.flush_async().await
+    }
+
+    /// Serializes the given value and flushes the resulting bytes to the
+    /// underlying writer.
+    ///
+    /// Returns the position of the serialized value.
+    pub async fn serialize<T>(&mut self, value: &T) -> Result<usize, E>
+    where
+        T: SerializeUnsized<Strategy<Self, E>> + ?Sized,
+    {
+        let pos = value.serialize_unsized(Strategy::wrap(self))?;
+        self.flush().await?;
+        Ok(pos)
+    }
+}
+
+impl<W: Positional, A, S> Positional for AsyncSerializer<W, A, S> {
+    fn pos(&self) -> usize {
+        self.writer.pos() + self.buffer.len()
+    }
+}
+
+impl<W: Positional, A, S, E> Writer<E> for AsyncSerializer<W, A, S> {
+    fn write(&mut self, bytes: &[u8]) -> Result<(), E> {
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+unsafe impl<W, A, S, E> Allocator<E> for AsyncSerializer<W, A, S>
+where
+    A: Allocator<E>,
+{
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, E> {
+        // SAFETY: The safety requirements for `A::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        unsafe { self.allocator.push_alloc(layout) }
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), E> {
+        // SAFETY: The safety requirements for `A::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        unsafe { self.allocator.pop_alloc(ptr, layout) }
+    }
+}
+
+impl<W, A, S: Sharing<E>, E> Sharing<E> for AsyncSerializer<W, A, S> {
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        self.sharing.start_sharing(address)
+    }
+
+    fn finish_sharing(&mut self, address: usize, pos: usize) -> Result<(), E> {
+        self.sharing.finish_sharing(address, pos)
+    }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        self.sharing.start_sharing_value(address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), E> {
+        self.sharing.finish_sharing_value(address, archived, pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        future::Future,
+        pin::pin,
+        ptr,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    use rancor::Panic;
+
+    use super::{AsyncSerializer, TokioWriter};
+    use crate::{
+        access_unchecked,
+        alloc::{vec, vec::Vec},
+        ser::{allocator::Arena, sharing::Share},
+        util::AlignedVec,
+        Archived,
+    };
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(
+            |_| RawWaker::new(ptr::null(), &VTABLE),
+            |_| (),
+            |_| (),
+            |_| (),
+        );
+        // SAFETY: The vtable functions do nothing and never dereference the
+        // data pointer.
+        let waker =
+            unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+        let mut cx = Context::from_waker(&waker);
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn async_serializer() {
+        let mut arena = Arena::new();
+        let mut serializer = AsyncSerializer::new(
+            TokioWriter::new(Vec::new()),
+            arena.acquire(),
+            Share::new(),
+        );
+
+        let value = vec![1u32, 2, 3, 4];
+        let result: Result<usize, Panic> =
+            block_on(serializer.serialize(&value));
+        result.unwrap();
+        assert_eq!(serializer.staged_len(), 0);
+
+        let bytes = serializer.into_writer().into_inner();
+        let mut aligned = AlignedVec::<16>::new();
+        aligned.extend_from_slice(&bytes);
+        // SAFETY: The bytes were serialized from a `Vec<u32>`.
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<u32>>>(&aligned) };
+        assert_eq!(archived.as_slice(), &[1, 2, 3, 4]);
+    }
+}
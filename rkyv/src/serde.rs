@@ -0,0 +1,1018 @@
+//! [`serde`](serde_1) serialization for archived types.
+//!
+//! Archived types can be serialized with serde without deserializing them
+//! first. This makes it possible to convert archived data to other formats like
+//! JSON directly from a buffer.
+//!
+//! Archived primitives like [`ArchivedU32`](crate::primitive::ArchivedU32) are
+//! defined in other crates, so they can't implement `serde::Serialize`
+//! directly. Instead, archived types implement [`SerializeArchived`], and
+//! [`AsSerde`] adapts any `SerializeArchived` type into a `serde::Serialize`
+//! one. Archived containers defined by rkyv implement both.
+//!
+//! Archived types generated by the derive macro can implement both traits by
+//! adding `#[rkyv(serde)]` to the derive input.
+//!
+//! # Example
+//!
+//! ```
+//! use rkyv::{
+//!     access, rancor::Error, serde::AsSerde, to_bytes, Archive, Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize)]
+//! #[rkyv(serde)]
+//! struct Point {
+//!     x: i32,
+//!     y: i32,
+//! }
+//!
+//! fn assert_serialize<T: rkyv::serde::serde_1::Serialize>(_: &T) {}
+//!
+//! let bytes = to_bytes::<Error>(&Point { x: 1, y: 2 }).unwrap();
+//! let archived = access::<ArchivedPoint, Error>(&bytes).unwrap();
+//!
+//! // `ArchivedPoint` can be serialized directly, and archived primitives can
+//! // be serialized through `AsSerde`.
+//! assert_serialize(archived);
+//! assert_serialize(&AsSerde(&archived.x));
+//! ```
+
+use core::ops::ControlFlow;
+
+#[doc(hidden)]
+pub use serde_1;
+use serde_1::{
+    ser::{SerializeMap as _, SerializeSeq as _, SerializeTuple as _},
+    Serialize, Serializer,
+};
+
+use crate::{
+    boxed::ArchivedBox,
+    collections::{
+        btree_map::ArchivedBTreeMap,
+        btree_set::ArchivedBTreeSet,
+        swiss_table::{ArchivedHashMap, ArchivedHashSet},
+    },
+    option::ArchivedOption,
+    primitive::{
+        ArchivedChar, ArchivedF32, ArchivedF64, ArchivedI128, ArchivedI16,
+        ArchivedI32, ArchivedI64, ArchivedNonZeroI128, ArchivedNonZeroI16,
+        ArchivedNonZeroI32, ArchivedNonZeroI64, ArchivedNonZeroU128,
+        ArchivedNonZeroU16, ArchivedNonZeroU32, ArchivedNonZeroU64,
+        ArchivedU128, ArchivedU16, ArchivedU32, ArchivedU64,
+    },
+    rc::{ArchivedRc, ArchivedRcWeak},
+    string::ArchivedString,
+    tuple::*,
+    vec::ArchivedVec,
+    ArchivePointee,
+};
+
+/// An archived type which can be serialized with serde.
+///
+/// This is implemented for archived types which can't implement
+/// `serde::Serialize` because they are defined in other crates. Use [`AsSerde`]
+/// to serialize these types with serde.
+pub trait SerializeArchived {
+    /// Serializes this value with the given serde serializer.
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>;
+}
+
+impl<T: SerializeArchived + ?Sized> SerializeArchived for &T {
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        T::serialize_archived(self, serializer)
+    }
+}
+
+/// A wrapper which serializes a [`SerializeArchived`] type with serde.
+#[derive(Debug)]
+pub struct AsSerde<'a, T: ?Sized>(pub &'a T);
+
+impl<T: SerializeArchived + ?Sized> Serialize for AsSerde<'_, T> {
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_archived(serializer)
+    }
+}
+
+// Primitives
+
+macro_rules! impl_native {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SerializeArchived for $ty {
+                #[inline]
+                fn serialize_archived<S: Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(self, serializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_native!(
+    (),
+    bool,
+    i8,
+    u8,
+    str,
+    core::num::NonZeroI8,
+    core::num::NonZeroU8,
+);
+
+macro_rules! impl_rend {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl SerializeArchived for $ty {
+                #[inline]
+                fn serialize_archived<S: Serializer>(
+                    &self,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    Serialize::serialize(&self.to_native(), serializer)
+                }
+            }
+        )*
+    };
+}
+
+impl_rend!(
+    ArchivedI16,
+    ArchivedI32,
+    ArchivedI64,
+    ArchivedI128,
+    ArchivedU16,
+    ArchivedU32,
+    ArchivedU64,
+    ArchivedU128,
+    ArchivedF32,
+    ArchivedF64,
+    ArchivedChar,
+    ArchivedNonZeroI16,
+    ArchivedNonZeroI32,
+    ArchivedNonZeroI64,
+    ArchivedNonZeroI128,
+    ArchivedNonZeroU16,
+    ArchivedNonZeroU32,
+    ArchivedNonZeroU64,
+    ArchivedNonZeroU128,
+);
+
+// Slices and arrays
+
+impl<T: SerializeArchived> SerializeArchived for [T] {
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for element in self {
+            seq.serialize_element(&AsSerde(element))?;
+        }
+        seq.end()
+    }
+}
+
+impl<T: SerializeArchived, const N: usize> SerializeArchived for [T; N] {
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in self {
+            tuple.serialize_element(&AsSerde(element))?;
+        }
+        tuple.end()
+    }
+}
+
+// Archived containers
+
+macro_rules! impl_serialize {
+    (
+        impl<$($param:ident $(: $bound:path)?),* $(; const $c:ident)?>
+            for $ty:ty
+    ) => {
+        impl<$($param $(: $bound)?,)* $(const $c: usize)?> Serialize for $ty
+        where
+            Self: SerializeArchived,
+        {
+            #[inline]
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                self.serialize_archived(serializer)
+            }
+        }
+    };
+}
+
+impl SerializeArchived for ArchivedString {
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl_serialize!(impl<> for ArchivedString);
+
+impl<T: SerializeArchived> SerializeArchived for ArchivedVec<T> {
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize_archived(serializer)
+    }
+}
+
+impl_serialize!(impl<T> for ArchivedVec<T>);
+
+impl<T> SerializeArchived for ArchivedBox<T>
+where
+    T: ArchivePointee + SerializeArchived + ?Sized,
+{
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.get().serialize_archived(serializer)
+    }
+}
+
+impl<T: ArchivePointee + ?Sized> Serialize for ArchivedBox<T>
+where
+    Self: SerializeArchived,
+{
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.serialize_archived(serializer)
+    }
+}
+
+// Shared pointers are serialized as their targets, so values which are shared
+// in the archive are serialized once for each pointer to them.
+
+impl<T, F> SerializeArchived for ArchivedRc<T, F>
+where
+    T: ArchivePointee + SerializeArchived + ?Sized,
+{
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.get().serialize_archived(serializer)
+    }
+}
+
+impl<T: ArchivePointee + ?Sized, F> Serialize for ArchivedRc<T, F>
+where
+    Self: SerializeArchived,
+{
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.serialize_archived(serializer)
+    }
+}
+
+impl<T, F> SerializeArchived for ArchivedRcWeak<T, F>
+where
+    T: ArchivePointee + SerializeArchived + ?Sized,
+{
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self.upgrade() {
+            None => serializer.serialize_none(),
+            Some(rc) => serializer.serialize_some(&AsSerde(rc)),
+        }
+    }
+}
+
+impl<T: ArchivePointee + ?Sized, F> Serialize for ArchivedRcWeak<T, F>
+where
+    Self: SerializeArchived,
+{
+    #[inline]
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        self.serialize_archived(serializer)
+    }
+}
+
+impl<T: SerializeArchived> SerializeArchived for ArchivedOption<T> {
+    #[inline]
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match self {
+            ArchivedOption::None => serializer.serialize_none(),
+            ArchivedOption::Some(value) => {
+                serializer.serialize_some(&AsSerde(value))
+            }
+        }
+    }
+}
+
+impl_serialize!(impl<T> for ArchivedOption<T>);
+
+impl<K, V, H> SerializeArchived for ArchivedHashMap<K, V, H>
+where
+    K: SerializeArchived,
+    V: SerializeArchived,
+{
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (key, value) in self.iter() {
+            map.serialize_entry(&AsSerde(key), &AsSerde(value))?;
+        }
+        map.end()
+    }
+}
+
+impl_serialize!(impl<K, V, H> for ArchivedHashMap<K, V, H>);
+
+impl<K: SerializeArchived, H> SerializeArchived for ArchivedHashSet<K, H> {
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for key in self.iter() {
+            seq.serialize_element(&AsSerde(key))?;
+        }
+        seq.end()
+    }
+}
+
+impl_serialize!(impl<K, H> for ArchivedHashSet<K, H>);
+
+impl<K, V, const E: usize> SerializeArchived for ArchivedBTreeMap<K, V, E>
+where
+    K: SerializeArchived,
+    V: SerializeArchived,
+{
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        let result = self.visit(|key, value| {
+            match map.serialize_entry(&AsSerde(key), &AsSerde(value)) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(e) => ControlFlow::Break(e),
+            }
+        });
+        match result {
+            Some(e) => Err(e),
+            None => map.end(),
+        }
+    }
+}
+
+impl_serialize!(impl<K, V; const E> for ArchivedBTreeMap<K, V, E>);
+
+impl<K, const E: usize> SerializeArchived for ArchivedBTreeSet<K, E>
+where
+    K: SerializeArchived,
+{
+    fn serialize_archived<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let result =
+            self.visit(|key| match seq.serialize_element(&AsSerde(key)) {
+                Ok(()) => ControlFlow::Continue(()),
+                Err(e) => ControlFlow::Break(e),
+            });
+        match result {
+            Some(e) => Err(e),
+            None => seq.end(),
+        }
+    }
+}
+
+impl_serialize!(impl<K; const E> for ArchivedBTreeSet<K, E>);
+
+macro_rules! impl_tuple {
+    ($name:ident $n:literal, $($t:ident $index:tt),* $(,)?) => {
+        impl<$($t: SerializeArchived),*> SerializeArchived for $name<$($t),*> {
+            fn serialize_archived<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error> {
+                let mut tuple = serializer.serialize_tuple($n)?;
+                $(
+                    tuple.serialize_element(&AsSerde(&self.$index))?;
+                )*
+                tuple.end()
+            }
+        }
+
+        impl_serialize!(impl<$($t),*> for $name<$($t),*>);
+    };
+}
+
+impl_tuple!(ArchivedTuple1 1, T0 0);
+impl_tuple!(ArchivedTuple2 2, T0 0, T1 1);
+impl_tuple!(ArchivedTuple3 3, T0 0, T1 1, T2 2);
+impl_tuple!(ArchivedTuple4 4, T0 0, T1 1, T2 2, T3 3);
+impl_tuple!(ArchivedTuple5 5, T0 0, T1 1, T2 2, T3 3, T4 4);
+impl_tuple!(ArchivedTuple6 6, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5);
+impl_tuple!(ArchivedTuple7 7, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6);
+impl_tuple!(ArchivedTuple8 8, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7);
+impl_tuple!(
+    ArchivedTuple9 9, T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8
+);
+impl_tuple!(
+    ArchivedTuple10 10,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+);
+impl_tuple!(
+    ArchivedTuple11 11,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+);
+impl_tuple!(
+    ArchivedTuple12 12,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+    T11 11,
+);
+impl_tuple!(
+    ArchivedTuple13 13,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+    T11 11,
+    T12 12,
+);
+impl_tuple!(
+    ArchivedTuple14 14,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+    T11 11,
+    T12 12,
+    T13 13,
+);
+impl_tuple!(
+    ArchivedTuple15 15,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+    T11 11,
+    T12 12,
+    T13 13,
+    T14 14,
+);
+impl_tuple!(
+    ArchivedTuple16 16,
+    T0 0,
+    T1 1,
+    T2 2,
+    T3 3,
+    T4 4,
+    T5 5,
+    T6 6,
+    T7 7,
+    T8 8,
+    T9 9,
+    T10 10,
+    T11 11,
+    T12 12,
+    T13 13,
+    T14 14,
+    T15 15,
+);
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use core::fmt::{self, Write as _};
+
+    use serde_1::{ser, Serialize};
+
+    use crate::{
+        alloc::{
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::test::{deserialize, to_archived},
+        Archive, Deserialize, Serialize as RkyvSerialize,
+    };
+
+    #[derive(Debug)]
+    struct Error;
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "serialization failed")
+        }
+    }
+
+    impl core::error::Error for Error {}
+
+    impl ser::Error for Error {
+        fn custom<T: fmt::Display>(_: T) -> Self {
+            Error
+        }
+    }
+
+    /// A minimal serializer which writes values in a debug-like format.
+    struct Writer<'a>(&'a mut String);
+
+    macro_rules! write_display {
+        ($($method:ident: $ty:ty),* $(,)?) => {
+            $(
+                fn $method(self, v: $ty) -> Result<(), Error> {
+                    write!(self.0, "{}", v).map_err(|_| Error)
+                }
+            )*
+        };
+    }
+
+    impl<'a> ser::Serializer for Writer<'a> {
+        type Ok = ();
+        type Error = Error;
+        type SerializeSeq = Compound<'a>;
+        type SerializeTuple = Compound<'a>;
+        type SerializeTupleStruct = Compound<'a>;
+        type SerializeTupleVariant = Compound<'a>;
+        type SerializeMap = Compound<'a>;
+        type SerializeStruct = Compound<'a>;
+        type SerializeStructVariant = Compound<'a>;
+
+        write_display!(
+            serialize_bool: bool,
+            serialize_i8: i8,
+            serialize_i16: i16,
+            serialize_i32: i32,
+            serialize_i64: i64,
+            serialize_u8: u8,
+            serialize_u16: u16,
+            serialize_u32: u32,
+            serialize_u64: u64,
+            serialize_f32: f32,
+            serialize_f64: f64,
+            serialize_char: char,
+        );
+
+        fn serialize_str(self, v: &str) -> Result<(), Error> {
+            write!(self.0, "{:?}", v).map_err(|_| Error)
+        }
+
+        fn serialize_bytes(self, v: &[u8]) -> Result<(), Error> {
+            write!(self.0, "{:?}", v).map_err(|_| Error)
+        }
+
+        fn serialize_none(self) -> Result<(), Error> {
+            self.0.push_str("None");
+            Ok(())
+        }
+
+        fn serialize_some<T: Serialize + ?Sized>(
+            self,
+            value: &T,
+        ) -> Result<(), Error> {
+            self.0.push_str("Some(");
+            value.serialize(Writer(&mut *self.0))?;
+            self.0.push(')');
+            Ok(())
+        }
+
+        fn serialize_unit(self) -> Result<(), Error> {
+            self.0.push_str("()");
+            Ok(())
+        }
+
+        fn serialize_unit_struct(self, name: &str) -> Result<(), Error> {
+            self.0.push_str(name);
+            Ok(())
+        }
+
+        fn serialize_unit_variant(
+            self,
+            _: &str,
+            _: u32,
+            variant: &str,
+        ) -> Result<(), Error> {
+            self.0.push_str(variant);
+            Ok(())
+        }
+
+        fn serialize_newtype_struct<T: Serialize + ?Sized>(
+            self,
+            name: &str,
+            value: &T,
+        ) -> Result<(), Error> {
+            self.0.push_str(name);
+            self.0.push('(');
+            value.serialize(Writer(&mut *self.0))?;
+            self.0.push(')');
+            Ok(())
+        }
+
+        fn serialize_newtype_variant<T: Serialize + ?Sized>(
+            self,
+            _: &str,
+            _: u32,
+            variant: &str,
+            value: &T,
+        ) -> Result<(), Error> {
+            self.serialize_newtype_struct(variant, value)
+        }
+
+        fn serialize_seq(
+            self,
+            _: Option<usize>,
+        ) -> Result<Compound<'a>, Error> {
+            Ok(Compound::new(self.0, "[", "]"))
+        }
+
+        fn serialize_tuple(self, _: usize) -> Result<Compound<'a>, Error> {
+            Ok(Compound::new(self.0, "(", ")"))
+        }
+
+        fn serialize_tuple_struct(
+            self,
+            name: &str,
+            _: usize,
+        ) -> Result<Compound<'a>, Error> {
+            self.0.push_str(name);
+            Ok(Compound::new(self.0, "(", ")"))
+        }
+
+        fn serialize_tuple_variant(
+            self,
+            _: &str,
+            _: u32,
+            variant: &str,
+            len: usize,
+        ) -> Result<Compound<'a>, Error> {
+            self.serialize_tuple_struct(variant, len)
+        }
+
+        fn serialize_map(
+            self,
+            _: Option<usize>,
+        ) -> Result<Compound<'a>, Error> {
+            Ok(Compound::new(self.0, "{", "}"))
+        }
+
+        fn serialize_struct(
+            self,
+            name: &str,
+            _: usize,
+        ) -> Result<Compound<'a>, Error> {
+            self.0.push_str(name);
+            Ok(Compound::new(self.0, " { ", " }"))
+        }
+
+        fn serialize_struct_variant(
+            self,
+            _: &str,
+            _: u32,
+            variant: &str,
+            len: usize,
+        ) -> Result<Compound<'a>, Error> {
+            self.serialize_struct(variant, len)
+        }
+    }
+
+    struct Compound<'a> {
+        out: &'a mut String,
+        first: bool,
+        close: &'static str,
+    }
+
+    impl<'a> Compound<'a> {
+        fn new(
+            out: &'a mut String,
+            open: &'static str,
+            close: &'static str,
+        ) -> Self {
+            out.push_str(open);
+            Self {
+                out,
+                first: true,
+                close,
+            }
+        }
+
+        fn element<T: Serialize + ?Sized>(
+            &mut self,
+            key: Option<&str>,
+            value: &T,
+        ) -> Result<(), Error> {
+            if !self.first {
+                self.out.push_str(", ");
+            }
+            self.first = false;
+            if let Some(key) = key {
+                self.out.push_str(key);
+                self.out.push_str(": ");
+            }
+            value.serialize(Writer(&mut *self.out))
+        }
+
+        fn finish(self) -> Result<(), Error> {
+            self.out.push_str(self.close);
+            Ok(())
+        }
+    }
+
+    macro_rules! impl_compound {
+        ($($trait:ident :: $method:ident),* $(,)?) => {
+            $(
+                impl ser::$trait for Compound<'_> {
+                    type Ok = ();
+                    type Error = Error;
+
+                    fn $method<T: Serialize + ?Sized>(
+                        &mut self,
+                        value: &T,
+                    ) -> Result<(), Error> {
+                        self.element(None, value)
+                    }
+
+                    fn end(self) -> Result<(), Error> {
+                        self.finish()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_compound!(
+        SerializeSeq::serialize_element,
+        SerializeTuple::serialize_element,
+        SerializeTupleStruct::serialize_field,
+        SerializeTupleVariant::serialize_field,
+    );
+
+    impl ser::SerializeMap for Compound<'_> {
+        type Ok = ();
+        type Error = Error;
+
+        fn serialize_key<T: Serialize + ?Sized>(
+            &mut self,
+            key: &T,
+        ) -> Result<(), Error> {
+            self.element(None, key)?;
+            self.out.push_str(": ");
+            self.first = true;
+            Ok(())
+        }
+
+        fn serialize_value<T: Serialize + ?Sized>(
+            &mut self,
+            value: &T,
+        ) -> Result<(), Error> {
+            value.serialize(Writer(&mut *self.out))?;
+            self.first = false;
+            Ok(())
+        }
+
+        fn end(self) -> Result<(), Error> {
+            self.finish()
+        }
+    }
+
+    macro_rules! impl_compound_struct {
+        ($($trait:ident),* $(,)?) => {
+            $(
+                impl ser::$trait for Compound<'_> {
+                    type Ok = ();
+                    type Error = Error;
+
+                    fn serialize_field<T: Serialize + ?Sized>(
+                        &mut self,
+                        key: &'static str,
+                        value: &T,
+                    ) -> Result<(), Error> {
+                        self.element(Some(key), value)
+                    }
+
+                    fn end(self) -> Result<(), Error> {
+                        self.finish()
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_compound_struct!(SerializeStruct, SerializeStructVariant);
+
+    fn to_string<T: Serialize + ?Sized>(value: &T) -> String {
+        let mut result = String::new();
+        value.serialize(Writer(&mut result)).unwrap();
+        result
+    }
+
+    #[test]
+    fn serialize_containers() {
+        let value = (
+            "hello".to_string(),
+            vec![1u32, 2, 3],
+            Some(-4i64),
+            Option::<char>::None,
+        );
+        to_archived(&value, |archived| {
+            assert_eq!(
+                to_string(&*archived),
+                r#"("hello", [1, 2, 3], Some(-4), None)"#,
+            );
+        });
+    }
+
+    #[derive(Archive, RkyvSerialize)]
+    #[rkyv(crate, serde)]
+    struct Example {
+        name: String,
+        values: Vec<Pair>,
+        #[rkyv(skip)]
+        _cache: Vec<u8>,
+        kind: Kind,
+        unit: Unit,
+    }
+
+    #[derive(Archive, RkyvSerialize)]
+    #[rkyv(crate, serde)]
+    struct Pair(u16, f32);
+
+    #[derive(Archive, RkyvSerialize)]
+    #[rkyv(crate, serde)]
+    struct Unit;
+
+    #[derive(Archive, RkyvSerialize)]
+    #[rkyv(crate, serde)]
+    enum Kind {
+        A,
+        B(bool),
+        C(u8, u8),
+        D { x: i32 },
+    }
+
+    #[test]
+    fn serialize_derived() {
+        let value = Example {
+            name: "example".to_string(),
+            values: vec![Pair(1, 0.5), Pair(2, 1.5)],
+            _cache: Vec::new(),
+            kind: Kind::D { x: -1 },
+            unit: Unit,
+        };
+        to_archived(&value, |archived| {
+            assert_eq!(
+                to_string(&*archived),
+                "Example { name: \"example\", values: [Pair(1, 0.5), Pair(2, \
+                 1.5)], kind: D { x: -1 }, unit: Unit }",
+            );
+        });
+
+        let kinds = vec![Kind::A, Kind::B(true), Kind::C(1, 2)];
+        to_archived(&kinds, |archived| {
+            assert_eq!(to_string(&*archived), "[A, B(true), C(1, 2)]");
+        });
+    }
+
+    fn default_retries() -> u32 {
+        3
+    }
+
+    #[derive(Archive, RkyvSerialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, serde)]
+    #[serde(rename = "Conf", deny_unknown_fields)]
+    struct Config {
+        #[serde(rename = "hostName")]
+        host: String,
+        #[serde(skip, default = "default_retries")]
+        retries: u32,
+        #[serde(skip_serializing)]
+        secret: String,
+        #[serde(rename(serialize = "m", deserialize = "mode"))]
+        mode: Mode,
+    }
+
+    #[derive(Archive, RkyvSerialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, serde)]
+    enum Mode {
+        #[serde(rename = "fast")]
+        Fast,
+        #[serde(skip)]
+        Hidden,
+    }
+
+    #[test]
+    fn serde_attributes() {
+        let value = Config {
+            host: "localhost".to_string(),
+            retries: 10,
+            secret: "hunter2".to_string(),
+            mode: Mode::Fast,
+        };
+        to_archived(&value, |archived| {
+            assert_eq!(
+                to_string(&*archived),
+                "Conf { hostName: \"localhost\", m: fast }",
+            );
+
+            let deserialized = deserialize::<Config>(&*archived);
+            assert_eq!(deserialized.retries, 3);
+            assert_eq!(deserialized.secret, "hunter2");
+        });
+
+        to_archived(&Mode::Hidden, |archived| {
+            let mut result = String::new();
+            assert!(
+                Serialize::serialize(&*archived, Writer(&mut result)).is_err()
+            );
+        });
+    }
+}
@@ -0,0 +1,243 @@
+//! An archived byte string.
+
+use core::{
+    borrow::Borrow,
+    cmp, fmt, hash,
+    ops::{
+        Deref, Index, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
+        RangeToInclusive,
+    },
+    str,
+};
+
+use munge::munge;
+use rancor::Fallible;
+
+use crate::{
+    ser::Writer,
+    vec::{ArchivedVec, VecResolver},
+    Place, Portable,
+};
+
+/// An archived byte string.
+///
+/// Byte strings are conventionally UTF-8, but may contain arbitrary bytes. An
+/// `ArchivedByteString` derefs to `[u8]` and formats its contents as a byte
+/// string literal.
+#[derive(Portable)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedByteString {
+    inner: ArchivedVec<u8>,
+}
+
+impl ArchivedByteString {
+    /// Returns the contents of the byte string as a slice of bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.inner.as_slice()
+    }
+
+    /// Returns the length of the byte string in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Returns whether the byte string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// Returns the byte string as a `str` if it is valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Result<&str, str::Utf8Error> {
+        str::from_utf8(self.as_bytes())
+    }
+
+    /// Returns the byte string as a `BStr`.
+    #[cfg(feature = "bstr-1")]
+    #[inline]
+    pub fn as_bstr(&self) -> &bstr_1::BStr {
+        bstr_1::BStr::new(self.as_bytes())
+    }
+
+    /// Resolves an archived byte string from the given bytes and parameters.
+    #[inline]
+    pub fn resolve_from_bytes(
+        bytes: &[u8],
+        resolver: ByteStringResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedByteString { inner } = out);
+        ArchivedVec::<u8>::resolve_from_len(bytes.len(), resolver.inner, inner);
+    }
+
+    /// Serializes an archived byte string from the given bytes.
+    pub fn serialize_from_bytes<S: Fallible + Writer + ?Sized>(
+        bytes: &[u8],
+        serializer: &mut S,
+    ) -> Result<ByteStringResolver, S::Error> {
+        let pos = serializer.pos();
+        serializer.write(bytes)?;
+        Ok(ByteStringResolver {
+            inner: VecResolver::from_pos(pos),
+        })
+    }
+}
+
+impl AsRef<[u8]> for ArchivedByteString {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Borrow<[u8]> for ArchivedByteString {
+    #[inline]
+    fn borrow(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl fmt::Debug for ArchivedByteString {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "b\"{}\"", self.as_bytes().escape_ascii())
+    }
+}
+
+impl Deref for ArchivedByteString {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_bytes()
+    }
+}
+
+impl Eq for ArchivedByteString {}
+
+impl hash::Hash for ArchivedByteString {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.as_bytes().hash(state)
+    }
+}
+
+macro_rules! impl_index {
+    ($index:ty) => {
+        impl Index<$index> for ArchivedByteString {
+            type Output = [u8];
+
+            #[inline]
+            fn index(&self, index: $index) -> &Self::Output {
+                self.as_bytes().index(index)
+            }
+        }
+    };
+}
+
+impl_index!(Range<usize>);
+impl_index!(RangeFrom<usize>);
+impl_index!(RangeFull);
+impl_index!(RangeInclusive<usize>);
+impl_index!(RangeTo<usize>);
+impl_index!(RangeToInclusive<usize>);
+
+impl Ord for ArchivedByteString {
+    #[inline]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.as_bytes().cmp(other.as_bytes())
+    }
+}
+
+impl PartialEq for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialOrd for ArchivedByteString {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq<&[u8]> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &&[u8]) -> bool {
+        PartialEq::eq(self.as_bytes(), *other)
+    }
+}
+
+impl PartialEq<[u8]> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &[u8]) -> bool {
+        PartialEq::eq(self.as_bytes(), other)
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &[u8; N]) -> bool {
+        PartialEq::eq(self.as_bytes(), other.as_slice())
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for ArchivedByteString {
+    #[inline]
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        PartialEq::eq(self.as_bytes(), other.as_slice())
+    }
+}
+
+impl PartialEq<ArchivedByteString> for &[u8] {
+    #[inline]
+    fn eq(&self, other: &ArchivedByteString) -> bool {
+        PartialEq::eq(other.as_bytes(), *self)
+    }
+}
+
+impl PartialEq<ArchivedByteString> for [u8] {
+    #[inline]
+    fn eq(&self, other: &ArchivedByteString) -> bool {
+        PartialEq::eq(other.as_bytes(), self)
+    }
+}
+
+impl PartialOrd<&[u8]> for ArchivedByteString {
+    #[inline]
+    fn partial_cmp(&self, other: &&[u8]) -> Option<cmp::Ordering> {
+        self.as_bytes().partial_cmp(*other)
+    }
+}
+
+impl PartialOrd<[u8]> for ArchivedByteString {
+    #[inline]
+    fn partial_cmp(&self, other: &[u8]) -> Option<cmp::Ordering> {
+        self.as_bytes().partial_cmp(other)
+    }
+}
+
+impl PartialOrd<ArchivedByteString> for &[u8] {
+    #[inline]
+    fn partial_cmp(&self, other: &ArchivedByteString) -> Option<cmp::Ordering> {
+        (*self).partial_cmp(other.as_bytes())
+    }
+}
+
+impl PartialOrd<ArchivedByteString> for [u8] {
+    #[inline]
+    fn partial_cmp(&self, other: &ArchivedByteString) -> Option<cmp::Ordering> {
+        self.partial_cmp(other.as_bytes())
+    }
+}
+
+/// The resolver for [`ArchivedByteString`].
+pub struct ByteStringResolver {
+    inner: VecResolver,
+}
@@ -1,5 +1,6 @@
 //! Archived versions of string types.
 
+pub mod byte_string;
 pub mod repr;
 
 use core::{
@@ -89,20 +90,6 @@ impl ArchivedString {
         if value.len() <= INLINE_CAPACITY {
             Ok(StringResolver { pos: 0 })
         } else if value.len() > repr::OUT_OF_LINE_CAPACITY {
-            #[derive(Debug)]
-            struct StringTooLongError;
-
-            impl fmt::Display for StringTooLongError {
-                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                    write!(
-                        f,
-                        "String was too long for the archived representation",
-                    )
-                }
-            }
-
-            impl Error for StringTooLongError {}
-
             fail!(StringTooLongError);
         } else {
             Ok(StringResolver {
@@ -110,6 +97,51 @@ impl ArchivedString {
             })
         }
     }
+
+    /// Resolves an archived string from a given `str`, storing the string
+    /// out-of-line regardless of its length.
+    ///
+    /// The resolver must have been returned from
+    /// [`serialize_out_of_line`](Self::serialize_out_of_line).
+    #[inline]
+    pub fn resolve_out_of_line(
+        value: &str,
+        resolver: StringResolver,
+        out: Place<Self>,
+    ) {
+        munge!(let ArchivedString { repr } = out);
+        // SAFETY: `serialize_out_of_line` checked that the length of `value`
+        // is at most `OUT_OF_LINE_CAPACITY`.
+        unsafe {
+            ArchivedStringRepr::emplace_out_of_line(
+                value,
+                resolver.pos as usize,
+                repr,
+            );
+        }
+    }
+
+    /// Serializes the bytes of a `str` so that it can be resolved out-of-line,
+    /// even if it is short enough to be inlined.
+    ///
+    /// Out-of-line strings always point to their bytes, so their layout does
+    /// not depend on their length.
+    pub fn serialize_out_of_line<S: Fallible + ?Sized>(
+        value: &str,
+        serializer: &mut S,
+    ) -> Result<StringResolver, S::Error>
+    where
+        S::Error: Source,
+        str: SerializeUnsized<S>,
+    {
+        if value.len() > repr::OUT_OF_LINE_CAPACITY {
+            fail!(StringTooLongError);
+        }
+
+        Ok(StringResolver {
+            pos: value.serialize_unsized(serializer)? as FixedUsize,
+        })
+    }
 }
 
 impl AsRef<str> for ArchivedString {
@@ -254,6 +286,17 @@ impl PartialOrd<ArchivedString> for str {
     }
 }
 
+#[derive(Debug)]
+struct StringTooLongError;
+
+impl fmt::Display for StringTooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "String was too long for the archived representation")
+    }
+}
+
+impl Error for StringTooLongError {}
+
 /// The resolver for `String`.
 pub struct StringResolver {
     pos: FixedUsize,
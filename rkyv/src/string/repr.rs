@@ -191,10 +191,13 @@ impl ArchivedStringRepr {
 
     /// Emplaces a new out-of-line representation for the given `str`.
     ///
+    /// Strings of any length may be stored out-of-line, including ones which
+    /// are short enough to be inlined.
+    ///
     /// # Safety
     ///
-    /// The length of `str` must be greater than [`INLINE_CAPACITY`] and less
-    /// than or equal to [`OUT_OF_LINE_CAPACITY`].
+    /// The length of `str` must be less than or equal to
+    /// [`OUT_OF_LINE_CAPACITY`].
     pub unsafe fn try_emplace_out_of_line<E: Source>(
         value: &str,
         target: usize,
@@ -228,8 +231,8 @@ impl ArchivedStringRepr {
     ///
     /// # Safety
     ///
-    /// The length of `str` must be greater than [`INLINE_CAPACITY`] and less
-    /// than or equal to [`OUT_OF_LINE_CAPACITY`].
+    /// The length of `str` must be less than or equal to
+    /// [`OUT_OF_LINE_CAPACITY`].
     #[inline]
     pub unsafe fn emplace_out_of_line(
         value: &str,
@@ -247,48 +250,17 @@ impl ArchivedStringRepr {
 
 #[cfg(feature = "bytecheck")]
 const _: () = {
-    use core::{error::Error, fmt};
-
     use bytecheck::{rancor::Fallible, CheckBytes};
-    use rancor::fail;
-
-    /// An error resulting from an invalid string representation.
-    ///
-    /// Strings that are inline must have a length of at most
-    /// [`INLINE_CAPACITY`].
-    #[derive(Debug)]
-    pub struct CheckStringReprError;
-
-    impl fmt::Display for CheckStringReprError {
-        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            write!(
-                f,
-                "String representation was out-of-line but the length was too \
-                 short",
-            )
-        }
-    }
-
-    impl Error for CheckStringReprError {}
 
-    unsafe impl<C> CheckBytes<C> for ArchivedStringRepr
-    where
-        C: Fallible + ?Sized,
-        C::Error: Source,
-    {
+    // SAFETY: The fields of `ArchivedStringRepr` are always valid for every bit
+    // pattern, and both inline and out-of-line representations may have any
+    // length up to their capacity.
+    unsafe impl<C: Fallible + ?Sized> CheckBytes<C> for ArchivedStringRepr {
         unsafe fn check_bytes(
-            value: *const Self,
+            _: *const Self,
             _: &mut C,
         ) -> Result<(), C::Error> {
-            // SAFETY: The fields of `ArchivedStringRepr` are always valid for
-            // every bit pattern.
-            let repr = unsafe { &*value };
-
-            if !repr.is_inline() && repr.len() <= INLINE_CAPACITY {
-                fail!(CheckStringReprError);
-            } else {
-                Ok(())
-            }
+            Ok(())
         }
     }
 };
@@ -0,0 +1,331 @@
+//! Archived versions of `chrono` types.
+
+use chrono_0_4::{
+    DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike as _,
+    Utc,
+};
+use munge::munge;
+
+use crate::{
+    primitive::{ArchivedI32, ArchivedI64, ArchivedU32},
+    Place, Portable,
+};
+
+const NANOS_PER_SEC: i32 = 1_000_000_000;
+
+/// An archived [`NaiveDate`].
+///
+/// The date is stored as the number of days from January 1, 1 CE.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedNaiveDate {
+    days_from_ce: ArchivedI32,
+}
+
+impl ArchivedNaiveDate {
+    /// Returns the number of days from January 1, 1 CE, where that day is day
+    /// one.
+    #[inline]
+    pub const fn num_days_from_ce(&self) -> i32 {
+        self.days_from_ce.to_native()
+    }
+
+    /// Returns a [`NaiveDate`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date is out of range. This can only happen if the
+    /// archive was accessed without validation.
+    #[inline]
+    pub fn as_naive_date(&self) -> NaiveDate {
+        NaiveDate::from_num_days_from_ce_opt(self.num_days_from_ce())
+            .expect("archived `NaiveDate` is out of range")
+    }
+
+    /// Emplaces an `ArchivedNaiveDate` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &NaiveDate, out: Place<Self>) {
+        use chrono_0_4::Datelike as _;
+
+        munge!(let ArchivedNaiveDate { days_from_ce } = out);
+        days_from_ce.write(ArchivedI32::from_native(value.num_days_from_ce()));
+    }
+}
+
+/// An archived [`NaiveTime`].
+///
+/// The time is stored as the number of seconds from midnight and a fractional
+/// number of nanoseconds. The nanoseconds may exceed one billion to represent
+/// a leap second.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedNaiveTime {
+    secs: ArchivedU32,
+    nanos: ArchivedU32,
+}
+
+impl ArchivedNaiveTime {
+    /// Returns the number of non-leap seconds past midnight.
+    #[inline]
+    pub const fn num_seconds_from_midnight(&self) -> u32 {
+        self.secs.to_native()
+    }
+
+    /// Returns the number of nanoseconds since the whole non-leap second.
+    #[inline]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanos.to_native()
+    }
+
+    /// Returns a [`NaiveTime`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived time is out of range. This can only happen if the
+    /// archive was accessed without validation.
+    #[inline]
+    pub fn as_naive_time(&self) -> NaiveTime {
+        NaiveTime::from_num_seconds_from_midnight_opt(
+            self.num_seconds_from_midnight(),
+            self.nanosecond(),
+        )
+        .expect("archived `NaiveTime` is out of range")
+    }
+
+    /// Emplaces an `ArchivedNaiveTime` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &NaiveTime, out: Place<Self>) {
+        munge!(let ArchivedNaiveTime { secs, nanos } = out);
+        secs.write(ArchivedU32::from_native(value.num_seconds_from_midnight()));
+        nanos.write(ArchivedU32::from_native(value.nanosecond()));
+    }
+}
+
+/// An archived [`NaiveDateTime`].
+///
+/// This is also the archived form of [`DateTime<Utc>`], which is stored as its
+/// UTC date and time.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedNaiveDateTime {
+    date: ArchivedNaiveDate,
+    time: ArchivedNaiveTime,
+}
+
+impl ArchivedNaiveDateTime {
+    /// Returns the date part of the archived date and time.
+    #[inline]
+    pub const fn date(&self) -> &ArchivedNaiveDate {
+        &self.date
+    }
+
+    /// Returns the time part of the archived date and time.
+    #[inline]
+    pub const fn time(&self) -> &ArchivedNaiveTime {
+        &self.time
+    }
+
+    /// Returns a [`NaiveDateTime`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date or time is out of range. This can only
+    /// happen if the archive was accessed without validation.
+    #[inline]
+    pub fn as_naive_date_time(&self) -> NaiveDateTime {
+        NaiveDateTime::new(self.date.as_naive_date(), self.time.as_naive_time())
+    }
+
+    /// Returns a [`DateTime<Utc>`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date or time is out of range. This can only
+    /// happen if the archive was accessed without validation.
+    #[inline]
+    pub fn as_date_time_utc(&self) -> DateTime<Utc> {
+        DateTime::from_naive_utc_and_offset(self.as_naive_date_time(), Utc)
+    }
+
+    /// Emplaces an `ArchivedNaiveDateTime` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &NaiveDateTime, out: Place<Self>) {
+        munge!(let ArchivedNaiveDateTime { date, time } = out);
+        ArchivedNaiveDate::emplace(&value.date(), date);
+        ArchivedNaiveTime::emplace(&value.time(), time);
+    }
+}
+
+/// An archived [`TimeDelta`].
+///
+/// The duration is stored as a number of whole seconds and a non-negative
+/// number of nanoseconds less than one billion, so a negative duration rounds
+/// its seconds down.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedTimeDelta {
+    secs: ArchivedI64,
+    nanos: ArchivedU32,
+}
+
+impl ArchivedTimeDelta {
+    /// Returns the total number of whole seconds in the duration, rounded
+    /// toward zero.
+    #[inline]
+    pub const fn num_seconds(&self) -> i64 {
+        let secs = self.secs.to_native();
+        if secs < 0 && self.nanos.to_native() > 0 {
+            secs + 1
+        } else {
+            secs
+        }
+    }
+
+    /// Returns the fractional part of the duration in nanoseconds, with the
+    /// same sign as the duration.
+    #[inline]
+    pub const fn subsec_nanos(&self) -> i32 {
+        let nanos = self.nanos.to_native() as i32;
+        if self.secs.to_native() < 0 && nanos > 0 {
+            nanos - NANOS_PER_SEC
+        } else {
+            nanos
+        }
+    }
+
+    /// Returns a [`TimeDelta`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived duration is out of range. This can only happen
+    /// if the archive was accessed without validation.
+    #[inline]
+    pub fn as_time_delta(&self) -> TimeDelta {
+        TimeDelta::new(self.secs.to_native(), self.nanos.to_native())
+            .expect("archived `TimeDelta` is out of range")
+    }
+
+    /// Emplaces an `ArchivedTimeDelta` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &TimeDelta, out: Place<Self>) {
+        let mut whole = value.num_seconds();
+        let mut frac = value.subsec_nanos();
+        if frac < 0 {
+            whole -= 1;
+            frac += NANOS_PER_SEC;
+        }
+
+        munge!(let ArchivedTimeDelta { secs, nanos } = out);
+        secs.write(ArchivedI64::from_native(whole));
+        nanos.write(ArchivedU32::from_native(frac as u32));
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use chrono_0_4::{NaiveDate, NaiveTime, TimeDelta};
+    use rancor::fail;
+
+    use super::{ArchivedNaiveDate, ArchivedNaiveTime, ArchivedTimeDelta};
+
+    /// An error resulting from an archived `chrono` value which is out of
+    /// range.
+    #[derive(Debug)]
+    pub struct OutOfRangeError {
+        type_name: &'static str,
+    }
+
+    impl fmt::Display for OutOfRangeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "archived `{}` is out of range", self.type_name)
+        }
+    }
+
+    impl Error for OutOfRangeError {}
+
+    unsafe impl<C> Verify<C> for ArchivedNaiveDate
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let days = self.num_days_from_ce();
+            if NaiveDate::from_num_days_from_ce_opt(days).is_none() {
+                fail!(OutOfRangeError {
+                    type_name: "NaiveDate",
+                });
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedNaiveTime
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let time = NaiveTime::from_num_seconds_from_midnight_opt(
+                self.num_seconds_from_midnight(),
+                self.nanosecond(),
+            );
+            if time.is_none() {
+                fail!(OutOfRangeError {
+                    type_name: "NaiveTime",
+                });
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedTimeDelta
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let delta =
+                TimeDelta::new(self.secs.to_native(), self.nanos.to_native());
+            if delta.is_none() {
+                fail!(OutOfRangeError {
+                    type_name: "TimeDelta",
+                });
+            }
+            Ok(())
+        }
+    }
+}
@@ -1,8 +1,15 @@
 //! Archived versions of `time` types.
 
+#[cfg(feature = "chrono-0_4")]
+pub mod chrono_0_4;
+#[cfg(feature = "time-0_3")]
+pub mod time_0_3;
+
+use munge::munge;
+
 use crate::{
-    primitive::{ArchivedU32, ArchivedU64},
-    Portable,
+    primitive::{ArchivedI64, ArchivedU32, ArchivedU64},
+    Place, Portable,
 };
 
 /// An archived [`Duration`](core::time::Duration).
@@ -141,6 +148,72 @@ impl ArchivedDuration {
     }
 }
 
+/// An archived [`SystemTime`](std::time::SystemTime).
+///
+/// The time is stored as a signed number of seconds since the Unix epoch and a
+/// non-negative number of nanoseconds less than one billion, so times before
+/// the epoch round their seconds down.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedSystemTime {
+    secs: ArchivedI64,
+    nanos: ArchivedU32,
+}
+
+impl ArchivedSystemTime {
+    /// Returns the number of whole seconds since the Unix epoch, rounded down.
+    #[inline]
+    pub const fn unix_secs(&self) -> i64 {
+        self.secs.to_native()
+    }
+
+    /// Returns the number of nanoseconds past the whole second returned by
+    /// [`unix_secs`](ArchivedSystemTime::unix_secs).
+    #[inline]
+    pub const fn subsec_nanos(&self) -> u32 {
+        self.nanos.to_native()
+    }
+
+    /// Returns a [`SystemTime`](std::time::SystemTime) with the same value, or
+    /// `None` if it can't be represented on this platform.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+        use core::time::Duration;
+        use std::time::UNIX_EPOCH;
+
+        let secs = self.unix_secs();
+        let nanos = Duration::new(0, self.subsec_nanos());
+        if secs >= 0 {
+            UNIX_EPOCH.checked_add(Duration::new(secs as u64, 0) + nanos)
+        } else {
+            UNIX_EPOCH
+                .checked_sub(Duration::new(secs.unsigned_abs(), 0))?
+                .checked_add(nanos)
+        }
+    }
+
+    /// Emplaces an `ArchivedSystemTime` with the given number of seconds since
+    /// the Unix epoch and subsecond nanoseconds into a place.
+    ///
+    /// `subsec_nanos` must be less than one billion, or else the archived time
+    /// will fail validation.
+    #[inline]
+    pub fn emplace(unix_secs: i64, subsec_nanos: u32, out: Place<Self>) {
+        munge!(let ArchivedSystemTime { secs, nanos } = out);
+        secs.write(ArchivedI64::from_native(unix_secs));
+        nanos.write(ArchivedU32::from_native(subsec_nanos));
+    }
+}
+
 #[cfg(feature = "bytecheck")]
 mod verify {
     use core::{error::Error, fmt};
@@ -151,7 +224,7 @@ mod verify {
     };
     use rancor::fail;
 
-    use super::ArchivedDuration;
+    use super::{ArchivedDuration, ArchivedSystemTime};
 
     /// An error resulting from an invalid duration.
     ///
@@ -187,4 +260,19 @@ mod verify {
             }
         }
     }
+
+    unsafe impl<C> Verify<C> for ArchivedSystemTime
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let nanos = self.nanos.to_native();
+            if nanos >= 1_000_000_000 {
+                fail!(DurationError { nanos });
+            } else {
+                Ok(())
+            }
+        }
+    }
 }
@@ -0,0 +1,368 @@
+//! Archived versions of `time` crate types.
+
+use munge::munge;
+use time_0_3::{
+    Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset,
+};
+
+use crate::{
+    primitive::{ArchivedI32, ArchivedI64, ArchivedU32},
+    Place, Portable,
+};
+
+const SECS_PER_DAY: u32 = 86_400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+
+/// An archived [`Date`].
+///
+/// The date is stored as its Julian day number.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedDate {
+    julian_day: ArchivedI32,
+}
+
+impl ArchivedDate {
+    /// Returns the Julian day number of the date.
+    #[inline]
+    pub const fn to_julian_day(&self) -> i32 {
+        self.julian_day.to_native()
+    }
+
+    /// Returns a [`Date`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date is out of range. This can only happen if the
+    /// archive was accessed without validation.
+    #[inline]
+    pub fn as_date(&self) -> Date {
+        Date::from_julian_day(self.to_julian_day())
+            .expect("archived `Date` is out of range")
+    }
+
+    /// Emplaces an `ArchivedDate` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &Date, out: Place<Self>) {
+        munge!(let ArchivedDate { julian_day } = out);
+        julian_day.write(ArchivedI32::from_native(value.to_julian_day()));
+    }
+}
+
+/// An archived [`Time`].
+///
+/// The time is stored as the number of seconds from midnight and a fractional
+/// number of nanoseconds.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedTime {
+    secs: ArchivedU32,
+    nanos: ArchivedU32,
+}
+
+impl ArchivedTime {
+    /// Returns the number of seconds past midnight.
+    #[inline]
+    pub const fn seconds_from_midnight(&self) -> u32 {
+        self.secs.to_native()
+    }
+
+    /// Returns the number of nanoseconds since the whole second.
+    #[inline]
+    pub const fn nanosecond(&self) -> u32 {
+        self.nanos.to_native()
+    }
+
+    /// Returns a [`Time`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived time is out of range. This can only happen if the
+    /// archive was accessed without validation.
+    #[inline]
+    pub fn as_time(&self) -> Time {
+        self.to_time().expect("archived `Time` is out of range")
+    }
+
+    fn to_time(self) -> Option<Time> {
+        let secs = self.seconds_from_midnight();
+        if secs >= SECS_PER_DAY {
+            return None;
+        }
+        Time::from_hms_nano(
+            (secs / 3600) as u8,
+            (secs / 60 % 60) as u8,
+            (secs % 60) as u8,
+            self.nanosecond(),
+        )
+        .ok()
+    }
+
+    /// Emplaces an `ArchivedTime` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &Time, out: Place<Self>) {
+        let (hour, minute, second, nanosecond) = value.as_hms_nano();
+        let seconds = hour as u32 * 3600 + minute as u32 * 60 + second as u32;
+
+        munge!(let ArchivedTime { secs, nanos } = out);
+        secs.write(ArchivedU32::from_native(seconds));
+        nanos.write(ArchivedU32::from_native(nanosecond));
+    }
+}
+
+/// An archived [`PrimitiveDateTime`].
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(feature = "bytecheck", derive(bytecheck::CheckBytes))]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedPrimitiveDateTime {
+    date: ArchivedDate,
+    time: ArchivedTime,
+}
+
+impl ArchivedPrimitiveDateTime {
+    /// Returns the date part of the archived date and time.
+    #[inline]
+    pub const fn date(&self) -> &ArchivedDate {
+        &self.date
+    }
+
+    /// Returns the time part of the archived date and time.
+    #[inline]
+    pub const fn time(&self) -> &ArchivedTime {
+        &self.time
+    }
+
+    /// Returns a [`PrimitiveDateTime`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date or time is out of range. This can only
+    /// happen if the archive was accessed without validation.
+    #[inline]
+    pub fn as_primitive_date_time(&self) -> PrimitiveDateTime {
+        PrimitiveDateTime::new(self.date.as_date(), self.time.as_time())
+    }
+
+    /// Emplaces an `ArchivedPrimitiveDateTime` of the given `value` into a
+    /// place.
+    #[inline]
+    pub fn emplace(value: &PrimitiveDateTime, out: Place<Self>) {
+        munge!(let ArchivedPrimitiveDateTime { date, time } = out);
+        ArchivedDate::emplace(&value.date(), date);
+        ArchivedTime::emplace(&value.time(), time);
+    }
+}
+
+/// An archived [`OffsetDateTime`].
+///
+/// The date and time are stored in the local time of the offset, along with
+/// the offset from UTC in seconds. Unlike `OffsetDateTime`, archived offset
+/// date times do not implement comparison traits, since two archived values
+/// with different offsets may refer to the same instant.
+#[derive(Clone, Copy, Debug, Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedOffsetDateTime {
+    local: ArchivedPrimitiveDateTime,
+    offset_secs: ArchivedI32,
+}
+
+impl ArchivedOffsetDateTime {
+    /// Returns the local date and time of the archived value.
+    #[inline]
+    pub const fn local(&self) -> &ArchivedPrimitiveDateTime {
+        &self.local
+    }
+
+    /// Returns the offset from UTC in seconds.
+    #[inline]
+    pub const fn offset_seconds(&self) -> i32 {
+        self.offset_secs.to_native()
+    }
+
+    /// Returns an [`OffsetDateTime`] with the same value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archived date, time, or offset is out of range. This can
+    /// only happen if the archive was accessed without validation.
+    #[inline]
+    pub fn as_offset_date_time(&self) -> OffsetDateTime {
+        let offset = UtcOffset::from_whole_seconds(self.offset_seconds())
+            .expect("archived `UtcOffset` is out of range");
+        self.local.as_primitive_date_time().assume_offset(offset)
+    }
+
+    /// Emplaces an `ArchivedOffsetDateTime` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &OffsetDateTime, out: Place<Self>) {
+        munge!(let ArchivedOffsetDateTime { local, offset_secs } = out);
+        let primitive = PrimitiveDateTime::new(value.date(), value.time());
+        ArchivedPrimitiveDateTime::emplace(&primitive, local);
+        offset_secs
+            .write(ArchivedI32::from_native(value.offset().whole_seconds()));
+    }
+}
+
+/// An archived [`Duration`].
+///
+/// The duration is stored as a number of whole seconds and a number of
+/// nanoseconds with the same sign.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Portable,
+)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(C)]
+pub struct ArchivedDuration {
+    secs: ArchivedI64,
+    nanos: ArchivedI32,
+}
+
+impl ArchivedDuration {
+    /// Returns the number of whole seconds in the duration.
+    #[inline]
+    pub const fn whole_seconds(&self) -> i64 {
+        self.secs.to_native()
+    }
+
+    /// Returns the fractional part of the duration in nanoseconds.
+    #[inline]
+    pub const fn subsec_nanoseconds(&self) -> i32 {
+        self.nanos.to_native()
+    }
+
+    /// Returns a [`Duration`] with the same value.
+    #[inline]
+    pub fn as_duration(&self) -> Duration {
+        Duration::new(self.whole_seconds(), self.subsec_nanoseconds())
+    }
+
+    /// Emplaces an `ArchivedDuration` of the given `value` into a place.
+    #[inline]
+    pub fn emplace(value: &Duration, out: Place<Self>) {
+        munge!(let ArchivedDuration { secs, nanos } = out);
+        secs.write(ArchivedI64::from_native(value.whole_seconds()));
+        nanos.write(ArchivedI32::from_native(value.subsec_nanoseconds()));
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use rancor::fail;
+    use time_0_3::{Date, UtcOffset};
+
+    use super::{
+        ArchivedDate, ArchivedDuration, ArchivedOffsetDateTime, ArchivedTime,
+        NANOS_PER_SEC,
+    };
+
+    /// An error resulting from an archived `time` value which is out of range.
+    #[derive(Debug)]
+    pub struct OutOfRangeError {
+        type_name: &'static str,
+    }
+
+    impl fmt::Display for OutOfRangeError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "archived `{}` is out of range", self.type_name)
+        }
+    }
+
+    impl Error for OutOfRangeError {}
+
+    unsafe impl<C> Verify<C> for ArchivedDate
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if Date::from_julian_day(self.to_julian_day()).is_err() {
+                fail!(OutOfRangeError { type_name: "Date" });
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedTime
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if self.to_time().is_none() {
+                fail!(OutOfRangeError { type_name: "Time" });
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedOffsetDateTime
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            if UtcOffset::from_whole_seconds(self.offset_seconds()).is_err() {
+                fail!(OutOfRangeError {
+                    type_name: "UtcOffset",
+                });
+            }
+            Ok(())
+        }
+    }
+
+    unsafe impl<C> Verify<C> for ArchivedDuration
+    where
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let secs = self.whole_seconds();
+            let nanos = self.subsec_nanoseconds();
+            let is_valid = nanos.unsigned_abs() < NANOS_PER_SEC
+                && !(secs > 0 && nanos < 0)
+                && !(secs < 0 && nanos > 0);
+            if !is_valid {
+                fail!(OutOfRangeError {
+                    type_name: "Duration",
+                });
+            }
+            Ok(())
+        }
+    }
+}
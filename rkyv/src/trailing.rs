@@ -0,0 +1,362 @@
+//! Structs with a trailing slice.
+//!
+//! A [`TrailingSlice`] is a dynamically sized type made of a sized head
+//! followed by a slice of elements stored inline. It can be archived behind
+//! any pointer which supports unsized values, like `Box`, `Rc`, and `Arc`, and
+//! the head and elements are archived in place without an extra indirection.
+//!
+//! The head is usually a struct which derives [`Archive`], so custom trailing
+//! slice types only need to name their head and element types:
+//!
+//! ```
+//! use rkyv::{
+//!     access, rancor::Error, to_bytes, trailing::TrailingSlice, Archive,
+//!     Archived, Serialize,
+//! };
+//!
+//! #[derive(Archive, Serialize)]
+//! struct Header {
+//!     name: String,
+//!     generation: u32,
+//! }
+//!
+//! type Arena = TrailingSlice<Header, u64>;
+//!
+//! let arena: Box<Arena> = TrailingSlice::new_boxed(
+//!     Header {
+//!         name: "blocks".to_string(),
+//!         generation: 3,
+//!     },
+//!     vec![1, 2, 3, 4],
+//! );
+//! let bytes = to_bytes::<Error>(&arena).unwrap();
+//!
+//! let archived = access::<Archived<Box<Arena>>, Error>(&bytes).unwrap();
+//! assert_eq!(archived.head.name, "blocks");
+//! assert_eq!(archived.head.generation, 3);
+//! assert_eq!(archived.tail, [1, 2, 3, 4]);
+//! ```
+//!
+//! Types with other kinds of pointer metadata can implement [`ArchivePointee`]
+//! and [`ArchiveUnsized`] themselves. See [`ArchiveUnsized`] for an example.
+
+use core::{
+    alloc::{Layout, LayoutError},
+    fmt,
+    mem::align_of,
+    ptr::addr_of_mut,
+};
+
+use ptr_meta::Pointee;
+use rancor::Fallible;
+
+use crate::{
+    primitive::ArchivedUsize,
+    ser::{Allocator, Writer, WriterExt as _},
+    traits::{ArchivePointee, LayoutRaw},
+    util::SerVec,
+    Archive, ArchiveUnsized, ArchivedMetadata, Deserialize, DeserializeUnsized,
+    Portable, Serialize, SerializeUnsized,
+};
+
+/// A sized head followed by a slice of elements stored inline.
+///
+/// A `TrailingSlice<H, T>` is archived as a `TrailingSlice<Archived<H>,
+/// Archived<T>>`. The archived pointer metadata of a trailing slice is the
+/// length of its tail, the same as for a slice.
+#[repr(C)]
+pub struct TrailingSlice<H, T> {
+    /// The head of the trailing slice.
+    pub head: H,
+    /// The elements which follow the head.
+    pub tail: [T],
+}
+
+#[cfg(feature = "alloc")]
+impl<H, T> TrailingSlice<H, T> {
+    /// Returns a boxed trailing slice with the given head and tail.
+    ///
+    /// # Panics
+    ///
+    /// - If the layout of the trailing slice overflows an `isize`
+    pub fn new_boxed(
+        head: H,
+        tail: crate::alloc::vec::Vec<T>,
+    ) -> crate::alloc::boxed::Box<Self> {
+        use crate::alloc::{
+            alloc::{alloc, handle_alloc_error},
+            boxed::Box,
+        };
+
+        let len = tail.len();
+        let layout = Self::layout_raw(len)
+            .expect("trailing slice layout overflowed an isize");
+        let data_address = if layout.size() > 0 {
+            // SAFETY: We just checked that `layout` has a non-zero size.
+            let ptr = unsafe { alloc(layout) };
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            ptr
+        } else {
+            crate::polyfill::dangling(&layout).as_ptr()
+        };
+
+        let out =
+            ptr_meta::from_raw_parts_mut::<Self>(data_address.cast(), len);
+        // SAFETY: `out` was allocated with the layout of a trailing slice with
+        // `len` elements, so the head and each element of the tail are valid
+        // for writes.
+        unsafe {
+            addr_of_mut!((*out).head).write(head);
+            let out_tail = addr_of_mut!((*out).tail).cast::<T>();
+            for (i, value) in tail.into_iter().enumerate() {
+                out_tail.add(i).write(value);
+            }
+        }
+        // SAFETY: `out` was allocated by the global allocator with the layout
+        // of `Self`, and its head and tail have been initialized.
+        unsafe { Box::from_raw(out) }
+    }
+}
+
+impl<H, T> TrailingSlice<H, T> {
+    fn archived_align() -> usize
+    where
+        H: Archive,
+        T: Archive,
+    {
+        align_of::<H::Archived>().max(align_of::<T::Archived>())
+    }
+}
+
+// SAFETY: The pointer metadata of a trailing slice is the length of its tail.
+unsafe impl<H, T> Pointee for TrailingSlice<H, T> {
+    type Metadata = usize;
+}
+
+impl<H, T> LayoutRaw for TrailingSlice<H, T> {
+    fn layout_raw(
+        metadata: <Self as Pointee>::Metadata,
+    ) -> Result<Layout, LayoutError> {
+        let (layout, _) =
+            Layout::new::<H>().extend(Layout::array::<T>(metadata)?)?;
+        Ok(layout.pad_to_align())
+    }
+}
+
+// SAFETY: `TrailingSlice` is `#[repr(C)]`, so it has a stable layout when its
+// head and elements are portable.
+unsafe impl<H: Portable, T: Portable> Portable for TrailingSlice<H, T> {}
+
+impl<H, T> ArchivePointee for TrailingSlice<H, T> {
+    type ArchivedMetadata = ArchivedUsize;
+
+    fn pointer_metadata(
+        archived: &Self::ArchivedMetadata,
+    ) -> <Self as Pointee>::Metadata {
+        archived.to_native() as usize
+    }
+}
+
+impl<H: Archive, T: Archive> ArchiveUnsized for TrailingSlice<H, T> {
+    type Archived = TrailingSlice<H::Archived, T::Archived>;
+
+    fn archived_metadata(&self) -> ArchivedMetadata<Self> {
+        ArchivedUsize::from_native(self.tail.len() as _)
+    }
+}
+
+impl<H, T, S> SerializeUnsized<S> for TrailingSlice<H, T>
+where
+    H: Serialize<S>,
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize_unsized(&self, serializer: &mut S) -> Result<usize, S::Error> {
+        let head_resolver = self.head.serialize(serializer)?;
+        SerVec::with_capacity(
+            serializer,
+            self.tail.len(),
+            |resolvers, serializer| {
+                for value in self.tail.iter() {
+                    unsafe {
+                        resolvers.push_unchecked(value.serialize(serializer)?);
+                    }
+                }
+
+                let align = Self::archived_align();
+                let result = serializer.align(align)?;
+                // SAFETY: `result` is aligned for both the head and the
+                // elements of the archived trailing slice.
+                unsafe {
+                    serializer.resolve_aligned(&self.head, head_resolver)?;
+                }
+                serializer.align_for::<T::Archived>()?;
+                for (value, resolver) in self.tail.iter().zip(resolvers.drain())
+                {
+                    // SAFETY: The serializer was just aligned for
+                    // `T::Archived`, and each element is the size of a
+                    // `T::Archived`.
+                    unsafe {
+                        serializer.resolve_aligned(value, resolver)?;
+                    }
+                }
+                // Trailing padding is part of the size of the archived value.
+                serializer.align(align)?;
+
+                Ok(result)
+            },
+        )?
+    }
+}
+
+impl<AH, AT, H, T, D> DeserializeUnsized<TrailingSlice<H, T>, D>
+    for TrailingSlice<AH, AT>
+where
+    AH: Deserialize<H, D>,
+    AT: Deserialize<T, D>,
+    D: Fallible + ?Sized,
+{
+    unsafe fn deserialize_unsized(
+        &self,
+        deserializer: &mut D,
+        out: *mut TrailingSlice<H, T>,
+    ) -> Result<(), D::Error> {
+        let head = self.head.deserialize(deserializer)?;
+        // SAFETY: The caller has guaranteed that `out` is non-null,
+        // properly-aligned, and valid for writes.
+        unsafe {
+            addr_of_mut!((*out).head).write(head);
+        }
+        // SAFETY: The caller has guaranteed that `out` was allocated with the
+        // length of our tail.
+        let out_tail = unsafe { addr_of_mut!((*out).tail).cast::<T>() };
+        for (i, value) in self.tail.iter().enumerate() {
+            // SAFETY: `i` is less than the length of the tail of `out`, so
+            // `out_tail.add(i)` is in-bounds and valid for writes.
+            unsafe {
+                out_tail.add(i).write(value.deserialize(deserializer)?);
+            }
+        }
+        Ok(())
+    }
+
+    fn deserialize_metadata(
+        &self,
+    ) -> <TrailingSlice<H, T> as Pointee>::Metadata {
+        self.tail.len()
+    }
+}
+
+impl<H: fmt::Debug, T: fmt::Debug> fmt::Debug for TrailingSlice<H, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrailingSlice")
+            .field("head", &self.head)
+            .field("tail", &&self.tail)
+            .finish()
+    }
+}
+
+impl<AH, AT, H, T> PartialEq<TrailingSlice<H, T>> for TrailingSlice<AH, AT>
+where
+    AH: PartialEq<H>,
+    AT: PartialEq<T>,
+{
+    fn eq(&self, other: &TrailingSlice<H, T>) -> bool {
+        self.head == other.head
+            && self.tail.len() == other.tail.len()
+            && self.tail.iter().zip(other.tail.iter()).all(|(a, b)| a == b)
+    }
+}
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::ptr::addr_of;
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        CheckBytes,
+    };
+
+    use crate::{trailing::TrailingSlice, validation::check_slice, Portable};
+
+    // SAFETY: `check_bytes` only returns `Ok` if the head and every element of
+    // the tail are valid.
+    unsafe impl<H, T, C> CheckBytes<C> for TrailingSlice<H, T>
+    where
+        H: CheckBytes<C>,
+        T: Portable + CheckBytes<C>,
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        unsafe fn check_bytes(
+            value: *const Self,
+            context: &mut C,
+        ) -> Result<(), C::Error> {
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for a trailing slice, so its head is also
+            // aligned and points to enough bytes.
+            unsafe {
+                H::check_bytes(addr_of!((*value).head), context)?;
+            }
+            // SAFETY: The caller has guaranteed that `value` is aligned and
+            // points to enough bytes for a trailing slice, so its tail is also
+            // aligned and points to enough bytes.
+            unsafe { check_slice(addr_of!((*value).tail), context) }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use core::{mem::align_of, ptr};
+
+    use super::TrailingSlice;
+    use crate::{
+        alloc::{boxed::Box, string::String, vec, vec::Vec},
+        api::test::{roundtrip, to_archived},
+        Archive, Archived, Deserialize, Serialize,
+    };
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    #[rkyv(crate, compare(PartialEq), derive(Debug))]
+    struct Header {
+        name: String,
+        flags: u8,
+    }
+
+    #[test]
+    fn roundtrip_trailing_slice() {
+        let value: Box<TrailingSlice<Header, u64>> = TrailingSlice::new_boxed(
+            Header {
+                name: String::from("header"),
+                flags: 7,
+            },
+            vec![1, 2, 3],
+        );
+        roundtrip(&value);
+
+        let empty: Box<TrailingSlice<u8, String>> =
+            TrailingSlice::new_boxed(42, Vec::new());
+        roundtrip(&empty);
+    }
+
+    #[test]
+    fn trailing_slice_layout() {
+        let value: Box<TrailingSlice<u8, String>> = TrailingSlice::new_boxed(
+            1,
+            vec![String::from("a string long enough to be out of line")],
+        );
+        to_archived(&value, |archived| {
+            assert_eq!(archived.head, 1);
+            assert_eq!(archived.tail.len(), 1);
+            assert_eq!(
+                archived.tail[0],
+                "a string long enough to be out of line",
+            );
+            let address = ptr::from_ref(archived.get()).cast::<u8>() as usize;
+            assert_eq!(address % align_of::<Archived<String>>(), 0);
+        });
+    }
+}
@@ -0,0 +1,663 @@
+//! Archiving trait objects.
+//!
+//! Trait objects can be archived by registering the types which implement a
+//! trait with the [`archive_dyn`] attribute:
+//!
+//! ```
+//! use rkyv::{
+//!     boxed::ArchivedBox, rancor::Error, trait_object::archive_dyn,
+//!     vec::ArchivedVec, Archive, Deserialize, Serialize,
+//! };
+//!
+//! #[archive_dyn(impls(Square, Circle))]
+//! trait Shape {
+//!     fn area(&self) -> f32;
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Square {
+//!     side: f32,
+//! }
+//!
+//! impl Shape for Square {
+//!     fn area(&self) -> f32 {
+//!         self.side * self.side
+//!     }
+//! }
+//!
+//! impl Shape for ArchivedSquare {
+//!     fn area(&self) -> f32 {
+//!         self.side.to_native() * self.side.to_native()
+//!     }
+//! }
+//!
+//! #[derive(Archive, Serialize, Deserialize)]
+//! struct Circle {
+//!     radius: f32,
+//! }
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f32 {
+//!         3.0 * self.radius * self.radius
+//!     }
+//! }
+//!
+//! impl Shape for ArchivedCircle {
+//!     fn area(&self) -> f32 {
+//!         3.0 * self.radius.to_native() * self.radius.to_native()
+//!     }
+//! }
+//!
+//! let shapes: Vec<Box<dyn SerializeShape>> = vec![
+//!     Box::new(Square { side: 2.0 }),
+//!     Box::new(Circle { radius: 1.0 }),
+//! ];
+//! let bytes = rkyv::to_bytes::<Error>(&shapes).unwrap();
+//! let archived =
+//!     rkyv::access::<ArchivedVec<ArchivedBox<dyn Shape>>, Error>(&bytes)
+//!         .unwrap();
+//! assert_eq!(archived[0].area(), 4.0);
+//! assert_eq!(archived[1].area(), 3.0);
+//!
+//! let deserialized =
+//!     rkyv::deserialize::<Vec<Box<dyn SerializeShape>>, Error>(archived)
+//!         .unwrap();
+//! assert_eq!(deserialized[0].area(), 4.0);
+//! assert_eq!(deserialized[1].area(), 3.0);
+//! ```
+//!
+//! Each archived trait object stores the index of its implementation in the
+//! `impls` list of the trait, and that index is used to look up the vtable of
+//! the archived type when the trait object is accessed. Because of this, the
+//! order of the `impls` list is part of the archived format: new
+//! implementations may only be appended to the end of the list.
+//!
+//! Archived trait objects can be deserialized into pointers to the generated
+//! serialization trait, like `Box<dyn SerializeShape>`, as long as the archived
+//! type of every registered implementation implements `Deserialize`. The
+//! implementation to deserialize is found by looking up the vtable of the
+//! archived trait object in the registry.
+
+use core::{
+    alloc::Layout,
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ptr::NonNull,
+};
+
+use ptr_meta::{DynMetadata, Pointee};
+use rancor::{fail, Fallible, Source};
+pub use rkyv_derive::archive_dyn;
+
+use crate::{
+    primitive::ArchivedU32,
+    ser::{
+        sharing::{SharedValue, SharingState},
+        Allocator, Positional, Sharing, Writer,
+    },
+    traits::NoUndef,
+    Portable, Serialize, SerializeUnsized,
+};
+
+/// A [`Serializer`](crate::ser::Serializer) which can be used as a trait
+/// object.
+pub trait DynSerializer:
+    Writer<rancor::Error> + Allocator<rancor::Error> + Sharing<rancor::Error>
+{
+}
+
+impl<S> DynSerializer for S where
+    S: Writer<rancor::Error>
+        + Allocator<rancor::Error>
+        + Sharing<rancor::Error>
+        + ?Sized
+{
+}
+
+impl Fallible for dyn DynSerializer + '_ {
+    type Error = rancor::Error;
+}
+
+/// A value which can be serialized with a [`DynSerializer`].
+///
+/// This is implemented for all types which implement `Serialize` for
+/// `dyn DynSerializer`.
+pub trait SerializeDyn {
+    /// Serializes this value and returns the position of the archived value.
+    fn serialize_dyn(
+        &self,
+        serializer: &mut dyn DynSerializer,
+    ) -> Result<usize, rancor::Error>;
+}
+
+impl<T> SerializeDyn for T
+where
+    T: for<'a> Serialize<dyn DynSerializer + 'a>,
+{
+    fn serialize_dyn(
+        &self,
+        serializer: &mut dyn DynSerializer,
+    ) -> Result<usize, rancor::Error> {
+        self.serialize_unsized(serializer)
+    }
+}
+
+#[derive(Debug)]
+struct SerializerError;
+
+impl fmt::Display for SerializerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the serializer returned an error")
+    }
+}
+
+impl core::error::Error for SerializerError {}
+
+struct DynSerializerAdapter<'a, S: Fallible + ?Sized> {
+    inner: &'a mut S,
+    error: Option<S::Error>,
+}
+
+impl<S: Fallible + ?Sized> DynSerializerAdapter<'_, S> {
+    fn forward<T>(
+        &mut self,
+        result: Result<T, S::Error>,
+    ) -> Result<T, rancor::Error> {
+        result.map_err(|error| {
+            self.error = Some(error);
+            rancor::Error::new(SerializerError)
+        })
+    }
+}
+
+impl<S: Fallible + Positional + ?Sized> Positional
+    for DynSerializerAdapter<'_, S>
+{
+    fn pos(&self) -> usize {
+        self.inner.pos()
+    }
+}
+
+impl<S: Fallible + Writer + ?Sized> Writer<rancor::Error>
+    for DynSerializerAdapter<'_, S>
+{
+    fn write(&mut self, bytes: &[u8]) -> Result<(), rancor::Error> {
+        let result = self.inner.write(bytes);
+        self.forward(result)
+    }
+
+    fn write_vectored(&mut self, bufs: &[&[u8]]) -> Result<(), rancor::Error> {
+        let result = self.inner.write_vectored(bufs);
+        self.forward(result)
+    }
+}
+
+unsafe impl<S: Fallible + Allocator + ?Sized> Allocator<rancor::Error>
+    for DynSerializerAdapter<'_, S>
+{
+    unsafe fn push_alloc(
+        &mut self,
+        layout: Layout,
+    ) -> Result<NonNull<[u8]>, rancor::Error> {
+        // SAFETY: The safety requirements for `S::push_alloc()` are the same as
+        // the safety requirements for `push_alloc()`.
+        let result = unsafe { self.inner.push_alloc(layout) };
+        self.forward(result)
+    }
+
+    unsafe fn pop_alloc(
+        &mut self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+    ) -> Result<(), rancor::Error> {
+        // SAFETY: The safety requirements for `S::pop_alloc()` are the same as
+        // the safety requirements for `pop_alloc()`.
+        let result = unsafe { self.inner.pop_alloc(ptr, layout) };
+        self.forward(result)
+    }
+}
+
+impl<S: Fallible + Sharing + ?Sized> Sharing<rancor::Error>
+    for DynSerializerAdapter<'_, S>
+{
+    fn start_sharing(&mut self, address: usize) -> SharingState {
+        self.inner.start_sharing(address)
+    }
+
+    fn finish_sharing(
+        &mut self,
+        address: usize,
+        pos: usize,
+    ) -> Result<(), rancor::Error> {
+        let result = self.inner.finish_sharing(address, pos);
+        self.forward(result)
+    }
+
+    fn start_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+    ) -> SharingState {
+        self.inner.start_sharing_value(address, archived)
+    }
+
+    fn finish_sharing_value(
+        &mut self,
+        address: usize,
+        archived: Option<SharedValue<'_>>,
+        pos: usize,
+    ) -> Result<(), rancor::Error> {
+        let result = self.inner.finish_sharing_value(address, archived, pos);
+        self.forward(result)
+    }
+}
+
+/// Serializes a [`SerializeDyn`] value with a serializer.
+///
+/// Errors returned by the serializer are passed through unchanged. Errors
+/// produced while serializing the value itself are wrapped in `S::Error`.
+pub fn serialize_dyn<T, S>(
+    value: &T,
+    serializer: &mut S,
+) -> Result<usize, S::Error>
+where
+    T: SerializeDyn + ?Sized,
+    S: Fallible + Writer + Allocator + Sharing + ?Sized,
+    S::Error: Source,
+{
+    let mut adapter = DynSerializerAdapter {
+        inner: serializer,
+        error: None,
+    };
+    value.serialize_dyn(&mut adapter).map_err(|error| {
+        adapter.error.take().unwrap_or_else(|| S::Error::new(error))
+    })
+}
+
+/// A trait object type with a registry of archived implementations.
+///
+/// This is implemented by [`archive_dyn`] for archived trait objects.
+///
+/// # Safety
+///
+/// - `impl_metadata` must return `Some` for every index less than the number of
+///   registered implementations, and `None` for every other index.
+/// - Each returned metadata must be the metadata of a [`Portable`] type.
+/// - `impl_metadata` must always return the same metadata for the same index.
+pub unsafe trait RegisteredDyn:
+    Pointee<Metadata = DynMetadata<Self>>
+{
+    /// Returns the trait object metadata for the registered implementation
+    /// with the given index.
+    fn impl_metadata(index: u32) -> Option<DynMetadata<Self>>;
+}
+
+/// An error resulting from a trait object whose vtable does not belong to any
+/// registered implementation.
+#[derive(Debug)]
+pub struct UnregisteredVtableError;
+
+impl fmt::Display for UnregisteredVtableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trait object vtable does not belong to any registered impl"
+        )
+    }
+}
+
+impl core::error::Error for UnregisteredVtableError {}
+
+/// An error resulting from a trait object whose vtable belongs to more than one
+/// registered implementation.
+#[derive(Debug)]
+pub struct AmbiguousVtableError;
+
+impl fmt::Display for AmbiguousVtableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trait object vtable belongs to more than one registered impl, so \
+             the impl to deserialize is ambiguous",
+        )
+    }
+}
+
+impl core::error::Error for AmbiguousVtableError {}
+
+/// Returns the index of the registered implementation which has the same
+/// metadata as the given trait object.
+///
+/// Identical vtables may be merged by the compiler, so this returns an error if
+/// the metadata belongs to more than one registered implementation, as well as
+/// if it doesn't belong to any.
+pub fn impl_index<T, E>(value: *const T) -> Result<u32, E>
+where
+    T: RegisteredDyn + ?Sized,
+    E: Source,
+{
+    let metadata = ptr_meta::metadata(value);
+    let mut found = None;
+    let mut index = 0;
+    while let Some(impl_metadata) = T::impl_metadata(index) {
+        if impl_metadata == metadata {
+            if found.is_some() {
+                fail!(AmbiguousVtableError);
+            }
+            found = Some(index);
+        }
+        index += 1;
+    }
+
+    match found {
+        Some(index) => Ok(index),
+        None => fail!(UnregisteredVtableError),
+    }
+}
+
+/// The archived metadata of a trait object.
+///
+/// This is the index of the registered implementation of the trait object.
+#[derive(Portable)]
+#[cfg_attr(
+    feature = "bytecheck",
+    derive(bytecheck::CheckBytes),
+    bytecheck(verify)
+)]
+#[rkyv(crate)]
+#[repr(transparent)]
+pub struct ArchivedDynMetadata<T: ?Sized> {
+    index: ArchivedU32,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: ?Sized> ArchivedDynMetadata<T> {
+    /// Creates new archived metadata for the registered implementation with
+    /// the given index.
+    pub fn new(index: u32) -> Self {
+        Self {
+            index: ArchivedU32::from_native(index),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Returns the index of the registered implementation.
+    pub fn index(&self) -> u32 {
+        self.index.to_native()
+    }
+}
+
+impl<T: RegisteredDyn + ?Sized> ArchivedDynMetadata<T> {
+    /// Returns the trait object metadata of the registered implementation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not refer to a registered implementation. This
+    /// can only happen if the archive was accessed without validation.
+    pub fn pointer_metadata(&self) -> DynMetadata<T> {
+        T::impl_metadata(self.index())
+            .expect("archived trait object has an unregistered impl index")
+    }
+}
+
+impl<T: ?Sized> Clone for ArchivedDynMetadata<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for ArchivedDynMetadata<T> {}
+
+impl<T: ?Sized> fmt::Debug for ArchivedDynMetadata<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ArchivedDynMetadata")
+            .field("index", &self.index())
+            .finish()
+    }
+}
+
+impl<T: ?Sized> Default for ArchivedDynMetadata<T> {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl<T: ?Sized> Eq for ArchivedDynMetadata<T> {}
+
+impl<T: ?Sized> Hash for ArchivedDynMetadata<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index().hash(state)
+    }
+}
+
+impl<T: ?Sized> Ord for ArchivedDynMetadata<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
+impl<T: ?Sized> PartialEq for ArchivedDynMetadata<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index() == other.index()
+    }
+}
+
+impl<T: ?Sized> PartialOrd for ArchivedDynMetadata<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// SAFETY: `ArchivedDynMetadata` only contains an `ArchivedU32`.
+unsafe impl<T: ?Sized> Send for ArchivedDynMetadata<T> {}
+
+// SAFETY: `ArchivedDynMetadata` only contains an `ArchivedU32`.
+unsafe impl<T: ?Sized> Sync for ArchivedDynMetadata<T> {}
+
+impl<T: ?Sized> Unpin for ArchivedDynMetadata<T> {}
+
+// SAFETY: `ArchivedDynMetadata` only contains an `ArchivedU32`, which has no
+// undefined bytes.
+unsafe impl<T: ?Sized> NoUndef for ArchivedDynMetadata<T> {}
+
+#[cfg(feature = "bytecheck")]
+pub use self::verify::*;
+
+#[cfg(feature = "bytecheck")]
+mod verify {
+    use core::{error::Error, fmt};
+
+    use bytecheck::{
+        rancor::{Fallible, Source},
+        Verify,
+    };
+    use rancor::fail;
+
+    use super::{ArchivedDynMetadata, RegisteredDyn, UnregisteredVtableError};
+
+    /// An error resulting from an archived trait object with an impl index
+    /// that is not registered.
+    #[derive(Debug)]
+    pub struct UnregisteredImplError {
+        index: u32,
+    }
+
+    impl fmt::Display for UnregisteredImplError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "archived trait object has impl index {}, which is not \
+                 registered",
+                self.index,
+            )
+        }
+    }
+
+    impl Error for UnregisteredImplError {}
+
+    unsafe impl<T, C> Verify<C> for ArchivedDynMetadata<T>
+    where
+        T: RegisteredDyn + ?Sized,
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        fn verify(&self, _: &mut C) -> Result<(), C::Error> {
+            let index = self.index();
+            if T::impl_metadata(index).is_none() {
+                fail!(UnregisteredImplError { index });
+            }
+            Ok(())
+        }
+    }
+
+    /// Checks a trait object by dispatching to the registered implementation
+    /// it points to.
+    ///
+    /// `check_impl` is called with the index of the registered implementation
+    /// which has the same metadata as `value`, and must check `value` as that
+    /// type. Identical vtables may be merged by the compiler, so `check_impl`
+    /// is called for every registered implementation with matching metadata.
+    pub fn check_dyn<T, C>(
+        value: *const T,
+        context: &mut C,
+        mut check_impl: impl FnMut(u32, &mut C) -> Result<(), C::Error>,
+    ) -> Result<(), C::Error>
+    where
+        T: RegisteredDyn + ?Sized,
+        C: Fallible + ?Sized,
+        C::Error: Source,
+    {
+        let metadata = ptr_meta::metadata(value);
+        let mut found = false;
+        let mut index = 0;
+        while let Some(impl_metadata) = T::impl_metadata(index) {
+            if impl_metadata == metadata {
+                check_impl(index, context)?;
+                found = true;
+            }
+            index += 1;
+        }
+
+        if !found {
+            fail!(UnregisteredVtableError);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        alloc::{boxed::Box, vec, vec::Vec},
+        api::test::{deserialize, to_archived},
+        trait_object::archive_dyn,
+        Archive, Deserialize, Serialize,
+    };
+
+    #[archive_dyn(crate, impls(Square, Rect))]
+    trait Shape {
+        fn area(&self) -> u32;
+    }
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[rkyv(crate)]
+    struct Square {
+        side: u32,
+    }
+
+    impl Shape for Square {
+        fn area(&self) -> u32 {
+            self.side * self.side
+        }
+    }
+
+    impl Shape for ArchivedSquare {
+        fn area(&self) -> u32 {
+            self.side.to_native() * self.side.to_native()
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[rkyv(crate)]
+    struct Rect {
+        width: u32,
+        height: u32,
+    }
+
+    impl Shape for Rect {
+        fn area(&self) -> u32 {
+            self.width * self.height
+        }
+    }
+
+    impl Shape for ArchivedRect {
+        fn area(&self) -> u32 {
+            self.width.to_native() * self.height.to_native()
+        }
+    }
+
+    #[test]
+    fn archive_trait_objects() {
+        let shapes: Vec<Box<dyn SerializeShape>> = vec![
+            Box::new(Square { side: 3 }),
+            Box::new(Rect {
+                width: 2,
+                height: 5,
+            }),
+            Box::new(Square { side: 4 }),
+        ];
+
+        to_archived(&shapes, |archived| {
+            assert_eq!(archived.len(), 3);
+            assert_eq!(archived[0].area(), 9);
+            assert_eq!(archived[1].area(), 10);
+            assert_eq!(archived[2].area(), 16);
+        });
+    }
+
+    #[test]
+    fn deserialize_trait_objects() {
+        let shapes: Vec<Box<dyn SerializeShape>> = vec![
+            Box::new(Rect {
+                width: 3,
+                height: 7,
+            }),
+            Box::new(Square { side: 5 }),
+        ];
+
+        to_archived(&shapes, |archived| {
+            let deserialized =
+                deserialize::<Vec<Box<dyn SerializeShape>>>(&*archived);
+            assert_eq!(deserialized.len(), 2);
+            assert_eq!(deserialized[0].area(), 21);
+            assert_eq!(deserialized[1].area(), 25);
+        });
+    }
+
+    #[cfg(feature = "bytecheck")]
+    #[test]
+    fn reject_unregistered_impl() {
+        use core::mem::size_of;
+
+        use rancor::Failure;
+
+        use crate::{
+            access, api::test::to_bytes, boxed::ArchivedBox,
+            primitive::ArchivedIsize,
+        };
+
+        let shape: Box<dyn SerializeShape> = Box::new(Square { side: 2 });
+        to_bytes(&shape, |bytes| {
+            assert!(access::<ArchivedBox<dyn Shape>, Failure>(bytes).is_ok());
+
+            // Overwrite everything in the root box after its offset, which
+            // covers the impl index and any trailing padding.
+            let start = bytes.len() - size_of::<ArchivedBox<dyn Shape>>()
+                + size_of::<ArchivedIsize>();
+            bytes[start..].fill(0xff);
+            assert!(access::<ArchivedBox<dyn Shape>, Failure>(bytes).is_err());
+        });
+    }
+}
@@ -21,7 +21,18 @@ use crate::{ptr_meta::Pointee, rancor::Fallible, ArchivedMetadata, Place};
 ///
 /// The implementing type must not have interior mutability (i.e. no
 /// `UnsafeCell`s).
-pub unsafe trait Portable {}
+///
+/// If [`ALWAYS_VALID`](Portable::ALWAYS_VALID) is `true`, every initialized bit
+/// pattern must be a valid value of the implementing type, and checking it with
+/// `CheckBytes` must always succeed.
+pub unsafe trait Portable {
+    /// Whether every initialized bit pattern is a valid value of this type.
+    ///
+    /// Validation skips checking the elements of slices of types which are
+    /// always valid, so large vectors of primitives can be validated in
+    /// constant time. This defaults to `false`.
+    const ALWAYS_VALID: bool = false;
+}
 
 /// A type with no undefined bytes.
 ///
@@ -288,8 +299,10 @@ pub trait Deserialize<T, D: Fallible + ?Sized> {
 /// `ArchiveUnsized` is automatically implemented for all types that implement
 /// [`Archive`]. Nothing special needs to be done to use them with types like
 /// `Box`, `Rc`, and `Arc`. It is also already implemented for slices and string
-/// slices, and the `rkyv_dyn` crate can be used to archive trait objects. Other
-/// unsized types must manually implement `ArchiveUnsized`.
+/// slices, and the `rkyv_dyn` crate can be used to archive trait objects.
+/// Structs with a sized head and a trailing slice can use
+/// [`TrailingSlice`](crate::trailing::TrailingSlice). Other unsized types must
+/// manually implement `ArchiveUnsized`.
 ///
 /// # Examples
 ///
@@ -430,12 +443,31 @@ pub trait ArchiveUnsized: Pointee {
 
     /// Creates the archived version of the metadata for this value.
     fn archived_metadata(&self) -> ArchivedMetadata<Self>;
+
+    /// Returns the bytes of this value and the alignment of its archived
+    /// counterpart if its archived bytes are identical to its native bytes.
+    ///
+    /// Sharing strategies like
+    /// [`DedupByValue`](crate::ser::sharing::DedupByValue) use this to
+    /// recognize identical values at different addresses. Returns `None` by
+    /// default.
+    fn archived_bytes(&self) -> Option<(&[u8], usize)> {
+        None
+    }
 }
 
 /// An archived type with associated metadata for its relative pointer.
 ///
 /// This is mostly used in the context of smart pointers and unsized types, and
 /// is implemented for all sized types by default.
+///
+/// The archived metadata is stored next to the offset of every relative pointer
+/// to the type, and is converted back into native pointer metadata when the
+/// pointer is dereferenced. Custom dynamically sized types implement this trait
+/// alongside [`ptr_meta::Pointee`] to choose how their metadata is archived.
+/// Types which are validated behind a pointer must also implement
+/// [`LayoutRaw`] so that the size of the pointee can be computed from its
+/// metadata. See [`ArchiveUnsized`] for an example.
 pub trait ArchivePointee: Pointee {
     /// The archived version of the pointer metadata for this type.
     type ArchivedMetadata: Copy
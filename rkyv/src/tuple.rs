@@ -28,6 +28,24 @@ macro_rules! impl_tuple {
                 $(self.$index == other.$index)&&*
             }
         }
+
+        impl<$($t,)* $($u),*> PartialOrd<($($u,)*)> for $name<$($t),*>
+        where
+            $($t: PartialOrd<$u>,)*
+        {
+            fn partial_cmp(
+                &self,
+                other: &($($u,)*),
+            ) -> Option<::core::cmp::Ordering> {
+                $(
+                    match self.$index.partial_cmp(&other.$index) {
+                        Some(::core::cmp::Ordering::Equal) => (),
+                        x => return x,
+                    }
+                )*
+                Some(::core::cmp::Ordering::Equal)
+            }
+        }
     };
 }
 
@@ -130,6 +148,60 @@ impl_tuple!(
     T11 U11 11,
     T12 U12 12,
 );
+impl_tuple!(
+    ArchivedTuple14 14,
+    T0 U0 0,
+    T1 U1 1,
+    T2 U2 2,
+    T3 U3 3,
+    T4 U4 4,
+    T5 U5 5,
+    T6 U6 6,
+    T7 U7 7,
+    T8 U8 8,
+    T9 U9 9,
+    T10 U10 10,
+    T11 U11 11,
+    T12 U12 12,
+    T13 U13 13,
+);
+impl_tuple!(
+    ArchivedTuple15 15,
+    T0 U0 0,
+    T1 U1 1,
+    T2 U2 2,
+    T3 U3 3,
+    T4 U4 4,
+    T5 U5 5,
+    T6 U6 6,
+    T7 U7 7,
+    T8 U8 8,
+    T9 U9 9,
+    T10 U10 10,
+    T11 U11 11,
+    T12 U12 12,
+    T13 U13 13,
+    T14 U14 14,
+);
+impl_tuple!(
+    ArchivedTuple16 16,
+    T0 U0 0,
+    T1 U1 1,
+    T2 U2 2,
+    T3 U3 3,
+    T4 U4 4,
+    T5 U5 5,
+    T6 U6 6,
+    T7 U7 7,
+    T8 U8 8,
+    T9 U9 9,
+    T10 U10 10,
+    T11 U11 11,
+    T12 U12 12,
+    T13 U13 13,
+    T14 U14 14,
+    T15 U15 15,
+);
 
 #[cfg(test)]
 mod tests {
@@ -757,6 +757,28 @@ impl<const ALIGNMENT: usize> AlignedVec<ALIGNMENT> {
     pub fn into_vec(self) -> Vec<u8> {
         Vec::from(self.as_ref())
     }
+
+    /// Copies the bytes of a `Vec<u8>` into a new `AlignedVec`.
+    ///
+    /// This method always copies, even if the allocation of the `Vec` happens
+    /// to be suitably aligned. The global allocator requires memory to be
+    /// deallocated with the same alignment it was allocated with, so an
+    /// `AlignedVec` can't take ownership of memory allocated by a `Vec<u8>`.
+    /// Any excess capacity is dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rkyv::util::AlignedVec;
+    /// let aligned = AlignedVec::<16>::from_vec(vec![1, 2, 3]);
+    ///
+    /// assert_eq!(aligned.as_ptr() as usize % 16, 0);
+    /// assert_eq!(aligned.as_slice(), &[1, 2, 3]);
+    /// ```
+    pub fn from_vec(vec: Vec<u8>) -> Self {
+        let mut result = Self::with_capacity(vec.len());
+        result.extend_from_slice(&vec);
+        result
+    }
 }
 
 #[cfg(feature = "std")]
@@ -870,6 +892,26 @@ const _: () = {
         }
     }
 
+    /// Writes append bytes to the end of the vector.
+    ///
+    /// `AlignedVec` doesn't track a read position, so it doesn't implement
+    /// [`io::Read`] itself. To read from one, wrap it in an [`io::Cursor`] or
+    /// read from its slice.
+    ///
+    /// # Examples
+    /// ```
+    /// # use rkyv::util::AlignedVec;
+    /// use std::io::{self, Cursor, Read};
+    ///
+    /// let mut bytes = AlignedVec::<16>::new();
+    /// io::copy(&mut [1u8, 2, 3].as_slice(), &mut bytes).unwrap();
+    /// assert_eq!(bytes.as_slice(), &[1, 2, 3]);
+    ///
+    /// let mut cursor = Cursor::new(bytes);
+    /// let mut first = [0; 2];
+    /// cursor.read_exact(&mut first).unwrap();
+    /// assert_eq!(first, [1, 2]);
+    /// ```
     impl<const A: usize> io::Write for AlignedVec<A> {
         fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
             self.extend_from_slice(buf);
@@ -905,6 +947,12 @@ impl<const A: usize> From<AlignedVec<A>> for Vec<u8> {
     }
 }
 
+impl<const A: usize> From<Vec<u8>> for AlignedVec<A> {
+    fn from(vec: Vec<u8>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
 impl<const A: usize> AsMut<[u8]> for AlignedVec<A> {
     fn as_mut(&mut self) -> &mut [u8] {
         self.as_mut_slice()
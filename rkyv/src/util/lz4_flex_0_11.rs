@@ -0,0 +1,42 @@
+use std::io;
+
+use lz4_flex_0_11::frame::FrameDecoder;
+
+use crate::util::AlignedVec;
+
+/// Decompresses an LZ4 frame from the given reader into an [`AlignedVec`].
+///
+/// This is the access-side counterpart to
+/// [`CompressWriter`](crate::ser::writer::CompressWriter).
+pub fn decompress_lz4<const A: usize, R: io::Read>(
+    reader: R,
+) -> io::Result<AlignedVec<A>> {
+    let mut result = AlignedVec::new();
+    result.extend_from_reader(&mut FrameDecoder::new(reader))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Panic;
+
+    use crate::{
+        access_unchecked, api::high::to_bytes_in, ser::writer::CompressWriter,
+        util::decompress_lz4, Archived,
+    };
+
+    #[test]
+    fn roundtrip_lz4() {
+        let value = (0..1000u32).map(|i| i % 7).collect::<Vec<_>>();
+        let writer = CompressWriter::new(Vec::new());
+        let writer = to_bytes_in::<_, Panic>(&value, writer).unwrap();
+        let compressed = writer.finish::<Panic>().unwrap();
+        assert!(compressed.len() < 4000);
+
+        let bytes = decompress_lz4::<16, _>(compressed.as_slice()).unwrap();
+        // SAFETY: The bytes were serialized from a `Vec<u32>`.
+        let archived =
+            unsafe { access_unchecked::<Archived<Vec<u32>>>(&bytes) };
+        assert_eq!(archived.as_slice(), value.as_slice());
+    }
+}
@@ -3,6 +3,8 @@
 #[cfg(feature = "alloc")]
 mod alloc;
 mod inline_vec;
+#[cfg(feature = "lz4_flex-0_11")]
+mod lz4_flex_0_11;
 mod ser_vec;
 
 use core::ops::{Deref, DerefMut};
@@ -11,6 +13,9 @@ use core::ops::{Deref, DerefMut};
 #[cfg(feature = "alloc")]
 pub use self::alloc::*;
 #[doc(inline)]
+#[cfg(feature = "lz4_flex-0_11")]
+pub use self::lz4_flex_0_11::decompress_lz4;
+#[doc(inline)]
 pub use self::{inline_vec::InlineVec, ser_vec::SerVec};
 
 /// A wrapper which aligns its inner value to 16 bytes.
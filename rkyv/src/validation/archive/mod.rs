@@ -53,6 +53,15 @@ pub unsafe trait ArchiveContext<E = <Self as Fallible>::Error> {
         &mut self,
         range: Range<usize>,
     ) -> Result<(), E>;
+
+    /// Claims `count` elements of a collection before they are checked.
+    ///
+    /// Validators can use this to limit the total number of elements checked
+    /// in an archive. The default implementation does nothing.
+    fn claim_elements(&mut self, count: usize) -> Result<(), E> {
+        let _ = count;
+        Ok(())
+    }
 }
 
 unsafe impl<T, E> ArchiveContext<E> for Strategy<T, E>
@@ -85,6 +94,10 @@ where
         // has the same safety requirements.
         unsafe { T::pop_subtree_range(self, range) }
     }
+
+    fn claim_elements(&mut self, count: usize) -> Result<(), E> {
+        T::claim_elements(self, count)
+    }
 }
 
 /// Helper methods for [`ArchiveContext`].
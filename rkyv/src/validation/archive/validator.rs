@@ -5,7 +5,10 @@ use core::{
 
 use rancor::{fail, OptionExt, Source};
 
-use crate::{fmt::Pointer, validation::ArchiveContext};
+use crate::{
+    fmt::Pointer,
+    validation::{ArchiveContext, ValidatorConfig},
+};
 
 #[derive(Debug)]
 struct UnalignedPointer {
@@ -62,6 +65,20 @@ impl fmt::Display for ExceededMaximumSubtreeDepth {
 
 impl Error for ExceededMaximumSubtreeDepth {}
 
+#[derive(Debug)]
+struct ExceededMaximumElements;
+
+impl fmt::Display for ExceededMaximumElements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "claimed more elements than the maximum number of elements",
+        )
+    }
+}
+
+impl Error for ExceededMaximumElements {}
+
 #[derive(Debug)]
 struct RangePoppedTooManyTimes;
 
@@ -89,6 +106,7 @@ impl Error for RangePoppedOutOfOrder {}
 pub struct ArchiveValidator<'a> {
     subtree_range: Range<usize>,
     max_subtree_depth: Option<NonZeroUsize>,
+    max_elements: Option<usize>,
     _phantom: PhantomData<&'a [u8]>,
 }
 
@@ -106,13 +124,27 @@ impl<'a> ArchiveValidator<'a> {
         bytes: &'a [u8],
         max_subtree_depth: Option<NonZeroUsize>,
     ) -> Self {
+        Self::with_config(
+            bytes,
+            &ValidatorConfig {
+                max_depth: max_subtree_depth,
+                ..ValidatorConfig::default()
+            },
+        )
+    }
+
+    /// Creates a new bounds validator for the given bytes which enforces the
+    /// depth and element limits of the given config.
+    #[inline]
+    pub fn with_config(bytes: &'a [u8], config: &ValidatorConfig) -> Self {
         let Range { start, end } = bytes.as_ptr_range();
         Self {
             subtree_range: Range {
                 start: start as usize,
                 end: end as usize,
             },
-            max_subtree_depth,
+            max_subtree_depth: config.max_depth,
+            max_elements: config.max_elements,
             _phantom: PhantomData,
         }
     }
@@ -175,4 +207,13 @@ unsafe impl<E: Source> ArchiveContext<E> for ArchiveValidator<'_> {
         }
         Ok(())
     }
+
+    fn claim_elements(&mut self, count: usize) -> Result<(), E> {
+        if let Some(max_elements) = &mut self.max_elements {
+            *max_elements = max_elements
+                .checked_sub(count)
+                .into_trace(ExceededMaximumElements)?;
+        }
+        Ok(())
+    }
 }
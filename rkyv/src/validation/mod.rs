@@ -1,14 +1,74 @@
 //! Validation implementations and helper types.
+//!
+//! Archived strings are checked with the `CheckBytes` implementation for
+//! `str`, which validates UTF-8 with the vectorized validator from
+//! [`simdutf8`](https://docs.rs/simdutf8).
 
 pub mod archive;
+#[cfg(feature = "rayon-1")]
+pub mod parallel;
+#[cfg(feature = "alloc")]
+pub mod path;
 pub mod shared;
 
-use core::{any::TypeId, ops::Range};
+use core::{any::TypeId, num::NonZeroUsize, ops::Range};
 
+use bytecheck::CheckBytes;
+use rancor::{Fallible, Source};
+
+#[cfg(feature = "alloc")]
+pub use self::path::PathError;
 pub use self::{
     archive::{ArchiveContext, ArchiveContextExt},
     shared::SharedContext,
 };
+use crate::Portable;
+
+/// Limits on the resources that validation may use.
+///
+/// Validating an untrusted archive without limits can take time proportional
+/// to the number of elements it claims to have, and can recurse as deeply as
+/// the archive is nested. A `ValidatorConfig` bounds these so that malicious
+/// archives fail validation quickly instead. All limits are disabled by
+/// default.
+///
+/// # Example
+///
+/// ```
+/// use core::num::NonZeroUsize;
+///
+/// use rkyv::{
+///     api::high::access_with, rancor::Error, to_bytes,
+///     validation::ValidatorConfig, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     values: Vec<u32>,
+/// }
+///
+/// let bytes = to_bytes::<Error>(&Example {
+///     values: vec![1, 2, 3, 4],
+/// })
+/// .unwrap();
+///
+/// let config = ValidatorConfig {
+///     max_depth: NonZeroUsize::new(16),
+///     max_elements: Some(3),
+///     max_shared: Some(256),
+/// };
+/// assert!(access_with::<ArchivedExample, Error>(&bytes, &config).is_err());
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ValidatorConfig {
+    /// The maximum depth of nested subtrees, like boxes within boxes.
+    pub max_depth: Option<NonZeroUsize>,
+    /// The maximum total number of collection elements, summed across all of
+    /// the vectors and hash tables in the archive.
+    pub max_elements: Option<usize>,
+    /// The maximum number of distinct shared pointers.
+    pub max_shared: Option<usize>,
+}
 
 /// The default validator.
 #[derive(Debug)]
@@ -25,6 +85,19 @@ impl<A, S> Validator<A, S> {
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<'a> Validator<archive::ArchiveValidator<'a>, shared::SharedValidator> {
+    /// Creates a new validator for the given bytes which enforces the limits
+    /// of the given config.
+    #[inline]
+    pub fn with_config(bytes: &'a [u8], config: &ValidatorConfig) -> Self {
+        Self::new(
+            archive::ArchiveValidator::with_config(bytes, config),
+            shared::SharedValidator::with_config(config),
+        )
+    }
+}
+
 unsafe impl<A, S, E> ArchiveContext<E> for Validator<A, S>
 where
     A: ArchiveContext<E>,
@@ -55,6 +128,10 @@ where
         // which has the same safety requirements.
         unsafe { self.archive.pop_subtree_range(range) }
     }
+
+    fn claim_elements(&mut self, count: usize) -> Result<(), E> {
+        self.archive.claim_elements(count)
+    }
 }
 
 impl<A, S, E> SharedContext<E> for Validator<A, S>
@@ -78,6 +155,30 @@ where
     }
 }
 
+/// Checks the elements of a slice, skipping the elements entirely if they are
+/// [always valid](Portable::ALWAYS_VALID).
+///
+/// # Safety
+///
+/// `ptr` must be properly aligned and point to enough bytes for the slice.
+pub(crate) unsafe fn check_slice<T, C>(
+    ptr: *const [T],
+    context: &mut C,
+) -> Result<(), C::Error>
+where
+    T: Portable + CheckBytes<C>,
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    if T::ALWAYS_VALID {
+        Ok(())
+    } else {
+        // SAFETY: The caller has guaranteed that `ptr` is properly aligned and
+        // points to enough bytes for the slice.
+        unsafe { <[T]>::check_bytes(ptr, context) }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rancor::Failure;
@@ -196,6 +297,62 @@ mod tests {
             .expect_err("expected out of bounds error");
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn resource_limits() {
+        use core::num::NonZeroUsize;
+
+        use crate::{
+            alloc::{boxed::Box, rc::Rc, vec, vec::Vec},
+            api::high::access_with,
+            to_bytes,
+            validation::ValidatorConfig,
+        };
+
+        let bytes = to_bytes::<Failure>(&vec![1u32, 2, 3, 4]).unwrap();
+        let config = ValidatorConfig {
+            max_elements: Some(4),
+            ..Default::default()
+        };
+        access_with::<Archived<Vec<u32>>, Failure>(&bytes, &config).unwrap();
+        let config = ValidatorConfig {
+            max_elements: Some(3),
+            ..Default::default()
+        };
+        access_with::<Archived<Vec<u32>>, Failure>(&bytes, &config)
+            .expect_err("expected too many elements error");
+
+        let value = Box::new(Box::new(Box::new(42u32)));
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        let config = ValidatorConfig {
+            max_depth: NonZeroUsize::new(8),
+            ..Default::default()
+        };
+        access_with::<Archived<Box<Box<Box<u32>>>>, Failure>(&bytes, &config)
+            .unwrap();
+        let config = ValidatorConfig {
+            max_depth: NonZeroUsize::new(2),
+            ..Default::default()
+        };
+        access_with::<Archived<Box<Box<Box<u32>>>>, Failure>(&bytes, &config)
+            .expect_err("expected exceeded depth error");
+
+        let value = vec![Rc::new(1u32), Rc::new(2), Rc::new(3)];
+        let bytes = to_bytes::<Failure>(&value).unwrap();
+        let config = ValidatorConfig {
+            max_shared: Some(3),
+            ..Default::default()
+        };
+        access_with::<Archived<Vec<Rc<u32>>>, Failure>(&bytes, &config)
+            .unwrap();
+        let config = ValidatorConfig {
+            max_shared: Some(2),
+            ..Default::default()
+        };
+        access_with::<Archived<Vec<Rc<u32>>>, Failure>(&bytes, &config)
+            .expect_err("expected too many shared pointers error");
+    }
+
     #[cfg(feature = "pointer_width_32")]
     #[test]
     fn invalid_tags() {
@@ -303,4 +460,64 @@ mod tests {
 
         access_pos::<ArchivedNode, Failure>(&*synthetic_buf, 0).unwrap_err();
     }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn always_valid_slices() {
+        use crate::{alloc::vec, api::test::to_bytes, vec::ArchivedVec};
+
+        to_bytes(&vec![u32::MAX; 4], |bytes| {
+            access::<ArchivedVec<Archived<u32>>, Failure>(bytes).unwrap();
+        });
+
+        to_bytes(&vec!['a'; 4], |bytes| {
+            // The elements are at the start of the archive, and no `char` has
+            // every bit set.
+            bytes[..4].fill(0xff);
+            access::<ArchivedVec<Archived<char>>, Failure>(bytes).unwrap_err();
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn always_valid_vec_skips_element_checks() {
+        use core::sync::atomic::{AtomicUsize, Ordering};
+
+        use bytecheck::CheckBytes;
+        use rancor::Fallible;
+
+        use crate::{
+            alloc::vec, api::test::to_bytes, vec::ArchivedVec, Portable,
+        };
+
+        static CHECKS: AtomicUsize = AtomicUsize::new(0);
+
+        #[repr(transparent)]
+        struct Counted<const V: bool>(u8);
+
+        unsafe impl<const V: bool> Portable for Counted<V> {
+            const ALWAYS_VALID: bool = V;
+        }
+
+        unsafe impl<C, const V: bool> CheckBytes<C> for Counted<V>
+        where
+            C: Fallible + ?Sized,
+        {
+            unsafe fn check_bytes(
+                _: *const Self,
+                _: &mut C,
+            ) -> Result<(), C::Error> {
+                CHECKS.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        }
+
+        to_bytes(&vec![1u8, 2, 3, 4], |bytes| {
+            access::<ArchivedVec<Counted<true>>, Failure>(bytes).unwrap();
+            assert_eq!(CHECKS.load(Ordering::Relaxed), 0);
+
+            access::<ArchivedVec<Counted<false>>, Failure>(bytes).unwrap();
+            assert_eq!(CHECKS.load(Ordering::Relaxed), 4);
+        });
+    }
 }
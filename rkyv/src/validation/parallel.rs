@@ -0,0 +1,461 @@
+//! Parallel validation for archives with large root vectors.
+//!
+//! Validating an archive is usually a single depth-first walk over its data.
+//! When the root of an archive is a large [`ArchivedVec`], most of that walk is
+//! spent checking its elements, which are independent of each other except
+//! for two invariants: the out-of-line data of each element must not overlap
+//! the data of the elements before it, and shared pointers must be validated
+//! as the same type everywhere they appear.
+//!
+//! [`access_vec`] splits the elements of a root vector into chunks and checks
+//! them on the rayon thread pool. Each chunk records the range of memory it
+//! claimed and the shared pointers it validated, and these are merged in order
+//! afterward so that the result is exactly the same as validating the archive
+//! with [`access`](crate::access).
+//!
+//! Only the elements of the root vector are split into chunks. Elements which
+//! are themselves large collections are still checked by a single thread.
+
+use core::{
+    alloc::Layout, any::TypeId, error::Error, fmt, hash::BuildHasherDefault,
+    ops::Range,
+};
+use std::collections::HashMap;
+
+use bytecheck::CheckBytes;
+use rancor::{fail, ResultExt as _, Source, Strategy};
+use rayon_1::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::{
+    alloc::vec::Vec,
+    api::root_position,
+    fmt::Pointer,
+    hash::FxHasher64,
+    validation::{
+        archive::ArchiveValidator,
+        shared::{SharedValidator, ValidationState},
+        ArchiveContext, ArchiveContextExt, SharedContext,
+    },
+    vec::ArchivedVec,
+    Portable,
+};
+
+type SharedMap = HashMap<usize, TypeId, BuildHasherDefault<FxHasher64>>;
+
+#[derive(Debug)]
+struct IndexContext {
+    index: usize,
+}
+
+impl fmt::Display for IndexContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "while checking index '{}' of slice", self.index)
+    }
+}
+
+#[derive(Debug)]
+struct OverlappingElements {
+    address: usize,
+    previous_end: usize,
+}
+
+impl fmt::Display for OverlappingElements {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "element subtree at ptr {} overlaps the subtree of a previous \
+             element ending at ptr {}",
+            Pointer(self.address),
+            Pointer(self.previous_end),
+        )
+    }
+}
+
+impl Error for OverlappingElements {}
+
+#[derive(Debug)]
+struct SharedTypeMismatch {
+    previous: TypeId,
+    current: TypeId,
+}
+
+impl fmt::Display for SharedTypeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the same memory region has been claimed as two different types: \
+             {:?} and {:?}",
+            self.previous, self.current,
+        )
+    }
+}
+
+impl Error for SharedTypeMismatch {}
+
+/// The memory and shared pointers claimed while validating a chunk.
+#[derive(Default)]
+struct Claims {
+    range: Option<Range<usize>>,
+    shared: Vec<(usize, TypeId)>,
+}
+
+/// A validator which checks a chunk of the elements of a root vector.
+///
+/// This is created by [`access_vec`] for each chunk of elements, and can't be
+/// constructed directly.
+pub struct ParallelValidator<'a> {
+    archive: ArchiveValidator<'a>,
+    shared: SharedValidator,
+    prior: &'a SharedMap,
+    claims: Claims,
+}
+
+impl<'a> ParallelValidator<'a> {
+    fn new(bytes: &'a [u8], prior: &'a SharedMap) -> Self {
+        Self {
+            archive: ArchiveValidator::new(bytes),
+            shared: SharedValidator::new(),
+            prior,
+            claims: Claims::default(),
+        }
+    }
+}
+
+unsafe impl<E: Source> ArchiveContext<E> for ParallelValidator<'_> {
+    fn check_subtree_ptr(
+        &mut self,
+        ptr: *const u8,
+        layout: &Layout,
+    ) -> Result<(), E> {
+        ArchiveContext::<E>::check_subtree_ptr(&mut self.archive, ptr, layout)
+    }
+
+    unsafe fn push_subtree_range(
+        &mut self,
+        root: *const u8,
+        end: *const u8,
+    ) -> Result<Range<usize>, E> {
+        // SAFETY: This just forwards the call to the underlying validator,
+        // which has the same safety requirements.
+        let range = unsafe {
+            ArchiveContext::<E>::push_subtree_range(
+                &mut self.archive,
+                root,
+                end,
+            )?
+        };
+
+        let root = root as usize;
+        match &mut self.claims.range {
+            Some(claimed) => claimed.start = claimed.start.min(root),
+            None => self.claims.range = Some(root..root),
+        }
+
+        Ok(range)
+    }
+
+    unsafe fn pop_subtree_range(
+        &mut self,
+        range: Range<usize>,
+    ) -> Result<(), E> {
+        let end = range.start;
+        // SAFETY: This just forwards the call to the underlying validator,
+        // which has the same safety requirements.
+        unsafe {
+            ArchiveContext::<E>::pop_subtree_range(&mut self.archive, range)?;
+        }
+
+        if let Some(claimed) = &mut self.claims.range {
+            claimed.end = claimed.end.max(end);
+        }
+
+        Ok(())
+    }
+
+    fn claim_elements(&mut self, count: usize) -> Result<(), E> {
+        ArchiveContext::<E>::claim_elements(&mut self.archive, count)
+    }
+}
+
+impl<E: Source> SharedContext<E> for ParallelValidator<'_> {
+    fn start_shared(
+        &mut self,
+        address: usize,
+        type_id: TypeId,
+    ) -> Result<ValidationState, E> {
+        if let Some(previous) = self.prior.get(&address) {
+            if *previous != type_id {
+                fail!(SharedTypeMismatch {
+                    previous: *previous,
+                    current: type_id,
+                });
+            }
+            return Ok(ValidationState::Finished);
+        }
+
+        let state = SharedContext::<E>::start_shared(
+            &mut self.shared,
+            address,
+            type_id,
+        )?;
+        if let ValidationState::Started = state {
+            self.claims.shared.push((address, type_id));
+        }
+        Ok(state)
+    }
+
+    fn finish_shared(
+        &mut self,
+        address: usize,
+        type_id: TypeId,
+    ) -> Result<(), E> {
+        SharedContext::<E>::finish_shared(&mut self.shared, address, type_id)
+    }
+}
+
+/// Checks the elements in `range`, treating the shared pointers in `prior` as
+/// already validated.
+///
+/// # Safety
+///
+/// `elements` must point to the elements of a vector located entirely within
+/// `bytes`, and `range` must be in bounds for that vector.
+unsafe fn check_chunk<T, E>(
+    bytes: &[u8],
+    elements: *const T,
+    range: Range<usize>,
+    prior: &SharedMap,
+) -> Result<Claims, E>
+where
+    T: for<'a> CheckBytes<Strategy<ParallelValidator<'a>, E>>,
+    E: Source,
+{
+    let mut validator = ParallelValidator::new(bytes, prior);
+    let context = Strategy::<_, E>::wrap(&mut validator);
+    for index in range {
+        // SAFETY: The caller has guaranteed that `index` is in bounds for the
+        // vector that `elements` points to.
+        unsafe { T::check_bytes(elements.add(index), context) }
+            .with_trace(|| IndexContext { index })?;
+    }
+    Ok(validator.claims)
+}
+
+/// Checks the elements of a vector in chunks on the rayon thread pool.
+///
+/// # Safety
+///
+/// `elements` must point to `len` elements located entirely within `bytes`.
+unsafe fn check_elements<T, E>(
+    bytes: &[u8],
+    elements: *const T,
+    len: usize,
+) -> Result<(), E>
+where
+    T: for<'a> CheckBytes<Strategy<ParallelValidator<'a>, E>>,
+    E: Source,
+{
+    // Elements may only claim memory before the start of the vector, the same
+    // as when validating sequentially.
+    let bytes = &bytes[..elements as usize - bytes.as_ptr() as usize];
+
+    let chunk_size = len.div_ceil(rayon_1::current_num_threads() * 4).max(1);
+    let chunk_count = len.div_ceil(chunk_size);
+    let chunk_range =
+        |chunk: usize| chunk * chunk_size..len.min((chunk + 1) * chunk_size);
+
+    let address = elements as usize;
+    let empty = SharedMap::default();
+    let results = (0..chunk_count)
+        .into_par_iter()
+        .map(|chunk| {
+            // SAFETY: The caller has guaranteed that `address` points to `len`
+            // elements in `bytes`, and every chunk range is within `0..len`.
+            unsafe {
+                check_chunk::<T, E>(
+                    bytes,
+                    address as *const T,
+                    chunk_range(chunk),
+                    &empty,
+                )
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Chunks are merged in order. A chunk which validated a shared pointer
+    // that an earlier chunk also validated would not have validated it
+    // sequentially, so it is checked again with the shared pointers of all the
+    // earlier chunks marked as finished.
+    let mut prior = SharedMap::default();
+    let mut previous_end = bytes.as_ptr() as usize;
+    for (chunk, result) in results.into_iter().enumerate() {
+        let claims = match result {
+            Ok(claims)
+                if claims
+                    .shared
+                    .iter()
+                    .all(|(address, _)| !prior.contains_key(address)) =>
+            {
+                claims
+            }
+            // SAFETY: The caller has guaranteed that `elements` points to
+            // `len` elements in `bytes`, and the chunk range is within
+            // `0..len`.
+            _ => unsafe {
+                check_chunk::<T, E>(
+                    bytes,
+                    elements,
+                    chunk_range(chunk),
+                    &prior,
+                )?
+            },
+        };
+
+        if let Some(range) = claims.range {
+            if range.start < previous_end {
+                fail!(OverlappingElements {
+                    address: range.start,
+                    previous_end,
+                });
+            }
+            previous_end = range.end;
+        }
+        prior.extend(claims.shared);
+    }
+
+    Ok(())
+}
+
+/// Accesses a byte slice whose root is an [`ArchivedVec`], validating its
+/// elements in parallel.
+///
+/// This accepts and rejects exactly the same archives as
+/// [`access`](crate::access), but splits the elements of the root vector into
+/// chunks which are checked on the rayon thread pool. Only the root vector is
+/// split, so this is most useful for archives of many small elements.
+///
+/// Elements which share pointers with elements in other chunks are checked
+/// again after the shared pointers have been merged, so archives with many
+/// shared pointers between distant elements may not validate any faster.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{rancor::Error, to_bytes, validation::parallel::access_vec};
+///
+/// let value = (0..10_000).map(|i| i.to_string()).collect::<Vec<_>>();
+/// let bytes = to_bytes::<Error>(&value).unwrap();
+///
+/// let archived =
+///     access_vec::<rkyv::string::ArchivedString, Error>(&bytes).unwrap();
+/// assert_eq!(archived.len(), 10_000);
+/// assert_eq!(archived[1234], "1234");
+/// ```
+pub fn access_vec<T, E>(bytes: &[u8]) -> Result<&ArchivedVec<T>, E>
+where
+    T: Portable + for<'a> CheckBytes<Strategy<ParallelValidator<'a>, E>>,
+    E: Source,
+{
+    let pos = root_position::<ArchivedVec<T>>(bytes.len());
+    let root = bytes.as_ptr().wrapping_add(pos).cast::<ArchivedVec<T>>();
+
+    let mut validator = ArchiveValidator::new(bytes);
+    let context = Strategy::<_, E>::wrap(&mut validator);
+    context.in_subtree(root, |context| {
+        // SAFETY: `in_subtree` has guaranteed that `root` is properly aligned
+        // and points to enough bytes for an `ArchivedVec<T>`. Its relative
+        // pointer and length are valid for any bytes.
+        let vec = unsafe { &*root };
+        let len = vec.len();
+        context.claim_elements(len)?;
+
+        let elements = vec.as_ptr_wrapping();
+        let layout = Layout::array::<T>(len).into_error()?;
+        context.in_subtree_raw(elements.cast(), layout, |_| {
+            // SAFETY: `in_subtree_raw` has guaranteed that all `len` elements
+            // are located within `bytes`.
+            unsafe { check_elements::<T, E>(bytes, elements, len) }
+        })
+    })?;
+
+    // SAFETY: The root vector and all of its elements have been checked.
+    unsafe { Ok(&*root) }
+}
+
+#[cfg(test)]
+mod tests {
+    use rancor::Failure;
+
+    use super::access_vec;
+    use crate::{
+        access,
+        alloc::{boxed::Box, format, rc::Rc, string::String, vec, vec::Vec},
+        api::{access_unchecked, test::to_bytes},
+        boxed::ArchivedBox,
+        primitive::{ArchivedIsize, FixedIsize},
+        string::ArchivedString,
+        vec::ArchivedVec,
+        Archived,
+    };
+
+    #[test]
+    fn validates_root_vec() {
+        let value = (0..1000)
+            .map(|i| format!("a string long enough to be out of line {i}"))
+            .collect::<Vec<String>>();
+
+        to_bytes(&value, |bytes| {
+            let archived = access_vec::<ArchivedString, Failure>(bytes)
+                .expect("failed to validate root vec");
+            assert_eq!(archived.len(), value.len());
+            for (archived, value) in archived.iter().zip(value.iter()) {
+                assert_eq!(archived, value);
+            }
+        });
+    }
+
+    #[test]
+    fn shared_across_chunks() {
+        let shared = (0..4).map(Rc::new).collect::<Vec<Rc<u32>>>();
+        let value = (0..1000)
+            .map(|i| shared[(i * 7) % shared.len()].clone())
+            .collect::<Vec<_>>();
+
+        to_bytes(&value, |bytes| {
+            let archived = access_vec::<Archived<Rc<u32>>, Failure>(bytes)
+                .expect("failed to validate shared pointers");
+            for (archived, value) in archived.iter().zip(value.iter()) {
+                assert_eq!(**archived, **value);
+            }
+        });
+    }
+
+    #[test]
+    fn rejects_overlapping_elements() {
+        let value = vec![Box::new(1u32), Box::new(2u32)];
+
+        to_bytes(&value, |bytes| {
+            let archived = unsafe {
+                access_unchecked::<ArchivedVec<ArchivedBox<Archived<u32>>>>(
+                    bytes,
+                )
+            };
+            let target = archived[0].get() as *const _ as usize;
+            let second = &archived[1] as *const _ as usize;
+            let pos = second - bytes.as_ptr() as usize;
+            let offset = target.wrapping_sub(second) as isize;
+
+            unsafe {
+                bytes
+                    .as_mut_ptr()
+                    .add(pos)
+                    .cast::<ArchivedIsize>()
+                    .write(ArchivedIsize::from_native(offset as FixedIsize));
+            }
+
+            type Element = ArchivedBox<Archived<u32>>;
+            assert!(access::<ArchivedVec<Element>, Failure>(bytes).is_err());
+            assert!(access_vec::<Element, Failure>(bytes).is_err());
+        });
+    }
+}
@@ -0,0 +1,297 @@
+//! An error type which records the path to the value that failed validation.
+
+use core::{any::Any, error::Error, fmt};
+
+use bytecheck::{
+    NamedEnumVariantCheckContext, StructCheckContext, TupleStructCheckContext,
+    UnnamedEnumVariantCheckContext,
+};
+use rancor::{Source, Trace};
+
+use crate::alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// A step along the path from the root of an archive to a nested value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PathSegment {
+    /// A named field of a struct or enum variant.
+    Field(&'static str),
+    /// An unnamed field of a tuple struct or enum variant.
+    TupleField(usize),
+    /// The variant of an enum.
+    Variant(&'static str),
+    /// An element of a slice or array.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Field(name) => write!(f, ".{}", name),
+            Self::TupleField(index) => write!(f, ".{}", index),
+            Self::Variant(name) => write!(f, "::{}", name),
+            Self::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
+
+/// An error which records where in an archive validation failed.
+///
+/// `PathError` collects the context traced by `CheckBytes` implementations as
+/// an error propagates out of validation, and turns it into a path from the
+/// root of the archive to the value which failed to validate:
+///
+/// ```text
+/// root.players[3].inventory.items[17].name: invalid utf-8 sequence
+/// ```
+///
+/// Paths are only built when validation fails, so a `PathError` adds no
+/// overhead to validating archives which are valid. Traces which don't
+/// correspond to a path segment are kept and displayed after the error.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access, rancor::Error, to_bytes, validation::PathError, Archive,
+///     Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Player {
+///     name: String,
+/// }
+///
+/// #[derive(Archive, Serialize)]
+/// struct Game {
+///     players: Vec<Player>,
+/// }
+///
+/// let game = Game {
+///     players: vec![
+///         Player {
+///             name: "the first player".to_string(),
+///         },
+///         Player {
+///             name: "the second player".to_string(),
+///         },
+///     ],
+/// };
+///
+/// let mut bytes = to_bytes::<Error>(&game).unwrap();
+/// let pos = bytes.windows(6).position(|w| w == b"second").unwrap();
+/// bytes[pos] = 0xff;
+///
+/// let error = access::<ArchivedGame, PathError>(&bytes).unwrap_err();
+/// assert_eq!(error.display_path().to_string(), "root.players[1].name");
+/// ```
+pub struct PathError {
+    path: Vec<PathSegment>,
+    notes: Vec<String>,
+    source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl PathError {
+    /// Returns the path from the root of the archive to the value which failed
+    /// to validate.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.path
+    }
+
+    /// Returns an object which displays the path starting from `root`.
+    pub fn display_path(&self) -> DisplayPath<'_> {
+        DisplayPath { path: &self.path }
+    }
+
+    fn push_front(&mut self, segments: &[PathSegment]) {
+        self.path.splice(0..0, segments.iter().copied());
+    }
+}
+
+/// Displays the path of a [`PathError`].
+pub struct DisplayPath<'a> {
+    path: &'a [PathSegment],
+}
+
+impl fmt::Display for DisplayPath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "root")?;
+        for segment in self.path {
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PathError")
+            .field("path", &self.path)
+            .field("notes", &self.notes)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.display_path(), self.source)?;
+        for note in self.notes.iter() {
+            write!(f, "\n{}", note)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for PathError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+/// Parses the index out of the traces that bytecheck emits for slices and
+/// arrays, which read like "while checking index '3' of slice".
+fn parse_index_trace(message: &str) -> Option<usize> {
+    let rest = message.strip_prefix("while checking index '")?;
+    let (index, rest) = rest.split_once('\'')?;
+    if rest.starts_with(" of ") {
+        index.parse().ok()
+    } else {
+        None
+    }
+}
+
+impl Trace for PathError {
+    fn trace<R>(mut self, trace: R) -> Self
+    where
+        R: fmt::Debug + fmt::Display + Send + Sync + 'static,
+    {
+        let any = &trace as &dyn Any;
+        if let Some(c) = any.downcast_ref::<StructCheckContext>() {
+            self.push_front(&[PathSegment::Field(c.field_name)]);
+        } else if let Some(c) = any.downcast_ref::<TupleStructCheckContext>() {
+            self.push_front(&[PathSegment::TupleField(c.field_index)]);
+        } else if let Some(c) =
+            any.downcast_ref::<NamedEnumVariantCheckContext>()
+        {
+            self.push_front(&[
+                PathSegment::Variant(c.variant_name),
+                PathSegment::Field(c.field_name),
+            ]);
+        } else if let Some(c) =
+            any.downcast_ref::<UnnamedEnumVariantCheckContext>()
+        {
+            self.push_front(&[
+                PathSegment::Variant(c.variant_name),
+                PathSegment::TupleField(c.field_index),
+            ]);
+        } else {
+            let message = trace.to_string();
+            if let Some(index) = parse_index_trace(&message) {
+                self.push_front(&[PathSegment::Index(index)]);
+            } else {
+                self.notes.push(message);
+            }
+        }
+        self
+    }
+}
+
+impl Source for PathError {
+    fn new<T: Error + Send + Sync + 'static>(source: T) -> Self {
+        Self {
+            path: Vec::new(),
+            notes: Vec::new(),
+            source: Box::new(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathError, PathSegment};
+    use crate::{
+        access,
+        alloc::{
+            string::{String, ToString},
+            vec,
+            vec::Vec,
+        },
+        api::test::to_bytes,
+        Archive, Serialize,
+    };
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate)]
+    struct Item {
+        name: String,
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate)]
+    enum Slot {
+        Empty,
+        Holding { item: Item },
+    }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(crate)]
+    struct Inventory {
+        slots: Vec<Slot>,
+    }
+
+    fn corrupt(bytes: &mut [u8], needle: &[u8]) {
+        let pos = bytes
+            .windows(needle.len())
+            .position(|w| w == needle)
+            .unwrap();
+        bytes[pos] = 0xff;
+    }
+
+    #[test]
+    fn records_path() {
+        let value = Inventory {
+            slots: vec![
+                Slot::Holding {
+                    item: Item {
+                        name: "a long enough sword".to_string(),
+                    },
+                },
+                Slot::Empty,
+                Slot::Holding {
+                    item: Item {
+                        name: "a long enough shield".to_string(),
+                    },
+                },
+            ],
+        };
+
+        to_bytes(&value, |bytes| {
+            assert!(access::<ArchivedInventory, PathError>(bytes).is_ok());
+
+            corrupt(bytes, b"shield");
+            let error =
+                access::<ArchivedInventory, PathError>(bytes).unwrap_err();
+            assert_eq!(
+                error.path(),
+                &[
+                    PathSegment::Field("slots"),
+                    PathSegment::Index(2),
+                    PathSegment::Variant("Holding"),
+                    PathSegment::Field("item"),
+                    PathSegment::Field("name"),
+                ]
+            );
+            assert_eq!(
+                error.display_path().to_string(),
+                "root.slots[2]::Holding.item.name",
+            );
+            assert!(error
+                .to_string()
+                .starts_with("root.slots[2]::Holding.item.name: "));
+        });
+    }
+}
@@ -11,7 +11,7 @@ use rancor::{fail, Source};
 
 use crate::{
     hash::FxHasher64,
-    validation::{shared::ValidationState, SharedContext},
+    validation::{shared::ValidationState, SharedContext, ValidatorConfig},
 };
 
 /// A validator that can verify shared pointers.
@@ -22,6 +22,7 @@ pub struct SharedValidator {
         (TypeId, bool),
         BuildHasherDefault<FxHasher64>,
     >,
+    max_shared: Option<usize>,
 }
 
 impl SharedValidator {
@@ -39,6 +40,17 @@ impl SharedValidator {
                 capacity,
                 Default::default(),
             ),
+            max_shared: None,
+        }
+    }
+
+    /// Creates a new shared pointer validator which enforces the shared
+    /// pointer limit of the given config.
+    #[inline]
+    pub fn with_config(config: &ValidatorConfig) -> Self {
+        Self {
+            max_shared: config.max_shared,
+            ..Self::default()
         }
     }
 }
@@ -62,6 +74,23 @@ impl fmt::Display for TypeMismatch {
 
 impl Error for TypeMismatch {}
 
+#[derive(Debug)]
+struct ExceededMaximumShared {
+    max_shared: usize,
+}
+
+impl fmt::Display for ExceededMaximumShared {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "validated more than the maximum of {} shared pointers",
+            self.max_shared,
+        )
+    }
+}
+
+impl Error for ExceededMaximumShared {}
+
 #[derive(Debug)]
 struct NotStarted;
 
@@ -90,8 +119,14 @@ impl<E: Source> SharedContext<E> for SharedValidator {
         address: usize,
         type_id: TypeId,
     ) -> Result<ValidationState, E> {
+        let len = self.shared.len();
         match self.shared.entry(address) {
             hash_map::Entry::Vacant(vacant) => {
+                if let Some(max_shared) = self.max_shared {
+                    if len >= max_shared {
+                        fail!(ExceededMaximumShared { max_shared });
+                    }
+                }
                 vacant.insert((type_id, false));
                 Ok(ValidationState::Started)
             }
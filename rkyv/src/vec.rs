@@ -9,11 +9,16 @@ use core::{
 
 use munge::munge;
 use rancor::Fallible;
+#[cfg(feature = "alloc")]
+use rancor::{fail, Source};
 
+#[cfg(feature = "alloc")]
+use crate::ser::Deferred;
 use crate::{
     primitive::{ArchivedUsize, FixedUsize},
     seal::Seal,
     ser::{Allocator, Writer, WriterExt as _},
+    traits::NoUndef,
     Archive, Place, Portable, RelPtr, Serialize, SerializeUnsized,
 };
 
@@ -41,6 +46,11 @@ impl<T> ArchivedVec<T> {
         unsafe { self.ptr.as_ptr() }
     }
 
+    #[cfg(all(feature = "bytecheck", feature = "rayon-1"))]
+    pub(crate) fn as_ptr_wrapping(&self) -> *const T {
+        self.ptr.as_ptr_wrapping()
+    }
+
     /// Returns the number of elements in the archived vec.
     pub fn len(&self) -> usize {
         self.len.to_native() as usize
@@ -66,6 +76,61 @@ impl<T> ArchivedVec<T> {
         Seal::new(slice)
     }
 
+    /// Gets a sealed mutable reference to the element at the given index, or
+    /// `None` if the index is out of bounds.
+    pub fn get_seal(this: Seal<'_, Self>, index: usize) -> Option<Seal<'_, T>> {
+        if index < this.len() {
+            Some(Self::as_slice_seal(this).index(index))
+        } else {
+            None
+        }
+    }
+
+    /// Swaps two elements of the archived vec.
+    ///
+    /// Only elements which are `Unpin` can be moved, which excludes elements
+    /// that contain relative pointers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    pub fn swap(this: Seal<'_, Self>, a: usize, b: usize)
+    where
+        T: Unpin,
+    {
+        // SAFETY: `T` is `Unpin`, so its values can be moved within the slice
+        // without invalidating them.
+        unsafe { Seal::unseal_unchecked(Self::as_slice_seal(this)) }.swap(a, b);
+    }
+
+    /// Sorts the elements of the archived vec with a comparison function,
+    /// without preserving the initial order of equal elements.
+    ///
+    /// Only elements which are `Unpin` can be moved, which excludes elements
+    /// that contain relative pointers. See [`slice::sort_unstable_by`] for more
+    /// information.
+    pub fn sort_unstable_by<F>(this: Seal<'_, Self>, compare: F)
+    where
+        T: Unpin,
+        F: FnMut(&T, &T) -> cmp::Ordering,
+    {
+        // SAFETY: `T` is `Unpin`, so its values can be moved within the slice
+        // without invalidating them.
+        unsafe { Seal::unseal_unchecked(Self::as_slice_seal(this)) }
+            .sort_unstable_by(compare);
+    }
+
+    /// Fills the archived vec with clones of the given value.
+    pub fn fill(this: Seal<'_, Self>, value: T)
+    where
+        T: Clone + NoUndef + Unpin,
+    {
+        // SAFETY: `T` is `NoUndef` and `Unpin`, so writing initialized values
+        // of `T` over its elements does not invalidate them.
+        unsafe { Seal::unseal_unchecked(Self::as_slice_seal(this)) }
+            .fill(value);
+    }
+
     /// Resolves an archived `Vec` from a given slice.
     pub fn resolve_from_slice<U: Archive<Archived = T>>(
         slice: &[U],
@@ -170,6 +235,110 @@ impl<T> ArchivedVec<T> {
             })
         }
     }
+
+    /// Serializes an archived `Vec` from a single pass over the given
+    /// iterator.
+    ///
+    /// Unlike `serialize_from_iter()`, the iterator does not need to be cloned
+    /// or have an exact length, and items may be dropped as soon as they are
+    /// serialized. Space for the elements is reserved using the upper bound of
+    /// the iterator's size hint (or the lower bound if it has no upper bound),
+    /// and the dependencies of each item are buffered and written after that
+    /// space. Any unused space is left as padding, so size hints should be as
+    /// tight as possible.
+    ///
+    /// Returns an error if the iterator yields more items than its size hint
+    /// allowed. Returns the resolver along with the number of serialized
+    /// elements, which must be passed to
+    /// [`resolve_from_len`](ArchivedVec::resolve_from_len).
+    #[cfg(feature = "alloc")]
+    pub fn serialize_from_single_pass_iter<U, I, S>(
+        iter: I,
+        serializer: &mut S,
+    ) -> Result<(VecResolver, usize), S::Error>
+    where
+        U: for<'a> Serialize<Deferred<'a, S>, Archived = T>,
+        I: IntoIterator,
+        I::Item: Borrow<U>,
+        S: Fallible + Allocator + Writer + ?Sized,
+        S::Error: Source,
+    {
+        use core::mem::{size_of, MaybeUninit};
+
+        use crate::util::SerVec;
+
+        #[derive(Debug)]
+        struct SizeHintError {
+            capacity: usize,
+        }
+
+        impl fmt::Display for SizeHintError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "iterator yielded more than the {} items allowed by its \
+                     size hint",
+                    self.capacity,
+                )
+            }
+        }
+
+        impl core::error::Error for SizeHintError {}
+
+        let iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        let capacity = upper.unwrap_or(lower);
+
+        let pos = serializer.align_for::<T>()?;
+        let base = pos + capacity * size_of::<T>();
+
+        SerVec::<MaybeUninit<T>>::with_capacity(
+            serializer,
+            capacity,
+            |elements, serializer| {
+                let mut deferred = Deferred::new(serializer, base);
+                for value in iter {
+                    if elements.len() == capacity {
+                        fail!(SizeHintError { capacity });
+                    }
+
+                    let value = value.borrow();
+                    let resolver = value.serialize(&mut deferred)?;
+                    let out_pos = pos + elements.len() * size_of::<T>();
+                    elements.push(MaybeUninit::zeroed());
+                    let resolved = elements.as_mut_slice().last_mut().unwrap();
+                    // SAFETY: `resolved` is properly aligned, dereferenceable,
+                    // and all of its bytes are initialized. Its contents will
+                    // be written at `out_pos`.
+                    let out = unsafe {
+                        Place::new_unchecked(out_pos, resolved.as_mut_ptr())
+                    };
+                    value.resolve(resolver, out);
+                }
+                let dependencies = deferred.into_bytes();
+
+                let len = elements.len();
+                // SAFETY: Every element has been zeroed and then resolved, so
+                // all of their bytes are initialized.
+                let bytes = unsafe {
+                    core::slice::from_raw_parts(
+                        elements.as_ptr().cast::<u8>(),
+                        len * size_of::<T>(),
+                    )
+                };
+                serializer.write(bytes)?;
+                serializer.pad((capacity - len) * size_of::<T>())?;
+                serializer.write(&dependencies)?;
+
+                Ok((
+                    VecResolver {
+                        pos: pos as FixedUsize,
+                    },
+                    len,
+                ))
+            },
+        )?
+    }
 }
 
 impl<T> AsRef<[T]> for ArchivedVec<T> {
@@ -291,25 +460,28 @@ mod verify {
     };
 
     use crate::{
-        validation::{ArchiveContext, ArchiveContextExt},
+        validation::{check_slice, ArchiveContext, ArchiveContextExt},
         vec::ArchivedVec,
+        Portable,
     };
 
     unsafe impl<T, C> Verify<C> for ArchivedVec<T>
     where
-        T: CheckBytes<C>,
+        T: Portable + CheckBytes<C>,
         C: Fallible + ArchiveContext + ?Sized,
         C::Error: Source,
     {
         fn verify(&self, context: &mut C) -> Result<(), C::Error> {
+            let len = self.len.to_native() as usize;
+            context.claim_elements(len)?;
+
             let ptr = core::ptr::slice_from_raw_parts(
                 self.ptr.as_ptr_wrapping(),
-                self.len.to_native() as usize,
+                len,
             );
 
-            context.in_subtree(ptr, |context| unsafe {
-                <[T]>::check_bytes(ptr, context)
-            })
+            context
+                .in_subtree(ptr, |context| unsafe { check_slice(ptr, context) })
         }
     }
 }
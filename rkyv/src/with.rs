@@ -197,16 +197,21 @@ where
 }
 
 /// A wrapper that applies another wrapper to the values contained in a type.
-/// This can be applied to a vector to map each element, or an option to map any
-/// contained value.
+/// This can be applied to a vector, array, or box to map each element, or an
+/// option to map any contained value.
+///
+/// Wrappers compose, so nested containers can be mapped by nesting `Map` along
+/// with [`MapKV`] and [`MapResult`].
 ///
 /// See [ArchiveWith] for more details.
 ///
 /// # Example
 ///
 /// ```
+/// use std::collections::HashMap;
+///
 /// use rkyv::{
-///     with::{InlineAsBox, Map},
+///     with::{Identity, InlineAsBox, Map, MapKV},
 ///     Archive,
 /// };
 ///
@@ -218,6 +223,12 @@ where
 ///     // This will apply `InlineAsBox` to each `&i32` contained in this vector
 ///     #[rkyv(with = Map<InlineAsBox>)]
 ///     vec: Vec<&'a i32>,
+///     // This will apply `InlineAsBox` to the `&i32` in each option
+///     #[rkyv(with = Map<Map<InlineAsBox>>)]
+///     nested: Vec<Option<&'a i32>>,
+///     // This will apply `InlineAsBox` to each `&i32` in the map's values
+///     #[rkyv(with = MapKV<Identity, Map<InlineAsBox>>)]
+///     map: HashMap<u32, Vec<&'a i32>>,
 /// }
 /// ```
 pub struct Map<T> {
@@ -249,6 +260,29 @@ pub struct MapKV<K, V> {
     _phantom: PhantomData<(K, V)>,
 }
 
+/// A wrapper that applies separate wrappers to the `Ok` and `Err` values of a
+/// `Result`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     with::{Identity, InlineAsBox, MapResult},
+///     Archive,
+/// };
+///
+/// #[derive(Archive)]
+/// struct Example<'a> {
+///     // This will apply `InlineAsBox` to the `&str` in the `Ok` variant, and
+///     // leave the `u32` in the `Err` variant unchanged.
+///     #[rkyv(with = MapResult<InlineAsBox, Identity>)]
+///     result: Result<&'a str, u32>,
+/// }
+/// ```
+pub struct MapResult<T, E> {
+    _phantom: PhantomData<(T, E)>,
+}
+
 /// A type indicating relaxed atomic loads.
 pub struct Relaxed;
 
@@ -264,6 +298,9 @@ pub struct SeqCst;
 /// When serializing, the specified ordering will be used to load the value from
 /// the source atomic. The underlying archived type is still a non-atomic value.
 ///
+/// Atomics can also be archived without a wrapper, in which case they are
+/// loaded with relaxed ordering and their archived type is an atomic as well.
+///
 /// # Example
 ///
 /// ```
@@ -327,6 +364,70 @@ pub struct Inline;
 #[derive(Debug)]
 pub struct AsBox;
 
+/// A wrapper that serializes a field out-of-line along with its size, so that
+/// fields can be appended to its type without breaking older readers.
+///
+/// A field archived with `Extensible` is archived as an
+/// [`ArchivedExtensible`](crate::extensible::ArchivedExtensible). Readers built
+/// against an older definition of the field type can still access archives
+/// written with a newer definition, as long as the only changes are fields
+/// appended to the end of a struct. The appended fields are ignored by older
+/// readers.
+///
+/// To make the root of an archive extensible, serialize it with
+/// [`With::cast`] and access it as an `ArchivedExtensible`.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::rancor::Error;
+///
+/// mod v1 {
+///     use rkyv::{with::Extensible, Archive, Serialize};
+///
+///     #[derive(Archive, Serialize)]
+///     pub struct Config {
+///         pub id: u32,
+///     }
+///
+///     #[derive(Archive, Serialize)]
+///     pub struct Message {
+///         #[rkyv(with = Extensible)]
+///         pub config: Config,
+///     }
+/// }
+///
+/// mod v2 {
+///     use rkyv::{with::Extensible, Archive, Serialize};
+///
+///     #[derive(Archive, Serialize)]
+///     pub struct Config {
+///         pub id: u32,
+///         pub name: String,
+///     }
+///
+///     #[derive(Archive, Serialize)]
+///     pub struct Message {
+///         #[rkyv(with = Extensible)]
+///         pub config: Config,
+///     }
+/// }
+///
+/// let message = v2::Message {
+///     config: v2::Config {
+///         id: 42,
+///         name: "new".to_string(),
+///     },
+/// };
+/// let bytes = rkyv::to_bytes::<Error>(&message).unwrap();
+///
+/// // A reader which only knows about `v1::Config` can still read the message.
+/// let archived = rkyv::access::<v1::ArchivedMessage, Error>(&bytes).unwrap();
+/// assert_eq!(archived.config.id, 42);
+/// ```
+#[derive(Debug)]
+pub struct Extensible;
+
 /// A wrapper that serializes a reference as if it were boxed.
 ///
 /// Unlike [`Inline`], unsized references can be serialized with `InlineAsBox`.
@@ -374,6 +475,110 @@ pub struct InlineAsBox;
 #[derive(Debug)]
 pub struct AsString;
 
+/// A wrapper that archives a string out-of-line, even if it is short enough to
+/// be inlined.
+///
+/// Archived strings usually store short strings inside the `ArchivedString`.
+/// With this wrapper, the string bytes are always written separately and the
+/// `ArchivedString` points to them, so the archived value has the same layout
+/// regardless of the string's length.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::OutOfLine, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = OutOfLine)]
+///     name: String,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct OutOfLine;
+
+/// A wrapper that archives a byte vector as an
+/// [`ArchivedByteString`](crate::string::byte_string::ArchivedByteString).
+///
+/// Byte strings have the same layout as archived byte vectors, but format
+/// their contents as byte string literals and can be compared directly against
+/// byte slices and arrays.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::AsByteString, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsByteString)]
+///     name: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsByteString;
+
+/// A wrapper that archives a byte buffer as an
+/// [`ArchivedBytes`](crate::bytes::ArchivedBytes) whose payload is aligned to
+/// `ALIGN` bytes.
+///
+/// This works for `Vec<u8>` and, with the `bytes-1` feature, `Bytes`. The
+/// payload is not validated element-wise, which makes this a good fit for large
+/// opaque blobs like images or serialized data.
+///
+/// `ALIGN` must be a power of two. The alignment is relative to the start of
+/// the archive, so the archive buffer must also be aligned to at least `ALIGN`
+/// bytes for the payload to be aligned in memory.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::AsBytes, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = AsBytes<64>)]
+///     image: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct AsBytes<const ALIGN: usize = 1>;
+
+/// A wrapper that pads the archive so that the contents of a byte buffer start
+/// on an `N`-byte boundary.
+///
+/// This works for `Vec<u8>` and `Box<[u8]>`. The archived type is unchanged, so
+/// the field has the same archived layout as it would without the wrapper. This
+/// is useful for byte buffers which are reinterpreted as slices of more
+/// strictly-aligned types, like `f32`.
+///
+/// `N` must be a power of two. The alignment is relative to the start of the
+/// archive, so the archive buffer must also be aligned to at least `N` bytes
+/// for the contents to be aligned in memory.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::Align, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = Align<4>)]
+///     samples: Vec<u8>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Align<const N: usize>;
+
+/// A wrapper that archives a set of bit flags as its raw bits.
+///
+/// This is implemented for types generated by the `bitflags!` macro when the
+/// `bitflags-2` feature is enabled. The archived type is an
+/// `ArchivedFlags`, which rejects bits that don't correspond to any flag during
+/// validation.
+#[derive(Debug)]
+pub struct AsBits;
+
 /// A wrapper that locks a lock and serializes the value immutably.
 ///
 /// This wrapper can panic under very specific circumstances when:
@@ -444,11 +649,206 @@ pub struct AsOwned;
 #[derive(Debug)]
 pub struct AsVec;
 
+/// A wrapper that archives vectors and strings with a varint-encoded length.
+///
+/// The archived types only store a relative pointer inline, and store their
+/// length at the start of their out-of-line data. This makes them smaller than
+/// [`ArchivedVec`](crate::vec::ArchivedVec) and
+/// [`ArchivedString`](crate::string::ArchivedString) at the cost of decoding
+/// the length on each access. See the [`compact`](crate::compact) module for
+/// more information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::Compact, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = Compact)]
+///     name: String,
+///     #[rkyv(with = Compact)]
+///     values: Vec<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Compact;
+
+/// A wrapper that archives vectors and strings with reserved capacity for
+/// `ADDITIONAL` more elements.
+///
+/// The archived types store a mutable length, and elements can be pushed into
+/// their reserved capacity through a [`Seal`](crate::seal::Seal) without
+/// serializing the archive again. Strings reserve capacity for `ADDITIONAL`
+/// more bytes. See the [`reserved`](crate::reserved) module for more
+/// information.
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{
+///     access_mut, munge::munge, rancor::Error, reserved::ArchivedReservedVec,
+///     to_bytes, with::Reserve, Archive, Serialize,
+/// };
+///
+/// #[derive(Archive, Serialize)]
+/// struct Log {
+///     #[rkyv(with = Reserve<16>)]
+///     events: Vec<u64>,
+/// }
+///
+/// let mut bytes = to_bytes::<Error>(&Log { events: vec![1, 2] }).unwrap();
+///
+/// let archived = access_mut::<ArchivedLog, Error>(&mut bytes).unwrap();
+/// munge!(let ArchivedLog { mut events } = archived);
+/// ArchivedReservedVec::push(events.as_mut(), 3.into()).unwrap();
+/// assert_eq!(events.as_slice(), [1u64, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct Reserve<const ADDITIONAL: usize>;
+
+/// A wrapper that archives fixed-capacity vectors with their elements stored
+/// inline.
+///
+/// This works for `ArrayVec<T, N>` with the `arrayvec-0_7` feature and
+/// `SmallVec<[T; N]>` with the `smallvec-1` feature, which are archived as an
+/// [`ArchivedInlineVec<T, N>`](crate::inline_vec::ArchivedInlineVec) instead of
+/// an out-of-line [`ArchivedVec`](crate::vec::ArchivedVec). Serializing a
+/// `SmallVec` which has spilled past its inline capacity fails. See the
+/// [`inline_vec`](crate::inline_vec) module for more information.
+#[derive(Debug)]
+pub struct InlineCapacity;
+
+/// A wrapper that serializes the entries of unordered collections in sorted
+/// order.
+///
+/// `HashMap` and `HashSet` normally serialize their entries in iteration order,
+/// which depends on their hasher state. Serializing the same logical value
+/// twice can produce different bytes. This wrapper sorts the entries by key
+/// before serializing them so that equal values always produce identical
+/// archives, which is useful for caching and signing.
+///
+/// The archived type is the same as without the wrapper. Sorting requires
+/// scratch space from the serializer's allocator.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::{HashMap, HashSet};
+///
+/// use rkyv::{with::Sorted, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = Sorted)]
+///     map: HashMap<String, u32>,
+///     #[rkyv(with = Sorted)]
+///     set: HashSet<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Sorted;
+
+/// A wrapper that archives hash maps and sets with the hasher `H`.
+///
+/// Archived hash maps and sets hash their keys with
+/// [`FxHasher64`](crate::hash::FxHasher64) by default. This wrapper archives
+/// them as an [`ArchivedHashMap`] or [`ArchivedHashSet`] which hashes its keys
+/// with `H` instead. Lookups hash keys with the same hasher, so a faster
+/// hasher speeds up every lookup on the archived map.
+///
+/// Hashes are computed when serializing and again when accessing, possibly on
+/// a different machine. `H` must produce the same hashes on every platform and
+/// in every process, so it must not be randomly seeded when it is created with
+/// `Default`.
+///
+/// # Example
+///
+/// ```
+/// use std::{collections::HashMap, hash::Hasher};
+///
+/// use rkyv::{
+///     access, rancor::Error, to_bytes, with::HashWith, Archive, Serialize,
+/// };
+///
+/// /// The 64-bit FNV-1a hash.
+/// struct Fnv(u64);
+///
+/// impl Default for Fnv {
+///     fn default() -> Self {
+///         Self(0xcbf29ce484222325)
+///     }
+/// }
+///
+/// impl Hasher for Fnv {
+///     fn finish(&self) -> u64 {
+///         self.0
+///     }
+///
+///     fn write(&mut self, bytes: &[u8]) {
+///         for byte in bytes {
+///             self.0 = (self.0 ^ *byte as u64).wrapping_mul(0x100000001b3);
+///         }
+///     }
+/// }
+///
+/// #[derive(Archive, Serialize)]
+/// struct Example {
+///     #[rkyv(with = HashWith<Fnv>)]
+///     values: HashMap<String, u32>,
+/// }
+///
+/// let mut values = HashMap::new();
+/// values.insert("a".to_string(), 1);
+/// values.insert("b".to_string(), 2);
+/// let bytes = to_bytes::<Error>(&Example { values }).unwrap();
+///
+/// let archived = access::<ArchivedExample, Error>(&bytes).unwrap();
+/// assert_eq!(archived.values.get("b"), Some(&2));
+/// ```
+///
+/// [`ArchivedHashMap`]: crate::collections::swiss_table::ArchivedHashMap
+/// [`ArchivedHashSet`]: crate::collections::swiss_table::ArchivedHashSet
+pub struct HashWith<H>(PhantomData<H>);
+
+/// A wrapper that archives hash maps and sets with a load factor of
+/// `NUMERATOR / DENOMINATOR`.
+///
+/// Archived hash maps and sets are sized so that they are at most 7/8 full by
+/// default. A lower load factor makes the archived table larger, but shortens
+/// the probe sequences of lookups. The load factor must be greater than zero
+/// and at most one, otherwise serialization fails.
+///
+/// The archived type is the same as without the wrapper.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::{HashMap, HashSet};
+///
+/// use rkyv::{with::LoadFactor, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     // At most half full
+///     #[rkyv(with = LoadFactor<1, 2>)]
+///     map: HashMap<String, u32>,
+///     #[rkyv(with = LoadFactor<1, 2>)]
+///     set: HashSet<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct LoadFactor<const NUMERATOR: usize, const DENOMINATOR: usize>;
+
 /// A wrapper that niches some type combinations.
 ///
 /// A common type combination is `Option<Box<T>>`. By using a null pointer, the
 /// archived version can save some space on-disk.
 ///
+/// The derive macros apply this wrapper automatically to fields of type
+/// `Option<Box<T>>` and `Option` of the `NonZero` integers. Use
+/// `#[rkyv(no_auto_niche)]` on a field or type to keep the unniched layout.
+///
 /// # Example
 ///
 /// ```
@@ -457,6 +857,7 @@ pub struct AsVec;
 /// use rkyv::{with::Niche, Archive, Archived};
 ///
 /// #[derive(Archive)]
+/// #[rkyv(no_auto_niche)]
 /// struct BasicExample {
 ///     value: Option<Box<str>>,
 /// }
@@ -492,6 +893,7 @@ pub struct Niche;
 /// };
 ///
 /// #[derive(Archive)]
+/// #[rkyv(no_auto_niche)]
 /// struct BasicExample {
 ///     maybe_box: Option<Box<str>>,
 ///     maybe_non_nan: Option<f32>,
@@ -592,6 +994,10 @@ impl<W: ?Sized, N: ?Sized> fmt::Debug for MapNiche<W, N> {
 /// [`ArchivedDuration`](crate::time::ArchivedDuration) relative to the UNIX
 /// epoch.
 ///
+/// `SystemTime` also implements `Archive` directly as an
+/// [`ArchivedSystemTime`](crate::time::ArchivedSystemTime), which supports
+/// times before the UNIX epoch.
+///
 /// # Example
 ///
 /// ```
@@ -642,7 +1048,9 @@ pub struct Unsafe;
 
 /// A wrapper that skips serializing a field.
 ///
-/// Skipped fields must implement `Default` to be deserialized.
+/// Skipped fields must implement `Default` to be deserialized. The derive macro
+/// applies this wrapper to fields marked with `#[rkyv(skip)]`, which can also
+/// provide a function to create the field with `default = ..`.
 ///
 /// # Example
 ///
@@ -658,10 +1066,59 @@ pub struct Unsafe;
 #[derive(Debug)]
 pub struct Skip;
 
+/// A wrapper that deserializes `Box`es, `Vec`s, and `String`s with memory from
+/// an [`Arena`](crate::de::Arena).
+///
+/// Fields archived with `InArena` have the same archived type as they would
+/// without it. The deserializer must provide an arena, so these fields can be
+/// deserialized with
+/// [`deserialize_in_arena`](crate::api::high::deserialize_in_arena) but not
+/// with [`deserialize`](crate::api::high::deserialize).
+///
+/// # Example
+///
+/// ```
+/// use rkyv::{with::InArena, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = InArena)]
+///     name: String,
+///     #[rkyv(with = InArena)]
+///     values: Vec<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct InArena;
+
 /// A wrapper that clones the contents of `Arc` and `Rc` pointers.
 #[derive(Debug)]
 pub struct Unshare;
 
+/// A wrapper that archives `Weak` pointers as null when they point back to a
+/// value which is still being serialized.
+///
+/// By default, serializing a weak pointer to one of its ancestors (like a
+/// child's link to its parent) fails because archived pointers can only point
+/// to data which has already been written. With `BreakCycles`, these weak
+/// pointers are archived as null and deserialize to an empty `Weak`.
+///
+/// # Example
+///
+/// ```
+/// use std::rc::Weak;
+///
+/// use rkyv::{with::BreakCycles, Archive};
+///
+/// #[derive(Archive)]
+/// struct Example {
+///     #[rkyv(with = BreakCycles)]
+///     parent: Weak<u32>,
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BreakCycles;
+
 /// A no-op wrapper which uses the default impls for the type.
 ///
 /// This is most useful for wrappers like [`MapKV`] when you only want to apply
@@ -317,6 +317,58 @@ fn unnamed_struct_private() {
     roundtrip::<ExampleThroughRef, _>(&remote);
 }
 
+#[test]
+fn nested_remote() {
+    mod remote {
+        #[derive(Debug, PartialEq)]
+        pub struct Inner {
+            pub value: u32,
+        }
+
+        #[derive(Debug, PartialEq)]
+        pub struct Outer {
+            pub first: Inner,
+            pub second: Option<Inner>,
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[rkyv(remote = remote::Inner)]
+    struct InnerDef {
+        value: u32,
+    }
+
+    impl From<InnerDef> for remote::Inner {
+        fn from(value: InnerDef) -> Self {
+            remote::Inner { value: value.value }
+        }
+    }
+
+    #[derive(Archive, Serialize, Deserialize)]
+    #[rkyv(remote = remote::Outer)]
+    struct OuterDef {
+        #[rkyv(with = InnerDef)]
+        first: remote::Inner,
+        #[rkyv(with = Map<InnerDef>)]
+        second: Option<remote::Inner>,
+    }
+
+    impl From<OuterDef> for remote::Outer {
+        fn from(value: OuterDef) -> Self {
+            remote::Outer {
+                first: value.first,
+                second: value.second,
+            }
+        }
+    }
+
+    let remote = remote::Outer {
+        first: remote::Inner { value: 1 },
+        second: Some(remote::Inner { value: 2 }),
+    };
+    roundtrip::<OuterDef, _>(&remote);
+}
+
 #[cfg(feature = "bytecheck")]
 pub trait CheckedArchived:
     for<'a> rkyv::bytecheck::CheckBytes<rkyv::api::low::LowValidator<'a, Panic>>
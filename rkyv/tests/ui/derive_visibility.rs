@@ -14,6 +14,16 @@ mod inner {
         B(i32),
         C { value: i32 },
     }
+
+    #[derive(Archive, Serialize)]
+    #[rkyv(archived = Wire, resolver = WireParts, vis = pub)]
+    struct TestRenamed {
+        pub value: i32,
+    }
+}
+
+mod archived {
+    pub use super::inner::Wire as TestRenamed;
 }
 
 use inner::{
@@ -30,4 +40,6 @@ fn main() {
     TestEnum::C { value: 42.into() };
     ArchivedTestEnum::B(42.into());
     ArchivedTestEnum::C { value: 42.into() };
+    archived::TestRenamed { value: 42.into() };
+    let _ = core::mem::size_of::<inner::WireParts>();
 }
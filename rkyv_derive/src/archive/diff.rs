@@ -0,0 +1,172 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, LitStr, Member};
+
+use crate::{
+    archive::printing::Printing,
+    attributes::{Attributes, FieldAttributes},
+    util::{iter_fields, strip_raw},
+};
+
+fn member_name(member: &Member) -> LitStr {
+    match member {
+        Member::Named(ident) => LitStr::new(&strip_raw(ident), ident.span()),
+        Member::Unnamed(index) => {
+            LitStr::new(&index.index.to_string(), index.span)
+        }
+    }
+}
+
+/// Returns a statement comparing each field which is not skipped.
+///
+/// `left` and `right` return expressions which evaluate to references to the
+/// field with the given index in each value.
+fn diff_fields(
+    attributes: &Attributes,
+    fields: &Fields,
+    left: impl Fn(usize, &Member) -> TokenStream,
+    right: impl Fn(usize, &Member) -> TokenStream,
+) -> Result<TokenStream, Error> {
+    let mut result = TokenStream::new();
+    for (i, (field, member)) in fields.iter().zip(fields.members()).enumerate()
+    {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some() {
+            continue;
+        }
+
+        let name = member_name(&member);
+        let left = left(i, &member);
+        let right = right(i, &member);
+        result.extend(quote! {
+            differ.field(#name, #left, #right);
+        });
+    }
+    Ok(result)
+}
+
+/// Returns a pattern which binds each field which is not skipped to an
+/// identifier with the given prefix.
+fn variant_pattern(
+    attributes: &Attributes,
+    fields: &Fields,
+    prefix: &str,
+) -> Result<TokenStream, Error> {
+    let mut bindings = Vec::new();
+    for (i, (field, member)) in fields.iter().zip(fields.members()).enumerate()
+    {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        let binding = format_ident!("{}_{}", prefix, i);
+        bindings.push(match (field_attrs.skip.is_some(), member) {
+            (true, Member::Named(ident)) => quote! { #ident: _ },
+            (false, Member::Named(ident)) => quote! { #ident: #binding },
+            (true, Member::Unnamed(_)) => quote! { _ },
+            (false, Member::Unnamed(_)) => quote! { #binding },
+        });
+    }
+
+    Ok(match fields {
+        Fields::Named(_) => quote! { { #(#bindings,)* } },
+        Fields::Unnamed(_) => quote! { ( #(#bindings,)* ) },
+        Fields::Unit => quote! {},
+    })
+}
+
+pub fn impl_diff(
+    input: &DeriveInput,
+    printing: &Printing,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in iter_fields(&input.data) {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some() || field_attrs.omit_bounds.is_some() {
+            continue;
+        }
+
+        let archived = field_attrs.archived(rkyv_path, field);
+        where_clause.predicates.push(parse_quote! {
+            #archived: #rkyv_path::diff::Diff
+        });
+    }
+
+    let body = match input.data {
+        Data::Struct(ref data) => diff_fields(
+            attributes,
+            &data.fields,
+            |_, member| quote! { &self.#member },
+            |_, member| quote! { &other.#member },
+        )?,
+        Data::Enum(ref data) => {
+            let mut arms = TokenStream::new();
+            for variant in data.variants.iter() {
+                let variant_name = &variant.ident;
+                let fields = &variant.fields;
+                let left = variant_pattern(attributes, fields, "left")?;
+                let right = variant_pattern(attributes, fields, "right")?;
+                let diff = diff_fields(
+                    attributes,
+                    fields,
+                    |i, _| {
+                        let binding = format_ident!("left_{}", i);
+                        quote! { #binding }
+                    },
+                    |i, _| {
+                        let binding = format_ident!("right_{}", i);
+                        quote! { #binding }
+                    },
+                )?;
+                arms.extend(quote! {
+                    (
+                        Self::#variant_name #left,
+                        Self::#variant_name #right,
+                    ) => {
+                        #diff
+                    }
+                });
+            }
+
+            quote! {
+                match (self, other) {
+                    #arms
+                    #[allow(unreachable_patterns)]
+                    _ => differ.report(
+                        ::core::option::Option::Some(self),
+                        ::core::option::Option::Some(other),
+                    ),
+                }
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    // Structs without any compared fields don't use the other value or the
+    // differ.
+    let (other, differ) = if body.is_empty() {
+        (quote! { _ }, quote! { _ })
+    } else {
+        (quote! { other }, quote! { differ })
+    };
+
+    let archived_name = &printing.archived_name;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::diff::Diff
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            fn diff<'__a>(
+                &'__a self,
+                #other: &'__a Self,
+                #differ: &mut #rkyv_path::diff::Differ<'__a>,
+            ) {
+                #body
+            }
+        }
+    })
+}
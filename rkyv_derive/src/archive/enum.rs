@@ -1,4 +1,4 @@
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Literal, Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{
     parse_quote, spanned::Spanned as _, DataEnum, Error, Field, Fields,
@@ -8,7 +8,7 @@ use syn::{
 use crate::{
     archive::{
         archived_doc, printing::Printing, resolver_doc, resolver_variant_doc,
-        variant_doc,
+        schema, variant_doc,
     },
     attributes::{Attributes, FieldAttributes},
     util::{strip_generics_from_path, strip_raw},
@@ -28,51 +28,110 @@ pub fn impl_enum(
         ..
     } = printing;
 
-    if data.variants.len() > 256 {
+    if let Some(ref accessors) = attributes.accessors {
         return Err(Error::new_spanned(
-            &printing.name,
-            "enums with more than 256 variants cannot derive Archive",
+            accessors,
+            "`accessors` is only supported on structs with named fields",
+        ));
+    }
+
+    if let Some(ref deserialize_fields) = attributes.deserialize_fields {
+        return Err(Error::new_spanned(
+            deserialize_fields,
+            "`deserialize_fields` is only supported on structs with named \
+             fields",
         ));
     }
 
+    let tag = tag_type(printing, attributes, data)?;
+    let discriminants = archived_discriminants(rkyv_path, &tag, data);
+
+    for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
+        if let Some(since) = FieldAttributes::parse(attributes, field)?.since {
+            return Err(Error::new_spanned(
+                since,
+                "`since` is only supported on struct fields",
+            ));
+        }
+    }
+
     let mut public = TokenStream::new();
     let mut private = TokenStream::new();
 
     if attributes.as_type.is_none() {
         public.extend(generate_archived_type(
-            printing, attributes, generics, data,
+            printing,
+            attributes,
+            generics,
+            data,
+            &tag,
+            &discriminants,
         )?);
 
         private.extend(generate_niching_impls(
             printing, attributes, generics, data,
         )?);
+
+        if attributes.sealed_variants.is_some() {
+            public.extend(generate_sealed_variants(
+                printing, attributes, generics, data,
+            )?);
+        }
     }
 
     public.extend(generate_resolver_type(
         printing, attributes, generics, data,
     )?);
 
-    let archived_variant_tags = data.variants.iter().map(|variant| {
-        let ident = &variant.ident;
-        let (eq, expr) = variant
-            .discriminant
-            .as_ref()
-            .map(|(eq, expr)| (eq, expr))
-            .unzip();
-        quote! { #ident #eq #expr }
-    });
-    private.extend(quote! {
-        #[derive(PartialEq, PartialOrd)]
-        #[repr(u8)]
-        enum ArchivedTag {
-            #(#archived_variant_tags,)*
-        }
-    });
+    let archived_variant_tags = data.variants.iter().zip(&discriminants).map(
+        |(variant, discriminant)| {
+            let ident = &variant.ident;
+            quote! { #ident #discriminant }
+        },
+    );
+    if tag == "u8" {
+        private.extend(quote! {
+            #[derive(PartialEq, PartialOrd)]
+            #[repr(u8)]
+            enum ArchivedTag {
+                #(#archived_variant_tags,)*
+            }
+        });
+    } else {
+        // Multibyte tags are stored in the byte order of the archive, so they
+        // have to be converted back to native values before being ordered.
+        let convert = format_ident!("archived_tag_{}", tag);
+        private.extend(quote! {
+            #[derive(Clone, Copy, PartialEq)]
+            #[repr(#tag)]
+            enum ArchivedTag {
+                #(#archived_variant_tags,)*
+            }
+
+            impl PartialOrd for ArchivedTag {
+                fn partial_cmp(
+                    &self,
+                    other: &Self,
+                ) -> Option<::core::cmp::Ordering> {
+                    let this = #rkyv_path::primitive::#convert(*self as #tag);
+                    let other =
+                        #rkyv_path::primitive::#convert(*other as #tag);
+                    this.partial_cmp(&other)
+                }
+            }
+        });
+    }
 
     private.extend(generate_variant_structs(
         printing, attributes, generics, data,
     )?);
 
+    if attributes.schema.is_some() && attributes.as_type.is_none() {
+        private.extend(schema::impl_enum_schema(
+            printing, generics, attributes, data, &tag,
+        )?);
+    }
+
     if let Some(ref compares) = attributes.compares {
         for compare in compares {
             if compare.is_ident("PartialEq") {
@@ -87,7 +146,7 @@ pub fn impl_enum(
                 return Err(Error::new_spanned(
                     compare,
                     "unrecognized compare argument, supported compares are \
-                     PartialEq (PartialOrd is not supported for enums)",
+                     PartialEq and PartialOrd",
                 ));
             }
         }
@@ -171,11 +230,94 @@ pub fn impl_enum(
     })
 }
 
+/// Returns the primitive type used for the tag of the archived enum.
+///
+/// Enums use a `u8` tag unless a different one is specified with
+/// `#[rkyv(tag = ...)]` or they have more than 256 variants.
+fn tag_type(
+    printing: &Printing,
+    attributes: &Attributes,
+    data: &DataEnum,
+) -> Result<Ident, Error> {
+    let variants = data.variants.len();
+    let tag = match attributes.tag {
+        Some(ref tag) => tag.clone(),
+        None if variants <= 1 << 8 => Ident::new("u8", Span::call_site()),
+        None => Ident::new("u16", Span::call_site()),
+    };
+
+    let max_variants: u64 = if tag == "u8" {
+        1 << 8
+    } else if tag == "u16" {
+        1 << 16
+    } else {
+        1 << 32
+    };
+    if variants as u64 > max_variants {
+        return Err(Error::new_spanned(
+            &printing.name,
+            format!(
+                "enums with more than {} variants cannot derive Archive with \
+                 a `{}` tag",
+                max_variants, tag,
+            ),
+        ));
+    }
+
+    Ok(tag)
+}
+
+/// Returns the discriminant of each archived variant, including the leading
+/// `=`.
+///
+/// `u8` tags use the discriminants of the original enum as-is. Multibyte tags
+/// spell out every discriminant so that it can be converted to the byte order
+/// of the archive.
+fn archived_discriminants(
+    rkyv_path: &Path,
+    tag: &Ident,
+    data: &DataEnum,
+) -> Vec<TokenStream> {
+    if tag == "u8" {
+        return data
+            .variants
+            .iter()
+            .map(|variant| match variant.discriminant {
+                Some((ref eq, ref expr)) => quote! { #eq #expr },
+                None => TokenStream::new(),
+            })
+            .collect();
+    }
+
+    let convert = format_ident!("archived_tag_{}", tag);
+    let mut base = None;
+    let mut offset = 0usize;
+    data.variants
+        .iter()
+        .map(|variant| {
+            if let Some((_, ref expr)) = variant.discriminant {
+                base = Some(expr);
+                offset = 0;
+            }
+            let offset_lit = Literal::usize_unsuffixed(offset);
+            let value = match base {
+                Some(expr) if offset == 0 => quote! { #expr },
+                Some(expr) => quote! { (#expr) + #offset_lit },
+                None => quote! { #offset_lit },
+            };
+            offset += 1;
+            quote! { = #rkyv_path::primitive::#convert(#value) }
+        })
+        .collect()
+}
+
 fn generate_archived_type(
     printing: &Printing,
     attributes: &Attributes,
     generics: &Generics,
     data: &DataEnum,
+    tag: &Ident,
+    discriminants: &[TokenStream],
 ) -> Result<TokenStream, Error> {
     let Printing {
         rkyv_path,
@@ -187,13 +329,8 @@ fn generate_archived_type(
     } = printing;
 
     let mut archived_variants = TokenStream::new();
-    for variant in &data.variants {
+    for (variant, discriminant) in data.variants.iter().zip(discriminants) {
         let variant_name = &variant.ident;
-        let (eq, expr) = variant
-            .discriminant
-            .as_ref()
-            .map(|(eq, expr)| (eq, expr))
-            .unzip();
 
         let variant_doc = variant_doc(name, variant_name);
 
@@ -221,17 +358,17 @@ fn generate_archived_type(
                 #[allow(dead_code)]
                 #variant_name {
                     #variant_fields
-                } #eq #expr,
+                } #discriminant,
             },
             Fields::Unnamed(_) => quote! {
                 #[doc = #variant_doc]
                 #[allow(dead_code)]
-                #variant_name(#variant_fields) #eq #expr,
+                #variant_name(#variant_fields) #discriminant,
             },
             Fields::Unit => quote! {
                 #[doc = #variant_doc]
                 #[allow(dead_code)]
-                #variant_name #eq #expr,
+                #variant_name #discriminant,
             },
         });
     }
@@ -242,13 +379,130 @@ fn generate_archived_type(
         #[automatically_derived]
         #[doc = #archived_doc]
         #(#[#archived_metas])*
-        #[repr(u8)]
+        #[repr(#tag)]
         #vis enum #archived_name #generics #where_clause {
             #archived_variants
         }
     })
 }
 
+fn generate_sealed_variants(
+    printing: &Printing,
+    attributes: &Attributes,
+    generics: &Generics,
+    data: &DataEnum,
+) -> Result<TokenStream, Error> {
+    let Printing {
+        rkyv_path,
+        vis,
+        archived_name,
+        ..
+    } = printing;
+
+    let sealed_name = format_ident!("{}Sealed", strip_raw(archived_name));
+
+    let mut sealed_variants = TokenStream::new();
+    let mut match_arms = TokenStream::new();
+    for variant in data.variants.iter() {
+        let variant_name = &variant.ident;
+        let variant_doc = format!(
+            "A sealed [`{}::{}`]",
+            archived_name,
+            strip_raw(variant_name),
+        );
+
+        let mut variant_fields = TokenStream::new();
+        let mut bindings = Vec::new();
+        for (i, field) in variant.fields.iter().enumerate() {
+            let field_attrs = FieldAttributes::parse(attributes, field)?;
+            let field_ty = field_attrs.archived(rkyv_path, field);
+            let ident = &field.ident;
+            let colon_token = &field.colon_token;
+            variant_fields.extend(quote! {
+                #ident #colon_token #rkyv_path::seal::Seal<'__a, #field_ty>,
+            });
+            bindings.push(match field.ident {
+                Some(ref ident) => ident.clone(),
+                None => format_ident!("__{}", i),
+            });
+        }
+
+        let (variant, arm) = match variant.fields {
+            Fields::Named(_) => (
+                quote! { #variant_name { #variant_fields } },
+                quote! {
+                    #archived_name::#variant_name { #(#bindings,)* } =>
+                        #sealed_name::#variant_name {
+                            #(
+                                #bindings:
+                                    #rkyv_path::seal::Seal::new(#bindings),
+                            )*
+                        },
+                },
+            ),
+            Fields::Unnamed(_) => (
+                quote! { #variant_name(#variant_fields) },
+                quote! {
+                    #archived_name::#variant_name(#(#bindings,)*) =>
+                        #sealed_name::#variant_name(
+                            #(#rkyv_path::seal::Seal::new(#bindings),)*
+                        ),
+                },
+            ),
+            Fields::Unit => (
+                quote! { #variant_name },
+                quote! {
+                    #archived_name::#variant_name =>
+                        #sealed_name::#variant_name,
+                },
+            ),
+        };
+        sealed_variants.extend(quote! {
+            #[doc = #variant_doc]
+            #variant,
+        });
+        match_arms.extend(arm);
+    }
+
+    let mut sealed_generics = generics.clone();
+    sealed_generics.params.insert(0, parse_quote! { '__a });
+    let (_, sealed_ty_generics, _) = sealed_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let sealed_doc = format!(
+        "The variants of a sealed [`{}`], returned from `as_sealed_variants`",
+        archived_name,
+    );
+    Ok(quote! {
+        #[automatically_derived]
+        #[doc = #sealed_doc]
+        #[allow(dead_code)]
+        #vis enum #sealed_name #sealed_generics #where_clause {
+            #sealed_variants
+        }
+
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #impl_generics #archived_name #ty_generics #where_clause {
+            /// Matches on a sealed archived enum, returning each of the fields
+            /// of its variant as a sealed reference.
+            #[inline]
+            #vis fn as_sealed_variants<'__a>(
+                this: #rkyv_path::seal::Seal<'__a, Self>,
+            ) -> #sealed_name #sealed_ty_generics {
+                // SAFETY: The variant of the enum is not changed, and each of
+                // its fields is immediately sealed again.
+                let this = unsafe {
+                    #rkyv_path::seal::Seal::unseal_unchecked(this)
+                };
+                match this {
+                    #match_arms
+                }
+            }
+        }
+    })
+}
+
 fn generate_resolver_type(
     printing: &Printing,
     attributes: &Attributes,
@@ -461,8 +715,8 @@ fn generate_resolve_arms(
                     let out = unsafe {
                         out.cast_unchecked::<ArchivedTag>()
                     };
-                    // SAFETY: `ArchivedTag` is `repr(u8)` and so is always
-                    // initialized.
+                    // SAFETY: `ArchivedTag` has a primitive repr and so is
+                    // always initialized.
                     unsafe {
                         out.write_unchecked(ArchivedTag::#variant_name);
                     }
@@ -548,7 +802,7 @@ fn generate_partial_eq_impl(
 
     for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
         let field_attrs = FieldAttributes::parse(attributes, field)?;
-        if field_attrs.omit_bounds.is_none() {
+        if field_attrs.omit_bounds.is_none() && field_attrs.is_compared() {
             let field_attrs = FieldAttributes::parse(attributes, field)?;
             let ty = &field.ty;
             let archived = field_attrs.archived(&printing.rkyv_path, field);
@@ -558,20 +812,20 @@ fn generate_partial_eq_impl(
         }
     }
 
-    let variant_impls = data.variants.iter().map(|v| {
-        let variant = &v.ident;
+    let bindings = data
+        .variants
+        .iter()
+        .map(|v| VariantBindings::new(attributes, &v.fields))
+        .collect::<Result<Vec<_>, Error>>()?;
 
-        let (self_fields, other_fields) = v
-            .fields
-            .iter()
-            .enumerate()
-            .map(|(i, f)| {
-                (
-                    Ident::new(&format!("self_{}", i), f.span()),
-                    Ident::new(&format!("other_{}", i), f.span()),
-                )
-            })
-            .unzip::<_, _, Vec<_>, Vec<_>>();
+    let variant_impls = data.variants.iter().zip(&bindings).map(|(v, b)| {
+        let variant = &v.ident;
+        let VariantBindings {
+            self_patterns,
+            other_patterns,
+            self_fields,
+            other_fields,
+        } = b;
 
         match v.fields {
             Fields::Named(ref fields) => {
@@ -580,10 +834,10 @@ fn generate_partial_eq_impl(
 
                 quote! {
                     #name::#variant {
-                        #(#field_names: #self_fields,)*
+                        #(#field_names: #self_patterns,)*
                     } => match other {
                         #archived_name::#variant {
-                            #(#field_names: #other_fields,)*
+                            #(#field_names: #other_patterns,)*
                         } => true #(&& #other_fields.eq(#self_fields))*,
                         #[allow(unreachable_patterns)]
                         _ => false,
@@ -592,8 +846,8 @@ fn generate_partial_eq_impl(
             }
             Fields::Unnamed(_) => {
                 quote! {
-                    #name::#variant(#(#self_fields,)*) => match other {
-                        #archived_name::#variant(#(#other_fields,)*) => {
+                    #name::#variant(#(#self_patterns,)*) => match other {
+                        #archived_name::#variant(#(#other_patterns,)*) => {
                             true #(&& #other_fields.eq(#self_fields))*
                         }
                         #[allow(unreachable_patterns)]
@@ -649,7 +903,7 @@ fn generate_partial_ord_impl(
 
     for field in data.variants.iter().flat_map(|v| v.fields.iter()) {
         let field_attrs = FieldAttributes::parse(attributes, field)?;
-        if field_attrs.omit_bounds.is_none() {
+        if field_attrs.omit_bounds.is_none() && field_attrs.is_compared() {
             let ty = &field.ty;
             let archived = field_attrs.archived(&printing.rkyv_path, field);
             where_clause
@@ -687,20 +941,20 @@ fn generate_partial_ord_impl(
         }
     });
 
-    let variant_impls = data.variants.iter().map(|v| {
-        let variant = &v.ident;
+    let bindings = data
+        .variants
+        .iter()
+        .map(|v| VariantBindings::new(attributes, &v.fields))
+        .collect::<Result<Vec<_>, Error>>()?;
 
-        let (self_fields, other_fields) = v
-            .fields
-            .iter()
-            .enumerate()
-            .map(|(i, f)| {
-                (
-                    Ident::new(&format!("self_{}", i), f.span()),
-                    Ident::new(&format!("other_{}", i), f.span()),
-                )
-            })
-            .unzip::<_, _, Vec<_>, Vec<_>>();
+    let variant_impls = data.variants.iter().zip(&bindings).map(|(v, b)| {
+        let variant = &v.ident;
+        let VariantBindings {
+            self_patterns,
+            other_patterns,
+            self_fields,
+            other_fields,
+        } = b;
 
         match v.fields {
             Fields::Named(ref fields) => {
@@ -709,10 +963,10 @@ fn generate_partial_ord_impl(
 
                 quote! {
                     #name::#variant {
-                        #(#field_names: #self_fields,)*
+                        #(#field_names: #self_patterns,)*
                     } => match other {
                         #archived_name::#variant {
-                            #(#field_names: #other_fields,)*
+                            #(#field_names: #other_patterns,)*
                         } => {
                             #(
                                 match #other_fields.partial_cmp(#self_fields) {
@@ -731,8 +985,8 @@ fn generate_partial_ord_impl(
             }
             Fields::Unnamed(_) => {
                 quote! {
-                    #name::#variant(#(#self_fields,)*) => match other {
-                        #archived_name::#variant(#(#other_fields,)*) => {
+                    #name::#variant(#(#self_patterns,)*) => match other {
+                        #archived_name::#variant(#(#other_patterns,)*) => {
                             #(
                                 match #other_fields.partial_cmp(#self_fields) {
                                     Some(::core::cmp::Ordering::Equal) => (),
@@ -945,3 +1199,41 @@ fn generate_niching_impls(
 
     Ok(result)
 }
+
+/// The bindings used to compare the fields of a variant with the fields of its
+/// archived counterpart. Fields which are not compared are bound to `_`.
+struct VariantBindings {
+    self_patterns: Vec<TokenStream>,
+    other_patterns: Vec<TokenStream>,
+    self_fields: Vec<Ident>,
+    other_fields: Vec<Ident>,
+}
+
+impl VariantBindings {
+    fn new(attributes: &Attributes, fields: &Fields) -> Result<Self, Error> {
+        let mut result = Self {
+            self_patterns: Vec::new(),
+            other_patterns: Vec::new(),
+            self_fields: Vec::new(),
+            other_fields: Vec::new(),
+        };
+
+        for (i, field) in fields.iter().enumerate() {
+            if FieldAttributes::parse(attributes, field)?.is_compared() {
+                let self_field =
+                    Ident::new(&format!("self_{}", i), field.span());
+                let other_field =
+                    Ident::new(&format!("other_{}", i), field.span());
+                result.self_patterns.push(quote! { #self_field });
+                result.other_patterns.push(quote! { #other_field });
+                result.self_fields.push(self_field);
+                result.other_fields.push(other_field);
+            } else {
+                result.self_patterns.push(quote! { _ });
+                result.other_patterns.push(quote! { _ });
+            }
+        }
+
+        Ok(result)
+    }
+}
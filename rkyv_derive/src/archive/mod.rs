@@ -1,5 +1,9 @@
+mod diff;
 mod r#enum;
+mod patch;
 pub mod printing;
+mod schema;
+mod serde_impls;
 mod r#struct;
 
 use proc_macro2::{Span, TokenStream};
@@ -71,6 +75,20 @@ fn derive_archive_impl(
     if attributes.as_type.is_none() {
         result
             .extend(impl_auto_trait(input, &printing, attributes, "Portable")?);
+
+        if attributes.serde.is_some() {
+            result.extend(serde_impls::impl_serialize(
+                input, &printing, attributes,
+            )?);
+        }
+
+        if attributes.diff.is_some() {
+            result.extend(diff::impl_diff(input, &printing, attributes)?);
+        }
+
+        if attributes.patch.is_some() {
+            result.extend(patch::impl_patch(input, &printing, attributes)?);
+        }
     }
 
     Ok(result)
@@ -0,0 +1,164 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields};
+
+use crate::{
+    archive::printing::Printing,
+    attributes::{Attributes, FieldAttributes},
+    util::{strip_generics_from_path, strip_raw},
+};
+
+pub fn impl_patch(
+    input: &DeriveInput,
+    printing: &Printing,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let patch = attributes.patch.as_ref().unwrap();
+    match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(_) => {
+                impl_struct_patch(input, printing, attributes, &data.fields)
+            }
+            _ => Err(Error::new_spanned(
+                patch,
+                "`patch` is only supported on structs with named fields",
+            )),
+        },
+        Data::Enum(ref data) => {
+            if data
+                .variants
+                .iter()
+                .any(|variant| !matches!(variant.fields, Fields::Unit))
+            {
+                return Err(Error::new_spanned(
+                    patch,
+                    "`patch` is only supported on enums without fields",
+                ));
+            }
+            Ok(impl_enum_patch(input, printing, attributes, data))
+        }
+        Data::Union(_) => unreachable!(),
+    }
+}
+
+fn impl_struct_patch(
+    input: &DeriveInput,
+    printing: &Printing,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let Printing {
+        rkyv_path,
+        vis,
+        archived_name,
+        ..
+    } = printing;
+
+    let mut methods = TokenStream::new();
+    for field in fields {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some() {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let name = strip_raw(ident);
+        let seal = format_ident!("{}_seal", name);
+        let set = format_ident!("set_{}", name);
+        let archived = field_attrs.archived(rkyv_path, field);
+
+        let seal_doc = format!(
+            "Projects a sealed archived value to its `{}` field.",
+            name
+        );
+        let set_doc = format!(
+            "Assigns a new value to the `{}` field of a sealed archived value.",
+            name,
+        );
+        methods.extend(quote! {
+            #[doc = #seal_doc]
+            #[inline]
+            #vis fn #seal(
+                this: #rkyv_path::seal::Seal<'_, Self>,
+            ) -> #rkyv_path::seal::Seal<'_, #archived> {
+                // SAFETY: The field is immediately sealed again.
+                let this = unsafe {
+                    #rkyv_path::seal::Seal::unseal_unchecked(this)
+                };
+                #rkyv_path::seal::Seal::new(&mut this.#ident)
+            }
+
+            #[doc = #set_doc]
+            #[inline]
+            #vis fn #set<__V>(
+                this: #rkyv_path::seal::Seal<'_, Self>,
+                value: __V,
+            )
+            where
+                #archived: #rkyv_path::patch::Assign<__V>,
+            {
+                <#archived as #rkyv_path::patch::Assign<__V>>::assign(
+                    Self::#seal(this),
+                    value,
+                );
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #impl_generics #archived_name #ty_generics #where_clause {
+            #methods
+        }
+    })
+}
+
+fn impl_enum_patch(
+    input: &DeriveInput,
+    printing: &Printing,
+    attributes: &Attributes,
+    data: &DataEnum,
+) -> TokenStream {
+    let Printing {
+        rkyv_path,
+        name,
+        archived_name,
+        ..
+    } = printing;
+
+    let (impl_generics, ty_generics, where_clause) =
+        input.generics.split_for_impl();
+    let (native, native_path) = match attributes.remote {
+        Some(ref remote) => {
+            let path = strip_generics_from_path(remote.clone());
+            (quote! { #remote }, quote! { #path })
+        }
+        None => (quote! { #name #ty_generics }, quote! { #name }),
+    };
+    let variants = data.variants.iter().map(|variant| &variant.ident);
+
+    quote! {
+        // SAFETY: The archived enum has no fields, so it consists of only its
+        // tag which is always initialized.
+        unsafe impl #impl_generics #rkyv_path::traits::NoUndef
+            for #archived_name #ty_generics
+        #where_clause
+        {}
+
+        #[automatically_derived]
+        impl #impl_generics ::core::convert::From<#native>
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn from(value: #native) -> Self {
+                match value {
+                    #(#native_path::#variants => Self::#variants,)*
+                }
+            }
+        }
+    }
+}
@@ -61,7 +61,7 @@ impl Printing {
 
         Ok(Self {
             rkyv_path,
-            vis: input.vis.clone(),
+            vis: attributes.vis.clone().unwrap_or_else(|| input.vis.clone()),
             name,
             archived_name,
             archived_type,
@@ -69,4 +69,9 @@ impl Printing {
             archived_metas,
         })
     }
+
+    /// Returns the name of the archived type for a previous version.
+    pub fn archived_version_name(&self, version: u32) -> Ident {
+        format_ident!("{}V{}", self.archived_name, version)
+    }
 }
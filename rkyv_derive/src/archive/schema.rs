@@ -0,0 +1,192 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, DataEnum, Error, Fields, Generics, Ident, Index, LitStr,
+    Member,
+};
+
+use crate::{
+    archive::printing::Printing,
+    attributes::{Attributes, FieldAttributes},
+    util::strip_raw,
+};
+
+fn member_name(member: &Member) -> LitStr {
+    match member {
+        Member::Named(ident) => LitStr::new(&strip_raw(ident), ident.span()),
+        Member::Unnamed(index) => {
+            LitStr::new(&index.index.to_string(), index.span)
+        }
+    }
+}
+
+/// Adds a `Reflect` bound for each field and returns the schema of each field.
+///
+/// `offset_of` is the type that the field offsets are taken from, and
+/// `member_offset` is added to the index of unnamed fields to find their
+/// member in that type.
+fn field_schemas(
+    printing: &Printing,
+    attributes: &Attributes,
+    generics: &mut Generics,
+    fields: &Fields,
+    offset_of: &TokenStream,
+    member_offset: u32,
+) -> Result<Vec<TokenStream>, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let where_clause = generics.make_where_clause();
+
+    let mut result = Vec::new();
+    for (field, member) in fields.iter().zip(fields.members()) {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        let archived = field_attrs.archived(rkyv_path, field);
+        if field_attrs.omit_bounds.is_none() {
+            where_clause.predicates.push(parse_quote! {
+                #archived: #rkyv_path::schema::Reflect
+            });
+        }
+
+        let name = member_name(&member);
+        let offset_member = match member {
+            Member::Named(_) => member,
+            Member::Unnamed(index) => Member::Unnamed(Index {
+                index: index.index + member_offset,
+                span: index.span,
+            }),
+        };
+        result.push(quote! {
+            #rkyv_path::schema::FieldSchema {
+                name: #name,
+                offset: ::core::mem::offset_of!(#offset_of, #offset_member),
+                schema: #rkyv_path::schema::SchemaRef::of::<#archived>(),
+            }
+        });
+    }
+
+    Ok(result)
+}
+
+fn impl_reflect(
+    printing: &Printing,
+    generics: &Generics,
+    kind: TokenStream,
+) -> TokenStream {
+    let Printing {
+        rkyv_path,
+        archived_name,
+        ..
+    } = printing;
+
+    let name = LitStr::new(&strip_raw(archived_name), archived_name.span());
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::schema::Reflect
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            const SCHEMA: #rkyv_path::schema::TypeSchema =
+                #rkyv_path::schema::TypeSchema {
+                    name: #name,
+                    size: ::core::mem::size_of::<Self>(),
+                    align: ::core::mem::align_of::<Self>(),
+                    kind: #kind,
+                };
+        }
+    }
+}
+
+pub fn impl_struct_schema(
+    printing: &Printing,
+    generics: &Generics,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let mut generics = generics.clone();
+
+    let fields = field_schemas(
+        printing,
+        attributes,
+        &mut generics,
+        fields,
+        &quote! { Self },
+        0,
+    )?;
+
+    let kind = quote! {
+        #rkyv_path::schema::SchemaKind::Struct(&[#(#fields,)*])
+    };
+    Ok(impl_reflect(printing, &generics, kind))
+}
+
+/// Generates the `Reflect` impl for an archived enum.
+///
+/// The offsets of variant fields are taken from the private variant structs,
+/// so this must be emitted in the same scope as them.
+pub fn impl_enum_schema(
+    printing: &Printing,
+    generics: &Generics,
+    attributes: &Attributes,
+    data: &DataEnum,
+    tag: &Ident,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let (_, ty_generics, _) = generics.split_for_impl();
+    let mut impl_generics = generics.clone();
+
+    let (tag_type, tag_value) = if tag == "u8" {
+        (quote! { u8 }, None)
+    } else {
+        let ty = format_ident!("Archived{}", tag.to_string().to_uppercase());
+        let convert = format_ident!("archived_tag_{}", tag);
+        (quote! { #rkyv_path::primitive::#ty }, Some(convert))
+    };
+
+    let mut variants = Vec::new();
+    for variant in data.variants.iter() {
+        let variant_name = &variant.ident;
+        let name = LitStr::new(&strip_raw(variant_name), variant_name.span());
+
+        let fields = if matches!(variant.fields, Fields::Unit) {
+            Vec::new()
+        } else {
+            let archived_variant_name =
+                format_ident!("ArchivedVariant{}", strip_raw(variant_name));
+            field_schemas(
+                printing,
+                attributes,
+                &mut impl_generics,
+                &variant.fields,
+                &quote! { #archived_variant_name #ty_generics },
+                1,
+            )?
+        };
+
+        let tag_value = match tag_value {
+            None => quote! { ArchivedTag::#variant_name as u32 },
+            Some(ref convert) => quote! {
+                #rkyv_path::primitive::#convert(
+                    ArchivedTag::#variant_name as #tag
+                ) as u32
+            },
+        };
+
+        variants.push(quote! {
+            #rkyv_path::schema::VariantSchema {
+                name: #name,
+                tag: #tag_value,
+                fields: &[#(#fields,)*],
+            }
+        });
+    }
+
+    let kind = quote! {
+        #rkyv_path::schema::SchemaKind::Enum {
+            tag: #rkyv_path::schema::SchemaRef::of::<#tag_type>(),
+            variants: &[#(#variants,)*],
+        }
+    };
+    Ok(impl_reflect(printing, &impl_generics, kind))
+}
@@ -0,0 +1,294 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, LitStr, Variant};
+
+use crate::{
+    archive::printing::Printing,
+    attributes::{Attributes, FieldAttributes, VariantAttributes},
+    util::iter_fields,
+};
+
+pub fn impl_serialize(
+    input: &DeriveInput,
+    printing: &Printing,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let serde = quote! { #rkyv_path::serde::serde_1 };
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in iter_fields(&input.data) {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some()
+            || field_attrs.serde_attributes.is_skipped()
+            || field_attrs.omit_bounds.is_some()
+        {
+            continue;
+        }
+
+        let archived_field_ty = field_attrs.archived(rkyv_path, field);
+        where_clause.predicates.push(parse_quote! {
+            #archived_field_ty: #rkyv_path::serde::SerializeArchived
+        });
+    }
+
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            serialize_struct(printing, attributes, &data.fields)?
+        }
+        Data::Enum(ref data) => {
+            let mut arms = TokenStream::new();
+            for (index, variant) in data.variants.iter().enumerate() {
+                let index = index as u32;
+                arms.extend(serialize_variant(
+                    printing, attributes, variant, index,
+                )?);
+            }
+            quote! {
+                match self {
+                    #arms
+                }
+            }
+        }
+        Data::Union(_) => unreachable!(),
+    };
+
+    let archived_name = &printing.archived_name;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #rkyv_path::serde::SerializeArchived
+            for #archived_name #ty_generics
+        #where_clause
+        {
+            fn serialize_archived<__S: #serde::Serializer>(
+                &self,
+                serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error> {
+                #body
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #serde::Serialize for #archived_name #ty_generics
+        #where_clause
+        {
+            #[inline]
+            fn serialize<__S: #serde::Serializer>(
+                &self,
+                serializer: __S,
+            ) -> ::core::result::Result<__S::Ok, __S::Error> {
+                #rkyv_path::serde::SerializeArchived::serialize_archived(
+                    self,
+                    serializer,
+                )
+            }
+        }
+    })
+}
+
+/// The serialized fields of a struct or variant.
+struct SerdeFields {
+    /// The name of each field, if the fields are named.
+    keys: Vec<LitStr>,
+    /// Expressions which evaluate to references to each archived field.
+    values: Vec<TokenStream>,
+    /// Whether each of the fields is skipped.
+    skipped: Vec<bool>,
+}
+
+fn serde_fields(
+    attributes: &Attributes,
+    fields: &Fields,
+    access: impl Fn(usize) -> TokenStream,
+) -> Result<SerdeFields, Error> {
+    let mut keys = Vec::new();
+    let mut values = Vec::new();
+    let mut skipped = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        let is_skipped = field_attrs.skip.is_some()
+            || field_attrs.serde_attributes.is_skipped();
+        skipped.push(is_skipped);
+        if is_skipped {
+            continue;
+        }
+
+        if let Some(ref ident) = field.ident {
+            keys.push(field_attrs.serde_attributes.name(ident));
+        }
+        values.push(access(i));
+    }
+
+    Ok(SerdeFields {
+        keys,
+        values,
+        skipped,
+    })
+}
+
+fn serialize_struct(
+    printing: &Printing,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let serde = quote! { #rkyv_path::serde::serde_1 };
+    let name = attributes.serde_attributes.name(&printing.name);
+    let members = fields.members().collect::<Vec<_>>();
+    let SerdeFields { keys, values, .. } =
+        serde_fields(attributes, fields, |i| {
+            let member = &members[i];
+            quote! { &self.#member }
+        })?;
+    let len = values.len();
+
+    Ok(match fields {
+        Fields::Named(_) => quote! {
+            use #serde::ser::SerializeStruct as _;
+            let mut state = serializer.serialize_struct(#name, #len)?;
+            #(
+                state.serialize_field(
+                    #keys,
+                    &#rkyv_path::serde::AsSerde(#values),
+                )?;
+            )*
+            state.end()
+        },
+        Fields::Unnamed(_) if len == 1 => quote! {
+            serializer.serialize_newtype_struct(
+                #name,
+                &#rkyv_path::serde::AsSerde(#(#values)*),
+            )
+        },
+        Fields::Unnamed(_) => quote! {
+            use #serde::ser::SerializeTupleStruct as _;
+            let mut state = serializer.serialize_tuple_struct(#name, #len)?;
+            #(
+                state.serialize_field(&#rkyv_path::serde::AsSerde(#values))?;
+            )*
+            state.end()
+        },
+        Fields::Unit => quote! {
+            serializer.serialize_unit_struct(#name)
+        },
+    })
+}
+
+fn serialize_variant(
+    printing: &Printing,
+    attributes: &Attributes,
+    variant: &Variant,
+    index: u32,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = &printing.rkyv_path;
+    let serde = quote! { #rkyv_path::serde::serde_1 };
+    let name = attributes.serde_attributes.name(&printing.name);
+    let variant_name = &variant.ident;
+    let fields = &variant.fields;
+
+    let variant_attrs = VariantAttributes::parse(attributes, variant)?;
+    if variant_attrs.serde_attributes.is_skipped() {
+        let message = format!(
+            "the enum variant {}::{} cannot be serialized",
+            name.value(),
+            variant_name,
+        );
+        return Ok(quote! {
+            Self::#variant_name { .. } => ::core::result::Result::Err(
+                <__S::Error as #serde::ser::Error>::custom(#message),
+            ),
+        });
+    }
+    let variant = variant_attrs.serde_attributes.name(variant_name);
+
+    let bindings = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match field.ident {
+            Some(ref ident) => ident.clone(),
+            None => format_ident!("_{}", i),
+        })
+        .collect::<Vec<_>>();
+    let SerdeFields {
+        keys,
+        values,
+        skipped,
+    } = serde_fields(attributes, fields, |i| {
+        let binding = &bindings[i];
+        quote! { #binding }
+    })?;
+    let len = values.len();
+
+    Ok(match fields {
+        Fields::Named(_) => quote! {
+            Self::#variant_name { #(#values,)* .. } => {
+                use #serde::ser::SerializeStructVariant as _;
+                let mut state = serializer.serialize_struct_variant(
+                    #name,
+                    #index,
+                    #variant,
+                    #len,
+                )?;
+                #(
+                    state.serialize_field(
+                        #keys,
+                        &#rkyv_path::serde::AsSerde(#values),
+                    )?;
+                )*
+                state.end()
+            }
+        },
+        Fields::Unnamed(_) => {
+            let patterns = bindings.iter().zip(skipped.iter()).map(
+                |(binding, skipped)| {
+                    if *skipped {
+                        quote! { _ }
+                    } else {
+                        quote! { #binding }
+                    }
+                },
+            );
+
+            let body = if len == 1 {
+                quote! {
+                    serializer.serialize_newtype_variant(
+                        #name,
+                        #index,
+                        #variant,
+                        &#rkyv_path::serde::AsSerde(#(#values)*),
+                    )
+                }
+            } else {
+                quote! {
+                    use #serde::ser::SerializeTupleVariant as _;
+                    let mut state = serializer.serialize_tuple_variant(
+                        #name,
+                        #index,
+                        #variant,
+                        #len,
+                    )?;
+                    #(
+                        state.serialize_field(
+                            &#rkyv_path::serde::AsSerde(#values),
+                        )?;
+                    )*
+                    state.end()
+                }
+            };
+
+            quote! {
+                Self::#variant_name(#(#patterns,)*) => { #body }
+            }
+        }
+        Fields::Unit => quote! {
+            Self::#variant_name => serializer.serialize_unit_variant(
+                #name,
+                #index,
+                #variant,
+            ),
+        },
+    })
+}
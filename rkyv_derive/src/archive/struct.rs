@@ -1,13 +1,14 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parse_quote, punctuated::Punctuated, Error, Field, Fields, Generics, Index,
-    Member,
+    Member, Type,
 };
 
 use crate::{
-    archive::{archived_doc, printing::Printing, resolver_doc},
-    attributes::{Attributes, FieldAttributes},
+    archive::{archived_doc, printing::Printing, resolver_doc, schema},
+    attributes::{previous_versions, Attributes, FieldAttributes},
+    util::strip_raw,
 };
 
 pub fn impl_struct(
@@ -24,6 +25,20 @@ pub fn impl_struct(
         ..
     } = printing;
 
+    if let Some(ref tag) = attributes.tag {
+        return Err(Error::new_spanned(
+            tag,
+            "`tag = ...` is only supported on enums",
+        ));
+    }
+
+    if let Some(ref sealed_variants) = attributes.sealed_variants {
+        return Err(Error::new_spanned(
+            sealed_variants,
+            "`sealed_variants` is only supported on enums",
+        ));
+    }
+
     let mut result = TokenStream::new();
 
     if attributes.as_type.is_none() {
@@ -34,6 +49,44 @@ pub fn impl_struct(
         result.extend(generate_niching_impls(
             printing, generics, attributes, fields,
         )?);
+
+        result.extend(generate_previous_versions(
+            printing, generics, attributes, fields,
+        )?);
+
+        if let Some(ref accessors) = attributes.accessors {
+            if !matches!(fields, Fields::Named(_)) {
+                return Err(Error::new_spanned(
+                    accessors,
+                    "`accessors` is only supported on structs with named \
+                     fields",
+                ));
+            }
+
+            result.extend(generate_accessors(
+                printing, generics, attributes, fields,
+            )?);
+        }
+
+        if let Some(ref deserialize_fields) = attributes.deserialize_fields {
+            if !matches!(fields, Fields::Named(_)) {
+                return Err(Error::new_spanned(
+                    deserialize_fields,
+                    "`deserialize_fields` is only supported on structs with \
+                     named fields",
+                ));
+            }
+
+            result.extend(generate_field_deserializers(
+                printing, generics, attributes, fields,
+            )?);
+        }
+
+        if attributes.schema.is_some() {
+            result.extend(schema::impl_struct_schema(
+                printing, generics, attributes, fields,
+            )?);
+        }
     }
 
     result.extend(generate_resolver_type(
@@ -203,6 +256,232 @@ fn generate_archived_type(
     })
 }
 
+fn generate_accessors(
+    printing: &Printing,
+    generics: &Generics,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let Printing {
+        rkyv_path,
+        vis,
+        archived_name,
+        ..
+    } = printing;
+
+    let mut accessors = TokenStream::new();
+    for field in fields {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some() {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let name = strip_raw(ident);
+
+        // Primitives are returned as native values, and everything else is
+        // returned as a reference to the archived field.
+        accessors.extend(match native_primitive(&field_attrs, ty) {
+            Some(primitive) => {
+                let doc =
+                    format!("Returns the `{}` field as a native value.", name);
+                let value = if ["bool", "i8", "u8"].contains(&primitive) {
+                    quote! { self.#ident }
+                } else {
+                    quote! { self.#ident.to_native() }
+                };
+                quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #vis fn #ident(&self) -> #ty {
+                        #value
+                    }
+                }
+            }
+            None => {
+                let doc = format!("Returns the archived `{}` field.", name);
+                let archived = field_attrs.archived(rkyv_path, field);
+                quote! {
+                    #[doc = #doc]
+                    #[inline]
+                    #vis fn #ident(&self) -> &#archived {
+                        &self.#ident
+                    }
+                }
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #impl_generics #archived_name #ty_generics #where_clause {
+            #accessors
+        }
+    })
+}
+
+fn generate_field_deserializers(
+    printing: &Printing,
+    generics: &Generics,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let Printing {
+        rkyv_path,
+        vis,
+        archived_name,
+        ..
+    } = printing;
+
+    let mut deserializers = TokenStream::new();
+    for field in fields {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        if field_attrs.skip.is_some() {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let name = strip_raw(ident);
+        let doc = format!("Deserializes only the `{}` field.", name);
+        let method = format_ident!("deserialize_{}", name);
+
+        let mut bounds = field_attrs.deserialize_bound(rkyv_path, field);
+        if let Some(ref container_bounds) = attributes.deserialize_bounds {
+            bounds.extend(container_bounds.iter().cloned());
+        }
+        let deserialize = field_attrs.deserialize(rkyv_path, field);
+
+        deserializers.extend(quote! {
+            #[doc = #doc]
+            #vis fn #method<__D>(
+                &self,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                #ty,
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            >
+            where
+                __D: #rkyv_path::rancor::Fallible + ?Sized,
+                #(#bounds,)*
+            {
+                #deserialize(&self.#ident, deserializer)
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        #[automatically_derived]
+        #[allow(dead_code)]
+        impl #impl_generics #archived_name #ty_generics #where_clause {
+            #deserializers
+        }
+    })
+}
+
+/// Returns the name of the primitive type of a field if its accessor returns
+/// a native value. `isize` and `usize` are archived as fixed-size integers, so
+/// their accessors return references instead.
+fn native_primitive(
+    field_attrs: &FieldAttributes,
+    ty: &Type,
+) -> Option<&'static str> {
+    const PRIMITIVES: [&str; 14] = [
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "u8",
+        "u16", "u32", "u64", "u128",
+    ];
+
+    if field_attrs.with.is_some() {
+        return None;
+    }
+    let Type::Path(ty) = ty else {
+        return None;
+    };
+    if ty.qself.is_some() {
+        return None;
+    }
+    let ident = ty.path.get_ident()?;
+    PRIMITIVES
+        .iter()
+        .copied()
+        .find(|primitive| ident == *primitive)
+}
+
+fn generate_previous_versions(
+    printing: &Printing,
+    generics: &Generics,
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<TokenStream, Error> {
+    let Printing {
+        rkyv_path,
+        vis,
+        name,
+        archived_metas,
+        ..
+    } = printing;
+
+    let mut result = TokenStream::new();
+    for version in previous_versions(attributes, fields)? {
+        let mut where_clause = generics.where_clause.clone().unwrap();
+        let mut archived_fields = TokenStream::new();
+        for field in fields {
+            let field_attrs = FieldAttributes::parse(attributes, field)?;
+            if field_attrs.since() > version {
+                continue;
+            }
+
+            let Field {
+                vis,
+                ident,
+                colon_token,
+                ..
+            } = field;
+            let field_metas = field_attrs.metas();
+            let ty = field_attrs.archived(rkyv_path, field);
+
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: #rkyv_path::traits::Portable });
+            archived_fields.extend(quote! {
+                #field_metas
+                #vis #ident #colon_token #ty,
+            });
+        }
+
+        let struct_where = &generics.where_clause;
+        let body = match fields {
+            Fields::Named(_) => quote! { #struct_where { #archived_fields } },
+            _ => quote! { (#archived_fields) #struct_where; },
+        };
+
+        let archived_name = printing.archived_version_name(version);
+        let (impl_generics, ty_generics, _) = generics.split_for_impl();
+        let doc_string =
+            format!("An archived [`{}`] from version {}", name, version);
+        result.extend(quote! {
+            #[automatically_derived]
+            #[doc = #doc_string]
+            #(#[#archived_metas])*
+            #[repr(C)]
+            #vis struct #archived_name #generics #body
+
+            // SAFETY: These pseudo-auto traits are implemented for the archived
+            // type if they are implemented for all of its fields.
+            unsafe impl #impl_generics #rkyv_path::traits::Portable
+                for #archived_name #ty_generics
+            #where_clause
+            {}
+        });
+    }
+
+    Ok(result)
+}
+
 fn generate_resolver_type(
     printing: &Printing,
     generics: &Generics,
@@ -260,7 +539,7 @@ fn generate_partial_eq_impl(
     let mut where_clause = generics.where_clause.clone().unwrap();
     for field in fields.iter() {
         let field_attrs = FieldAttributes::parse(attributes, field)?;
-        if field_attrs.omit_bounds.is_none() {
+        if field_attrs.omit_bounds.is_none() && field_attrs.is_compared() {
             let ty = &field.ty;
             let archived_ty = field_attrs.archived(rkyv_path, field);
             where_clause
@@ -269,7 +548,7 @@ fn generate_partial_eq_impl(
         }
     }
 
-    let members = fields.members();
+    let members = compared_members(attributes, fields)?;
     let (impl_generics, ty_generics, _) = generics.split_for_impl();
 
     Ok(quote! {
@@ -308,7 +587,7 @@ fn generate_partial_ord_impl(
 
     for field in fields.iter() {
         let field_attrs = FieldAttributes::parse(attributes, field)?;
-        if field_attrs.omit_bounds.is_none() {
+        if field_attrs.omit_bounds.is_none() && field_attrs.is_compared() {
             let ty = &field.ty;
             let archived_ty = field_attrs.archived(rkyv_path, field);
             where_clause
@@ -317,7 +596,7 @@ fn generate_partial_ord_impl(
         }
     }
 
-    let members = fields.members();
+    let members = compared_members(attributes, fields)?;
     let (impl_generics, ty_generics, _) = generics.split_for_impl();
 
     Ok(quote! {
@@ -512,3 +791,16 @@ fn generate_niching_impls(
 
     Ok(result)
 }
+
+fn compared_members(
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<Vec<Member>, Error> {
+    let mut result = Vec::new();
+    for (field, member) in fields.iter().zip(fields.members()) {
+        if FieldAttributes::parse(attributes, field)?.is_compared() {
+            result.push(member);
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,349 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    meta::ParseNestedMeta, parenthesized, parse::Parse, parse_quote,
+    punctuated::Punctuated, Error, Ident, ItemTrait, Path, Token, Type,
+};
+
+use crate::{attributes::try_set_attribute, util::strip_raw};
+
+#[derive(Default)]
+pub struct Arguments {
+    impls: Option<Punctuated<Type, Token![,]>>,
+    serialize: Option<Ident>,
+    crate_path: Option<Path>,
+}
+
+impl Arguments {
+    pub fn parse_meta(
+        &mut self,
+        meta: ParseNestedMeta<'_>,
+    ) -> Result<(), Error> {
+        if meta.path.is_ident("impls") {
+            let impls;
+            parenthesized!(impls in meta.input);
+            try_set_attribute(
+                &mut self.impls,
+                impls.parse_terminated(Type::parse, Token![,])?,
+                "impls",
+            )
+        } else if meta.path.is_ident("serialize") {
+            meta.input.parse::<Token![=]>()?;
+            try_set_attribute(
+                &mut self.serialize,
+                meta.input.parse::<Ident>()?,
+                "serialize",
+            )
+        } else if meta.path.is_ident("crate") {
+            if meta.input.parse::<Token![=]>().is_ok() {
+                let path = meta.input.parse::<Path>()?;
+                try_set_attribute(&mut self.crate_path, path, "crate")
+            } else if meta.input.is_empty() || meta.input.peek(Token![,]) {
+                try_set_attribute(
+                    &mut self.crate_path,
+                    parse_quote! { crate },
+                    "crate",
+                )
+            } else {
+                Err(meta.error("expected `crate` or `crate = ...`"))
+            }
+        } else {
+            Err(meta.error("unrecognized archive_dyn argument"))
+        }
+    }
+}
+
+pub fn expand(
+    arguments: Arguments,
+    input: ItemTrait,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "archive_dyn does not support generic traits",
+        ));
+    }
+
+    let Some(impls) = arguments.impls else {
+        return Err(Error::new_spanned(
+            name,
+            "archive_dyn requires a list of implementations with `impls(..)`",
+        ));
+    };
+    let impls = impls.into_iter().collect::<Vec<_>>();
+    if u32::try_from(impls.len()).is_err() {
+        return Err(Error::new_spanned(
+            name,
+            "archive_dyn supports at most u32::MAX implementations",
+        ));
+    }
+    let indices = (0..impls.len() as u32)
+        .map(Literal::u32_unsuffixed)
+        .collect::<Vec<_>>();
+
+    let rkyv_path = arguments
+        .crate_path
+        .unwrap_or_else(|| parse_quote! { ::rkyv });
+    let serialize_name = arguments
+        .serialize
+        .unwrap_or_else(|| format_ident!("Serialize{}", strip_raw(name)));
+    let vis = &input.vis;
+    let serialize_doc =
+        format!("A [`{}`] which can be serialized as a trait object.", name);
+
+    let check_bytes_impl = if cfg!(feature = "bytecheck") {
+        quote! {
+            unsafe impl<__C> #rkyv_path::bytecheck::CheckBytes<__C>
+                for dyn #name + '_
+            where
+                __C: #rkyv_path::rancor::Fallible + ?Sized,
+                <__C as #rkyv_path::rancor::Fallible>::Error:
+                    #rkyv_path::rancor::Source,
+                #(
+                    #rkyv_path::Archived<#impls>:
+                        #rkyv_path::bytecheck::CheckBytes<__C>,
+                )*
+            {
+                unsafe fn check_bytes(
+                    value: *const Self,
+                    context: &mut __C,
+                ) -> ::core::result::Result<
+                    (),
+                    <__C as #rkyv_path::rancor::Fallible>::Error,
+                > {
+                    #rkyv_path::trait_object::check_dyn(
+                        value,
+                        context,
+                        |index, context| match index {
+                            #(
+                                #indices => unsafe {
+                                    <
+                                        #rkyv_path::Archived<#impls>
+                                        as #rkyv_path::bytecheck::CheckBytes<
+                                            __C,
+                                        >
+                                    >::check_bytes(value.cast(), context)
+                                },
+                            )*
+                            _ => ::core::unreachable!(),
+                        },
+                    )
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    Ok(quote! {
+        #input
+
+        #[doc = #serialize_doc]
+        #vis trait #serialize_name:
+            #name + #rkyv_path::trait_object::SerializeDyn
+        {
+            #[doc(hidden)]
+            fn __archived_impl_index(&self) -> u32;
+        }
+
+        #(
+            impl #serialize_name for #impls {
+                fn __archived_impl_index(&self) -> u32 {
+                    #indices
+                }
+            }
+        )*
+
+        const _: () = {
+            use #rkyv_path::ptr_meta::{DynMetadata, Pointee};
+
+            unsafe impl Pointee for dyn #name + '_ {
+                type Metadata = DynMetadata<Self>;
+            }
+
+            unsafe impl Pointee for dyn #serialize_name + '_ {
+                type Metadata = DynMetadata<Self>;
+            }
+
+            #[allow(dead_code)]
+            fn assert_portable<T: #rkyv_path::Portable>() {}
+
+            #[allow(dead_code)]
+            fn assert_impls_portable() {
+                #(assert_portable::<#rkyv_path::Archived<#impls>>();)*
+            }
+
+            // SAFETY: Every registered archived type is asserted to be
+            // `Portable` above, and the returned metadata only depends on the
+            // index.
+            unsafe impl #rkyv_path::trait_object::RegisteredDyn
+                for dyn #name + '_
+            {
+                #[inline(never)]
+                fn impl_metadata(
+                    index: u32,
+                ) -> ::core::option::Option<DynMetadata<Self>> {
+                    match index {
+                        #(
+                            #indices => ::core::option::Option::Some(
+                                #rkyv_path::ptr_meta::metadata(
+                                    ::core::ptr::null::<
+                                        #rkyv_path::Archived<#impls>
+                                    >() as *const Self,
+                                ),
+                            ),
+                        )*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+
+            // SAFETY: Archived trait objects always point to one of the
+            // registered archived types, which are all `Portable`.
+            unsafe impl #rkyv_path::Portable for dyn #name + '_ {}
+
+            impl #rkyv_path::traits::ArchivePointee for dyn #name + '_ {
+                type ArchivedMetadata =
+                    #rkyv_path::trait_object::ArchivedDynMetadata<Self>;
+
+                fn pointer_metadata(
+                    archived: &Self::ArchivedMetadata,
+                ) -> DynMetadata<Self> {
+                    archived.pointer_metadata()
+                }
+            }
+
+            impl #rkyv_path::traits::LayoutRaw for dyn #name + '_ {
+                fn layout_raw(
+                    metadata: DynMetadata<Self>,
+                ) -> ::core::result::Result<
+                    ::core::alloc::Layout,
+                    ::core::alloc::LayoutError,
+                > {
+                    ::core::result::Result::Ok(metadata.layout())
+                }
+            }
+
+            impl #rkyv_path::traits::LayoutRaw for dyn #serialize_name + '_ {
+                fn layout_raw(
+                    metadata: DynMetadata<Self>,
+                ) -> ::core::result::Result<
+                    ::core::alloc::Layout,
+                    ::core::alloc::LayoutError,
+                > {
+                    ::core::result::Result::Ok(metadata.layout())
+                }
+            }
+
+            impl #rkyv_path::ArchiveUnsized for dyn #serialize_name + '_ {
+                type Archived = dyn #name;
+
+                fn archived_metadata(
+                    &self,
+                ) -> #rkyv_path::ArchivedMetadata<Self> {
+                    #rkyv_path::trait_object::ArchivedDynMetadata::new(
+                        self.__archived_impl_index(),
+                    )
+                }
+            }
+
+            impl<__S> #rkyv_path::SerializeUnsized<__S>
+                for dyn #serialize_name + '_
+            where
+                __S: #rkyv_path::rancor::Fallible
+                    + #rkyv_path::ser::Writer
+                    + #rkyv_path::ser::Allocator
+                    + #rkyv_path::ser::Sharing
+                    + ?Sized,
+                <__S as #rkyv_path::rancor::Fallible>::Error:
+                    #rkyv_path::rancor::Source,
+            {
+                fn serialize_unsized(
+                    &self,
+                    serializer: &mut __S,
+                ) -> ::core::result::Result<
+                    usize,
+                    <__S as #rkyv_path::rancor::Fallible>::Error,
+                > {
+                    #rkyv_path::trait_object::serialize_dyn(self, serializer)
+                }
+            }
+
+            impl<__D> #rkyv_path::DeserializeUnsized<dyn #serialize_name, __D>
+                for dyn #name + '_
+            where
+                __D: #rkyv_path::rancor::Fallible + ?Sized,
+                <__D as #rkyv_path::rancor::Fallible>::Error:
+                    #rkyv_path::rancor::Source,
+                #(
+                    #rkyv_path::Archived<#impls>:
+                        #rkyv_path::Deserialize<#impls, __D>,
+                )*
+            {
+                unsafe fn deserialize_unsized(
+                    &self,
+                    deserializer: &mut __D,
+                    out: *mut dyn #serialize_name,
+                ) -> ::core::result::Result<
+                    (),
+                    <__D as #rkyv_path::rancor::Fallible>::Error,
+                > {
+                    let index = #rkyv_path::trait_object::impl_index::<
+                        Self,
+                        <__D as #rkyv_path::rancor::Fallible>::Error,
+                    >(self)?;
+                    match index {
+                        #(
+                            #indices => {
+                                // SAFETY: The metadata of `self` belongs to
+                                // this registered implementation, so `self`
+                                // points to its archived type.
+                                let archived = unsafe {
+                                    &*(self as *const Self).cast::<
+                                        #rkyv_path::Archived<#impls>
+                                    >()
+                                };
+                                let value = <
+                                    #rkyv_path::Archived<#impls>
+                                    as #rkyv_path::Deserialize<#impls, __D>
+                                >::deserialize(archived, deserializer)?;
+                                // SAFETY: `out` was allocated with the layout
+                                // returned from `deserialize_metadata`, which
+                                // is the layout of this implementation.
+                                unsafe {
+                                    out.cast::<#impls>().write(value);
+                                }
+                                ::core::result::Result::Ok(())
+                            }
+                        )*
+                        _ => ::core::unreachable!(),
+                    }
+                }
+
+                fn deserialize_metadata(
+                    &self,
+                ) -> DynMetadata<dyn #serialize_name> {
+                    // Errors are reported by `deserialize_unsized`, so any
+                    // metadata can be returned for invalid trait objects.
+                    let index = #rkyv_path::trait_object::impl_index::<
+                        Self,
+                        #rkyv_path::rancor::Failure,
+                    >(self)
+                    .unwrap_or(0);
+                    match index {
+                        #(
+                            #indices => #rkyv_path::ptr_meta::metadata(
+                                ::core::ptr::null::<#impls>()
+                                    as *const dyn #serialize_name,
+                            ),
+                        )*
+                        _ => ::core::unreachable!(),
+                    }
+                }
+            }
+
+            #check_bytes_impl
+        };
+    })
+}
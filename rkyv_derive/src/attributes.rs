@@ -2,11 +2,14 @@ use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{quote, ToTokens};
 use syn::{
     meta::ParseNestedMeta, parenthesized, parse::Parse, parse_quote,
-    punctuated::Punctuated, DeriveInput, Error, Field, Fields, Ident, Meta,
-    Path, Token, Type, Variant, WherePredicate,
+    punctuated::Punctuated, token, Attribute, DeriveInput, Error, Expr, Field,
+    Fields, GenericArgument, Ident, LitInt, LitStr, Meta, Path, PathArguments,
+    PathSegment, Token, Type, Variant, Visibility, WherePredicate,
 };
 
-fn try_set_attribute<T: ToTokens>(
+use crate::util::strip_raw;
+
+pub fn try_set_attribute<T: ToTokens>(
     attribute: &mut Option<T>,
     value: T,
     name: &'static str,
@@ -22,11 +25,21 @@ fn try_set_attribute<T: ToTokens>(
     }
 }
 
+fn parse_bounds(
+    meta: &ParseNestedMeta<'_>,
+) -> Result<Punctuated<WherePredicate, Token![,]>, Error> {
+    let bounds;
+    parenthesized!(bounds in meta.input);
+    bounds.parse_terminated(WherePredicate::parse, Token![,])
+}
+
 #[derive(Default)]
 pub struct Attributes {
     pub as_type: Option<Type>,
+    pub borrow_from: Option<Type>,
     pub archived: Option<Ident>,
     pub resolver: Option<Ident>,
+    pub vis: Option<Visibility>,
     pub remote: Option<Path>,
     pub metas: Vec<Meta>,
     pub compares: Option<Punctuated<Path, Token![,]>>,
@@ -35,6 +48,17 @@ pub struct Attributes {
     pub deserialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub bytecheck: Option<TokenStream>,
     pub crate_path: Option<Path>,
+    pub no_auto_niche: Option<Path>,
+    pub omit_bounds: Option<Path>,
+    pub tag: Option<Ident>,
+    pub accessors: Option<Path>,
+    pub deserialize_fields: Option<Path>,
+    pub sealed_variants: Option<Path>,
+    pub serde: Option<Path>,
+    pub serde_attributes: SerdeAttributes,
+    pub schema: Option<Path>,
+    pub diff: Option<Path>,
+    pub patch: Option<Path>,
 }
 
 impl Attributes {
@@ -61,33 +85,21 @@ impl Attributes {
             let traits = traits.parse_terminated(Path::parse, Token![,])?;
             try_set_attribute(&mut self.compares, traits, "compare")
         } else if meta.path.is_ident("archive_bounds") {
-            let bounds;
-            parenthesized!(bounds in meta.input);
-            let clauses =
-                bounds.parse_terminated(WherePredicate::parse, Token![,])?;
             try_set_attribute(
                 &mut self.archive_bounds,
-                clauses,
+                parse_bounds(&meta)?,
                 "archive_bounds",
             )
         } else if meta.path.is_ident("serialize_bounds") {
-            let bounds;
-            parenthesized!(bounds in meta.input);
-            let clauses =
-                bounds.parse_terminated(WherePredicate::parse, Token![,])?;
             try_set_attribute(
                 &mut self.serialize_bounds,
-                clauses,
+                parse_bounds(&meta)?,
                 "serialize_bounds",
             )
         } else if meta.path.is_ident("deserialize_bounds") {
-            let bounds;
-            parenthesized!(bounds in meta.input);
-            let clauses =
-                bounds.parse_terminated(WherePredicate::parse, Token![,])?;
             try_set_attribute(
                 &mut self.deserialize_bounds,
-                clauses,
+                parse_bounds(&meta)?,
                 "deserialize_bounds",
             )
         } else if meta.path.is_ident("archived") {
@@ -102,6 +114,8 @@ impl Attributes {
                 meta.value()?.parse()?,
                 "resolver",
             )
+        } else if meta.path.is_ident("vis") {
+            try_set_attribute(&mut self.vis, meta.value()?.parse()?, "vis")
         } else if meta.path.is_ident("as") {
             meta.input.parse::<Token![=]>()?;
             try_set_attribute(
@@ -109,6 +123,13 @@ impl Attributes {
                 meta.input.parse::<Type>()?,
                 "as",
             )
+        } else if meta.path.is_ident("borrow_from") {
+            meta.input.parse::<Token![=]>()?;
+            try_set_attribute(
+                &mut self.borrow_from,
+                meta.input.parse::<Type>()?,
+                "borrow_from",
+            )
         } else if meta.path.is_ident("crate") {
             if meta.input.parse::<Token![=]>().is_ok() {
                 let path = meta.input.parse::<Path>()?;
@@ -144,6 +165,46 @@ impl Attributes {
                 meta.value()?.parse()?,
                 "remote",
             )
+        } else if meta.path.is_ident("no_auto_niche") {
+            try_set_attribute(
+                &mut self.no_auto_niche,
+                meta.path,
+                "no_auto_niche",
+            )
+        } else if meta.path.is_ident("omit_bounds") {
+            try_set_attribute(&mut self.omit_bounds, meta.path, "omit_bounds")
+        } else if meta.path.is_ident("accessors") {
+            try_set_attribute(&mut self.accessors, meta.path, "accessors")
+        } else if meta.path.is_ident("deserialize_fields") {
+            try_set_attribute(
+                &mut self.deserialize_fields,
+                meta.path,
+                "deserialize_fields",
+            )
+        } else if meta.path.is_ident("sealed_variants") {
+            try_set_attribute(
+                &mut self.sealed_variants,
+                meta.path,
+                "sealed_variants",
+            )
+        } else if meta.path.is_ident("schema") {
+            try_set_attribute(&mut self.schema, meta.path, "schema")
+        } else if meta.path.is_ident("diff") {
+            try_set_attribute(&mut self.diff, meta.path, "diff")
+        } else if meta.path.is_ident("patch") {
+            try_set_attribute(&mut self.patch, meta.path, "patch")
+        } else if meta.path.is_ident("serde") {
+            try_set_attribute(&mut self.serde, meta.path, "serde")
+        } else if meta.path.is_ident("tag") {
+            let tag = meta.value()?.parse::<Ident>()?;
+            if !["u8", "u16", "u32"].iter().any(|ty| tag == *ty) {
+                return Err(Error::new_spanned(
+                    tag,
+                    "unrecognized tag type, supported tag types are u8, u16, \
+                     and u32",
+                ));
+            }
+            try_set_attribute(&mut self.tag, tag, "tag")
         } else {
             Err(meta.error("unrecognized rkyv argument"))
         }
@@ -158,6 +219,10 @@ impl Attributes {
             }
         }
 
+        if result.serde.is_some() {
+            result.serde_attributes = SerdeAttributes::parse(&input.attrs)?;
+        }
+
         if result.as_type.is_some() {
             if let Some(ref ident) = result.archived {
                 return Err(Error::new_spanned(
@@ -182,6 +247,70 @@ impl Attributes {
                      does not generate an archived type",
                 ));
             }
+
+            if let Some(ref accessors) = result.accessors {
+                return Err(Error::new_spanned(
+                    accessors,
+                    "`accessors` may not be used with `as = ...` because no \
+                     type is generated",
+                ));
+            }
+
+            if let Some(ref deserialize_fields) = result.deserialize_fields {
+                return Err(Error::new_spanned(
+                    deserialize_fields,
+                    "`deserialize_fields` may not be used with `as = ...` \
+                     because no type is generated",
+                ));
+            }
+
+            if let Some(ref sealed_variants) = result.sealed_variants {
+                return Err(Error::new_spanned(
+                    sealed_variants,
+                    "`sealed_variants` may not be used with `as = ...` \
+                     because no type is generated",
+                ));
+            }
+
+            if let Some(ref schema) = result.schema {
+                return Err(Error::new_spanned(
+                    schema,
+                    "`schema` may not be used with `as = ...` because no type \
+                     is generated",
+                ));
+            }
+
+            if let Some(ref diff) = result.diff {
+                return Err(Error::new_spanned(
+                    diff,
+                    "`diff` may not be used with `as = ...` because no type \
+                     is generated",
+                ));
+            }
+
+            if let Some(ref patch) = result.patch {
+                return Err(Error::new_spanned(
+                    patch,
+                    "`patch` may not be used with `as = ...` because no type \
+                     is generated",
+                ));
+            }
+
+            if let Some(ref serde) = result.serde {
+                return Err(Error::new_spanned(
+                    serde,
+                    "`serde` may not be used with `as = ...` because no type \
+                     is generated",
+                ));
+            }
+
+            if let Some(ref tag) = result.tag {
+                return Err(Error::new_spanned(
+                    tag,
+                    "`tag = ...` may not be used with `as = ...` because no \
+                     type is generated",
+                ));
+            }
         }
 
         Ok(result)
@@ -198,9 +327,17 @@ impl Attributes {
 pub struct FieldAttributes {
     pub attrs: Punctuated<Meta, Token![,]>,
     pub omit_bounds: Option<Path>,
+    pub archive_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub serialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
+    pub deserialize_bounds: Option<Punctuated<WherePredicate, Token![,]>>,
     pub with: Option<Type>,
     pub getter: Option<Path>,
     pub niches: Vec<Niche>,
+    pub no_auto_niche: Option<Path>,
+    pub skip: Option<Path>,
+    pub default: Option<Path>,
+    pub since: Option<LitInt>,
+    pub serde_attributes: SerdeAttributes,
 }
 
 impl FieldAttributes {
@@ -213,6 +350,24 @@ impl FieldAttributes {
         } else if meta.path.is_ident("omit_bounds") {
             self.omit_bounds = Some(meta.path);
             Ok(())
+        } else if meta.path.is_ident("archive_bounds") {
+            try_set_attribute(
+                &mut self.archive_bounds,
+                parse_bounds(&meta)?,
+                "archive_bounds",
+            )
+        } else if meta.path.is_ident("serialize_bounds") {
+            try_set_attribute(
+                &mut self.serialize_bounds,
+                parse_bounds(&meta)?,
+                "serialize_bounds",
+            )
+        } else if meta.path.is_ident("deserialize_bounds") {
+            try_set_attribute(
+                &mut self.deserialize_bounds,
+                parse_bounds(&meta)?,
+                "deserialize_bounds",
+            )
         } else if meta.path.is_ident("with") {
             meta.input.parse::<Token![=]>()?;
             self.with = Some(meta.input.parse::<Type>()?);
@@ -233,6 +388,25 @@ impl FieldAttributes {
             self.niches.push(niche);
 
             Ok(())
+        } else if meta.path.is_ident("no_auto_niche") {
+            self.no_auto_niche = Some(meta.path);
+            Ok(())
+        } else if meta.path.is_ident("skip") {
+            self.skip = Some(meta.path);
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            meta.input.parse::<Token![=]>()?;
+            self.default = Some(meta.input.parse::<Path>()?);
+            Ok(())
+        } else if meta.path.is_ident("since") {
+            let since = meta.value()?.parse::<LitInt>()?;
+            if since.base10_parse::<u32>()? < 2 {
+                return Err(Error::new_spanned(
+                    since,
+                    "versions start at 1, so `since` must be at least 2",
+                ));
+            }
+            try_set_attribute(&mut self.since, since, "since")
         } else {
             Err(meta.error("unrecognized rkyv arguments"))
         }
@@ -250,6 +424,25 @@ impl FieldAttributes {
             }
         }
 
+        if result.omit_bounds.is_none() {
+            result.omit_bounds = attributes.omit_bounds.clone();
+        }
+
+        // With `#[rkyv(serde)]`, fields skipped by serde are also skipped by
+        // rkyv so that both produce the same values.
+        if attributes.serde.is_some() {
+            let serde = SerdeAttributes::parse(&input.attrs)?;
+            if result.skip.is_none() {
+                result.skip = serde.skip.clone();
+            }
+            if result.default.is_none()
+                && (result.skip.is_some() || result.since.is_some())
+            {
+                result.default = serde.default.clone();
+            }
+            result.serde_attributes = serde;
+        }
+
         if result.getter.is_some() && attributes.remote.is_none() {
             return Err(Error::new_spanned(
                 result.getter,
@@ -257,6 +450,58 @@ impl FieldAttributes {
             ));
         }
 
+        if let Some(skip) = &result.skip {
+            if result.with.is_some() || !result.niches.is_empty() {
+                return Err(Error::new_spanned(
+                    skip,
+                    "skipped fields may not also use `with` or `niche`",
+                ));
+            }
+            if result.getter.is_some() {
+                return Err(Error::new_spanned(
+                    skip,
+                    "skipped fields may not have a getter",
+                ));
+            }
+
+            let rkyv_path = attributes.crate_path();
+            result.with = Some(parse_quote! { #rkyv_path::with::Skip });
+        } else if let Some(default) = &result.default {
+            if result.since.is_none() {
+                return Err(Error::new_spanned(
+                    default,
+                    "`default = ...` may only be used with `skip` or `since`",
+                ));
+            }
+        }
+
+        if let Some(since) = &result.since {
+            if result.skip.is_some() {
+                return Err(Error::new_spanned(
+                    since,
+                    "skipped fields are never archived, so they may not use \
+                     `since`",
+                ));
+            }
+            if attributes.remote.is_some() || attributes.as_type.is_some() {
+                return Err(Error::new_spanned(
+                    since,
+                    "`since` may not be used with `remote = ...` or `as = ...`",
+                ));
+            }
+        }
+
+        if result.with.is_none()
+            && result.niches.is_empty()
+            && result.no_auto_niche.is_none()
+            && attributes.no_auto_niche.is_none()
+            && attributes.as_type.is_none()
+            && is_auto_nicheable(&input.ty)
+        {
+            let rkyv_path = attributes.crate_path();
+            result.with = Some(parse_quote! { #rkyv_path::with::Niche });
+        }
+
         Ok(result)
     }
 
@@ -264,20 +509,23 @@ impl FieldAttributes {
         &self,
         rkyv_path: &Path,
         field: &Field,
-    ) -> Option<WherePredicate> {
+    ) -> Vec<WherePredicate> {
+        if let Some(bounds) = &self.archive_bounds {
+            return bounds.iter().cloned().collect();
+        }
         if self.omit_bounds.is_some() {
-            return None;
+            return Vec::new();
         }
 
         let ty = &field.ty;
         if let Some(with) = &self.with {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #with: #rkyv_path::with::ArchiveWith<#ty>
-            })
+            }]
         } else {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #ty: #rkyv_path::Archive
-            })
+            }]
         }
     }
 
@@ -285,20 +533,23 @@ impl FieldAttributes {
         &self,
         rkyv_path: &Path,
         field: &Field,
-    ) -> Option<WherePredicate> {
+    ) -> Vec<WherePredicate> {
+        if let Some(bounds) = &self.serialize_bounds {
+            return bounds.iter().cloned().collect();
+        }
         if self.omit_bounds.is_some() {
-            return None;
+            return Vec::new();
         }
 
         let ty = &field.ty;
         if let Some(with) = &self.with {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #with: #rkyv_path::with::SerializeWith<#ty, __S>
-            })
+            }]
         } else {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #ty: #rkyv_path::Serialize<__S>
-            })
+            }]
         }
     }
 
@@ -306,25 +557,53 @@ impl FieldAttributes {
         &self,
         rkyv_path: &Path,
         field: &Field,
-    ) -> Option<WherePredicate> {
-        if self.omit_bounds.is_some() {
-            return None;
+    ) -> Vec<WherePredicate> {
+        if let Some(bounds) = &self.deserialize_bounds {
+            return bounds.iter().cloned().collect();
+        }
+        if self.omit_bounds.is_some() || self.is_skipped_with_default() {
+            return Vec::new();
         }
 
         let archived = self.archived(rkyv_path, field);
 
         let ty = &field.ty;
         if let Some(with) = &self.with {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #with: #rkyv_path::with::DeserializeWith<#archived, #ty, __D>
-            })
+            }]
         } else {
-            Some(parse_quote! {
+            vec![parse_quote! {
                 #archived: #rkyv_path::Deserialize<#ty, __D>
-            })
+            }]
         }
     }
 
+    pub fn deserialize_in_place_bound(
+        &self,
+        rkyv_path: &Path,
+        field: &Field,
+    ) -> Vec<WherePredicate> {
+        if self.skip.is_some() {
+            return Vec::new();
+        }
+        if self.with.is_some() {
+            return self.deserialize_bound(rkyv_path, field);
+        }
+        if let Some(bounds) = &self.deserialize_bounds {
+            return bounds.iter().cloned().collect();
+        }
+        if self.omit_bounds.is_some() {
+            return Vec::new();
+        }
+
+        let archived = self.archived(rkyv_path, field);
+        let ty = &field.ty;
+        vec![parse_quote! {
+            #archived: #rkyv_path::de::DeserializeInPlace<#ty, __D>
+        }]
+    }
+
     fn archive_item(
         &self,
         rkyv_path: &Path,
@@ -377,6 +656,18 @@ impl FieldAttributes {
         let ty = &field.ty;
         let archived = self.archived(rkyv_path, field);
 
+        if self.is_skipped_with_default() {
+            let default = self.default_value();
+            return quote! {
+                (|_: &#archived, _: &mut __D| -> ::core::result::Result<
+                    #ty,
+                    <__D as #rkyv_path::rancor::Fallible>::Error,
+                > {
+                    ::core::result::Result::Ok(#default)
+                })
+            };
+        }
+
         if let Some(with) = &self.with {
             quote! {
                 <
@@ -394,6 +685,65 @@ impl FieldAttributes {
         }
     }
 
+    /// Returns a statement which deserializes `archived` into `out`.
+    ///
+    /// Skipped fields are left unchanged, and fields with wrappers are
+    /// deserialized into new values.
+    pub fn deserialize_in_place(
+        &self,
+        rkyv_path: &Path,
+        field: &Field,
+        archived: TokenStream,
+        out: TokenStream,
+    ) -> TokenStream {
+        if self.skip.is_some() {
+            return TokenStream::new();
+        }
+
+        if self.with.is_some() {
+            let deserialize = self.deserialize(rkyv_path, field);
+            return quote! {
+                *#out = #deserialize(#archived, deserializer)?;
+            };
+        }
+
+        let ty = &field.ty;
+        let archived_ty = self.archived(rkyv_path, field);
+        quote! {
+            <
+                #archived_ty as #rkyv_path::de::DeserializeInPlace<#ty, __D>
+            >::deserialize_in_place(#archived, #out, deserializer)?;
+        }
+    }
+
+    fn is_skipped_with_default(&self) -> bool {
+        self.skip.is_some() && self.default.is_some()
+    }
+
+    /// Returns the expression used to create the field when it is not present
+    /// in the archive.
+    pub fn default_value(&self) -> TokenStream {
+        if let Some(default) = &self.default {
+            quote! { #default() }
+        } else {
+            quote! { ::core::default::Default::default() }
+        }
+    }
+
+    /// Returns the first archive version which contains the field. Fields
+    /// without a `since` attribute are part of every version.
+    pub fn since(&self) -> u32 {
+        self.since
+            .as_ref()
+            .map_or(1, |since| since.base10_parse().unwrap())
+    }
+
+    /// Returns whether the field is compared by `compare(..)` impls. Skipped
+    /// fields are not archived, so they are not compared.
+    pub fn is_compared(&self) -> bool {
+        self.skip.is_none()
+    }
+
     pub fn access_field(
         &self,
         this: &Ident,
@@ -422,9 +772,91 @@ impl FieldAttributes {
     }
 }
 
+/// Returns the previous archive versions of a struct with the given fields, in
+/// ascending order.
+///
+/// Fields added with `since = N` must come after all of the fields from
+/// earlier versions, so every previous version is a prefix of the current one.
+/// Versions which didn't add any fields are still returned, so every version
+/// before the current one has an archived type.
+pub fn previous_versions(
+    attributes: &Attributes,
+    fields: &Fields,
+) -> Result<Vec<u32>, Error> {
+    let mut versions = Vec::new();
+    let mut latest = 1;
+    for field in fields.iter() {
+        let field_attrs = FieldAttributes::parse(attributes, field)?;
+        let since = field_attrs.since();
+        if since < latest {
+            return Err(Error::new_spanned(
+                field,
+                "fields must be declared in the order of the versions they \
+                 were added in",
+            ));
+        }
+        if since > latest {
+            versions.extend(latest..since);
+            latest = since;
+        }
+    }
+    Ok(versions)
+}
+
+/// Returns whether the given type is an `Option` which can be niched by the
+/// `Niche` wrapper without any annotation.
+///
+/// Types are matched by name since the derive can't resolve them, so this only
+/// recognizes `Option<Box<T>>` and `Option` of the `NonZero` integers.
+fn is_auto_nicheable(ty: &Type) -> bool {
+    fn last_segment(ty: &Type) -> Option<&PathSegment> {
+        match ty {
+            Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last(),
+            _ => None,
+        }
+    }
+
+    let Some(option) = last_segment(ty) else {
+        return false;
+    };
+    if option.ident != "Option" {
+        return false;
+    }
+    let PathArguments::AngleBracketed(args) = &option.arguments else {
+        return false;
+    };
+    let mut args = args.args.iter();
+    let (Some(GenericArgument::Type(inner)), None) = (args.next(), args.next())
+    else {
+        return false;
+    };
+    let Some(inner) = last_segment(inner) else {
+        return false;
+    };
+
+    matches!(
+        inner.ident.to_string().as_str(),
+        "Box"
+            | "NonZero"
+            | "NonZeroI8"
+            | "NonZeroI16"
+            | "NonZeroI32"
+            | "NonZeroI64"
+            | "NonZeroI128"
+            | "NonZeroIsize"
+            | "NonZeroU8"
+            | "NonZeroU16"
+            | "NonZeroU32"
+            | "NonZeroU64"
+            | "NonZeroU128"
+            | "NonZeroUsize"
+    )
+}
+
 #[derive(Default)]
 pub struct VariantAttributes {
     pub other: Option<Path>,
+    pub serde_attributes: SerdeAttributes,
 }
 
 impl VariantAttributes {
@@ -449,6 +881,10 @@ impl VariantAttributes {
             }
         }
 
+        if attributes.serde.is_some() {
+            result.serde_attributes = SerdeAttributes::parse(&input.attrs)?;
+        }
+
         if result.other.is_some() {
             if attributes.remote.is_none() {
                 return Err(Error::new_spanned(
@@ -467,6 +903,92 @@ impl VariantAttributes {
     }
 }
 
+/// The subset of serde attributes which are understood with `#[rkyv(serde)]`.
+///
+/// Other serde attributes are ignored.
+#[derive(Default)]
+pub struct SerdeAttributes {
+    pub rename: Option<LitStr>,
+    pub skip: Option<Path>,
+    pub skip_serializing: Option<Path>,
+    pub default: Option<Path>,
+}
+
+impl SerdeAttributes {
+    fn parse_meta(&mut self, meta: ParseNestedMeta<'_>) -> Result<(), Error> {
+        if meta.path.is_ident("rename") {
+            if meta.input.peek(token::Paren) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("serialize") {
+                        try_set_attribute(
+                            &mut self.rename,
+                            meta.value()?.parse()?,
+                            "rename",
+                        )
+                    } else {
+                        skip_serde_meta(&meta)
+                    }
+                })
+            } else {
+                try_set_attribute(
+                    &mut self.rename,
+                    meta.value()?.parse()?,
+                    "rename",
+                )
+            }
+        } else if meta.path.is_ident("skip") {
+            self.skip = Some(meta.path);
+            Ok(())
+        } else if meta.path.is_ident("skip_serializing") {
+            self.skip_serializing = Some(meta.path);
+            Ok(())
+        } else if meta.path.is_ident("default") {
+            if meta.input.peek(Token![=]) {
+                let path = meta.value()?.parse::<LitStr>()?;
+                self.default = Some(path.parse()?);
+            }
+            Ok(())
+        } else {
+            skip_serde_meta(&meta)
+        }
+    }
+
+    pub fn parse(attrs: &[Attribute]) -> Result<Self, Error> {
+        let mut result = Self::default();
+
+        for attr in attrs.iter() {
+            if attr.path().is_ident("serde") {
+                attr.parse_nested_meta(|meta| result.parse_meta(meta))?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Returns whether the annotated item is left out when serializing.
+    pub fn is_skipped(&self) -> bool {
+        self.skip.is_some() || self.skip_serializing.is_some()
+    }
+
+    /// Returns the name used for the annotated item when serializing.
+    pub fn name(&self, ident: &Ident) -> LitStr {
+        self.rename
+            .clone()
+            .unwrap_or_else(|| LitStr::new(&strip_raw(ident), ident.span()))
+    }
+}
+
+/// Consumes the arguments of a serde attribute which isn't understood.
+fn skip_serde_meta(meta: &ParseNestedMeta<'_>) -> Result<(), Error> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(token::Paren) {
+        let _content;
+        parenthesized!(_content in meta.input);
+    }
+    Ok(())
+}
+
 pub enum Niche {
     Type(Type),
     Default,
@@ -7,7 +7,7 @@ use syn::{
 
 use crate::{
     archive::printing::Printing,
-    attributes::{Attributes, FieldAttributes},
+    attributes::{previous_versions, Attributes, FieldAttributes},
 };
 
 pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
@@ -104,6 +104,14 @@ fn derive_deserialize_impl(
             name,
         )?;
 
+        let previous_version_impls = generate_previous_version_impls(
+            &input,
+            attributes,
+            &rkyv_path,
+            &impl_input_generics,
+            where_clause,
+        )?;
+
         Ok(quote! {
             #[automatically_derived]
             impl #impl_generics #rkyv_path::Deserialize<#name #ty_generics, __D>
@@ -121,10 +129,79 @@ fn derive_deserialize_impl(
                     #body
                 }
             }
+
+            #previous_version_impls
         })
     }
 }
 
+fn generate_previous_version_impls(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+    impl_input_generics: &Generics,
+    where_clause: &WhereClause,
+) -> Result<TokenStream, Error> {
+    let Data::Struct(ref data) = input.data else {
+        return Ok(TokenStream::new());
+    };
+    let fields = &data.fields;
+
+    let printing = Printing::new(input, attributes)?;
+    let name = &input.ident;
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let mut result = TokenStream::new();
+    for version in previous_versions(attributes, fields)? {
+        let mut deserialize_where = where_clause.clone();
+        let values = fields
+            .iter()
+            .zip(fields.members())
+            .map(|(field, member)| {
+                let field_attrs = FieldAttributes::parse(attributes, field)?;
+                if field_attrs.since() > version {
+                    let default = field_attrs.default_value();
+                    return Ok(quote! { #member: #default });
+                }
+
+                deserialize_where
+                    .predicates
+                    .extend(field_attrs.archive_bound(rkyv_path, field));
+                deserialize_where
+                    .predicates
+                    .extend(field_attrs.deserialize_bound(rkyv_path, field));
+
+                let deserialize = field_attrs.deserialize(rkyv_path, field);
+                Ok(quote! {
+                    #member: #deserialize(&self.#member, deserializer)?
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        let archived_name = printing.archived_version_name(version);
+        result.extend(quote! {
+            #[automatically_derived]
+            impl #impl_generics #rkyv_path::Deserialize<#name #ty_generics, __D>
+                for #archived_name #ty_generics
+            #deserialize_where
+            {
+                fn deserialize(
+                    &self,
+                    deserializer: &mut __D,
+                ) -> ::core::result::Result<
+                    #name #ty_generics,
+                    <__D as #rkyv_path::rancor::Fallible>::Error,
+                > {
+                    ::core::result::Result::Ok(#name { #(#values,)* })
+                }
+            }
+        });
+    }
+
+    Ok(result)
+}
+
 fn generate_deserialize_body(
     input: &DeriveInput,
     attributes: &Attributes,
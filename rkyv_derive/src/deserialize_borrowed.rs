@@ -0,0 +1,222 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, punctuated::Punctuated, Data, DeriveInput, Error, Field,
+    Fields, GenericParam, Generics, Lifetime, LifetimeParam, Path,
+};
+
+use crate::attributes::{Attributes, FieldAttributes};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = Attributes::parse(&input)?;
+    derive_deserialize_borrowed_impl(input, &attributes)
+}
+
+fn derive_deserialize_borrowed_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    if let Some(ref remote) = attributes.remote {
+        return Err(Error::new_spanned(
+            remote,
+            "DeserializeBorrowed cannot be derived for remote types",
+        ));
+    }
+    let Some(ref borrow_from) = attributes.borrow_from else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "DeserializeBorrowed requires the archived type to borrow from \
+             with `#[rkyv(borrow_from = ...)]`",
+        ));
+    };
+
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.deserialize_bounds {
+        for bound in bounds {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+
+    // The archived value is borrowed for the first lifetime of the type, or
+    // for a new lifetime if the type doesn't have any.
+    let existing_lifetime = input
+        .generics
+        .lifetimes()
+        .next()
+        .map(|l| l.lifetime.clone());
+    let lifetime = existing_lifetime
+        .clone()
+        .unwrap_or_else(|| Lifetime::new("'__a", Span::call_site()));
+
+    let mut impl_input_params = Punctuated::<GenericParam, _>::default();
+    if existing_lifetime.is_none() {
+        let param = LifetimeParam::new(lifetime.clone());
+        impl_input_params.push(GenericParam::Lifetime(param));
+    }
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    impl_input_params
+        .push(parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized });
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let body =
+        generate_deserialize_borrowed_body(&input, attributes, &rkyv_path)?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics
+            #rkyv_path::de::DeserializeBorrowed<
+                #lifetime,
+                #name #ty_generics,
+                __D,
+            >
+            for #borrow_from
+        #where_clause
+        {
+            fn deserialize_borrowed(
+                &#lifetime self,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                #name #ty_generics,
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                ::core::result::Result::Ok(#body)
+            }
+        }
+    })
+}
+
+fn deserialize_field(
+    attributes: &Attributes,
+    field: &Field,
+    rkyv_path: &Path,
+    archived: TokenStream,
+) -> Result<TokenStream, Error> {
+    let field_attrs = FieldAttributes::parse(attributes, field)?;
+    if field_attrs.skip.is_some() {
+        return Ok(field_attrs.default_value());
+    }
+    if let Some(ref with) = field_attrs.with {
+        return Err(Error::new_spanned(
+            with,
+            "DeserializeBorrowed does not support `with = ...`",
+        ));
+    }
+
+    Ok(quote! {
+        #rkyv_path::de::DeserializeBorrowed::deserialize_borrowed(
+            #archived,
+            deserializer,
+        )?
+    })
+}
+
+fn generate_deserialize_borrowed_body(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let name = &input.ident;
+    match input.data {
+        Data::Struct(ref data) => {
+            let mut values = Vec::new();
+            for (field, member) in data.fields.iter().zip(data.fields.members())
+            {
+                values.push(deserialize_field(
+                    attributes,
+                    field,
+                    rkyv_path,
+                    quote! { &self.#member },
+                )?);
+            }
+
+            let members = data.fields.members();
+            Ok(match data.fields {
+                Fields::Named(_) => quote! {
+                    #name { #(#members: #values,)* }
+                },
+                Fields::Unnamed(_) => quote! { #name(#(#values,)*) },
+                Fields::Unit => quote! { #name },
+            })
+        }
+        Data::Enum(ref data) => {
+            let mut arms = TokenStream::new();
+            for variant in data.variants.iter() {
+                let variant_name = &variant.ident;
+
+                let mut bindings = Vec::new();
+                let mut values = Vec::new();
+                for (i, field) in variant.fields.iter().enumerate() {
+                    let field_attrs =
+                        FieldAttributes::parse(attributes, field)?;
+                    let binding = match field.ident {
+                        Some(ref ident) => ident.clone(),
+                        None => format_ident!("_{}", i),
+                    };
+
+                    values.push(deserialize_field(
+                        attributes,
+                        field,
+                        rkyv_path,
+                        quote! { #binding },
+                    )?);
+                    if field_attrs.skip.is_some() {
+                        bindings.push(None);
+                    } else {
+                        bindings.push(Some(binding));
+                    }
+                }
+
+                arms.extend(match variant.fields {
+                    Fields::Named(_) => {
+                        let members = variant.fields.members();
+                        let patterns = bindings.iter().flatten();
+                        quote! {
+                            Self::#variant_name { #(#patterns,)* .. } => {
+                                #name::#variant_name {
+                                    #(#members: #values,)*
+                                }
+                            }
+                        }
+                    }
+                    Fields::Unnamed(_) => {
+                        let patterns = bindings.iter().map(|b| match b {
+                            Some(binding) => quote! { #binding },
+                            None => quote! { _ },
+                        });
+                        quote! {
+                            Self::#variant_name(#(#patterns,)* ..) => {
+                                #name::#variant_name(#(#values,)*)
+                            }
+                        }
+                    }
+                    Fields::Unit => quote! {
+                        Self::#variant_name { .. } => #name::#variant_name,
+                    },
+                });
+            }
+
+            Ok(quote! {
+                match self {
+                    #arms
+                }
+            })
+        }
+        Data::Union(_) => Err(Error::new_spanned(
+            input,
+            "DeserializeBorrowed cannot be derived for unions",
+        )),
+    }
+}
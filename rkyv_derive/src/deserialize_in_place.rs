@@ -0,0 +1,223 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_quote, punctuated::Punctuated, Data, DeriveInput, Error, Fields,
+    Generics, Path, WhereClause,
+};
+
+use crate::{
+    attributes::{Attributes, FieldAttributes},
+    util::strip_raw,
+};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream, Error> {
+    let attributes = Attributes::parse(&input)?;
+    derive_deserialize_in_place_impl(input, &attributes)
+}
+
+fn derive_deserialize_in_place_impl(
+    mut input: DeriveInput,
+    attributes: &Attributes,
+) -> Result<TokenStream, Error> {
+    let rkyv_path = attributes.crate_path();
+
+    if let Some(ref remote) = attributes.remote {
+        return Err(Error::new_spanned(
+            remote,
+            "DeserializeInPlace cannot be derived for remote types",
+        ));
+    }
+
+    let where_clause = input.generics.make_where_clause();
+    if let Some(ref bounds) = attributes.archive_bounds {
+        for bound in bounds {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+    if let Some(ref bounds) = attributes.deserialize_bounds {
+        for bound in bounds {
+            where_clause.predicates.push(bound.clone());
+        }
+    }
+
+    let mut impl_input_params = Punctuated::default();
+    impl_input_params
+        .push(parse_quote! { __D: #rkyv_path::rancor::Fallible + ?Sized });
+    for param in input.generics.params.iter() {
+        impl_input_params.push(param.clone());
+    }
+    let impl_input_generics = Generics {
+        lt_token: Some(Default::default()),
+        params: impl_input_params,
+        gt_token: Some(Default::default()),
+        where_clause: input.generics.where_clause.clone(),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ..) = impl_input_generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut deserialize_where = where_clause.unwrap().clone();
+
+    let body = generate_deserialize_in_place_body(
+        &input,
+        attributes,
+        &mut deserialize_where,
+        &rkyv_path,
+    )?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics
+            #rkyv_path::de::DeserializeInPlace<#name #ty_generics, __D>
+            for #rkyv_path::Archived<#name #ty_generics>
+        #deserialize_where
+        {
+            fn deserialize_in_place(
+                &self,
+                out: &mut #name #ty_generics,
+                deserializer: &mut __D,
+            ) -> ::core::result::Result<
+                (),
+                <__D as #rkyv_path::rancor::Fallible>::Error,
+            > {
+                #body
+                ::core::result::Result::Ok(())
+            }
+        }
+    })
+}
+
+fn generate_deserialize_in_place_body(
+    input: &DeriveInput,
+    attributes: &Attributes,
+    deserialize_where: &mut WhereClause,
+    rkyv_path: &Path,
+) -> Result<TokenStream, Error> {
+    let body = match input.data {
+        Data::Struct(ref data) => {
+            let mut statements = TokenStream::new();
+            for (field, member) in data.fields.iter().zip(data.fields.members())
+            {
+                let field_attrs = FieldAttributes::parse(attributes, field)?;
+                deserialize_where.predicates.extend(
+                    field_attrs.deserialize_in_place_bound(rkyv_path, field),
+                );
+                statements.extend(field_attrs.deserialize_in_place(
+                    rkyv_path,
+                    field,
+                    quote! { &self.#member },
+                    quote! { &mut out.#member },
+                ));
+            }
+            statements
+        }
+        Data::Enum(ref data) => {
+            let name = &input.ident;
+            let (_, ty_generics, _) = input.generics.split_for_impl();
+
+            // Variants can only be deserialized in place if they match, so
+            // the whole enum is deserialized when the variant changes.
+            deserialize_where.predicates.push(parse_quote! {
+                #rkyv_path::Archived<#name #ty_generics>:
+                    #rkyv_path::Deserialize<#name #ty_generics, __D>
+            });
+
+            let mut arms = TokenStream::new();
+            for variant in data.variants.iter() {
+                let variant_name = &variant.ident;
+
+                let mut archived_bindings = Vec::new();
+                let mut out_bindings = Vec::new();
+                let mut statements = TokenStream::new();
+                for (i, field) in variant.fields.iter().enumerate() {
+                    let field_attrs =
+                        FieldAttributes::parse(attributes, field)?;
+                    let (archived, out) = match field.ident {
+                        Some(ref ident) => (
+                            ident.clone(),
+                            format_ident!("__out_{}", strip_raw(ident)),
+                        ),
+                        None => (
+                            format_ident!("_{}", i),
+                            format_ident!("__out_{}", i),
+                        ),
+                    };
+
+                    if field_attrs.skip.is_some() {
+                        archived_bindings.push(quote! { _ });
+                        out_bindings.push(quote! { _ });
+                        continue;
+                    }
+
+                    deserialize_where.predicates.extend(
+                        field_attrs
+                            .deserialize_in_place_bound(rkyv_path, field),
+                    );
+                    statements.extend(field_attrs.deserialize_in_place(
+                        rkyv_path,
+                        field,
+                        quote! { #archived },
+                        quote! { #out },
+                    ));
+                    archived_bindings.push(quote! { #archived });
+                    out_bindings.push(quote! { #out });
+                }
+
+                let members = variant.fields.members();
+                let patterns = match variant.fields {
+                    Fields::Named(_) => {
+                        let members = members.collect::<Vec<_>>();
+                        quote! {
+                            (
+                                Self::#variant_name {
+                                    #(#members: #archived_bindings,)*
+                                },
+                                #name::#variant_name {
+                                    #(#members: #out_bindings,)*
+                                },
+                            )
+                        }
+                    }
+                    Fields::Unnamed(_) => quote! {
+                        (
+                            Self::#variant_name(#(#archived_bindings,)*),
+                            #name::#variant_name(#(#out_bindings,)*),
+                        )
+                    },
+                    Fields::Unit => quote! {
+                        (Self::#variant_name, #name::#variant_name)
+                    },
+                };
+
+                arms.extend(quote! {
+                    #patterns => {
+                        #statements
+                        false
+                    }
+                });
+            }
+
+            quote! {
+                #[allow(unreachable_patterns)]
+                let variant_changed = match (self, &mut *out) {
+                    #arms
+                    _ => true,
+                };
+                if variant_changed {
+                    *out = #rkyv_path::Deserialize::deserialize(
+                        self,
+                        deserializer,
+                    )?;
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                input,
+                "DeserializeInPlace cannot be derived for unions",
+            ))
+        }
+    };
+
+    Ok(body)
+}
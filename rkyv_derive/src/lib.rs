@@ -7,8 +7,11 @@
 )]
 
 mod archive;
+mod archive_dyn;
 mod attributes;
 mod deserialize;
+mod deserialize_borrowed;
+mod deserialize_in_place;
 mod portable;
 mod repr;
 mod serde;
@@ -17,7 +20,7 @@ mod util;
 
 extern crate proc_macro;
 
-use syn::{parse_macro_input, DeriveInput};
+use syn::{parse_macro_input, DeriveInput, ItemTrait};
 
 /// Derives `Portable` for the labeled type.
 #[proc_macro_derive(Portable, attributes(rkyv))]
@@ -43,6 +46,9 @@ pub fn derive_portable(
 /// ## Types and fields
 ///
 /// - `attr(..)`: Passes along attributes to the generated archived type.
+/// - `no_auto_niche`: Disables automatic niching for the annotated field, or
+///   for all fields of the annotated type. See [Niching](#niching) for more
+///   information.
 ///
 /// ## Types only
 ///
@@ -51,10 +57,15 @@ pub fn derive_portable(
 /// - `crate = ..`: Chooses an alternative crate path to import rkyv from.
 /// - `compare(..)`: Implements common comparison operators between the original
 ///   and archived types. Supported comparisons are `PartialEq` and `PartialOrd`
-///   (i.e. `#[rkyv(compare(PartialEq, PartialOrd))]`).
+///   (i.e. `#[rkyv(compare(PartialEq, PartialOrd))]`). To compare archived
+///   values with each other or use them as keys, pass `Eq`, `Hash`, `Ord`, etc.
+///   to `derive(..)` instead.
 /// - `{archive, serialize, deserialize}_bounds(..)`: Adds additional bounds to
 ///   trait implementations. This can be useful for recursive types, where
 ///   bounds may need to be omitted to prevent recursive trait impls.
+/// - `omit_bounds`: Omits the trait bounds generated for every field. Only the
+///   bounds given with `{archive, serialize, deserialize}_bounds(..)` are
+///   added.
 /// - `bytecheck(..)`: Passed through to the underlying `CheckBytes` derive for
 ///   the archived type.
 /// - `as = ..`: Uses the given archived type instead of generating a new one.
@@ -64,14 +75,72 @@ pub fn derive_portable(
 ///   default, archived types are named "Archived" + `the name of the type`.
 /// - `resolver = ..`: Changes the name of the generated resolver type. By
 ///   default, resolver types are named `the name of the type` + "Resolver".
+/// - `vis = ..`: Changes the visibility of the generated archived and resolver
+///   types. By default, they have the same visibility as the annotated type.
 /// - `remote = ..`: Generate a remote derive for the annotated type instead of
 ///   a regular derive.
+/// - `accessors`: Generates a method on the archived type for each field, named
+///   after the field and with the visibility of the archived type. Fields with
+///   primitive types like `u32` and `bool` are returned as native values, and
+///   all other fields are returned as references to their archived values. This
+///   allows fields to stay private while still being readable from other
+///   crates. Only supported on structs with named fields.
+/// - `deserialize_fields`: Generates a `deserialize_` + field name method on
+///   the archived type for each field, which deserializes only that field with
+///   the given deserializer. Fields which use `with = ..` are deserialized with
+///   their wrapper. Only supported on structs with named fields.
+/// - `sealed_variants`: Generates an enum named the archived type + "Sealed"
+///   with the same variants as the archived enum, but with each field wrapped
+///   in a `Seal`. The archived enum gets an `as_sealed_variants` method which
+///   converts a `Seal` of the enum into it, so the fields of a variant can be
+///   mutated in place without unsafe code. Only supported on enums.
+/// - `schema`: Implements `rkyv::schema::Reflect` for the archived type, which
+///   describes the offsets and types of its fields and the variants and tags of
+///   enums. Every field type must also implement `Reflect`, except for fields
+///   with `omit_bounds`.
+/// - `diff`: Implements `rkyv::diff::Diff` for the archived type, which
+///   compares two archived values field by field and reports the paths of the
+///   fields which differ. Skipped fields are not compared. Every field type
+///   must also implement `Diff`, except for fields with `omit_bounds`, and the
+///   archived type must implement `Debug`.
+/// - `patch`: Generates methods for patching sealed archived values in place.
+///   Structs with named fields get a `{field}_seal` method for each field,
+///   which projects a `Seal` of the struct to a `Seal` of the field, and a
+///   `set_{field}` method which assigns a value with `rkyv::patch::Assign`.
+///   Enums without fields implement `NoUndef` and `From` their native type, so
+///   their variant can be assigned in place.
+/// - `serde`: Implements `serde::Serialize` for the archived type, so archived
+///   values can be serialized with serde without deserializing them first.
+///   Skipped fields are left out. Requires the `serde-1` feature of rkyv.
+///
+///   With `serde`, some `#[serde(..)]` attributes are also understood so that
+///   they don't have to be repeated for rkyv. `rename = ".."` (and
+///   `rename(serialize = "..")`) changes the serialized name of the container,
+///   a variant, or a field. `skip` on a field skips it in the archive as well,
+///   and `default = ".."` provides its value when deserializing.
+///   `skip_serializing` only leaves the field out of the serialized archived
+///   value. All other serde attributes are ignored.
+/// - `tag = ..`: Chooses the primitive type used for the tag of an archived
+///   enum. May be `u8`, `u16`, or `u32`. By default, enums with up to 256
+///   variants use a `u8` tag and larger enums use a `u16` tag. Multibyte tags
+///   are stored with the byte order of the archive and are not available with
+///   the `unaligned` feature.
 ///
 /// ## Fields only
 ///
 /// - `with = ..`: Applies the given wrapper type to the field.
 /// - `omit_bounds`: Omits trait bounds for the annotated field in the generated
 ///   impl.
+/// - `{archive, serialize, deserialize}_bounds(..)`: Replaces the trait bounds
+///   generated for the annotated field in the corresponding impl. This takes
+///   precedence over `omit_bounds`, so the two can be combined to omit some
+///   field bounds and replace others.
+/// - `skip`: Omits the field from the archived type. The field is created with
+///   `Default::default()` when deserializing.
+/// - `default = ..`: Used with `skip` or `since` to create the field by calling
+///   the given function when deserializing instead of using `Default`.
+/// - `since = ..`: Marks the field as added in the given version of the type.
+///   See [Versioning](#versioning) for more information.
 ///
 /// # Recursive types
 ///
@@ -82,7 +151,45 @@ pub fn derive_portable(
 /// as well. Adding the attribute `#[rkyv(omit_bounds)]` to a field will
 /// suppress this trait bound and allow recursive structures. This may be too
 /// coarse for some types, in which case additional type bounds may be required
-/// with `{archive, serialize, deserialize}_bounds(..)`.
+/// with `{archive, serialize, deserialize}_bounds(..)`. These may be placed
+/// on the recursive field itself, like
+/// `#[rkyv(omit_bounds, serialize_bounds(__S: Allocator + Writer))]`.
+///
+/// # Versioning
+///
+/// Structs can add fields over time while still being able to read archives
+/// written by older versions. Each struct starts at version 1, and fields
+/// added later are marked with `#[rkyv(since = N)]`. Fields must be declared
+/// in the order of the versions they were added in, so that every older
+/// version is a prefix of the current one.
+///
+/// For each older version `N`, the derive generates an additional archived type
+/// named `Archived{Name}V{N}` which only contains the fields from that version.
+/// Versions which didn't add any fields also get an archived type, so skipping
+/// from `since = 2` to `since = 4` still generates `Archived{Name}V3`.
+/// Archives written by an older version can be accessed as that type, and
+/// deserializing it creates the current type with the missing fields set to
+/// their `default = ..` or `Default::default()`. Defaults are only applied when
+/// deserializing: an older archived type doesn't have the missing fields, and
+/// accessing it doesn't fill them in.
+///
+/// Version selection is out-of-band. The derive does not record versions in
+/// the archive itself, so the version of an archive must be stored alongside
+/// it (for example, in a file header or a message envelope) and used to pick
+/// the matching archived type when accessing it.
+///
+/// # Niching
+///
+/// Fields of type `Option<Box<T>>` and `Option` of any `NonZero` integer
+/// automatically use the `Niche` wrapper, which stores `None` in the unused
+/// null pointer or zero value instead of adding a discriminant. Because the
+/// derive can only see the names of types, this applies to any field whose
+/// type is spelled `Option<Box<..>>` or `Option<NonZero..>`.
+///
+/// Niching changes the archived layout of a type. To keep the layout of an
+/// existing format, or when the derive misidentifies a type with a matching
+/// name, add `#[rkyv(no_auto_niche)]` to the field or type. Fields with an
+/// explicit `with = ..` or `niche` attribute are never niched automatically.
 ///
 /// # Wrappers
 ///
@@ -91,7 +198,7 @@ pub fn derive_portable(
 /// but the `Inline` wrapper serializes a reference as if it were a field of the
 /// struct. Wrappers can be applied to fields using the `#[rkyv_with = ..]`
 /// attribute.
-#[proc_macro_derive(Archive, attributes(rkyv))]
+#[proc_macro_derive(Archive, attributes(rkyv, serde))]
 pub fn derive_archive(
     input: proc_macro::TokenStream,
 ) -> proc_macro::TokenStream {
@@ -137,3 +244,86 @@ pub fn derive_deserialize(
         Err(e) => e.to_compile_error().into(),
     }
 }
+
+/// Derives `DeserializeBorrowed` for the labeled type.
+///
+/// The labeled type is deserialized by borrowing from the archived type given
+/// with `#[rkyv(borrow_from = ..)]`. Each field is deserialized from the field
+/// of the archived type with the same name, so the labeled type may leave out
+/// fields of the archived type. For enums, each variant is deserialized from
+/// the archived variant with the same name. Skipped fields are created with
+/// `Default` or `default = ..`.
+///
+/// This macro also supports the `#[rkyv]` attribute. See [`Archive`] for more
+/// information.
+#[proc_macro_derive(DeserializeBorrowed, attributes(rkyv))]
+pub fn derive_deserialize_borrowed(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut derive_input = parse_macro_input!(input as DeriveInput);
+    serde::receiver::replace_receiver(&mut derive_input);
+
+    match deserialize_borrowed::derive(derive_input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derives `DeserializeInPlace` for the labeled type.
+///
+/// Each field is deserialized in place into the corresponding field of the
+/// existing value. Skipped fields are left unchanged, and fields which use
+/// `with = ..` are deserialized into new values. Enums also require the
+/// archived type to implement `Deserialize`, which is used when the variant of
+/// the existing value doesn't match.
+///
+/// This macro also supports the `#[rkyv]` attribute. See [`Archive`] for more
+/// information.
+#[proc_macro_derive(DeserializeInPlace, attributes(rkyv))]
+pub fn derive_deserialize_in_place(
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut derive_input = parse_macro_input!(input as DeriveInput);
+    serde::receiver::replace_receiver(&mut derive_input);
+
+    match deserialize_in_place::derive(derive_input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Registers the implementations of a trait so that its trait objects can be
+/// archived.
+///
+/// See `rkyv::trait_object` for an example.
+///
+/// `#[archive_dyn(impls(A, B, ...))]` generates a `SerializeTrait` trait which
+/// is implemented for each of the listed types. `Box<dyn SerializeTrait>` and
+/// other pointers to `dyn SerializeTrait` can be serialized, and archive as
+/// pointers to `dyn Trait`. The archived type of every listed type must also
+/// implement the trait. Archived trait objects can be deserialized back into
+/// pointers to `dyn SerializeTrait` if the archived type of every listed type
+/// implements `Deserialize`.
+///
+/// The following arguments are supported:
+///
+/// - `impls(..)`: The types which can be serialized as trait objects. The order
+///   of this list is part of the archived format.
+/// - `serialize = ..`: The name of the generated serialization trait. Defaults
+///   to the name of the trait prefixed with `Serialize`.
+/// - `crate = ..`: The path to the `rkyv` crate. Defaults to `::rkyv`.
+#[proc_macro_attribute]
+pub fn archive_dyn(
+    attr: proc_macro::TokenStream,
+    item: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let mut arguments = archive_dyn::Arguments::default();
+    let parser = syn::meta::parser(|meta| arguments.parse_meta(meta));
+    parse_macro_input!(attr with parser);
+    let input = parse_macro_input!(item as ItemTrait);
+
+    match archive_dyn::expand(arguments, input) {
+        Ok(result) => result.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}